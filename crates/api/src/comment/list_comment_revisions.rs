@@ -0,0 +1,43 @@
+use actix_web::web::{Data, Json, Query};
+use lemmy_api_common::{
+  comment::{ListCommentRevisions, ListCommentRevisionsResponse},
+  context::LemmyContext,
+  utils::{check_private_instance, is_mod_or_admin_opt},
+};
+use lemmy_db_views::structs::{CommentRevisionView, CommentView, LocalUserView, SiteView};
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+
+/// Lists the previous versions of a comment
+pub async fn list_comment_revisions(
+  data: Query<ListCommentRevisions>,
+  context: Data<LemmyContext>,
+  local_user_view: Option<LocalUserView>,
+) -> LemmyResult<Json<ListCommentRevisionsResponse>> {
+  let site_view = SiteView::read_local(&mut context.pool()).await?;
+  check_private_instance(&local_user_view, &site_view.local_site)?;
+
+  let comment_view = CommentView::read(
+    &mut context.pool(),
+    data.comment_id,
+    local_user_view.as_ref().map(|l| &l.local_user),
+  )
+  .await?;
+  let mod_check = is_mod_or_admin_opt(
+    &mut context.pool(),
+    local_user_view.as_ref(),
+    Some(comment_view.community.id),
+  )
+  .await;
+  let is_mod_or_admin = mod_check.is_ok();
+  if !site_view.site.edit_history_public {
+    mod_check?;
+  }
+  // The comment view only blanks the content of deleted comments, so check it here
+  if !is_mod_or_admin && (comment_view.comment.deleted || comment_view.comment.removed) {
+    Err(LemmyErrorType::NotFound)?
+  }
+
+  let revisions = CommentRevisionView::list(&mut context.pool(), comment_view.comment.id).await?;
+
+  Ok(Json(ListCommentRevisionsResponse { revisions }))
+}
@@ -10,6 +10,7 @@ use lemmy_db_schema::{
   source::{
     actor_language::CommunityLanguage,
     community::{Community, CommunityFollower, CommunityFollowerForm, CommunityFollowerState},
+    community_backfill::{CommunityBackfill, CommunityBackfillInsertForm},
   },
   traits::{Crud, Followable},
   CommunityVisibility,
@@ -52,10 +53,24 @@ pub async fn follow_community(
       ..CommunityFollowerForm::new(community.id, local_user_view.person.id)
     };
 
+    // Fetch older posts and comments of public remote communities which get their first local
+    // follower, as they are only federated from now on.
+    let backfill = !community.local
+      && community.visibility != CommunityVisibility::Private
+      && context.settings().federation.backfill_on_first_follow
+      && CommunityFollower::check_has_local_followers(&mut context.pool(), community.id)
+        .await
+        .is_err();
+
     // Write to db
     CommunityFollower::follow(&mut context.pool(), &form)
       .await
       .with_lemmy_type(LemmyErrorType::CommunityFollowerAlreadyExists)?;
+
+    if backfill {
+      let form = CommunityBackfillInsertForm::new(community.id, community.instance_id);
+      CommunityBackfill::create(&mut context.pool(), &form).await?;
+    }
   } else {
     CommunityFollower::unfollow(&mut context.pool(), &form)
       .await
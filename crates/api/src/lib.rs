@@ -5,11 +5,13 @@ use lemmy_api_common::{
   community::BanFromCommunity,
   context::LemmyContext,
   send_activity::{ActivityChannel, SendActivityData},
-  utils::{check_expire_time, local_site_to_slur_regex},
+  utils::{check_community_mod_action, check_expire_time, is_admin, local_site_to_slur_regex},
 };
 use lemmy_db_schema::{
+  newtypes::CommunityId,
   source::{
     community::{
+      Community,
       CommunityFollower,
       CommunityFollowerForm,
       CommunityPersonBan,
@@ -224,6 +226,28 @@ pub(crate) async fn ban_nonlocal_user_from_local_communities(
   Ok(())
 }
 
+/// Checks that the user is a moderator of the community, or an admin if no community is given.
+/// Used for moderator notes and warnings, which exist both per community and site-wide.
+pub(crate) async fn check_community_mod_or_admin(
+  community_id: Option<CommunityId>,
+  local_user_view: &LocalUserView,
+  context: &LemmyContext,
+) -> LemmyResult<()> {
+  match community_id {
+    Some(community_id) => {
+      let community = Community::read(&mut context.pool(), community_id).await?;
+      check_community_mod_action(
+        &local_user_view.person,
+        &community,
+        false,
+        &mut context.pool(),
+      )
+      .await
+    }
+    None => is_admin(local_user_view),
+  }
+}
+
 #[cfg(test)]
 mod tests {
 
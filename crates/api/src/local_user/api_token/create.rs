@@ -0,0 +1,35 @@
+use actix_web::web::{Data, Json};
+use lemmy_api_common::{
+  context::LemmyContext,
+  person::{CreateApiToken, CreateApiTokenResponse},
+  utils::{check_expire_time, generate_api_token},
+};
+use lemmy_db_schema::source::api_token::{ApiToken, ApiTokenInsertForm};
+use lemmy_db_views::structs::{ApiTokenView, LocalUserView};
+use lemmy_utils::{
+  error::{LemmyErrorType, LemmyResult},
+  utils::validation::is_valid_api_token_name,
+};
+
+pub async fn create_api_token(
+  data: Json<CreateApiToken>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<CreateApiTokenResponse>> {
+  let name = data.name.trim().to_string();
+  is_valid_api_token_name(&name)?;
+  if data.scopes.is_empty() {
+    Err(LemmyErrorType::MissingApiTokenScope)?
+  }
+  let expires = check_expire_time(data.expires)?;
+
+  let (token, token_hash) = generate_api_token();
+  let form = ApiTokenInsertForm::new(local_user_view.local_user.id, name, token_hash, expires);
+  let api_token = ApiToken::create(&mut context.pool(), &form, &data.scopes).await?;
+  let api_token = ApiTokenView::from_token(&mut context.pool(), api_token).await?;
+
+  Ok(Json(CreateApiTokenResponse {
+    token: token.into(),
+    api_token,
+  }))
+}
@@ -0,0 +1,23 @@
+use actix_web::web::{Data, Json};
+use lemmy_api_common::{context::LemmyContext, person::DeleteApiToken, SuccessResponse};
+use lemmy_db_schema::source::api_token::ApiToken;
+use lemmy_db_views::structs::LocalUserView;
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+
+pub async fn delete_api_token(
+  data: Json<DeleteApiToken>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<SuccessResponse>> {
+  let deleted = ApiToken::delete_for_user(
+    &mut context.pool(),
+    data.api_token_id,
+    local_user_view.local_user.id,
+  )
+  .await?;
+  if deleted == 0 {
+    Err(LemmyErrorType::NotFound)?
+  }
+
+  Ok(Json(SuccessResponse::default()))
+}
@@ -0,0 +1,48 @@
+use actix_web::web::{Data, Json};
+use lemmy_api_common::{
+  context::LemmyContext,
+  person::{CreateKeywordFilter, KeywordFilterResponse},
+  utils::{check_keyword_filter, MAX_KEYWORD_FILTERS},
+};
+use lemmy_db_schema::{
+  source::local_user_keyword_filter::{LocalUserKeywordFilter, LocalUserKeywordFilterInsertForm},
+  traits::Crud,
+};
+use lemmy_db_views::structs::{KeywordFilterView, LocalUserView};
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+
+pub async fn create_keyword_filter(
+  data: Json<CreateKeywordFilter>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<KeywordFilterResponse>> {
+  let local_user_id = local_user_view.local_user.id;
+  let keyword = data.keyword.trim().to_string();
+  check_keyword_filter(&keyword, data.mode.unwrap_or_default(), &context).await?;
+  let existing = LocalUserKeywordFilter::list(&mut context.pool(), local_user_id).await?;
+  if existing.len() >= MAX_KEYWORD_FILTERS {
+    Err(LemmyErrorType::TooManyItems)?
+  }
+
+  let form = LocalUserKeywordFilterInsertForm {
+    mode: data.mode,
+    match_title: data.match_title,
+    match_body: data.match_body,
+    match_url: data.match_url,
+    ..LocalUserKeywordFilterInsertForm::new(local_user_id, keyword)
+  };
+  let keyword_filter = LocalUserKeywordFilter::create(&mut context.pool(), &form)
+    .await
+    .with_lemmy_type(LemmyErrorType::KeywordFilterAlreadyExists)?;
+  if let Some(listing_types) = &data.listing_types {
+    LocalUserKeywordFilter::set_listing_types(
+      &mut context.pool(),
+      keyword_filter.id,
+      listing_types,
+    )
+    .await?;
+  }
+
+  let keyword_filter = KeywordFilterView::from_filter(&mut context.pool(), keyword_filter).await?;
+  Ok(Json(KeywordFilterResponse { keyword_filter }))
+}
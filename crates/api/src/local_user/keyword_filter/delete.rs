@@ -0,0 +1,21 @@
+use actix_web::web::{Data, Json};
+use lemmy_api_common::{context::LemmyContext, person::DeleteKeywordFilter, SuccessResponse};
+use lemmy_db_schema::{source::local_user_keyword_filter::LocalUserKeywordFilter, traits::Crud};
+use lemmy_db_views::structs::LocalUserView;
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+
+pub async fn delete_keyword_filter(
+  data: Json<DeleteKeywordFilter>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<SuccessResponse>> {
+  let keyword_filter =
+    LocalUserKeywordFilter::read(&mut context.pool(), data.keyword_filter_id).await?;
+  // Don't reveal filters of other users
+  if keyword_filter.local_user_id != local_user_view.local_user.id {
+    Err(LemmyErrorType::NotFound)?
+  }
+  LocalUserKeywordFilter::delete(&mut context.pool(), keyword_filter.id).await?;
+
+  Ok(Json(SuccessResponse::default()))
+}
@@ -0,0 +1,56 @@
+use actix_web::web::{Data, Json};
+use lemmy_api_common::{
+  context::LemmyContext,
+  person::{KeywordFilterResponse, UpdateKeywordFilter},
+  utils::check_keyword_filter,
+};
+use lemmy_db_schema::{
+  source::local_user_keyword_filter::{LocalUserKeywordFilter, LocalUserKeywordFilterUpdateForm},
+  traits::Crud,
+};
+use lemmy_db_views::structs::{KeywordFilterView, LocalUserView};
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+
+pub async fn update_keyword_filter(
+  data: Json<UpdateKeywordFilter>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<KeywordFilterResponse>> {
+  let keyword_filter =
+    LocalUserKeywordFilter::read(&mut context.pool(), data.keyword_filter_id).await?;
+  // Don't reveal filters of other users
+  if keyword_filter.local_user_id != local_user_view.local_user.id {
+    Err(LemmyErrorType::NotFound)?
+  }
+
+  let keyword = data.keyword.as_ref().map(|k| k.trim().to_string());
+  check_keyword_filter(
+    keyword.as_ref().unwrap_or(&keyword_filter.keyword),
+    data.mode.unwrap_or(keyword_filter.mode),
+    &context,
+  )
+  .await?;
+
+  let form = LocalUserKeywordFilterUpdateForm {
+    keyword,
+    mode: data.mode,
+    match_title: data.match_title,
+    match_body: data.match_body,
+    match_url: data.match_url,
+  };
+  let keyword_filter =
+    LocalUserKeywordFilter::update(&mut context.pool(), keyword_filter.id, &form)
+      .await
+      .with_lemmy_type(LemmyErrorType::KeywordFilterAlreadyExists)?;
+  if let Some(listing_types) = &data.listing_types {
+    LocalUserKeywordFilter::set_listing_types(
+      &mut context.pool(),
+      keyword_filter.id,
+      listing_types,
+    )
+    .await?;
+  }
+
+  let keyword_filter = KeywordFilterView::from_filter(&mut context.pool(), keyword_filter).await?;
+  Ok(Json(KeywordFilterResponse { keyword_filter }))
+}
@@ -1,7 +1,7 @@
 use actix_web::web::{Data, Json};
 use lemmy_api_common::{context::LemmyContext, person::ListLoginsResponse};
 use lemmy_db_schema::source::login_token::LoginToken;
-use lemmy_db_views::structs::LocalUserView;
+use lemmy_db_views::structs::{ApiTokenView, LocalUserView};
 use lemmy_utils::error::LemmyResult;
 
 pub async fn list_logins(
@@ -9,6 +9,7 @@ pub async fn list_logins(
   local_user_view: LocalUserView,
 ) -> LemmyResult<Json<ListLoginsResponse>> {
   let logins = LoginToken::list(&mut context.pool(), local_user_view.local_user.id).await?;
+  let api_tokens = ApiTokenView::list(&mut context.pool(), local_user_view.local_user.id).await?;
 
-  Ok(Json(ListLoginsResponse { logins }))
+  Ok(Json(ListLoginsResponse { logins, api_tokens }))
 }
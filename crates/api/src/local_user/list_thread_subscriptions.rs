@@ -0,0 +1,17 @@
+use actix_web::web::{Data, Json};
+use lemmy_api_common::{context::LemmyContext, person::ListThreadSubscriptionsResponse};
+use lemmy_db_schema::source::thread_subscription::ThreadSubscription;
+use lemmy_db_views::structs::LocalUserView;
+use lemmy_utils::error::LemmyResult;
+
+pub async fn list_thread_subscriptions(
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<ListThreadSubscriptionsResponse>> {
+  let thread_subscriptions =
+    ThreadSubscription::list(&mut context.pool(), local_user_view.person.id).await?;
+
+  Ok(Json(ListThreadSubscriptionsResponse {
+    thread_subscriptions,
+  }))
+}
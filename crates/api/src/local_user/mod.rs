@@ -1,4 +1,5 @@
 pub mod add_admin;
+pub mod api_token;
 pub mod ban_person;
 pub mod block;
 pub mod change_password;
@@ -6,17 +7,23 @@ pub mod change_password_after_reset;
 pub mod donation_dialog_shown;
 pub mod generate_totp_secret;
 pub mod get_captcha;
+pub mod keyword_filter;
 pub mod list_banned;
 pub mod list_logins;
 pub mod list_media;
 pub mod list_saved;
+pub mod list_thread_subscriptions;
 pub mod login;
 pub mod logout;
+pub mod mod_note;
 pub mod notifications;
+pub mod push_subscription;
 pub mod report_count;
 pub mod reset_password;
 pub mod save_settings;
+pub mod unsubscribe_email;
 pub mod update_totp;
 pub mod user_block_instance;
 pub mod validate_auth;
 pub mod verify_email;
+pub mod warn_person;
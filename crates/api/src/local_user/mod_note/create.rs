@@ -0,0 +1,52 @@
+use crate::check_community_mod_or_admin;
+use actix_web::web::{Data, Json};
+use lemmy_api_common::{
+  context::LemmyContext,
+  person::{CreateModNote, ModNoteResponse},
+};
+use lemmy_db_schema::{
+  source::{
+    community::Community,
+    mod_note::{ModNote, ModNoteInsertForm},
+  },
+  traits::Crud,
+};
+use lemmy_db_views::structs::{LocalUserView, ModNoteView};
+use lemmy_utils::{
+  error::{LemmyErrorType, LemmyResult},
+  utils::validation::is_valid_body_field,
+};
+
+pub async fn create_mod_note(
+  data: Json<CreateModNote>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<ModNoteResponse>> {
+  check_community_mod_or_admin(data.community_id, &local_user_view, &context).await?;
+
+  let content = data.content.trim();
+  if content.is_empty() {
+    Err(LemmyErrorType::InvalidBodyField)?
+  }
+  is_valid_body_field(content, false)?;
+
+  let form = ModNoteInsertForm::new(
+    data.person_id,
+    local_user_view.person.id,
+    data.community_id,
+    content.to_string(),
+  );
+  let mod_note = ModNote::create(&mut context.pool(), &form).await?;
+
+  let community = match mod_note.community_id {
+    Some(community_id) => Some(Community::read(&mut context.pool(), community_id).await?),
+    None => None,
+  };
+  let mod_note_view = ModNoteView {
+    mod_note,
+    creator: local_user_view.person,
+    community,
+  };
+
+  Ok(Json(ModNoteResponse { mod_note_view }))
+}
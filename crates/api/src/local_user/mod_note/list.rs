@@ -0,0 +1,20 @@
+use crate::check_community_mod_or_admin;
+use actix_web::web::{Data, Json, Query};
+use lemmy_api_common::{
+  context::LemmyContext,
+  person::{ListModNotes, ListModNotesResponse},
+};
+use lemmy_db_views::structs::{LocalUserView, ModNoteView};
+use lemmy_utils::error::LemmyResult;
+
+pub async fn list_mod_notes(
+  data: Query<ListModNotes>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<ListModNotesResponse>> {
+  check_community_mod_or_admin(data.community_id, &local_user_view, &context).await?;
+
+  let notes = ModNoteView::list(&mut context.pool(), data.person_id, data.community_id).await?;
+
+  Ok(Json(ListModNotesResponse { notes }))
+}
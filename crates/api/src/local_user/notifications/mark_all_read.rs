@@ -2,9 +2,12 @@ use actix_web::web::{Data, Json};
 use lemmy_api_common::{context::LemmyContext, SuccessResponse};
 use lemmy_db_schema::source::{
   comment_reply::CommentReply,
+  mod_log::moderator::ModWarnPerson,
+  modmail::ModmailMessage,
   person_comment_mention::PersonCommentMention,
   person_post_mention::PersonPostMention,
   private_message::PrivateMessage,
+  thread_subscription::ThreadReply,
 };
 use lemmy_db_views::structs::LocalUserView;
 use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
@@ -35,5 +38,18 @@ pub async fn mark_all_notifications_read(
     .await
     .with_lemmy_type(LemmyErrorType::CouldntUpdatePrivateMessage)?;
 
+  // Mark all warnings as read
+  ModWarnPerson::mark_all_as_read(&mut context.pool(), person_id)
+    .await
+    .with_lemmy_type(LemmyErrorType::CouldntUpdateWarning)?;
+
+  // Mark all modmail messages as read
+  ModmailMessage::mark_all_as_read(&mut context.pool(), person_id).await?;
+
+  // Mark all replies in subscribed threads as read
+  ThreadReply::mark_all_as_read(&mut context.pool(), person_id)
+    .await
+    .with_lemmy_type(LemmyErrorType::CouldntUpdateComment)?;
+
   Ok(Json(SuccessResponse::default()))
 }
@@ -0,0 +1,27 @@
+use actix_web::web::{Data, Json};
+use lemmy_api_common::{
+  context::LemmyContext,
+  modmail::MarkModmailThreadAsRead,
+  utils::check_modmail_thread_access,
+  SuccessResponse,
+};
+use lemmy_db_schema::{
+  source::modmail::{ModmailMessage, ModmailThread},
+  traits::Crud,
+};
+use lemmy_db_views::structs::LocalUserView;
+use lemmy_utils::error::LemmyResult;
+
+pub async fn mark_modmail_thread_as_read(
+  data: Json<MarkModmailThreadAsRead>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<SuccessResponse>> {
+  let thread = ModmailThread::read(&mut context.pool(), data.thread_id).await?;
+  let as_moderator =
+    check_modmail_thread_access(&thread, &local_user_view, &mut context.pool()).await?;
+
+  ModmailMessage::mark_thread_as_read(&mut context.pool(), thread.id, as_moderator).await?;
+
+  Ok(Json(SuccessResponse::default()))
+}
@@ -0,0 +1,29 @@
+use actix_web::web::{Data, Json};
+use lemmy_api_common::{context::LemmyContext, person::MarkThreadReplyAsRead, SuccessResponse};
+use lemmy_db_schema::{
+  source::thread_subscription::{ThreadReply, ThreadReplyUpdateForm},
+  traits::Crud,
+};
+use lemmy_db_views::structs::LocalUserView;
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+
+pub async fn mark_thread_reply_as_read(
+  data: Json<MarkThreadReplyAsRead>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<SuccessResponse>> {
+  let thread_reply = ThreadReply::read(&mut context.pool(), data.thread_reply_id).await?;
+
+  if local_user_view.person.id != thread_reply.recipient_id {
+    Err(LemmyErrorType::CouldntUpdateComment)?
+  }
+
+  let form = ThreadReplyUpdateForm {
+    read: Some(data.read),
+  };
+  ThreadReply::update(&mut context.pool(), thread_reply.id, &form)
+    .await
+    .with_lemmy_type(LemmyErrorType::CouldntUpdateComment)?;
+
+  Ok(Json(SuccessResponse::default()))
+}
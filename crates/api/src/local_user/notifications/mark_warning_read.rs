@@ -0,0 +1,29 @@
+use actix_web::web::{Data, Json};
+use lemmy_api_common::{context::LemmyContext, person::MarkWarningAsRead, SuccessResponse};
+use lemmy_db_schema::{
+  source::mod_log::moderator::{ModWarnPerson, ModWarnPersonUpdateForm},
+  traits::Crud,
+};
+use lemmy_db_views::structs::LocalUserView;
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+
+pub async fn mark_warning_as_read(
+  data: Json<MarkWarningAsRead>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<SuccessResponse>> {
+  let warning = ModWarnPerson::read(&mut context.pool(), data.mod_warn_person_id).await?;
+
+  if local_user_view.person.id != warning.other_person_id {
+    Err(LemmyErrorType::CouldntUpdateWarning)?
+  }
+
+  let form = ModWarnPersonUpdateForm {
+    read: Some(data.read),
+  };
+  ModWarnPerson::update(&mut context.pool(), warning.id, &form)
+    .await
+    .with_lemmy_type(LemmyErrorType::CouldntUpdateWarning)?;
+
+  Ok(Json(SuccessResponse::default()))
+}
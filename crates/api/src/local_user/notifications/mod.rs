@@ -1,6 +1,9 @@
 pub mod list_inbox;
 pub mod mark_all_read;
 pub mod mark_comment_mention_read;
+pub mod mark_modmail_read;
 pub mod mark_post_mention_read;
 pub mod mark_reply_read;
+pub mod mark_thread_reply_read;
+pub mod mark_warning_read;
 pub mod unread_count;
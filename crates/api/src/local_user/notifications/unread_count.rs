@@ -1,6 +1,6 @@
 use actix_web::web::{Data, Json};
 use lemmy_api_common::{context::LemmyContext, person::GetUnreadCountResponse};
-use lemmy_db_views::structs::{InboxCombinedViewInternal, LocalUserView};
+use lemmy_db_views::structs::{InboxCombinedViewInternal, LocalUserView, ModmailThreadView};
 use lemmy_utils::error::LemmyResult;
 
 pub async fn unread_count(
@@ -12,6 +12,11 @@ pub async fn unread_count(
   let count =
     InboxCombinedViewInternal::get_unread_count(&mut context.pool(), person_id, show_bot_accounts)
       .await?;
+  let modmail_count =
+    ModmailThreadView::get_unread_count_for_moderator(&mut context.pool(), person_id).await?;
 
-  Ok(Json(GetUnreadCountResponse { count }))
+  Ok(Json(GetUnreadCountResponse {
+    count,
+    modmail_count,
+  }))
 }
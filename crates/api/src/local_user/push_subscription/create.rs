@@ -0,0 +1,49 @@
+use actix_web::web::{Data, Json};
+use lemmy_api_common::{
+  context::LemmyContext,
+  person::CreatePushSubscription,
+  request::check_url_is_public,
+  utils::MAX_PUSH_SUBSCRIPTIONS,
+  SuccessResponse,
+};
+use lemmy_db_schema::source::push_subscription::{PushSubscription, PushSubscriptionInsertForm};
+use lemmy_db_views::structs::LocalUserView;
+use lemmy_utils::{
+  error::{LemmyErrorExt, LemmyErrorExt2, LemmyErrorType, LemmyResult},
+  web_push::check_subscription_keys,
+};
+use url::Url;
+
+pub async fn create_push_subscription(
+  data: Json<CreatePushSubscription>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<SuccessResponse>> {
+  let endpoint =
+    Url::parse(&data.endpoint).with_lemmy_type(LemmyErrorType::InvalidPushSubscription)?;
+  // Web Push requires https, and the push service must not be on an internal network
+  if endpoint.scheme() != "https" {
+    Err(LemmyErrorType::InvalidPushSubscription)?
+  }
+  check_url_is_public(&endpoint)
+    .await
+    .with_lemmy_type(LemmyErrorType::InvalidPushSubscription)?;
+  check_subscription_keys(&data.p256dh, &data.auth)?;
+
+  let local_user_id = local_user_view.local_user.id;
+  let existing = PushSubscription::list(&mut context.pool(), local_user_id).await?;
+  let is_renewal = existing.iter().any(|s| s.endpoint == data.endpoint);
+  if !is_renewal && existing.len() >= MAX_PUSH_SUBSCRIPTIONS {
+    Err(LemmyErrorType::TooManyItems)?
+  }
+
+  let form = PushSubscriptionInsertForm::new(
+    local_user_id,
+    data.endpoint.clone(),
+    data.p256dh.clone(),
+    data.auth.clone(),
+  );
+  PushSubscription::create(&mut context.pool(), &form).await?;
+
+  Ok(Json(SuccessResponse::default()))
+}
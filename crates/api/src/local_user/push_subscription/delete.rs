@@ -0,0 +1,23 @@
+use actix_web::web::{Data, Json};
+use lemmy_api_common::{context::LemmyContext, person::DeletePushSubscription, SuccessResponse};
+use lemmy_db_schema::source::push_subscription::PushSubscription;
+use lemmy_db_views::structs::LocalUserView;
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+
+pub async fn delete_push_subscription(
+  data: Json<DeletePushSubscription>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<SuccessResponse>> {
+  let deleted = PushSubscription::delete_by_endpoint(
+    &mut context.pool(),
+    local_user_view.local_user.id,
+    &data.endpoint,
+  )
+  .await?;
+  if deleted == 0 {
+    Err(LemmyErrorType::NotFound)?
+  }
+
+  Ok(Json(SuccessResponse::default()))
+}
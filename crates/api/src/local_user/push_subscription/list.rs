@@ -0,0 +1,18 @@
+use actix_web::web::{Data, Json};
+use lemmy_api_common::{context::LemmyContext, person::ListPushSubscriptionsResponse};
+use lemmy_db_schema::source::push_subscription::PushSubscription;
+use lemmy_db_views::structs::LocalUserView;
+use lemmy_utils::error::LemmyResult;
+
+pub async fn list_push_subscriptions(
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<ListPushSubscriptionsResponse>> {
+  let push_subscriptions =
+    PushSubscription::list(&mut context.pool(), local_user_view.local_user.id).await?;
+
+  Ok(Json(ListPushSubscriptionsResponse {
+    push_subscriptions,
+    vapid_public_key: context.secret().vapid_public_key.clone(),
+  }))
+}
@@ -15,6 +15,7 @@ use lemmy_db_schema::{
   source::{
     actor_language::LocalUserLanguage,
     local_user::{LocalUser, LocalUserUpdateForm},
+    local_user_email_digest::LocalUserEmailDigest,
     local_user_vote_display_mode::{LocalUserVoteDisplayMode, LocalUserVoteDisplayModeUpdateForm},
     person::{Person, PersonUpdateForm},
   },
@@ -132,11 +133,16 @@ pub async fn save_user_settings(
     collapse_bot_comments: data.collapse_bot_comments,
     auto_mark_fetched_posts_as_read: data.auto_mark_fetched_posts_as_read,
     hide_media: data.hide_media,
+    auto_subscribe_own_posts: data.auto_subscribe_own_posts,
     ..Default::default()
   };
 
   LocalUser::update(&mut context.pool(), local_user_id, &local_user_form).await?;
 
+  if let Some(email_digest_mode) = data.email_digest_mode {
+    LocalUserEmailDigest::set_mode(&mut context.pool(), local_user_id, email_digest_mode).await?;
+  }
+
   // Update the vote display modes
   let vote_display_modes_form = LocalUserVoteDisplayModeUpdateForm {
     score: data.show_scores,
@@ -0,0 +1,58 @@
+use actix_web::{
+  web::{Data, Json, Query},
+  HttpResponse,
+};
+use lemmy_api_common::{
+  claims::UnsubscribeClaims,
+  context::LemmyContext,
+  person::UnsubscribeEmail,
+  SuccessResponse,
+};
+use lemmy_db_schema::source::local_user::{LocalUser, LocalUserUpdateForm};
+use lemmy_db_views::structs::LocalUserView;
+use lemmy_utils::error::LemmyResult;
+
+/// Opened from the link in notification emails. Only shows a confirmation page, because mail
+/// scanners and link previews also open links, so the update happens in a separate POST request.
+pub async fn unsubscribe_email_confirm(
+  data: Query<UnsubscribeEmail>,
+  context: Data<LemmyContext>,
+) -> LemmyResult<HttpResponse> {
+  let local_user_id = UnsubscribeClaims::validate(&data.token, &context)?;
+  let local_user_view = LocalUserView::read(&mut context.pool(), local_user_id).await?;
+  // A valid token only consists of url safe characters, so it can be placed in the form action
+  // without escaping.
+  let content = if local_user_view.local_user.send_notifications_to_email {
+    format!(
+      "<form method=\"post\" action=\"?token={}\">\
+       <p>Unsubscribe from notification emails?</p>\
+       <button type=\"submit\">Unsubscribe</button></form>",
+      data.token
+    )
+  } else {
+    "<p>You are already unsubscribed from notification emails.</p>".to_string()
+  };
+  let body = format!("<!DOCTYPE html><html><body>{content}</body></html>");
+
+  Ok(
+    HttpResponse::Ok()
+      .content_type("text/html; charset=utf-8")
+      .body(body),
+  )
+}
+
+/// Handles the confirmation from [unsubscribe_email_confirm] as well as one-click unsubscribe
+/// requests from mail clients (RFC 8058), which send a POST request to the same url.
+pub async fn unsubscribe_email(
+  data: Query<UnsubscribeEmail>,
+  context: Data<LemmyContext>,
+) -> LemmyResult<Json<SuccessResponse>> {
+  let local_user_id = UnsubscribeClaims::validate(&data.token, &context)?;
+  let form = LocalUserUpdateForm {
+    send_notifications_to_email: Some(false),
+    ..Default::default()
+  };
+  LocalUser::update(&mut context.pool(), local_user_id, &form).await?;
+
+  Ok(Json(SuccessResponse::default()))
+}
@@ -0,0 +1,58 @@
+use crate::check_community_mod_or_admin;
+use actix_web::web::{Data, Json};
+use lemmy_api_common::{
+  context::LemmyContext,
+  person::{WarnPerson, WarnPersonResponse},
+};
+use lemmy_db_schema::{
+  source::{
+    local_user::LocalUser,
+    mod_log::moderator::{ModWarnPerson, ModWarnPersonForm},
+  },
+  traits::Crud,
+};
+use lemmy_db_views::structs::LocalUserView;
+use lemmy_utils::{
+  error::{LemmyErrorType, LemmyResult},
+  utils::validation::is_valid_body_field,
+};
+
+pub async fn warn_person(
+  data: Json<WarnPerson>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<WarnPersonResponse>> {
+  check_community_mod_or_admin(data.community_id, &local_user_view, &context).await?;
+
+  // Make sure you're a higher mod or admin than the target
+  let my_person_id = local_user_view.person.id;
+  if let Some(community_id) = data.community_id {
+    LocalUser::is_higher_mod_or_admin_check(
+      &mut context.pool(),
+      community_id,
+      my_person_id,
+      vec![data.person_id],
+    )
+    .await?;
+  } else {
+    LocalUser::is_higher_admin_check(&mut context.pool(), my_person_id, vec![data.person_id])
+      .await?;
+  }
+
+  let reason = data.reason.trim();
+  if reason.is_empty() {
+    Err(LemmyErrorType::InvalidBodyField)?
+  }
+  is_valid_body_field(reason, false)?;
+
+  // The warning is added to the inbox of the person by a database trigger
+  let form = ModWarnPersonForm::new(
+    my_person_id,
+    data.person_id,
+    data.community_id,
+    reason.to_string(),
+  );
+  let mod_warn_person = ModWarnPerson::create(&mut context.pool(), &form).await?;
+
+  Ok(Json(WarnPersonResponse { mod_warn_person }))
+}
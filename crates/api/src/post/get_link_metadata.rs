@@ -4,18 +4,39 @@ use lemmy_api_common::{
   post::{GetSiteMetadata, GetSiteMetadataResponse},
   request::fetch_link_metadata,
 };
-use lemmy_db_views::structs::LocalUserView;
-use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+use lemmy_db_schema::ListingType;
+use lemmy_db_views::{
+  post::post_view::PostQuery,
+  structs::{LocalUserView, SiteView},
+};
+use lemmy_utils::{
+  error::{LemmyErrorExt, LemmyErrorType, LemmyResult},
+  utils::validation::equivalent_urls,
+};
 use url::Url;
 
 pub async fn get_link_metadata(
   data: Query<GetSiteMetadata>,
   context: Data<LemmyContext>,
   // Require an account for this API
-  _local_user_view: LocalUserView,
+  local_user_view: LocalUserView,
 ) -> LemmyResult<Json<GetSiteMetadataResponse>> {
   let url = Url::parse(&data.url).with_lemmy_type(LemmyErrorType::InvalidUrl)?;
   let metadata = fetch_link_metadata(&url, &context, false).await?;
 
-  Ok(Json(GetSiteMetadataResponse { metadata }))
+  // Let the client know if this link was already posted somewhere
+  let site_view = SiteView::read_local(&mut context.pool()).await?;
+  let existing_posts = PostQuery {
+    listing_type: Some(ListingType::All),
+    urls: Some(equivalent_urls(&url).into_iter().map(Into::into).collect()),
+    local_user: Some(&local_user_view.local_user),
+    ..Default::default()
+  }
+  .list(&site_view.site, &mut context.pool())
+  .await?;
+
+  Ok(Json(GetSiteMetadataResponse {
+    metadata,
+    existing_posts,
+  }))
 }
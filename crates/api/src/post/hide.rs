@@ -34,5 +34,8 @@ pub async fn hide_post(
   )
   .await?;
 
-  Ok(Json(PostResponse { post_view }))
+  Ok(Json(PostResponse {
+    post_view,
+    duplicate_of: None,
+  }))
 }
@@ -0,0 +1,46 @@
+use actix_web::web::{Data, Json, Query};
+use lemmy_api_common::{
+  context::LemmyContext,
+  post::{ListPostRevisions, ListPostRevisionsResponse},
+  utils::{check_private_instance, is_mod_or_admin_opt},
+};
+use lemmy_db_schema::{source::post::Post, traits::Crud};
+use lemmy_db_views::structs::{LocalUserView, PostRevisionView, PostView, SiteView};
+use lemmy_utils::error::LemmyResult;
+
+/// Lists the previous versions of a post
+pub async fn list_post_revisions(
+  data: Query<ListPostRevisions>,
+  context: Data<LemmyContext>,
+  local_user_view: Option<LocalUserView>,
+) -> LemmyResult<Json<ListPostRevisionsResponse>> {
+  let site_view = SiteView::read_local(&mut context.pool()).await?;
+  check_private_instance(&local_user_view, &site_view.local_site)?;
+
+  let community_id = Post::read(&mut context.pool(), data.post_id)
+    .await?
+    .community_id;
+  let mod_check = is_mod_or_admin_opt(
+    &mut context.pool(),
+    local_user_view.as_ref(),
+    Some(community_id),
+  )
+  .await;
+  let is_mod_or_admin = mod_check.is_ok();
+  if !site_view.site.edit_history_public {
+    mod_check?;
+  }
+
+  // Fails if the post is deleted, removed or otherwise not visible to the user
+  let post_view = PostView::read(
+    &mut context.pool(),
+    data.post_id,
+    local_user_view.as_ref().map(|l| &l.local_user),
+    is_mod_or_admin,
+  )
+  .await?;
+
+  let revisions = PostRevisionView::list(&mut context.pool(), post_view.post.id).await?;
+
+  Ok(Json(ListPostRevisionsResponse { revisions }))
+}
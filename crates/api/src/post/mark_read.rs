@@ -30,5 +30,8 @@ pub async fn mark_post_as_read(
   )
   .await?;
 
-  Ok(Json(PostResponse { post_view }))
+  Ok(Json(PostResponse {
+    post_view,
+    duplicate_of: None,
+  }))
 }
@@ -3,7 +3,11 @@ pub mod get_link_metadata;
 pub mod hide;
 pub mod like;
 pub mod list_post_likes;
+pub mod list_post_revisions;
 pub mod lock;
 pub mod mark_many_read;
 pub mod mark_read;
+pub mod mute_thread;
 pub mod save;
+pub mod subscribe_thread;
+pub mod vote_poll;
@@ -0,0 +1,41 @@
+use actix_web::web::{Data, Json};
+use lemmy_api_common::{
+  context::LemmyContext,
+  post::MuteThread,
+  utils::check_comment_thread_access,
+  SuccessResponse,
+};
+use lemmy_db_schema::source::thread_subscription::{ThreadSubscription, ThreadSubscriptionForm};
+use lemmy_db_views::structs::LocalUserView;
+use lemmy_utils::error::LemmyResult;
+
+pub async fn mute_thread(
+  data: Json<MuteThread>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<SuccessResponse>> {
+  let person_id = local_user_view.person.id;
+  check_comment_thread_access(
+    data.post_id,
+    data.comment_id,
+    &local_user_view,
+    &mut context.pool(),
+  )
+  .await?;
+
+  if data.mute {
+    let form = ThreadSubscriptionForm::new(person_id, data.post_id, data.comment_id, true);
+    ThreadSubscription::set(&mut context.pool(), &form).await?;
+  } else {
+    ThreadSubscription::remove(
+      &mut context.pool(),
+      person_id,
+      data.post_id,
+      data.comment_id,
+      true,
+    )
+    .await?;
+  }
+
+  Ok(Json(SuccessResponse::default()))
+}
@@ -40,5 +40,8 @@ pub async fn save_post(
   let read_form = PostReadForm::new(post_id, person_id);
   PostRead::mark_as_read(&mut context.pool(), &read_form).await?;
 
-  Ok(Json(PostResponse { post_view }))
+  Ok(Json(PostResponse {
+    post_view,
+    duplicate_of: None,
+  }))
 }
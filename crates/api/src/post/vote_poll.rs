@@ -0,0 +1,80 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use chrono::Utc;
+use lemmy_api_common::{
+  build_response::build_post_response,
+  context::LemmyContext,
+  post::{PostResponse, VotePostPoll},
+  send_activity::{ActivityChannel, SendActivityData},
+  utils::{check_bot_account, check_community_user_action},
+};
+use lemmy_db_schema::source::{
+  post::{PostRead, PostReadForm},
+  post_poll::{PostPoll, PostPollOption, PostPollVote},
+};
+use lemmy_db_views::structs::{LocalUserView, PostView};
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+use std::ops::Deref;
+
+pub async fn vote_post_poll(
+  data: Json<VotePostPoll>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<PostResponse>> {
+  let post_id = data.post_id;
+  let person_id = local_user_view.person.id;
+  check_bot_account(&local_user_view.person)?;
+
+  let post_view = PostView::read(
+    &mut context.pool(),
+    post_id,
+    Some(&local_user_view.local_user),
+    false,
+  )
+  .await?;
+  check_community_user_action(
+    &local_user_view.person,
+    &post_view.community,
+    &mut context.pool(),
+  )
+  .await?;
+
+  let poll = PostPoll::read(&mut context.pool(), post_id).await?;
+  if poll.end_time.is_some_and(|end_time| end_time < Utc::now()) {
+    Err(LemmyErrorType::PollEnded)?
+  }
+  // All chosen options must belong to this poll
+  let options = PostPollOption::read_for_poll(&mut context.pool(), post_id)
+    .await?
+    .into_iter()
+    .filter(|o| data.option_ids.contains(&o.id))
+    .collect::<Vec<_>>();
+  let valid_count = options.len() == 1 || (poll.multiple_choice && !options.is_empty());
+  if !valid_count || options.len() != data.option_ids.len() {
+    Err(LemmyErrorType::InvalidPoll)?
+  }
+
+  let option_ids = options.iter().map(|o| o.id).collect::<Vec<_>>();
+  // Votes can't be changed, so this fails if the person voted before
+  PostPollVote::vote(&mut context.pool(), post_id, person_id, &option_ids, true).await?;
+
+  let read_form = PostReadForm::new(post_id, person_id);
+  PostRead::mark_as_read(&mut context.pool(), &read_form).await?;
+
+  ActivityChannel::submit_activity(
+    SendActivityData::VotePostPoll {
+      post: post_view.post.clone(),
+      voter: local_user_view.person.clone(),
+      option_names: options.into_iter().map(|o| o.name).collect(),
+    },
+    &context,
+  )?;
+
+  build_post_response(
+    context.deref(),
+    post_view.community.id,
+    local_user_view,
+    post_id,
+  )
+  .await
+}
@@ -0,0 +1,56 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use chrono::Utc;
+use lemmy_api_common::{
+  context::LemmyContext,
+  site::AdminLimitInstanceParams,
+  utils::is_admin,
+  SuccessResponse,
+};
+use lemmy_db_schema::{
+  source::{
+    federation_limit::{FederationLimit, FederationLimitForm},
+    instance::Instance,
+    mod_log::admin::{AdminLimitInstance, AdminLimitInstanceForm},
+  },
+  traits::Crud,
+};
+use lemmy_db_views::structs::LocalUserView;
+use lemmy_utils::error::LemmyResult;
+
+pub async fn admin_limit_instance(
+  data: Json<AdminLimitInstanceParams>,
+  local_user_view: LocalUserView,
+  context: Data<LemmyContext>,
+) -> LemmyResult<Json<SuccessResponse>> {
+  is_admin(&local_user_view)?;
+
+  let instance_id = Instance::read_or_create(&mut context.pool(), data.instance.clone())
+    .await?
+    .id;
+
+  if data.limited || data.reject_media || data.force_nsfw {
+    let form = FederationLimitForm {
+      instance_id,
+      limited: data.limited,
+      reject_media: data.reject_media,
+      force_nsfw: data.force_nsfw,
+      updated: Some(Utc::now()),
+    };
+    FederationLimit::upsert(&mut context.pool(), &form).await?;
+  } else {
+    FederationLimit::delete(&mut context.pool(), instance_id).await?;
+  }
+
+  let mod_log_form = AdminLimitInstanceForm {
+    instance_id,
+    admin_person_id: local_user_view.person.id,
+    limited: data.limited,
+    reject_media: data.reject_media,
+    force_nsfw: data.force_nsfw,
+    reason: data.reason.clone(),
+  };
+  AdminLimitInstance::create(&mut context.pool(), &mod_log_form).await?;
+
+  Ok(Json(SuccessResponse::default()))
+}
@@ -0,0 +1,24 @@
+use activitypub_federation::config::Data;
+use actix_web::web::{Json, Query};
+use lemmy_api_common::{
+  context::LemmyContext,
+  site::{ListFailedEmails, ListFailedEmailsResponse},
+  utils::is_admin,
+};
+use lemmy_db_schema::source::outgoing_email::OutgoingEmail;
+use lemmy_db_views::structs::LocalUserView;
+use lemmy_utils::error::LemmyResult;
+
+/// Lists the emails which could not be sent even after retrying.
+pub async fn list_failed_emails(
+  data: Query<ListFailedEmails>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<ListFailedEmailsResponse>> {
+  // Make sure user is an admin
+  is_admin(&local_user_view)?;
+
+  let emails = OutgoingEmail::list_failed(&mut context.pool(), data.page, data.limit).await?;
+
+  Ok(Json(ListFailedEmailsResponse { emails }))
+}
@@ -0,0 +1,27 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_common::{
+  context::LemmyContext,
+  site::{OutgoingEmailResponse, RetryEmail},
+  utils::is_admin,
+};
+use lemmy_db_schema::source::outgoing_email::OutgoingEmail;
+use lemmy_db_views::structs::LocalUserView;
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+
+/// Queues an email which was not sent to be sent again, for example after fixing the email
+/// configuration.
+pub async fn retry_email(
+  data: Json<RetryEmail>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<OutgoingEmailResponse>> {
+  // Make sure user is an admin
+  is_admin(&local_user_view)?;
+
+  let email = OutgoingEmail::retry(&mut context.pool(), data.email_id)
+    .await
+    .with_lemmy_type(LemmyErrorType::NotFound)?;
+
+  Ok(Json(OutgoingEmailResponse { email }))
+}
@@ -2,17 +2,19 @@ use actix_web::web::{Data, Json};
 use lemmy_api_common::{
   context::LemmyContext,
   site::GetFederatedInstancesResponse,
-  utils::build_federated_instances,
+  utils::{build_federated_instances, is_admin},
 };
-use lemmy_db_views::structs::SiteView;
+use lemmy_db_views::structs::{LocalUserView, SiteView};
 use lemmy_utils::error::LemmyResult;
 
 pub async fn get_federated_instances(
   context: Data<LemmyContext>,
+  local_user_view: Option<LocalUserView>,
 ) -> LemmyResult<Json<GetFederatedInstancesResponse>> {
   let site_view = SiteView::read_local(&mut context.pool()).await?;
+  let admin = local_user_view.is_some_and(|l| is_admin(&l).is_ok());
   let federated_instances =
-    build_federated_instances(&site_view.local_site, &mut context.pool()).await?;
+    build_federated_instances(&site_view.local_site, admin, &mut context.pool()).await?;
 
   Ok(Json(GetFederatedInstancesResponse {
     federated_instances,
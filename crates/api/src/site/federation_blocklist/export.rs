@@ -0,0 +1,23 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_common::{
+  context::LemmyContext,
+  federation_blocklist::export_blocklist,
+  site::ExportFederationBlocklistResponse,
+  utils::is_admin,
+};
+use lemmy_db_views::structs::LocalUserView;
+use lemmy_utils::error::LemmyResult;
+
+/// Exports the blocked and limited instances in the Mastodon CSV format.
+pub async fn export_federation_blocklist(
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<ExportFederationBlocklistResponse>> {
+  // Make sure user is an admin
+  is_admin(&local_user_view)?;
+
+  let csv = export_blocklist(&mut context.pool()).await?;
+
+  Ok(Json(ExportFederationBlocklistResponse { csv }))
+}
@@ -0,0 +1,29 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_common::{
+  context::LemmyContext,
+  federation_blocklist::{apply_blocklist_import, plan_blocklist_import},
+  site::{ImportFederationBlocklist, ImportFederationBlocklistResponse},
+  utils::is_admin,
+};
+use lemmy_db_views::structs::LocalUserView;
+use lemmy_utils::error::LemmyResult;
+
+/// Imports a blocklist in the Mastodon CSV format. Returns the change for each line, which allows
+/// reviewing a list with `dry_run` before applying it.
+pub async fn import_federation_blocklist(
+  data: Json<ImportFederationBlocklist>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<ImportFederationBlocklistResponse>> {
+  // Make sure user is an admin
+  is_admin(&local_user_view)?;
+
+  let local_domain = context.settings().get_hostname_without_port()?;
+  let entries = plan_blocklist_import(&data.csv, &local_domain, &mut context.pool()).await?;
+  if !data.dry_run.unwrap_or_default() {
+    apply_blocklist_import(&entries, local_user_view.person.id, &mut context.pool()).await?;
+  }
+
+  Ok(Json(ImportFederationBlocklistResponse { entries }))
+}
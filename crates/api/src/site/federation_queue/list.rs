@@ -0,0 +1,34 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_common::{
+  context::LemmyContext,
+  site::{ListFederationQueuesResponse, ReadableFederationState},
+  utils::{is_admin, read_instances_with_federation_state},
+};
+use lemmy_db_views::structs::LocalUserView;
+use lemmy_utils::error::LemmyResult;
+use std::cmp::Reverse;
+
+/// Lists the outgoing federation queue of every instance, with its lag and failures.
+pub async fn list_federation_queues(
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<ListFederationQueuesResponse>> {
+  // Make sure user is an admin
+  is_admin(&local_user_view)?;
+
+  let mut instances = read_instances_with_federation_state(true, &mut context.pool())
+    .await?
+    .into_iter()
+    .map(|(i, _, _)| i)
+    .collect::<Vec<_>>();
+  instances.sort_by_key(|i| {
+    Reverse(
+      i.federation_state
+        .as_ref()
+        .and_then(ReadableFederationState::activities_behind),
+    )
+  });
+
+  Ok(Json(ListFederationQueuesResponse { instances }))
+}
@@ -0,0 +1,4 @@
+pub mod list;
+pub mod pause;
+pub mod reset_backoff;
+pub mod skip_backlog;
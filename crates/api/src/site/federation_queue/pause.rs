@@ -0,0 +1,29 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_common::{
+  context::LemmyContext,
+  site::PauseFederation,
+  utils::is_admin,
+  SuccessResponse,
+};
+use lemmy_db_schema::source::{federation_queue_state::FederationQueueState, instance::Instance};
+use lemmy_db_views::structs::LocalUserView;
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+
+/// Pauses or resumes sending activities to an instance. The federation worker checks this within
+/// a minute.
+pub async fn pause_federation(
+  data: Json<PauseFederation>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<SuccessResponse>> {
+  // Make sure user is an admin
+  is_admin(&local_user_view)?;
+
+  Instance::read(&mut context.pool(), data.instance_id)
+    .await
+    .with_lemmy_type(LemmyErrorType::NotFound)?;
+  FederationQueueState::set_paused(&mut context.pool(), data.instance_id, data.paused).await?;
+
+  Ok(Json(SuccessResponse::default()))
+}
@@ -0,0 +1,28 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_common::{
+  context::LemmyContext,
+  site::ResetFederationBackoff,
+  utils::is_admin,
+  SuccessResponse,
+};
+use lemmy_db_schema::source::{federation_queue_state::FederationQueueState, instance::Instance};
+use lemmy_db_views::structs::LocalUserView;
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+
+/// Retries sending to an instance immediately, for example after it was down for a while.
+pub async fn reset_federation_backoff(
+  data: Json<ResetFederationBackoff>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<SuccessResponse>> {
+  // Make sure user is an admin
+  is_admin(&local_user_view)?;
+
+  Instance::read(&mut context.pool(), data.instance_id)
+    .await
+    .with_lemmy_type(LemmyErrorType::NotFound)?;
+  FederationQueueState::request_reset_backoff(&mut context.pool(), data.instance_id).await?;
+
+  Ok(Json(SuccessResponse::default()))
+}
@@ -0,0 +1,29 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_common::{
+  context::LemmyContext,
+  site::SkipFederationBacklog,
+  utils::is_admin,
+  SuccessResponse,
+};
+use lemmy_db_schema::source::{federation_queue_state::FederationQueueState, instance::Instance};
+use lemmy_db_views::structs::LocalUserView;
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+
+/// Drops the activities which were not sent to an instance yet. This is useful if the instance
+/// is so far behind that catching up would take too long.
+pub async fn skip_federation_backlog(
+  data: Json<SkipFederationBacklog>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<SuccessResponse>> {
+  // Make sure user is an admin
+  is_admin(&local_user_view)?;
+
+  Instance::read(&mut context.pool(), data.instance_id)
+    .await
+    .with_lemmy_type(LemmyErrorType::NotFound)?;
+  FederationQueueState::request_skip_backlog(&mut context.pool(), data.instance_id).await?;
+
+  Ok(Json(SuccessResponse::default()))
+}
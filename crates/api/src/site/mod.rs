@@ -1,8 +1,13 @@
 pub mod admin_allow_instance;
 pub mod admin_block_instance;
+pub mod admin_limit_instance;
+pub mod email_queue;
 pub mod federated_instances;
+pub mod federation_blocklist;
+pub mod federation_queue;
 pub mod leave_admin;
 pub mod list_all_media;
 pub mod mod_log;
 pub mod purge;
 pub mod registration_applications;
+pub mod scheduled_jobs;
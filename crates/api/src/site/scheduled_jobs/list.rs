@@ -0,0 +1,19 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_common::{context::LemmyContext, site::ListScheduledJobsResponse, utils::is_admin};
+use lemmy_db_schema::source::scheduled_job::ScheduledJob;
+use lemmy_db_views::structs::LocalUserView;
+use lemmy_utils::error::LemmyResult;
+
+/// Lists all scheduled background jobs with their status.
+pub async fn list_scheduled_jobs(
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<ListScheduledJobsResponse>> {
+  // Make sure user is an admin
+  is_admin(&local_user_view)?;
+
+  let jobs = ScheduledJob::list(&mut context.pool()).await?;
+
+  Ok(Json(ListScheduledJobsResponse { jobs }))
+}
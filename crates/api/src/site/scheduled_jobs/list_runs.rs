@@ -0,0 +1,24 @@
+use activitypub_federation::config::Data;
+use actix_web::web::{Json, Query};
+use lemmy_api_common::{
+  context::LemmyContext,
+  site::{ListScheduledJobRuns, ListScheduledJobRunsResponse},
+  utils::is_admin,
+};
+use lemmy_db_schema::source::scheduled_job::ScheduledJobRun;
+use lemmy_db_views::structs::LocalUserView;
+use lemmy_utils::error::LemmyResult;
+
+/// Lists the run history of scheduled jobs, optionally only for a single job.
+pub async fn list_scheduled_job_runs(
+  data: Query<ListScheduledJobRuns>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<ListScheduledJobRunsResponse>> {
+  // Make sure user is an admin
+  is_admin(&local_user_view)?;
+
+  let runs = ScheduledJobRun::list(&mut context.pool(), data.job_id, data.page, data.limit).await?;
+
+  Ok(Json(ListScheduledJobRunsResponse { runs }))
+}
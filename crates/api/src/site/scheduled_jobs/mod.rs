@@ -0,0 +1,3 @@
+pub mod list;
+pub mod list_runs;
+pub mod run;
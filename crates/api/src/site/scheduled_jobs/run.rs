@@ -0,0 +1,24 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_common::{
+  context::LemmyContext,
+  site::{RunScheduledJob, ScheduledJobResponse},
+  utils::is_admin,
+};
+use lemmy_db_schema::source::scheduled_job::ScheduledJob;
+use lemmy_db_views::structs::LocalUserView;
+use lemmy_utils::error::LemmyResult;
+
+/// Schedules a job to run immediately. It is picked up by the next idle Lemmy process.
+pub async fn run_scheduled_job(
+  data: Json<RunScheduledJob>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<ScheduledJobResponse>> {
+  // Make sure user is an admin
+  is_admin(&local_user_view)?;
+
+  let job = ScheduledJob::run_now(&mut context.pool(), &data.name).await?;
+
+  Ok(Json(ScheduledJobResponse { job }))
+}
@@ -0,0 +1,109 @@
+use lemmy_db_schema::{
+  newtypes::{AutomodRuleId, CommunityId, LanguageId, TagId},
+  source::automod_rule::AutomodRule,
+  AutomodAction,
+};
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+#[cfg(feature = "full")]
+use ts_rs::TS;
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Create an automod rule for a community. The rule matches a post or comment if all of the given
+/// conditions match, at least one condition is required.
+pub struct CreateAutomodRule {
+  pub community_id: CommunityId,
+  pub name: String,
+  /// Defaults to true.
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub check_posts: Option<bool>,
+  /// Defaults to true.
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub check_comments: Option<bool>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub title_regex: Option<String>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub body_regex: Option<String>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub url_domain: Option<String>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub max_author_age_days: Option<i32>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub max_author_karma: Option<i64>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub language_id: Option<LanguageId>,
+  pub action: AutomodAction,
+  /// Required for [AutomodAction::Tag].
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub tag_id: Option<TagId>,
+  /// Required for [AutomodAction::Reply], and used as reason for [AutomodAction::Report].
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub message: Option<String>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Update an automod rule. The conditions, action, tag and message replace the existing ones, so
+/// conditions which are left out are removed from the rule.
+pub struct UpdateAutomodRule {
+  pub rule_id: AutomodRuleId,
+  pub name: String,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub enabled: Option<bool>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub check_posts: Option<bool>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub check_comments: Option<bool>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub title_regex: Option<String>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub body_regex: Option<String>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub url_domain: Option<String>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub max_author_age_days: Option<i32>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub max_author_karma: Option<i64>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub language_id: Option<LanguageId>,
+  pub action: AutomodAction,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub tag_id: Option<TagId>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub message: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Delete an automod rule.
+pub struct DeleteAutomodRule {
+  pub rule_id: AutomodRuleId,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Lists the automod rules of a community. Only visible to the moderators.
+pub struct ListAutomodRules {
+  pub community_id: CommunityId,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+pub struct AutomodRuleResponse {
+  pub rule: AutomodRule,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+pub struct ListAutomodRulesResponse {
+  pub rules: Vec<AutomodRule>,
+}
@@ -8,6 +8,7 @@ use crate::{
     get_interface_language,
     is_mod_or_admin,
     send_email_to_user,
+    send_push_to_user,
   },
 };
 use actix_web::web::Json;
@@ -22,6 +23,7 @@ use lemmy_db_schema::{
     person_comment_mention::{PersonCommentMention, PersonCommentMentionInsertForm},
     person_post_mention::{PersonPostMention, PersonPostMentionInsertForm},
     post::Post,
+    thread_subscription::{ThreadReply, ThreadReplyInsertForm, ThreadSubscription},
   },
   traits::Crud,
 };
@@ -87,7 +89,10 @@ pub async fn build_post_response(
     is_mod_or_admin,
   )
   .await?;
-  Ok(Json(PostResponse { post_view }))
+  Ok(Json(PostResponse {
+    post_view,
+    duplicate_of: None,
+  }))
 }
 
 // TODO: this function is a mess and should be split up to handle email separately
@@ -141,6 +146,20 @@ pub async fn send_local_notifs(
     }
   };
 
+  let content_link = if let Some(comment) = &comment_opt {
+    format!(
+      "{}/comment/{}",
+      context.settings().get_protocol_and_hostname(),
+      comment.id
+    )
+  } else {
+    format!(
+      "{}/post/{}",
+      context.settings().get_protocol_and_hostname(),
+      post.id
+    )
+  };
+
   // Send the local mentions
   for mention in mentions
     .iter()
@@ -191,9 +210,16 @@ pub async fn send_local_notifs(
           &mention_user_view,
           &lang.notification_mentioned_by_subject(&person.name),
           &lang.notification_mentioned_by_body(&content, &inbox_link, &person.name),
-          context.settings(),
+          context,
         )
-        .await
+        .await;
+        send_push_to_user(
+          &mention_user_view,
+          &lang.notification_mentioned_by_subject(&person.name),
+          &comment_content_or_post_body,
+          content_link.clone(),
+          context,
+        );
       }
     }
   }
@@ -244,9 +270,16 @@ pub async fn send_local_notifs(
                 &parent_user_view,
                 &lang.notification_comment_reply_subject(&person.name),
                 &lang.notification_comment_reply_body(&content, &inbox_link, &person.name),
-                context.settings(),
+                context,
               )
-              .await
+              .await;
+              send_push_to_user(
+                &parent_user_view,
+                &lang.notification_comment_reply_subject(&person.name),
+                &comment.content,
+                content_link.clone(),
+                context,
+              );
             }
           }
         }
@@ -290,14 +323,77 @@ pub async fn send_local_notifs(
                 &parent_user_view,
                 &lang.notification_post_reply_subject(&person.name),
                 &lang.notification_post_reply_body(&content, &inbox_link, &person.name),
-                context.settings(),
+                context,
               )
-              .await
+              .await;
+              send_push_to_user(
+                &parent_user_view,
+                &lang.notification_post_reply_subject(&person.name),
+                &comment.content,
+                content_link.clone(),
+                context,
+              );
             }
           }
         }
       }
     }
+
+    // Send thread_reply to everyone subscribed to the post or to one of the parent comments
+    let subscribers = ThreadSubscription::list_subscribers(&mut context.pool(), comment).await?;
+    for subscriber_id in subscribers.into_iter().filter(|s| *s != person.id) {
+      let check_blocks = check_person_instance_community_block(
+        person.id,
+        subscriber_id,
+        // Only block from the community's instance_id
+        community.instance_id,
+        community.id,
+        &mut context.pool(),
+      )
+      .await
+      .is_err();
+      if check_blocks {
+        continue;
+      }
+
+      let user_view = LocalUserView::read_person(&mut context.pool(), subscriber_id).await;
+      if let Ok(subscriber_view) = user_view {
+        // Don't duplicate notif if already mentioned or replied to
+        if !recipient_ids.contains(&subscriber_view.local_user.id) {
+          recipient_ids.push(subscriber_view.local_user.id);
+
+          let thread_reply_form = ThreadReplyInsertForm {
+            recipient_id: subscriber_view.person.id,
+            comment_id: comment.id,
+            read: None,
+          };
+
+          // Allow this to fail softly, since comment edits might re-update or replace it
+          ThreadReply::create(&mut context.pool(), &thread_reply_form)
+            .await
+            .ok();
+
+          if do_send_email {
+            let lang = get_interface_language(&subscriber_view);
+            let content = markdown_to_html(&comment.content);
+            send_email_to_user(
+              &subscriber_view,
+              &lang.notification_comment_reply_subject(&person.name),
+              &lang.notification_comment_reply_body(&content, &inbox_link, &person.name),
+              context,
+            )
+            .await;
+            send_push_to_user(
+              &subscriber_view,
+              &lang.notification_comment_reply_subject(&person.name),
+              &comment.content,
+              content_link.clone(),
+              context,
+            );
+          }
+        }
+      }
+    }
   }
 
   Ok(recipient_ids)
@@ -68,10 +68,51 @@ impl Claims {
   }
 }
 
+/// Audience of the tokens in unsubscribe links, so that they can't be confused with login tokens.
+const UNSUBSCRIBE_AUDIENCE: &str = "email_unsubscribe";
+
+/// Claims of the signed token in notification emails, which allows disabling notification emails
+/// without logging in.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+pub struct UnsubscribeClaims {
+  /// local_user_id, standard claim by RFC 7519.
+  pub sub: String,
+  pub iss: String,
+  pub aud: String,
+}
+
+impl UnsubscribeClaims {
+  pub fn validate(token: &str, context: &LemmyContext) -> LemmyResult<LocalUserId> {
+    let mut validation = Validation::default();
+    validation.validate_exp = false;
+    validation.required_spec_claims.remove("exp");
+    validation.set_audience(&[UNSUBSCRIBE_AUDIENCE]);
+    let jwt_secret = &context.secret().jwt_secret;
+    let key = DecodingKey::from_secret(jwt_secret.as_ref());
+    let claims = decode::<UnsubscribeClaims>(token, &key, &validation)
+      .with_lemmy_type(LemmyErrorType::InvalidUnsubscribeToken)?;
+    Ok(LocalUserId(claims.claims.sub.parse()?))
+  }
+
+  pub fn generate(user_id: LocalUserId, context: &LemmyContext) -> LemmyResult<String> {
+    let claims = UnsubscribeClaims {
+      sub: user_id.0.to_string(),
+      iss: context.settings().hostname.clone(),
+      aud: UNSUBSCRIBE_AUDIENCE.to_string(),
+    };
+    let secret = &context.secret().jwt_secret;
+    let key = EncodingKey::from_secret(secret.as_ref());
+    Ok(encode(&Header::default(), &claims, &key)?)
+  }
+}
+
 #[cfg(test)]
 mod tests {
 
-  use crate::{claims::Claims, context::LemmyContext};
+  use crate::{
+    claims::{Claims, UnsubscribeClaims},
+    context::LemmyContext,
+  };
   use actix_web::test::TestRequest;
   use lemmy_db_schema::{
     source::{
@@ -112,4 +153,31 @@ mod tests {
 
     Ok(())
   }
+  #[tokio::test]
+  #[serial]
+  async fn test_unsubscribe_token() -> LemmyResult<()> {
+    let context = LemmyContext::init_test_context().await;
+    let pool = &mut context.pool();
+
+    let inserted_instance = Instance::read_or_create(pool, "my_domain.tld".to_string()).await?;
+    let new_person = PersonInsertForm::test_form(inserted_instance.id, "unsubscribe_user");
+    let inserted_person = Person::create(pool, &new_person).await?;
+    let local_user_form = LocalUserInsertForm::test_form(inserted_person.id);
+    let inserted_local_user = LocalUser::create(pool, &local_user_form, vec![]).await?;
+
+    let token = UnsubscribeClaims::generate(inserted_local_user.id, &context)?;
+    assert_eq!(
+      inserted_local_user.id,
+      UnsubscribeClaims::validate(&token, &context)?
+    );
+
+    // Login tokens can't be used to unsubscribe, and the other way round
+    let req = TestRequest::default().to_http_request();
+    let jwt = Claims::generate(inserted_local_user.id, req, &context).await?;
+    assert!(UnsubscribeClaims::validate(&jwt, &context).is_err());
+    assert!(Claims::validate(&token, &context).await.is_err());
+
+    Person::delete(pool, inserted_person.id).await?;
+    Ok(())
+  }
 }
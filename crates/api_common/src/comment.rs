@@ -3,7 +3,7 @@ use lemmy_db_schema::{
   CommentSortType,
   ListingType,
 };
-use lemmy_db_views::structs::{CommentSlimView, CommentView, VoteView};
+use lemmy_db_views::structs::{CommentRevisionView, CommentSlimView, CommentView, VoteView};
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
 #[cfg(feature = "full")]
@@ -172,3 +172,19 @@ pub struct ListCommentLikes {
 pub struct ListCommentLikesResponse {
   pub comment_likes: Vec<VoteView>,
 }
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// List the previous versions of a comment. Only mods and admins can do this, unless the site
+/// makes the edit history public.
+pub struct ListCommentRevisions {
+  pub comment_id: CommentId,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+pub struct ListCommentRevisionsResponse {
+  pub revisions: Vec<CommentRevisionView>,
+}
@@ -1,7 +1,8 @@
 use lemmy_db_schema::{
-  newtypes::{CommunityId, LanguageId, PersonId},
-  source::site::Site,
+  newtypes::{CommunityId, LanguageId, PersonId, TagId},
+  source::{site::Site, tag::Tag},
   CommunityVisibility,
+  DuplicateLinkPolicy,
   ListingType,
 };
 use lemmy_db_views::structs::{
@@ -75,6 +76,12 @@ pub struct CreateCommunity {
   pub discussion_languages: Option<Vec<LanguageId>>,
   #[cfg_attr(feature = "full", ts(optional))]
   pub visibility: Option<CommunityVisibility>,
+  /// What happens when a link is posted which was already posted here recently.
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub duplicate_link_policy: Option<DuplicateLinkPolicy>,
+  /// How many days back posts are checked for duplicate links, between 1 and 365.
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub duplicate_link_window_days: Option<i32>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -187,6 +194,12 @@ pub struct EditCommunity {
   pub discussion_languages: Option<Vec<LanguageId>>,
   #[cfg_attr(feature = "full", ts(optional))]
   pub visibility: Option<CommunityVisibility>,
+  /// What happens when a link is posted which was already posted here recently.
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub duplicate_link_policy: Option<DuplicateLinkPolicy>,
+  /// How many days back posts are checked for duplicate links, between 1 and 365.
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub duplicate_link_window_days: Option<i32>,
 }
 
 #[skip_serializing_none]
@@ -318,3 +331,51 @@ pub struct ApproveCommunityPendingFollower {
   pub follower_id: PersonId,
   pub approve: bool,
 }
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Create a tag which can be assigned to posts in the community. Only for moderators.
+pub struct CreateCommunityTag {
+  pub community_id: CommunityId,
+  pub name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Rename a community tag. Only for moderators.
+pub struct UpdateCommunityTag {
+  pub tag_id: TagId,
+  pub name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Delete a community tag. It is removed from all posts. Only for moderators.
+pub struct DeleteCommunityTag {
+  pub tag_id: TagId,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// List the tags of a community.
+pub struct ListCommunityTags {
+  pub community_id: CommunityId,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+pub struct CommunityTagResponse {
+  pub tag: Tag,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+pub struct ListCommunityTagsResponse {
+  pub tags: Vec<Tag>,
+}
@@ -0,0 +1,99 @@
+use lemmy_db_schema::{
+  newtypes::{CommentId, CommunityId, DraftId, LanguageId, PostId},
+  source::draft::Draft,
+};
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+#[cfg(feature = "full")]
+use ts_rs::TS;
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Save an unfinished post or comment. For comments, set the post_id and put the content in body.
+pub struct CreateDraft {
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub community_id: Option<CommunityId>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub post_id: Option<PostId>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub parent_id: Option<CommentId>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub name: Option<String>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub url: Option<String>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub body: Option<String>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub alt_text: Option<String>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub nsfw: Option<bool>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub language_id: Option<LanguageId>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub custom_thumbnail: Option<String>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Update a draft. The given fields replace the whole content of the draft.
+pub struct UpdateDraft {
+  pub draft_id: DraftId,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub community_id: Option<CommunityId>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub post_id: Option<PostId>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub parent_id: Option<CommentId>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub name: Option<String>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub url: Option<String>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub body: Option<String>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub alt_text: Option<String>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub nsfw: Option<bool>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub language_id: Option<LanguageId>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub custom_thumbnail: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Delete a draft.
+pub struct DeleteDraft {
+  pub draft_id: DraftId,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Turn a post draft into a post, and delete the draft.
+pub struct PublishDraft {
+  pub draft_id: DraftId,
+  /// Time when this post should be scheduled. Null means publish immediately.
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub scheduled_publish_time: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+pub struct DraftResponse {
+  pub draft: Draft,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+pub struct ListDraftsResponse {
+  pub drafts: Vec<Draft>,
+}
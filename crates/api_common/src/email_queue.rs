@@ -0,0 +1,92 @@
+use crate::context::LemmyContext;
+use activitypub_federation::config::Data;
+use chrono::TimeDelta;
+use lemmy_db_schema::{
+  source::outgoing_email::{OutgoingEmail, OutgoingEmailInsertForm},
+  utils::DbPool,
+};
+use lemmy_utils::{
+  email::{send_email, send_notification_email},
+  error::{LemmyErrorType, LemmyResult},
+  settings::structs::Settings,
+};
+use std::time::Duration;
+use tracing::warn;
+
+/// Emails which are not sent after this time are attempted again.
+const SEND_LEASE: TimeDelta = TimeDelta::minutes(5);
+
+/// How often to check for emails which are due
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Number of emails which are taken from the queue at once.
+const BATCH_SIZE: i64 = 20;
+
+/// Adds an email to the queue, from where it is sent in the background. Fails if email is not
+/// configured, so that callers notice it right away like before.
+pub async fn queue_email(
+  form: &OutgoingEmailInsertForm,
+  pool: &mut DbPool<'_>,
+  settings: &Settings,
+) -> LemmyResult<()> {
+  if settings.email.is_none() {
+    Err(LemmyErrorType::NoEmailSetup)?
+  }
+  OutgoingEmail::create(pool, form).await?;
+  Ok(())
+}
+
+/// Sends the queued emails, forever. Emails which fail are retried with exponential backoff.
+pub async fn send_queued_emails(context: Data<LemmyContext>) -> LemmyResult<()> {
+  loop {
+    match OutgoingEmail::claim_due(&mut context.pool(), BATCH_SIZE, SEND_LEASE).await {
+      Ok(emails) if !emails.is_empty() => {
+        for email in emails {
+          send_one(&email, &context).await;
+        }
+      }
+      Ok(_) => tokio::time::sleep(POLL_INTERVAL).await,
+      Err(e) => {
+        warn!("Failed to read email queue: {e}");
+        tokio::time::sleep(POLL_INTERVAL).await
+      }
+    }
+  }
+}
+
+async fn send_one(email: &OutgoingEmail, context: &LemmyContext) {
+  let settings = context.settings();
+  let res = match &email.unsubscribe_link {
+    Some(unsubscribe_link) => {
+      send_notification_email(
+        &email.subject,
+        &email.to_email,
+        &email.to_name,
+        &email.body,
+        unsubscribe_link,
+        settings,
+      )
+      .await
+    }
+    None => {
+      send_email(
+        &email.subject,
+        &email.to_email,
+        &email.to_name,
+        &email.body,
+        settings,
+      )
+      .await
+    }
+  };
+  let error = res.err().map(|e| e.to_string());
+  if let Some(error) = &error {
+    warn!(
+      "Failed to send email {} to {}: {error}",
+      email.id.0, email.to_email
+    );
+  }
+  if let Err(e) = OutgoingEmail::finish_attempt(&mut context.pool(), email, error).await {
+    warn!("Failed to store result of email {}: {e}", email.id.0);
+  }
+}
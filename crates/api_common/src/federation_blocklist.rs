@@ -0,0 +1,335 @@
+use crate::site::FederationBlocklistImportEntry;
+use chrono::Utc;
+use lemmy_db_schema::{
+  newtypes::{InstanceId, PersonId},
+  source::{
+    federation_blocklist::{FederationBlockList, FederationBlockListForm},
+    federation_limit::{FederationLimit, FederationLimitForm},
+    instance::Instance,
+    mod_log::admin::{
+      AdminBlockInstance,
+      AdminBlockInstanceForm,
+      AdminLimitInstance,
+      AdminLimitInstanceForm,
+    },
+  },
+  traits::Crud,
+  utils::DbPool,
+  FederationBlocklistImportAction,
+  FederationBlocklistSkipReason,
+};
+use lemmy_utils::error::LemmyResult;
+use std::collections::{HashMap, HashSet};
+
+/// Columns of a blocklist, in the order used by Mastodon. Lists without header must use this
+/// order.
+const COLUMNS: [&str; 6] = [
+  "domain",
+  "severity",
+  "reject_media",
+  "reject_reports",
+  "public_comment",
+  "obfuscate",
+];
+
+/// Compares a blocklist in the Mastodon CSV format with the instances which are currently blocked
+/// or limited, and returns the change which each line makes. Imports only add restrictions, so
+/// instances which are not in the list are left alone.
+pub async fn plan_blocklist_import(
+  csv: &str,
+  local_domain: &str,
+  pool: &mut DbPool<'_>,
+) -> LemmyResult<Vec<FederationBlocklistImportEntry>> {
+  use FederationBlocklistImportAction::*;
+  use FederationBlocklistSkipReason::*;
+
+  // Blocking instances isn't possible while the allowlist is used
+  let use_allowlist = !Instance::allowlist(pool).await?.is_empty();
+  let current = Instance::read_all_with_fed_state(pool)
+    .await?
+    .into_iter()
+    .map(|(instance, _, blocked, _, limit)| (instance.domain.to_lowercase(), (blocked, limit)))
+    .collect::<HashMap<_, _>>();
+
+  let mut rows = parse_csv(csv)
+    .into_iter()
+    .filter(|row| row.iter().any(|f| !f.trim().is_empty()))
+    .peekable();
+  let header = rows.next_if(|row| {
+    row
+      .first()
+      .is_some_and(|f| column_name(f).eq_ignore_ascii_case("domain"))
+  });
+  let column = |name: &str| match &header {
+    Some(header) => header
+      .iter()
+      .position(|c| column_name(c).eq_ignore_ascii_case(name)),
+    None => COLUMNS.iter().position(|c| *c == name),
+  };
+  let (domain_col, severity_col, reject_media_col, comment_col) = (
+    column("domain"),
+    column("severity"),
+    column("reject_media"),
+    column("public_comment"),
+  );
+
+  let mut seen = HashSet::new();
+  let mut entries = vec![];
+  for row in rows {
+    let field = |col: Option<usize>| {
+      col
+        .and_then(|i| row.get(i))
+        .map(|f| f.trim())
+        .unwrap_or_default()
+    };
+    let domain = field(domain_col).to_lowercase();
+    // Mastodon defaults to suspend if the severity is missing
+    let severity = match field(severity_col).to_lowercase() {
+      s if s.is_empty() => "suspend".to_string(),
+      s => s,
+    };
+    let listed_reject_media = parse_bool(field(reject_media_col));
+    let reason = Some(field(comment_col))
+      .filter(|c| !c.is_empty())
+      .map(ToString::to_string);
+
+    // Existing limits are kept, and only extended by the list
+    let (blocked, limit) = current.get(&domain).cloned().unwrap_or_default();
+    let limited_before = limit.as_ref().is_some_and(|l| l.limited);
+    let reject_media_before = limit.as_ref().is_some_and(|l| l.reject_media);
+    let limited = limited_before || severity == "silence";
+    let reject_media = reject_media_before || listed_reject_media;
+    let unchanged = blocked || (limited == limited_before && reject_media == reject_media_before);
+
+    let (action, skip_reason) = if !is_valid_domain(&domain) {
+      (Skip, Some(InvalidDomain))
+    } else if domain == local_domain {
+      (Skip, Some(LocalInstance))
+    } else if !seen.insert(domain.clone()) {
+      (Skip, Some(DuplicateDomain))
+    } else {
+      match severity.as_str() {
+        "suspend" if blocked => (Unchanged, None),
+        "suspend" if use_allowlist => (Skip, Some(AllowlistInUse)),
+        "suspend" => (Block, None),
+        "noop" if !listed_reject_media => (Skip, Some(NoRestrictions)),
+        "silence" | "noop" if unchanged => (Unchanged, None),
+        "silence" | "noop" => (Limit, None),
+        _ => (Skip, Some(InvalidSeverity)),
+      }
+    };
+    entries.push(FederationBlocklistImportEntry {
+      domain,
+      action,
+      limited,
+      reject_media,
+      reason,
+      skip_reason,
+    });
+  }
+  Ok(entries)
+}
+
+/// Applies the changes of a blocklist import, and logs each one in the modlog.
+pub async fn apply_blocklist_import(
+  entries: &[FederationBlocklistImportEntry],
+  admin_person_id: PersonId,
+  pool: &mut DbPool<'_>,
+) -> LemmyResult<()> {
+  for entry in entries {
+    match entry.action {
+      FederationBlocklistImportAction::Block => {
+        let instance_id = Instance::read_or_create(pool, entry.domain.clone())
+          .await?
+          .id;
+        let form = FederationBlockListForm {
+          instance_id,
+          expires: None,
+          updated: None,
+        };
+        FederationBlockList::block(pool, &form).await?;
+        let mod_log_form = AdminBlockInstanceForm {
+          instance_id,
+          admin_person_id,
+          blocked: true,
+          reason: entry.reason.clone(),
+        };
+        AdminBlockInstance::create(pool, &mod_log_form).await?;
+      }
+      FederationBlocklistImportAction::Limit => {
+        let instance_id = Instance::read_or_create(pool, entry.domain.clone())
+          .await?
+          .id;
+        // The blocklist format has no column for this, so keep the existing value
+        let force_nsfw = FederationLimit::read(pool, instance_id)
+          .await?
+          .is_some_and(|l| l.force_nsfw);
+        let form = FederationLimitForm {
+          instance_id,
+          limited: entry.limited,
+          reject_media: entry.reject_media,
+          force_nsfw,
+          updated: Some(Utc::now()),
+        };
+        FederationLimit::upsert(pool, &form).await?;
+        let mod_log_form = AdminLimitInstanceForm {
+          instance_id,
+          admin_person_id,
+          limited: entry.limited,
+          reject_media: entry.reject_media,
+          force_nsfw,
+          reason: entry.reason.clone(),
+        };
+        AdminLimitInstance::create(pool, &mod_log_form).await?;
+      }
+      FederationBlocklistImportAction::Unchanged | FederationBlocklistImportAction::Skip => {}
+    }
+  }
+  Ok(())
+}
+
+/// Writes the blocked and limited instances in the Mastodon CSV format. The public comment of
+/// each instance is the reason of its latest modlog entry.
+pub async fn export_blocklist(pool: &mut DbPool<'_>) -> LemmyResult<String> {
+  let mut instances = Instance::read_all_with_fed_state(pool).await?;
+  instances.sort_by(|a, b| a.0.domain.cmp(&b.0.domain));
+  let block_reasons = AdminBlockInstance::read_latest(pool)
+    .await?
+    .into_iter()
+    .map(|b| (b.instance_id, b.reason))
+    .collect::<HashMap<InstanceId, Option<String>>>();
+  let limit_reasons = AdminLimitInstance::read_latest(pool)
+    .await?
+    .into_iter()
+    .map(|l| (l.instance_id, l.reason))
+    .collect::<HashMap<InstanceId, Option<String>>>();
+
+  let mut csv = COLUMNS.map(|c| format!("#{c}")).join(",");
+  csv.push('\n');
+  for (instance, _, blocked, _, limit) in instances {
+    let reject_media = limit.as_ref().is_some_and(|l| l.reject_media);
+    let (severity, reason) = match limit {
+      _ if blocked => ("suspend", block_reasons.get(&instance.id)),
+      Some(limit) if limit.limited => ("silence", limit_reasons.get(&instance.id)),
+      Some(_) if reject_media => ("noop", limit_reasons.get(&instance.id)),
+      // Instances which are not restricted, or only have forced nsfw which the format can't
+      // express
+      _ => continue,
+    };
+    let reason = reason.cloned().flatten().unwrap_or_default();
+    let fields = [
+      instance.domain.as_str(),
+      severity,
+      if reject_media { "true" } else { "false" },
+      "false",
+      &reason,
+      "false",
+    ];
+    csv += &fields.map(csv_field).join(",");
+    csv.push('\n');
+  }
+  Ok(csv)
+}
+
+/// Header names may be prefixed with `#`, as in blocklists exported by Mastodon.
+fn column_name(header: &str) -> &str {
+  header.trim().trim_start_matches('#')
+}
+
+fn parse_bool(value: &str) -> bool {
+  value.eq_ignore_ascii_case("true") || value == "1"
+}
+
+/// Obfuscated domains, which contain `*`, can't be blocked.
+fn is_valid_domain(domain: &str) -> bool {
+  domain.contains('.')
+    && domain
+      .chars()
+      .all(|c| c.is_alphanumeric() || matches!(c, '.' | '-' | '_' | ':'))
+}
+
+/// Splits CSV into rows of fields. Quoted fields may contain commas, line breaks and quotes, which
+/// are escaped by doubling them.
+fn parse_csv(input: &str) -> Vec<Vec<String>> {
+  let mut rows = vec![];
+  let mut row = vec![];
+  let mut field = String::new();
+  let mut in_quotes = false;
+  let mut chars = input.chars().peekable();
+  while let Some(c) = chars.next() {
+    match c {
+      '"' if in_quotes && chars.peek() == Some(&'"') => {
+        chars.next();
+        field.push('"');
+      }
+      '"' if in_quotes => in_quotes = false,
+      '"' if field.is_empty() => in_quotes = true,
+      ',' if !in_quotes => row.push(std::mem::take(&mut field)),
+      '\n' if !in_quotes => {
+        row.push(std::mem::take(&mut field));
+        rows.push(std::mem::take(&mut row));
+      }
+      '\r' if !in_quotes => {}
+      _ => field.push(c),
+    }
+  }
+  if !field.is_empty() || !row.is_empty() {
+    row.push(field);
+    rows.push(row);
+  }
+  rows
+}
+
+fn csv_field(value: &str) -> String {
+  if value.contains([',', '"', '\n', '\r']) {
+    format!("\"{}\"", value.replace('"', "\"\""))
+  } else {
+    value.to_string()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use pretty_assertions::assert_eq;
+
+  #[test]
+  fn test_parse_csv() {
+    let csv = "#domain,#severity,#reject_media,#reject_reports,#public_comment\r\n\
+               spam.tld,suspend,false,false,\"spam, and \"\"more\"\"\"\n\
+               noisy.tld,silence,true,false,\n";
+    let rows = parse_csv(csv);
+    assert_eq!(3, rows.len());
+    assert_eq!(
+      Some(&vec![
+        "spam.tld".to_string(),
+        "suspend".to_string(),
+        "false".to_string(),
+        "false".to_string(),
+        "spam, and \"more\"".to_string(),
+      ]),
+      rows.get(1)
+    );
+    assert_eq!(
+      Some(""),
+      rows.get(2).and_then(|r| r.get(4)).map(String::as_str)
+    );
+  }
+
+  #[test]
+  fn test_csv_field_roundtrip() {
+    for value in ["plain", "with, comma", "with \"quotes\"", "two\nlines"] {
+      let rows = parse_csv(&csv_field(value));
+      assert_eq!(vec![vec![value.to_string()]], rows);
+    }
+  }
+
+  #[test]
+  fn test_is_valid_domain() {
+    assert!(is_valid_domain("lemmy.ml"));
+    assert!(is_valid_domain("localhost.tld:8536"));
+    assert!(!is_valid_domain("lem*y.ml"));
+    assert!(!is_valid_domain("https://lemmy.ml/"));
+    assert!(!is_valid_domain(""));
+  }
+}
@@ -0,0 +1,78 @@
+use lemmy_db_schema::newtypes::{CommunityId, FeedId, PersonId};
+use lemmy_db_views::structs::FeedView;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+#[cfg(feature = "full")]
+use ts_rs::TS;
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Create a feed, which groups several local or remote communities.
+pub struct CreateFeed {
+  pub name: String,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub description: Option<String>,
+  /// Public feeds can be viewed by anyone, private feeds only by their creator.
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub public: Option<bool>,
+  pub community_ids: Vec<CommunityId>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Update a feed. The community list replaces the existing communities.
+pub struct UpdateFeed {
+  pub feed_id: FeedId,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub name: Option<String>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub description: Option<String>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub public: Option<bool>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub community_ids: Option<Vec<CommunityId>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Delete a feed.
+pub struct DeleteFeed {
+  pub feed_id: FeedId,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Get a feed.
+pub struct GetFeed {
+  pub feed_id: FeedId,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// List the feeds of a person. Without a person, lists your own feeds.
+pub struct ListFeeds {
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub creator_id: Option<PersonId>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+pub struct FeedResponse {
+  pub feed_view: FeedView,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+pub struct ListFeedsResponse {
+  pub feeds: Vec<FeedView>,
+}
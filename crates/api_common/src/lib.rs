@@ -1,3 +1,4 @@
+pub mod automod;
 #[cfg(feature = "full")]
 pub mod build_response;
 #[cfg(feature = "full")]
@@ -7,7 +8,14 @@ pub mod community;
 #[cfg(feature = "full")]
 pub mod context;
 pub mod custom_emoji;
+pub mod draft;
+#[cfg(feature = "full")]
+pub mod email_queue;
+#[cfg(feature = "full")]
+pub mod federation_blocklist;
+pub mod feed;
 pub mod image;
+pub mod modmail;
 pub mod oauth_provider;
 pub mod person;
 pub mod post;
@@ -16,11 +24,18 @@ pub mod reports;
 #[cfg(feature = "full")]
 pub mod request;
 #[cfg(feature = "full")]
+pub mod run_automod;
+#[cfg(feature = "full")]
 pub mod send_activity;
+#[cfg(feature = "full")]
+pub mod send_email_digest;
+#[cfg(feature = "full")]
+pub mod send_webhook;
 pub mod site;
 pub mod tagline;
 #[cfg(feature = "full")]
 pub mod utils;
+pub mod webhook;
 
 pub extern crate lemmy_db_schema;
 pub extern crate lemmy_db_views;
@@ -0,0 +1,82 @@
+use lemmy_db_schema::newtypes::{CommunityId, ModmailThreadId};
+use lemmy_db_views::structs::{ModmailMessageView, ModmailThreadView};
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+#[cfg(feature = "full")]
+use ts_rs::TS;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Start a modmail conversation with the moderators of a local community.
+pub struct CreateModmailThread {
+  pub community_id: CommunityId,
+  pub subject: String,
+  pub content: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Reply to a modmail thread, either as its creator or as a moderator of the community.
+pub struct CreateModmailMessage {
+  pub thread_id: ModmailThreadId,
+  pub content: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Archive a modmail thread once it is resolved. Only for moderators.
+pub struct ArchiveModmailThread {
+  pub thread_id: ModmailThreadId,
+  pub archived: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Mark the messages of a modmail thread as read.
+pub struct MarkModmailThreadAsRead {
+  pub thread_id: ModmailThreadId,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Get a modmail thread with all of its messages.
+pub struct GetModmailThread {
+  pub thread_id: ModmailThreadId,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// List modmail threads. With a community, lists the threads of that community, which is only
+/// possible for its moderators. Otherwise lists your own threads.
+pub struct ListModmailThreads {
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub community_id: Option<CommunityId>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub archived: Option<bool>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub page: Option<i64>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+pub struct ModmailThreadResponse {
+  pub thread_view: ModmailThreadView,
+  pub messages: Vec<ModmailMessageView>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+pub struct ListModmailThreadsResponse {
+  pub threads: Vec<ModmailThreadView>,
+}
@@ -1,26 +1,43 @@
 use lemmy_db_schema::{
   newtypes::{
+    ApiTokenId,
     CommentReplyId,
     CommunityId,
+    DbUrl,
+    KeywordFilterId,
     LanguageId,
+    ModWarnPersonId,
     PersonCommentMentionId,
     PersonId,
     PersonPostMentionId,
+    ThreadReplyId,
   },
   sensitive::SensitiveString,
-  source::{login_token::LoginToken, site::Site},
+  source::{
+    login_token::LoginToken,
+    mod_log::moderator::ModWarnPerson,
+    push_subscription::PushSubscription,
+    site::Site,
+    thread_subscription::ThreadSubscription,
+  },
+  ApiTokenScope,
   CommentSortType,
+  EmailDigestMode,
   InboxDataType,
+  KeywordFilterMode,
   ListingType,
   PersonContentType,
   PostListingMode,
   PostSortType,
 };
 use lemmy_db_views::structs::{
+  ApiTokenView,
   CommunityModeratorView,
   InboxCombinedPaginationCursor,
   InboxCombinedView,
+  KeywordFilterView,
   LocalImageView,
+  ModNoteView,
   PersonContentCombinedPaginationCursor,
   PersonContentCombinedView,
   PersonSavedCombinedPaginationCursor,
@@ -190,6 +207,12 @@ pub struct SaveUserSettings {
   /// Whether to hide posts containing images/videos.
   #[cfg_attr(feature = "full", ts(optional))]
   pub hide_media: Option<bool>,
+  /// Whether to subscribe to the comments of your own posts.
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub auto_subscribe_own_posts: Option<bool>,
+  /// How often to send notification emails.
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub email_digest_mode: Option<EmailDigestMode>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
@@ -354,6 +377,76 @@ pub struct BanPersonResponse {
   pub banned: bool,
 }
 
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Send a formal warning to a person. It is shown in their inbox and in the modlog.
+///
+/// Without a community, the warning is sent by an admin.
+pub struct WarnPerson {
+  pub person_id: PersonId,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub community_id: Option<CommunityId>,
+  pub reason: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+pub struct WarnPersonResponse {
+  pub mod_warn_person: ModWarnPerson,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Mark a warning as read.
+pub struct MarkWarningAsRead {
+  pub mod_warn_person_id: ModWarnPersonId,
+  pub read: bool,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Add a private note about a person, which is only visible to the moderators of the community.
+///
+/// Without a community, the note is only visible to admins.
+pub struct CreateModNote {
+  pub person_id: PersonId,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub community_id: Option<CommunityId>,
+  pub content: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+pub struct ModNoteResponse {
+  pub mod_note_view: ModNoteView,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// List the moderator notes about a person. Without a community, the notes of all communities are
+/// listed, which is only allowed for admins.
+pub struct ListModNotes {
+  pub person_id: PersonId,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub community_id: Option<CommunityId>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+pub struct ListModNotesResponse {
+  pub notes: Vec<ModNoteView>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "full", derive(TS))]
 #[cfg_attr(feature = "full", ts(export))]
@@ -423,6 +516,23 @@ pub struct MarkCommentReplyAsRead {
   pub read: bool,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Mark a reply in a subscribed thread as read.
+pub struct MarkThreadReplyAsRead {
+  pub thread_reply_id: ThreadReplyId,
+  pub read: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// The threads which you are subscribed to, or which you muted.
+pub struct ListThreadSubscriptionsResponse {
+  pub thread_subscriptions: Vec<ThreadSubscription>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "full", derive(TS))]
 #[cfg_attr(feature = "full", ts(export))]
@@ -475,6 +585,9 @@ pub struct GetReportCountResponse {
 /// A response containing a count of unread notifications.
 pub struct GetUnreadCountResponse {
   pub count: i64,
+  /// Unread modmail messages in communities which you moderate. These are also included in
+  /// `count`.
+  pub modmail_count: i64,
 }
 
 #[derive(Serialize, Deserialize, Clone, Default, Debug, PartialEq, Eq, Hash)]
@@ -531,4 +644,160 @@ pub struct ListMediaResponse {
 #[cfg_attr(feature = "full", ts(export))]
 pub struct ListLoginsResponse {
   pub logins: Vec<LoginToken>,
+  pub api_tokens: Vec<ApiTokenView>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Create a personal api token. It can only be used for the endpoints which are allowed by its
+/// scopes, and not for managing the account itself.
+pub struct CreateApiToken {
+  pub name: String,
+  pub scopes: Vec<ApiTokenScope>,
+  /// Unix timestamp after which the token can't be used anymore.
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub expires: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+pub struct CreateApiTokenResponse {
+  /// The token to use in the `Authorization: Bearer` header. It is only returned once.
+  pub token: SensitiveString,
+  pub api_token: ApiTokenView,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Revoke a personal api token.
+pub struct DeleteApiToken {
+  pub api_token_id: ApiTokenId,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Register a Web Push subscription, as returned by `PushManager.subscribe()` in the browser. The
+/// subscription must be created with the `vapid_public_key` of the server.
+pub struct CreatePushSubscription {
+  pub endpoint: String,
+  /// Base64url encoded public key of the subscription.
+  pub p256dh: String,
+  /// Base64url encoded authentication secret of the subscription.
+  pub auth: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Stop sending push notifications to the given endpoint.
+pub struct DeletePushSubscription {
+  pub endpoint: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Disable notification emails, using the signed token from the link in an email. Works without
+/// login.
+pub struct UnsubscribeEmail {
+  pub token: String,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+pub struct ListPushSubscriptionsResponse {
+  pub push_subscriptions: Vec<PushSubscription>,
+  /// The key which browsers need as `applicationServerKey` to create a subscription.
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub vapid_public_key: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Declare your other accounts (`alsoKnownAs`), given as actor urls or `name@instance`. This is
+/// required before moving an account from another instance to this one.
+pub struct UpdateAccountAliases {
+  pub aliases: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+pub struct AccountAliasesResponse {
+  pub aliases: Vec<DbUrl>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Move your account to another one, given as actor url or `name@instance`. The target account
+/// needs to list this account as an alias. Community follows are migrated to the new account.
+pub struct MoveAccount {
+  pub target: String,
+  pub password: SensitiveString,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Hide posts and comments which contain the keyword. By default it matches whole words in post
+/// titles and bodies, and comments.
+pub struct CreateKeywordFilter {
+  pub keyword: String,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub mode: Option<KeywordFilterMode>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub match_title: Option<bool>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub match_body: Option<bool>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub match_url: Option<bool>,
+  /// Only apply the filter in these listings. If empty, it is applied everywhere.
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub listing_types: Option<Vec<ListingType>>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Edit a keyword filter.
+pub struct UpdateKeywordFilter {
+  pub keyword_filter_id: KeywordFilterId,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub keyword: Option<String>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub mode: Option<KeywordFilterMode>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub match_title: Option<bool>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub match_body: Option<bool>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub match_url: Option<bool>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub listing_types: Option<Vec<ListingType>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Delete a keyword filter.
+pub struct DeleteKeywordFilter {
+  pub keyword_filter_id: KeywordFilterId,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+pub struct KeywordFilterResponse {
+  pub keyword_filter: KeywordFilterView,
 }
@@ -1,5 +1,5 @@
 use lemmy_db_schema::{
-  newtypes::{CommentId, CommunityId, DbUrl, LanguageId, PostId, TagId},
+  newtypes::{CommentId, CommunityId, DbUrl, FeedId, LanguageId, PostId, PostPollOptionId, TagId},
   ListingType,
   PostFeatureType,
   PostSortType,
@@ -8,11 +8,12 @@ use lemmy_db_views::structs::{
   CommunityModeratorView,
   CommunityView,
   PaginationCursor,
+  PostRevisionView,
   PostView,
   VoteView,
 };
 use serde::{Deserialize, Serialize};
-use serde_with::skip_serializing_none;
+use serde_with::{formats::CommaSeparator, serde_as, skip_serializing_none, StringWithSeparator};
 #[cfg(feature = "full")]
 use ts_rs::TS;
 
@@ -47,13 +48,41 @@ pub struct CreatePost {
   /// Time when this post should be scheduled. Null means publish immediately.
   #[cfg_attr(feature = "full", ts(optional))]
   pub scheduled_publish_time: Option<i64>,
+  /// Attach a poll to the post.
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub poll: Option<CreatePostPoll>,
+  /// Create the post as a crosspost of another post. Without a url, the url of the original
+  /// post is used.
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub crosspost_of_id: Option<PostId>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A poll for a new post.
+pub struct CreatePostPoll {
+  /// Between 2 and 20 options, which must be unique.
+  pub options: Vec<String>,
+  /// Allow voting for multiple options. Defaults to false.
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub multiple_choice: Option<bool>,
+  /// Unix timestamp after which no more votes are accepted. Null means the poll never ends.
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub end_time: Option<i64>,
 }
 
+#[skip_serializing_none]
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "full", derive(TS))]
 #[cfg_attr(feature = "full", ts(export))]
 pub struct PostResponse {
   pub post_view: PostView,
+  /// When creating a post in a community which warns about duplicate links, this is the
+  /// existing post with the same link.
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub duplicate_of: Option<PostView>,
 }
 
 #[skip_serializing_none]
@@ -78,10 +107,12 @@ pub struct GetPostResponse {
   pub post_view: PostView,
   pub community_view: CommunityView,
   pub moderators: Vec<CommunityModeratorView>,
-  /// A list of cross-posts, or other times / communities this link has been posted to.
+  /// Other discussions of this post: crossposts of the same original post, and other times /
+  /// communities this link has been posted to.
   pub cross_posts: Vec<PostView>,
 }
 
+#[serde_as]
 #[skip_serializing_none]
 #[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "full", derive(TS))]
@@ -101,6 +132,9 @@ pub struct GetPosts {
   pub community_id: Option<CommunityId>,
   #[cfg_attr(feature = "full", ts(optional))]
   pub community_name: Option<String>,
+  /// Only show posts from the communities of this feed. Overrides the listing type.
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub feed_id: Option<FeedId>,
   #[cfg_attr(feature = "full", ts(optional))]
   pub saved_only: Option<bool>,
   #[cfg_attr(feature = "full", ts(optional))]
@@ -126,6 +160,11 @@ pub struct GetPosts {
   #[cfg_attr(feature = "full", ts(optional))]
   /// If true, then only show posts with no comments
   pub no_comments_only: Option<bool>,
+  /// Only show posts which have at least one of these tags, as comma separated list of tag ids.
+  #[cfg_attr(feature = "full", ts(optional, type = "string"))]
+  #[serde_as(as = "Option<StringWithSeparator<CommaSeparator, TagId>>")]
+  #[serde(default)]
+  pub tag_ids: Option<Vec<TagId>>,
   #[cfg_attr(feature = "full", ts(optional))]
   pub page_cursor: Option<PaginationCursor>,
   #[cfg_attr(feature = "full", ts(optional))]
@@ -154,6 +193,16 @@ pub struct CreatePostLike {
   pub score: i16,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Vote in the poll of a post. Votes can't be changed afterwards.
+pub struct VotePostPoll {
+  pub post_id: PostId,
+  /// Exactly one option, unless the poll allows multiple choices.
+  pub option_ids: Vec<PostPollOptionId>,
+}
+
 #[skip_serializing_none]
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "full", derive(TS))]
@@ -263,6 +312,30 @@ pub struct SavePost {
   pub save: bool,
 }
 
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Get notified about all new comments in a post, or below a comment.
+pub struct SubscribeToThread {
+  pub post_id: PostId,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub comment_id: Option<CommentId>,
+  pub subscribe: bool,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Stop notifications for a part of a thread which you are subscribed to.
+pub struct MuteThread {
+  pub post_id: PostId,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub comment_id: Option<CommentId>,
+  pub mute: bool,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "full", derive(TS))]
 #[cfg_attr(feature = "full", ts(export))]
@@ -277,6 +350,8 @@ pub struct GetSiteMetadata {
 /// The site metadata response.
 pub struct GetSiteMetadataResponse {
   pub metadata: LinkMetadata,
+  /// Existing posts which link to the same url.
+  pub existing_posts: Vec<PostView>,
 }
 
 #[skip_serializing_none]
@@ -327,3 +402,19 @@ pub struct ListPostLikes {
 pub struct ListPostLikesResponse {
   pub post_likes: Vec<VoteView>,
 }
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// List the previous versions of a post. Only mods and admins can do this, unless the site makes
+/// the edit history public.
+pub struct ListPostRevisions {
+  pub post_id: PostId,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+pub struct ListPostRevisionsResponse {
+  pub revisions: Vec<PostRevisionView>,
+}
@@ -33,7 +33,7 @@ use serde::{Deserialize, Serialize};
 use std::net::IpAddr;
 use tokio::net::lookup_host;
 use tracing::{info, warn};
-use url::Url;
+use url::{Host, Url};
 use urlencoding::encode;
 use webpage::HTML;
 
@@ -48,6 +48,60 @@ pub fn client_builder(settings: &Settings) -> ClientBuilder {
     .use_rustls_tls()
 }
 
+/// Resolves the host of the url, and throws an error if it points to any internal IP. This prevents
+/// users from making the server send requests into its own network. Skipped in debug builds, where
+/// federation tests run on the local network.
+pub async fn check_url_is_public(url: &Url) -> LemmyResult<()> {
+  if cfg!(debug_assertions) {
+    return Ok(());
+  }
+  let port = url.port_or_known_default().unwrap_or(80);
+  let ips: Vec<IpAddr> = match url.host() {
+    Some(Host::Domain(domain)) => lookup_host((domain, port))
+      .await?
+      .map(|addr| addr.ip())
+      .collect(),
+    Some(Host::Ipv4(ip)) => vec![ip.into()],
+    Some(Host::Ipv6(ip)) => vec![ip.into()],
+    None => Err(FederationError::UrlWithoutDomain)?,
+  };
+  if ips.is_empty() || !ips.into_iter().all(is_public_ip) {
+    Err(LemmyErrorType::InvalidUrl)?
+  }
+  Ok(())
+}
+
+/// Uses logic from nightly IpAddr::is_global.
+// TODO: Replace with IpAddr::is_global() once stabilized
+//       https://doc.rust-lang.org/std/net/enum.IpAddr.html#method.is_global
+fn is_public_ip(ip: IpAddr) -> bool {
+  match ip {
+    IpAddr::V4(addr) => {
+      let [a, b, ..] = addr.octets();
+      !(addr.is_private()
+        || addr.is_loopback()
+        || addr.is_link_local()
+        || addr.is_unspecified()
+        || addr.is_multicast()
+        || addr.is_broadcast()
+        || addr.is_documentation()
+        // shared address space, used for carrier-grade NAT
+        || (a == 100 && (b & 0xc0) == 64))
+    }
+    IpAddr::V6(addr) => match addr.to_ipv4_mapped() {
+      Some(addr) => is_public_ip(addr.into()),
+      None => {
+        let segment = addr.segments()[0];
+        !(addr.is_loopback()
+          || addr.is_unspecified()
+          || addr.is_multicast()
+          || (segment & 0xfe00) == 0xfc00 // is_unique_local
+          || (segment & 0xffc0) == 0xfe80) // is_unicast_link_local
+      }
+    },
+  }
+}
+
 /// Fetches metadata for the given link and optionally generates thumbnail.
 pub async fn fetch_link_metadata(
   url: &Url,
@@ -58,29 +112,7 @@ pub async fn fetch_link_metadata(
     return Err(LemmyErrorType::InvalidUrl.into());
   }
 
-  // Resolve the domain and throw an error if it points to any internal IP,
-  // using logic from nightly IpAddr::is_global.
-  if !cfg!(debug_assertions) {
-    // TODO: Replace with IpAddr::is_global() once stabilized
-    //       https://doc.rust-lang.org/std/net/enum.IpAddr.html#method.is_global
-    let domain = url.domain().ok_or(FederationError::UrlWithoutDomain)?;
-    let invalid_ip = lookup_host((domain.to_owned(), 80))
-      .await?
-      .any(|addr| match addr.ip() {
-        IpAddr::V4(addr) => {
-          addr.is_private() || addr.is_link_local() || addr.is_loopback() || addr.is_multicast()
-        }
-        IpAddr::V6(addr) => {
-          addr.is_loopback()
-                        || addr.is_multicast()
-                        || ((addr.segments()[0] & 0xfe00) == 0xfc00) // is_unique_local
-                        || ((addr.segments()[0] & 0xffc0) == 0xfe80) // is_unicast_link_local
-        }
-      });
-    if invalid_ip {
-      return Err(LemmyErrorType::InvalidUrl.into());
-    }
-  }
+  check_url_is_public(url).await?;
 
   info!("Fetching site metadata for url: {}", url);
   // We only fetch the first MB of data in order to not waste bandwidth especially for large
@@ -530,7 +562,7 @@ mod tests {
 
   use crate::{
     context::LemmyContext,
-    request::{extract_opengraph_data, fetch_link_metadata},
+    request::{extract_opengraph_data, fetch_link_metadata, is_public_ip},
   };
   use lemmy_utils::error::LemmyResult;
   use pretty_assertions::assert_eq;
@@ -607,4 +639,28 @@ mod tests {
 
     Ok(())
   }
+
+  #[test]
+  fn test_is_public_ip() -> LemmyResult<()> {
+    for ip in ["1.1.1.1", "2606:4700:4700::1111"] {
+      assert!(is_public_ip(ip.parse()?));
+    }
+    for ip in [
+      "127.0.0.1",
+      "10.1.2.3",
+      "192.168.0.1",
+      "169.254.169.254",
+      "0.0.0.0",
+      "100.64.0.1",
+      "224.0.0.1",
+      "::1",
+      "::",
+      "fd00::1",
+      "fe80::1",
+      "::ffff:127.0.0.1",
+    ] {
+      assert!(!is_public_ip(ip.parse()?), "{ip} is not public");
+    }
+    Ok(())
+  }
 }
@@ -0,0 +1,546 @@
+use crate::{
+  context::LemmyContext,
+  send_activity::{ActivityChannel, SendActivityData},
+  utils::{generate_inbox_url, generate_local_apub_endpoint, is_mod_or_admin, EndpointType},
+};
+use activitypub_federation::{config::Data, http_signatures::generate_actor_keypair};
+use chrono::{TimeDelta, Utc};
+use lemmy_db_schema::{
+  aggregates::structs::PersonAggregates,
+  newtypes::{CommunityId, DbUrl, LanguageId, PersonId},
+  source::{
+    automod_rule::AutomodRule,
+    comment::{Comment, CommentInsertForm, CommentUpdateForm},
+    comment_report::{CommentReport, CommentReportForm},
+    community::{Community, CommunityModerator, CommunityModeratorForm},
+    local_site::{LocalSite, LocalSiteUpdateForm},
+    mod_log::moderator::{
+      ModAddCommunity,
+      ModAddCommunityForm,
+      ModAutomodAction,
+      ModAutomodActionForm,
+    },
+    person::{Person, PersonInsertForm},
+    post::{Post, PostUpdateForm},
+    post_report::{PostReport, PostReportForm},
+    tag::PostTagInsertForm,
+  },
+  traits::{ApubActor, Crud, Joinable, Reportable},
+  AutomodAction,
+};
+use lemmy_db_views::structs::{CommunityModeratorView, LocalUserView, SiteView};
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+use regex::RegexBuilder;
+use url::Url;
+
+/// Name of the local bot account which performs the automod actions.
+pub const AUTOMOD_NAME: &str = "automod";
+
+/// The fields of a post or comment which automod rules are checked against.
+struct AutomodTarget<'a> {
+  title: Option<&'a str>,
+  body: Option<&'a str>,
+  url: Option<&'a Url>,
+  language_id: LanguageId,
+  is_post: bool,
+}
+
+/// Applies the automod rules of the community to a new or edited post. Failures are only logged,
+/// so that they don't prevent the post from being created.
+pub async fn run_post_automod(post: &Post, is_new: bool, context: &Data<LemmyContext>) {
+  if let Err(e) = apply_post_automod(post, is_new, context).await {
+    tracing::warn!("automod failed for post {}: {e}", post.id);
+  }
+}
+
+/// Applies the automod rules of the community to a new or edited comment.
+pub async fn run_comment_automod(comment: &Comment, is_new: bool, context: &Data<LemmyContext>) {
+  if let Err(e) = apply_comment_automod(comment, is_new, context).await {
+    tracing::warn!("automod failed for comment {}: {e}", comment.id);
+  }
+}
+
+async fn apply_post_automod(
+  post: &Post,
+  is_new: bool,
+  context: &Data<LemmyContext>,
+) -> LemmyResult<()> {
+  let target = AutomodTarget {
+    title: Some(&post.name),
+    body: post.body.as_deref(),
+    url: post.url.as_ref().map(DbUrl::inner),
+    language_id: post.language_id,
+    is_post: true,
+  };
+  let Some((rules, community)) =
+    matching_rules(post.community_id, post.creator_id, &target, context).await?
+  else {
+    return Ok(());
+  };
+  let automod = automod_person(context).await?;
+  for rule in rules {
+    apply_action(&rule, post, None, &community, &automod, is_new, context).await?;
+  }
+  Ok(())
+}
+
+async fn apply_comment_automod(
+  comment: &Comment,
+  is_new: bool,
+  context: &Data<LemmyContext>,
+) -> LemmyResult<()> {
+  let post = Post::read(&mut context.pool(), comment.post_id).await?;
+  let target = AutomodTarget {
+    title: None,
+    body: Some(&comment.content),
+    url: None,
+    language_id: comment.language_id,
+    is_post: false,
+  };
+  let Some((rules, community)) =
+    matching_rules(post.community_id, comment.creator_id, &target, context).await?
+  else {
+    return Ok(());
+  };
+  let automod = automod_person(context).await?;
+  for rule in rules {
+    apply_action(
+      &rule,
+      &post,
+      Some(comment),
+      &community,
+      &automod,
+      is_new,
+      context,
+    )
+    .await?;
+  }
+  Ok(())
+}
+
+/// Returns the enabled rules which match, or `None` if there is nothing to do. Rules are only
+/// applied in local communities, and never to moderators and admins.
+async fn matching_rules(
+  community_id: CommunityId,
+  author_id: PersonId,
+  target: &AutomodTarget<'_>,
+  context: &LemmyContext,
+) -> LemmyResult<Option<(Vec<AutomodRule>, Community)>> {
+  let rules = AutomodRule::list_enabled(&mut context.pool(), community_id).await?;
+  if rules.is_empty() {
+    return Ok(None);
+  }
+  let community = Community::read(&mut context.pool(), community_id).await?;
+  let author = Person::read(&mut context.pool(), author_id).await?;
+  if !community.local
+    || is_mod_or_admin(&mut context.pool(), &author, community_id)
+      .await
+      .is_ok()
+  {
+    return Ok(None);
+  }
+  let karma = PersonAggregates::read(&mut context.pool(), author_id)
+    .await
+    .map(|a| a.post_score + a.comment_score)
+    .unwrap_or_default();
+
+  let rules = rules
+    .into_iter()
+    .filter(|rule| rule_matches(rule, target, &author, karma))
+    .collect::<Vec<_>>();
+  Ok((!rules.is_empty()).then_some((rules, community)))
+}
+
+fn rule_matches(
+  rule: &AutomodRule,
+  target: &AutomodTarget<'_>,
+  author: &Person,
+  author_karma: i64,
+) -> bool {
+  let checked = if target.is_post {
+    rule.check_posts
+  } else {
+    rule.check_comments
+  };
+  // An invalid regex never matches, it is already rejected when the rule is saved
+  let regex_matches = |regex: &Option<String>, text: Option<&str>| {
+    regex.as_ref().is_none_or(|regex| {
+      text.is_some_and(|text| {
+        RegexBuilder::new(regex)
+          .case_insensitive(true)
+          .build()
+          .is_ok_and(|regex| regex.is_match(text))
+      })
+    })
+  };
+  let domain_matches = rule.url_domain.as_ref().is_none_or(|domain| {
+    target
+      .url
+      .and_then(Url::domain)
+      .is_some_and(|host| host == domain || host.ends_with(&format!(".{domain}")))
+  });
+  let age_matches = rule
+    .max_author_age_days
+    .is_none_or(|days| author.published > Utc::now() - TimeDelta::days(days.into()));
+  let karma_matches = rule
+    .max_author_karma
+    .is_none_or(|karma| author_karma <= karma);
+  let language_matches = rule
+    .language_id
+    .is_none_or(|language_id| language_id == target.language_id);
+
+  checked
+    && regex_matches(&rule.title_regex, target.title)
+    && regex_matches(&rule.body_regex, target.body)
+    && domain_matches
+    && age_matches
+    && karma_matches
+    && language_matches
+}
+
+/// Performs the action of a matching rule and logs it to the modlog. Actions which would have no
+/// effect, like removing an already removed post, are skipped.
+async fn apply_action(
+  rule: &AutomodRule,
+  post: &Post,
+  comment: Option<&Comment>,
+  community: &Community,
+  automod: &Person,
+  is_new: bool,
+  context: &Data<LemmyContext>,
+) -> LemmyResult<()> {
+  let reason = Some(rule.name.clone());
+  match (rule.action, comment) {
+    (AutomodAction::Remove, Some(comment)) => {
+      if comment.removed {
+        return Ok(());
+      }
+      let form = CommentUpdateForm {
+        removed: Some(true),
+        ..Default::default()
+      };
+      let comment = Comment::update(&mut context.pool(), comment.id, &form).await?;
+      ActivityChannel::submit_activity(
+        SendActivityData::RemoveComment {
+          comment,
+          moderator: automod.clone(),
+          community: community.clone(),
+          reason: reason.clone(),
+        },
+        context,
+      )?;
+    }
+    (AutomodAction::Remove, None) => {
+      if post.removed {
+        return Ok(());
+      }
+      let form = PostUpdateForm {
+        removed: Some(true),
+        ..Default::default()
+      };
+      let post = Post::update(&mut context.pool(), post.id, &form).await?;
+      ActivityChannel::submit_activity(
+        SendActivityData::RemovePost {
+          post,
+          moderator: automod.clone(),
+          reason: reason.clone(),
+          removed: true,
+        },
+        context,
+      )?;
+    }
+    (AutomodAction::Lock, None) => {
+      if post.locked {
+        return Ok(());
+      }
+      let form = PostUpdateForm {
+        locked: Some(true),
+        ..Default::default()
+      };
+      let post = Post::update(&mut context.pool(), post.id, &form).await?;
+      ActivityChannel::submit_activity(
+        SendActivityData::LockPost(post, automod.clone(), true),
+        context,
+      )?;
+    }
+    // Reports only go to the moderators of this instance, as the community is local. Reporting
+    // the same post or comment twice fails, which means there is nothing to do.
+    (AutomodAction::Report, Some(comment)) => {
+      let form = CommentReportForm {
+        creator_id: automod.id,
+        comment_id: comment.id,
+        original_comment_text: comment.content.clone(),
+        reason: rule.message.clone().unwrap_or(rule.name.clone()),
+      };
+      if CommentReport::report(&mut context.pool(), &form)
+        .await
+        .is_err()
+      {
+        return Ok(());
+      }
+    }
+    (AutomodAction::Report, None) => {
+      let form = PostReportForm {
+        creator_id: automod.id,
+        post_id: post.id,
+        original_post_name: post.name.clone(),
+        original_post_url: post.url.clone(),
+        original_post_body: post.body.clone(),
+        reason: rule.message.clone().unwrap_or(rule.name.clone()),
+      };
+      if PostReport::report(&mut context.pool(), &form)
+        .await
+        .is_err()
+      {
+        return Ok(());
+      }
+    }
+    (AutomodAction::Tag, None) => {
+      let Some(tag_id) = rule.tag_id else {
+        return Ok(());
+      };
+      if !PostTagInsertForm::add_to_post(&mut context.pool(), post.id, tag_id).await? {
+        return Ok(());
+      }
+    }
+    (AutomodAction::Reply, _) => {
+      let Some(message) = rule.message.clone().filter(|_| is_new) else {
+        return Ok(());
+      };
+      let form = CommentInsertForm {
+        distinguished: Some(true),
+        ..CommentInsertForm::new(automod.id, post.id, message)
+      };
+      let parent_path = comment.map(|c| &c.path);
+      let reply = Comment::create(&mut context.pool(), &form, parent_path)
+        .await
+        .with_lemmy_type(LemmyErrorType::CouldntCreateComment)?;
+      ActivityChannel::submit_activity(SendActivityData::CreateComment(reply), context)?;
+    }
+    // Comments can't be locked or tagged
+    (AutomodAction::Lock | AutomodAction::Tag, Some(_)) => return Ok(()),
+  }
+
+  let form = ModAutomodActionForm {
+    mod_person_id: automod.id,
+    community_id: community.id,
+    post_id: post.id,
+    comment_id: comment.map(|c| c.id),
+    action: rule.action,
+    reason,
+  };
+  ModAutomodAction::create(&mut context.pool(), &form).await?;
+  Ok(())
+}
+
+/// Returns the local bot account which performs the automod actions, and creates it on first use.
+pub async fn automod_person(context: &LemmyContext) -> LemmyResult<Person> {
+  let local_site = LocalSite::read(&mut context.pool()).await?;
+  if let Some(person_id) = local_site.automod_person_id {
+    return Ok(Person::read(&mut context.pool(), person_id).await?);
+  }
+
+  // The name can't be registered, but a user may have taken it before it was reserved
+  let names =
+    std::iter::once(AUTOMOD_NAME.to_string()).chain((1..10).map(|i| format!("{AUTOMOD_NAME}_{i}")));
+  for name in names {
+    if let Some(person) = create_automod_person(&name, context).await? {
+      let form = LocalSiteUpdateForm {
+        automod_person_id: Some(Some(person.id)),
+        ..Default::default()
+      };
+      LocalSite::update(&mut context.pool(), &form).await?;
+      return Ok(person);
+    }
+  }
+  Err(LemmyErrorType::UserAlreadyExists)?
+}
+
+/// Creates the automod account with the given name. If it already exists because of a
+/// concurrent call, the existing account is returned instead. Returns `None` if the name is taken
+/// by a different account.
+async fn create_automod_person(name: &str, context: &LemmyContext) -> LemmyResult<Option<Person>> {
+  let site_view = SiteView::read_local(&mut context.pool()).await?;
+  let actor_keypair = generate_actor_keypair()?;
+  let actor_id = generate_local_apub_endpoint(
+    EndpointType::Person,
+    name,
+    &context.settings().get_protocol_and_hostname(),
+  )?;
+  let person_form = PersonInsertForm {
+    actor_id: Some(actor_id.clone()),
+    inbox_url: Some(generate_inbox_url()?),
+    private_key: Some(actor_keypair.private_key),
+    bot_account: Some(true),
+    ..PersonInsertForm::new(
+      name.to_string(),
+      actor_keypair.public_key,
+      site_view.site.instance_id,
+    )
+  };
+  if let Ok(person) = Person::create(&mut context.pool(), &person_form).await {
+    return Ok(Some(person));
+  }
+
+  let Some(existing) = Person::read_from_apub_id(&mut context.pool(), &actor_id).await? else {
+    return Ok(None);
+  };
+  // Registered users always have a local user, the automod account doesn't
+  let is_registered = LocalUserView::read_person(&mut context.pool(), existing.id)
+    .await
+    .is_ok();
+  Ok((existing.bot_account && !is_registered).then_some(existing))
+}
+
+/// Makes the automod account a moderator of the community, which is necessary for its actions
+/// to be accepted by other instances.
+pub async fn add_automod_moderator(
+  community: &Community,
+  added_by: &Person,
+  context: &Data<LemmyContext>,
+) -> LemmyResult<()> {
+  let automod = automod_person(context).await?;
+  let is_moderator = CommunityModeratorView::check_is_community_moderator(
+    &mut context.pool(),
+    community.id,
+    automod.id,
+  )
+  .await
+  .is_ok();
+  if is_moderator {
+    return Ok(());
+  }
+
+  let form = CommunityModeratorForm {
+    community_id: community.id,
+    person_id: automod.id,
+  };
+  CommunityModerator::join(&mut context.pool(), &form).await?;
+
+  let form = ModAddCommunityForm {
+    mod_person_id: added_by.id,
+    other_person_id: automod.id,
+    community_id: community.id,
+    removed: Some(false),
+  };
+  ModAddCommunity::create(&mut context.pool(), &form).await?;
+
+  ActivityChannel::submit_activity(
+    SendActivityData::AddModToCommunity {
+      moderator: added_by.clone(),
+      community_id: community.id,
+      target: automod.id,
+      added: true,
+    },
+    context,
+  )
+}
+
+#[cfg(test)]
+#[expect(clippy::indexing_slicing)]
+mod tests {
+
+  use super::*;
+  use lemmy_db_schema::{
+    source::{
+      automod_rule::AutomodRuleInsertForm,
+      community::CommunityInsertForm,
+      instance::Instance,
+      local_site::LocalSiteInsertForm,
+      local_site_rate_limit::{LocalSiteRateLimit, LocalSiteRateLimitInsertForm},
+      local_user::{LocalUser, LocalUserInsertForm},
+      post::PostInsertForm,
+      site::{Site, SiteInsertForm},
+    },
+    ModlogActionType,
+  };
+  use lemmy_db_views::{
+    combined::modlog_combined_view::ModlogCombinedQuery,
+    structs::ModlogCombinedView,
+  };
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_automod_remove_post() -> LemmyResult<()> {
+    let context = LemmyContext::init_test_context().await;
+    let pool = &mut context.pool();
+
+    let instance = Instance::read_or_create(pool, "my_domain.tld".to_string()).await?;
+    let site = Site::create(pool, &SiteInsertForm::new("site".to_string(), instance.id)).await?;
+    let local_site = LocalSite::create(pool, &LocalSiteInsertForm::new(site.id)).await?;
+    LocalSiteRateLimit::create(pool, &LocalSiteRateLimitInsertForm::new(local_site.id)).await?;
+
+    let person = Person::create(pool, &PersonInsertForm::test_form(instance.id, "spammer")).await?;
+    // A user who registered the name before it was reserved
+    let protocol_and_hostname = context.settings().get_protocol_and_hostname();
+    let user_form = PersonInsertForm {
+      actor_id: Some(generate_local_apub_endpoint(
+        EndpointType::Person,
+        AUTOMOD_NAME,
+        &protocol_and_hostname,
+      )?),
+      ..PersonInsertForm::test_form(instance.id, AUTOMOD_NAME)
+    };
+    let user = Person::create(pool, &user_form).await?;
+    LocalUser::create(pool, &LocalUserInsertForm::test_form(user.id), vec![]).await?;
+    let community_form = CommunityInsertForm::new(
+      instance.id,
+      "automod_community".to_string(),
+      "nada".to_owned(),
+      "pubkey".to_string(),
+    );
+    let community = Community::create(pool, &community_form).await?;
+    let rule_form = AutomodRuleInsertForm {
+      title_regex: Some("^buy".to_string()),
+      ..AutomodRuleInsertForm::new(
+        community.id,
+        person.id,
+        "no ads".to_string(),
+        AutomodAction::Remove,
+      )
+    };
+    AutomodRule::create(pool, &rule_form).await?;
+
+    let spam = PostInsertForm::new("BUY cheap pills".to_string(), person.id, community.id);
+    let spam = Post::create(pool, &spam).await?;
+    let normal = PostInsertForm::new("Hello world".to_string(), person.id, community.id);
+    let normal = Post::create(pool, &normal).await?;
+
+    apply_post_automod(&spam, true, &context).await?;
+    apply_post_automod(&normal, true, &context).await?;
+    // Removed posts are not removed again
+    let spam = Post::read(pool, spam.id).await?;
+    apply_post_automod(&spam, false, &context).await?;
+
+    assert!(spam.removed);
+    assert!(!Post::read(pool, normal.id).await?.removed);
+
+    let automod = automod_person(&context).await?;
+    assert!(automod.bot_account);
+    assert_eq!("automod_1", automod.name);
+    assert_eq!(
+      Some(automod.id),
+      LocalSite::read(pool).await?.automod_person_id
+    );
+
+    let modlog = ModlogCombinedQuery {
+      type_: Some(ModlogActionType::ModAutomodAction),
+      ..Default::default()
+    }
+    .list(pool)
+    .await?;
+    assert_eq!(1, modlog.len());
+    let ModlogCombinedView::ModAutomodAction(view) = &modlog[0] else {
+      panic!("wrong modlog type");
+    };
+    assert_eq!(spam.id, view.post.id);
+    assert_eq!(Some("no ads".to_string()), view.mod_automod_action.reason);
+    assert_eq!(AutomodAction::Remove, view.mod_automod_action.action);
+
+    Instance::delete(pool, instance.id).await?;
+
+    Ok(())
+  }
+}
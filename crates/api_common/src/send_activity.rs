@@ -58,6 +58,11 @@ pub enum SendActivityData {
     community: Community,
     score: i16,
   },
+  VotePostPoll {
+    post: Post,
+    voter: Person,
+    option_names: Vec<String>,
+  },
   FollowCommunity(Community, Person, bool),
   AcceptFollower(CommunityId, PersonId),
   RejectFollower(CommunityId, PersonId),
@@ -93,6 +98,7 @@ pub enum SendActivityData {
   UpdatePrivateMessage(PrivateMessageView),
   DeletePrivateMessage(Person, PrivateMessage, bool),
   DeleteUser(Person, bool),
+  MoveUser(Person, Person),
   CreateReport {
     object_id: Url,
     actor: Person,
@@ -0,0 +1,100 @@
+use crate::{context::LemmyContext, utils::send_notification_email_to_user};
+use lemmy_db_schema::{
+  newtypes::LocalUserId,
+  source::{
+    local_user_email_digest::LocalUserEmailDigest,
+    queued_email_notification::QueuedEmailNotification,
+  },
+};
+use lemmy_db_views::structs::LocalUserView;
+use lemmy_utils::error::LemmyResult;
+use tracing::warn;
+
+/// Sends the queued notifications of all users whose digest period is over, combined into a
+/// single email per user.
+pub async fn send_email_digests(context: &LemmyContext) -> LemmyResult<()> {
+  let users = QueuedEmailNotification::list_due_users(&mut context.pool()).await?;
+  for local_user_id in users {
+    if let Err(e) = send_digest(local_user_id, context).await {
+      warn!(
+        "Failed to send email digest to user {}: {e}",
+        local_user_id.0
+      );
+    }
+  }
+  Ok(())
+}
+
+async fn send_digest(local_user_id: LocalUserId, context: &LemmyContext) -> LemmyResult<()> {
+  let local_user_view = LocalUserView::read(&mut context.pool(), local_user_id).await?;
+  let notifications = QueuedEmailNotification::list(&mut context.pool(), local_user_id).await?;
+  let ids = notifications.iter().map(|n| n.id).collect::<Vec<_>>();
+
+  // The user may have been banned or disabled notification emails since they were queued, in
+  // which case the notifications are dropped.
+  let local_user = &local_user_view.local_user;
+  if let Some(email) = &local_user.email {
+    if !local_user_view.person.banned && local_user.send_notifications_to_email {
+      let (subject, body) = digest_email(&notifications, &context.settings().hostname);
+      send_notification_email_to_user(&local_user_view, email, &subject, &body, context).await?;
+    }
+  }
+
+  QueuedEmailNotification::delete(&mut context.pool(), &ids).await?;
+  LocalUserEmailDigest::mark_sent(&mut context.pool(), local_user_id).await?;
+  Ok(())
+}
+
+/// Combines the notifications into the subject and html body of a single email.
+fn digest_email(notifications: &[QueuedEmailNotification], hostname: &str) -> (String, String) {
+  if let [notification] = notifications {
+    return (notification.subject.clone(), notification.body.clone());
+  }
+  let subject = format!("{} new notifications on {hostname}", notifications.len());
+  let body = notifications
+    .iter()
+    .map(|n| n.body.as_str())
+    .collect::<Vec<_>>()
+    .join("<hr>");
+  (subject, body)
+}
+
+#[cfg(test)]
+mod tests {
+
+  use super::*;
+  use chrono::Utc;
+  use lemmy_db_schema::newtypes::QueuedEmailNotificationId;
+  use pretty_assertions::assert_eq;
+
+  fn notification(id: i32, subject: &str, body: &str) -> QueuedEmailNotification {
+    QueuedEmailNotification {
+      id: QueuedEmailNotificationId(id),
+      local_user_id: LocalUserId(1),
+      subject: subject.to_string(),
+      body: body.to_string(),
+      published: Utc::now(),
+    }
+  }
+
+  #[test]
+  fn test_digest_email() {
+    let reply = notification(1, "Reply from alice", "<h1>Comment reply</h1>");
+    let mention = notification(2, "Mentioned by bob", "<h1>Person Mention</h1>");
+
+    assert_eq!(
+      (
+        "Reply from alice".to_string(),
+        "<h1>Comment reply</h1>".to_string()
+      ),
+      digest_email(&[reply.clone()], "example.com")
+    );
+    assert_eq!(
+      (
+        "2 new notifications on example.com".to_string(),
+        "<h1>Comment reply</h1><hr><h1>Person Mention</h1>".to_string()
+      ),
+      digest_email(&[reply, mention], "example.com")
+    );
+  }
+}
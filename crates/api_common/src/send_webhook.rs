@@ -0,0 +1,406 @@
+use crate::{context::LemmyContext, request::check_url_is_public, send_activity::SendActivityData};
+use chrono::{DateTime, TimeDelta, Utc};
+use hmac::{Hmac, Mac};
+use lemmy_db_schema::{
+  newtypes::{CommentId, CommunityId, PersonId, PostId, WebhookId},
+  source::{
+    post::Post,
+    webhook::{Webhook, WebhookDelivery, WebhookDeliveryInsertForm},
+  },
+  traits::Crud,
+  WebhookEventType,
+};
+use lemmy_utils::{
+  error::{LemmyErrorType, LemmyResult},
+  spawn_try_task,
+};
+use reqwest::header::CONTENT_TYPE;
+use serde::Serialize;
+use sha2::Sha256;
+use std::{collections::HashMap, time::Duration};
+use tracing::warn;
+use url::Url;
+
+/// Deliveries which are not finished after this time are attempted again.
+const DELIVERY_LEASE: TimeDelta = TimeDelta::minutes(5);
+
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Number of failed deliveries which are retried at once.
+const RETRY_BATCH_SIZE: i64 = 50;
+
+/// The request body which is sent to webhooks.
+#[derive(Serialize)]
+struct WebhookPayload<'a, T: Serialize> {
+  event: WebhookEventType,
+  community_id: Option<CommunityId>,
+  published: DateTime<Utc>,
+  data: &'a T,
+}
+
+/// The data of a [WebhookEventType::ModAction] event.
+#[derive(Serialize)]
+#[serde(tag = "action")]
+enum ModActionData<'a> {
+  RemovePost {
+    moderator_id: PersonId,
+    post_id: PostId,
+    reason: &'a Option<String>,
+    removed: bool,
+  },
+  LockPost {
+    moderator_id: PersonId,
+    post_id: PostId,
+    locked: bool,
+  },
+  FeaturePost {
+    moderator_id: PersonId,
+    post_id: PostId,
+    featured: bool,
+  },
+  RemoveComment {
+    moderator_id: PersonId,
+    comment_id: CommentId,
+    reason: &'a Option<String>,
+    removed: bool,
+  },
+  RemoveCommunity {
+    moderator_id: PersonId,
+    community_id: CommunityId,
+    reason: &'a Option<String>,
+    removed: bool,
+  },
+  AddModToCommunity {
+    moderator_id: PersonId,
+    community_id: CommunityId,
+    person_id: PersonId,
+    added: bool,
+  },
+  BanFromCommunity {
+    moderator_id: PersonId,
+    community_id: CommunityId,
+    person_id: PersonId,
+    reason: &'a Option<String>,
+    ban: bool,
+    expires: Option<i64>,
+  },
+  BanFromSite {
+    moderator_id: PersonId,
+    person_id: PersonId,
+    reason: &'a Option<String>,
+    ban: bool,
+    expires: Option<i64>,
+  },
+}
+
+/// The data of a [WebhookEventType::ReportCreated] event.
+#[derive(Serialize)]
+struct ReportData<'a> {
+  object_id: &'a Url,
+  creator_id: PersonId,
+  reason: &'a str,
+}
+
+/// Sends the webhook event which corresponds to an outgoing activity, if any.
+pub async fn send_activity_webhooks(
+  data: &SendActivityData,
+  context: &LemmyContext,
+) -> LemmyResult<()> {
+  use SendActivityData::*;
+  use WebhookEventType::*;
+  match data {
+    CreatePost(post) => send_webhook_event(PostCreated, Some(post.community_id), post, context),
+    UpdatePost(post) => send_webhook_event(PostUpdated, Some(post.community_id), post, context),
+    CreateComment(comment) | UpdateComment(comment) => {
+      let event = if matches!(data, CreateComment(_)) {
+        CommentCreated
+      } else {
+        CommentUpdated
+      };
+      let post = Post::read(&mut context.pool(), comment.post_id).await?;
+      send_webhook_event(event, Some(post.community_id), comment, context)
+    }
+    CreateReport {
+      object_id,
+      actor,
+      community,
+      reason,
+    } => {
+      let report = ReportData {
+        object_id,
+        creator_id: actor.id,
+        reason,
+      };
+      send_webhook_event(ReportCreated, Some(community.id), &report, context)
+    }
+    RemovePost {
+      post,
+      moderator,
+      reason,
+      removed,
+    } => {
+      let action = ModActionData::RemovePost {
+        moderator_id: moderator.id,
+        post_id: post.id,
+        reason,
+        removed: *removed,
+      };
+      send_webhook_event(ModAction, Some(post.community_id), &action, context)
+    }
+    LockPost(post, moderator, locked) => {
+      let action = ModActionData::LockPost {
+        moderator_id: moderator.id,
+        post_id: post.id,
+        locked: *locked,
+      };
+      send_webhook_event(ModAction, Some(post.community_id), &action, context)
+    }
+    FeaturePost(post, moderator, featured) => {
+      let action = ModActionData::FeaturePost {
+        moderator_id: moderator.id,
+        post_id: post.id,
+        featured: *featured,
+      };
+      send_webhook_event(ModAction, Some(post.community_id), &action, context)
+    }
+    RemoveComment {
+      comment,
+      moderator,
+      community,
+      reason,
+    } => {
+      let action = ModActionData::RemoveComment {
+        moderator_id: moderator.id,
+        comment_id: comment.id,
+        reason,
+        removed: comment.removed,
+      };
+      send_webhook_event(ModAction, Some(community.id), &action, context)
+    }
+    RemoveCommunity {
+      moderator,
+      community,
+      reason,
+      removed,
+    } => {
+      let action = ModActionData::RemoveCommunity {
+        moderator_id: moderator.id,
+        community_id: community.id,
+        reason,
+        removed: *removed,
+      };
+      send_webhook_event(ModAction, Some(community.id), &action, context)
+    }
+    AddModToCommunity {
+      moderator,
+      community_id,
+      target,
+      added,
+    } => {
+      let action = ModActionData::AddModToCommunity {
+        moderator_id: moderator.id,
+        community_id: *community_id,
+        person_id: *target,
+        added: *added,
+      };
+      send_webhook_event(ModAction, Some(*community_id), &action, context)
+    }
+    BanFromCommunity {
+      moderator,
+      community_id,
+      target,
+      data,
+    } => {
+      let action = ModActionData::BanFromCommunity {
+        moderator_id: moderator.id,
+        community_id: *community_id,
+        person_id: target.id,
+        reason: &data.reason,
+        ban: data.ban,
+        expires: data.expires,
+      };
+      send_webhook_event(ModAction, Some(*community_id), &action, context)
+    }
+    BanFromSite {
+      moderator,
+      banned_user,
+      reason,
+      ban,
+      expires,
+      ..
+    } => {
+      let action = ModActionData::BanFromSite {
+        moderator_id: moderator.id,
+        person_id: banned_user.id,
+        reason,
+        ban: *ban,
+        expires: *expires,
+      };
+      send_webhook_event(ModAction, None, &action, context)
+    }
+    _ => Ok(()),
+  }
+}
+
+/// Queues the event for all webhooks which are subscribed to it, and attempts to deliver it in
+/// the background. Events without a community are only sent to site-wide webhooks.
+pub fn send_webhook_event<T: Serialize>(
+  event: WebhookEventType,
+  community_id: Option<CommunityId>,
+  data: &T,
+  context: &LemmyContext,
+) -> LemmyResult<()> {
+  let payload = serde_json::to_string(&WebhookPayload {
+    event,
+    community_id,
+    published: Utc::now(),
+    data,
+  })?;
+  let context = context.clone();
+  spawn_try_task(async move {
+    let webhooks = Webhook::list_for_event(&mut context.pool(), event, community_id).await?;
+    if webhooks.is_empty() {
+      return Ok(());
+    }
+    let next_attempt = Utc::now() + DELIVERY_LEASE;
+    let forms = webhooks
+      .iter()
+      .map(|webhook| WebhookDeliveryInsertForm {
+        webhook_id: webhook.id,
+        event,
+        payload: payload.clone(),
+        next_attempt,
+      })
+      .collect::<Vec<_>>();
+    let deliveries = WebhookDelivery::create(&mut context.pool(), &forms).await?;
+    let webhooks = webhooks.into_iter().map(|w| (w.id, w)).collect();
+    deliver(deliveries, webhooks, &context).await
+  });
+  Ok(())
+}
+
+/// Attempts failed webhook deliveries again, once their retry time has come.
+pub async fn retry_webhook_deliveries(context: &LemmyContext) -> LemmyResult<()> {
+  loop {
+    let deliveries =
+      WebhookDelivery::claim_due(&mut context.pool(), RETRY_BATCH_SIZE, DELIVERY_LEASE).await?;
+    if deliveries.is_empty() {
+      return Ok(());
+    }
+    let mut webhooks = HashMap::new();
+    for delivery in &deliveries {
+      if !webhooks.contains_key(&delivery.webhook_id) {
+        let webhook = Webhook::read(&mut context.pool(), delivery.webhook_id).await?;
+        webhooks.insert(webhook.id, webhook);
+      }
+    }
+    deliver(deliveries, webhooks, context).await?;
+  }
+}
+
+async fn deliver(
+  deliveries: Vec<WebhookDelivery>,
+  webhooks: HashMap<WebhookId, Webhook>,
+  context: &LemmyContext,
+) -> LemmyResult<()> {
+  for delivery in deliveries {
+    let Some(webhook) = webhooks.get(&delivery.webhook_id) else {
+      continue;
+    };
+    let (status, error) = if webhook.enabled {
+      deliver_one(&delivery, webhook, context).await
+    } else {
+      (None, Some("Webhook is disabled".to_string()))
+    };
+    if let Some(error) = &error {
+      warn!(
+        "Failed to deliver webhook {} to {}: {error}",
+        delivery.id, webhook.url
+      );
+    }
+    WebhookDelivery::finish_attempt(&mut context.pool(), &delivery, status, error).await?;
+  }
+  Ok(())
+}
+
+/// Sends a single delivery, and returns the http status and error, if any.
+async fn deliver_one(
+  delivery: &WebhookDelivery,
+  webhook: &Webhook,
+  context: &LemmyContext,
+) -> (Option<i32>, Option<String>) {
+  // Check again, as the domain may resolve to a different address than on creation
+  let is_public = match Url::parse(&webhook.url) {
+    Ok(url) => check_url_is_public(&url).await.is_ok(),
+    Err(_) => false,
+  };
+  if !is_public {
+    return (
+      None,
+      Some("Url does not point to a public address".to_string()),
+    );
+  }
+  let signature = match sign_payload(&webhook.secret, &delivery.payload) {
+    Ok(signature) => signature,
+    Err(e) => return (None, Some(e.to_string())),
+  };
+  let res = context
+    .client()
+    .post(&webhook.url)
+    .timeout(DELIVERY_TIMEOUT)
+    .header(CONTENT_TYPE, "application/json")
+    .header("X-Lemmy-Event", delivery.event.to_string())
+    .header("X-Lemmy-Delivery", delivery.id.to_string())
+    .header("X-Lemmy-Signature", format!("sha256={signature}"))
+    .body(delivery.payload.clone())
+    .send()
+    .await;
+  match res {
+    Ok(res) => {
+      let status = res.status();
+      let error = (!status.is_success()).then(|| format!("Unexpected response status {status}"));
+      (Some(status.as_u16().into()), error)
+    }
+    // Only a generic description is stored, as the error message is visible to moderators
+    Err(e) => {
+      let error = if e.is_timeout() {
+        "Request timed out"
+      } else if e.is_connect() {
+        "Failed to connect"
+      } else {
+        "Request failed"
+      };
+      warn!(
+        "Failed to deliver webhook {} to {}: {e}",
+        delivery.id, webhook.url
+      );
+      (None, Some(error.to_string()))
+    }
+  }
+}
+
+/// Hex encoded HMAC-SHA256 of the request body, so that the receiver can verify that it was sent by
+/// this instance.
+fn sign_payload(secret: &str, payload: &str) -> LemmyResult<String> {
+  let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+    .map_err(|e| LemmyErrorType::Unknown(e.to_string()))?;
+  mac.update(payload.as_bytes());
+  Ok(hex::encode(mac.finalize().into_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+
+  use super::*;
+  use pretty_assertions::assert_eq;
+
+  #[test]
+  fn test_sign_payload() -> LemmyResult<()> {
+    // Test vector from RFC 4231
+    assert_eq!(
+      "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843",
+      sign_payload("Jefe", "what do ya want for nothing?")?
+    );
+    Ok(())
+  }
+}
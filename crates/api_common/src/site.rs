@@ -2,25 +2,34 @@ use crate::federate_retry_sleep_duration;
 use chrono::{DateTime, Utc};
 use lemmy_db_schema::{
   newtypes::{
+    ActivityId,
     CommentId,
     CommunityId,
     InstanceId,
     LanguageId,
+    OutgoingEmailId,
     PersonId,
     PostId,
     RegistrationApplicationId,
+    ScheduledJobId,
   },
   source::{
     community::Community,
+    federation_limit::FederationLimit,
     federation_queue_state::FederationQueueState,
     instance::Instance,
     language::Language,
     local_site_url_blocklist::LocalSiteUrlBlocklist,
     oauth_provider::{OAuthProvider, PublicOAuthProvider},
+    outgoing_email::OutgoingEmail,
     person::Person,
+    scheduled_job::{ScheduledJob, ScheduledJobRun},
     tagline::Tagline,
   },
   CommentSortType,
+  EmailDigestMode,
+  FederationBlocklistImportAction,
+  FederationBlocklistSkipReason,
   FederationMode,
   ListingType,
   ModlogActionType,
@@ -35,6 +44,7 @@ use lemmy_db_views::structs::{
   CommunityFollowerView,
   CommunityModeratorView,
   CommunityView,
+  KeywordFilterView,
   LocalUserView,
   ModlogCombinedPaginationCursor,
   ModlogCombinedView,
@@ -238,6 +248,8 @@ pub struct CreateSite {
   pub comment_downvotes: Option<FederationMode>,
   #[cfg_attr(feature = "full", ts(optional))]
   pub disable_donation_dialog: Option<bool>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub edit_history_public: Option<bool>,
 }
 
 #[skip_serializing_none]
@@ -369,6 +381,10 @@ pub struct EditSite {
   /// donations.
   #[cfg_attr(feature = "full", ts(optional))]
   pub disable_donation_dialog: Option<bool>,
+  /// Whether everyone can see the edit history of posts and comments, instead of only mods and
+  /// admins.
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub edit_history_public: Option<bool>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -431,6 +447,8 @@ pub struct MyUserInfo {
   pub instance_blocks: Vec<Instance>,
   pub person_blocks: Vec<Person>,
   pub discussion_languages: Vec<LanguageId>,
+  pub keyword_filters: Vec<KeywordFilterView>,
+  pub email_digest_mode: EmailDigestMode,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -453,6 +471,9 @@ pub struct ReadableFederationState {
   /// timestamp of the next retry attempt (null if fail count is 0)
   #[cfg_attr(feature = "full", ts(optional))]
   next_retry: Option<DateTime<Utc>>,
+  /// how many activities were not sent to the instance yet. only shown to admins
+  #[cfg_attr(feature = "full", ts(optional))]
+  activities_behind: Option<i64>,
 }
 
 #[allow(clippy::expect_used)]
@@ -464,10 +485,29 @@ impl From<FederationQueueState> for ReadableFederationState {
           .expect("sleep duration longer than 2**63 ms (262 million years)")
       }),
       internal_state,
+      activities_behind: None,
     }
   }
 }
 
+impl ReadableFederationState {
+  /// Adds the lag of the queue, given the id of the newest activity.
+  pub fn with_activities_behind(self, latest_activity_id: ActivityId) -> Self {
+    let activities_behind = self
+      .internal_state
+      .last_successful_id
+      .map(|id| (latest_activity_id.0 - id.0).max(0));
+    ReadableFederationState {
+      activities_behind,
+      ..self
+    }
+  }
+
+  pub fn activities_behind(&self) -> Option<i64> {
+    self.activities_behind
+  }
+}
+
 #[skip_serializing_none]
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "full", derive(TS))]
@@ -479,6 +519,9 @@ pub struct InstanceWithFederationState {
   /// instance
   #[cfg_attr(feature = "full", ts(optional))]
   pub federation_state: Option<ReadableFederationState>,
+  /// restrictions for content from this instance
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub limit: Option<FederationLimit>,
 }
 
 #[skip_serializing_none]
@@ -615,3 +658,182 @@ pub struct AdminAllowInstanceParams {
   #[cfg_attr(feature = "full", ts(optional))]
   pub reason: Option<String>,
 }
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Restricts content from an instance without blocking it. All limits are removed if every flag is
+/// false.
+pub struct AdminLimitInstanceParams {
+  pub instance: String,
+  /// Only show content of the instance to users who follow its community or creator
+  pub limited: bool,
+  /// Dont store or proxy images and thumbnails of the instance
+  pub reject_media: bool,
+  /// Mark all posts of the instance as nsfw
+  pub force_nsfw: bool,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub reason: Option<String>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Blocks and limits the instances of a blocklist in the Mastodon CSV format, with the columns
+/// `domain,severity,reject_media,reject_reports,public_comment,obfuscate`. Instances are never
+/// unblocked or unlimited by an import.
+pub struct ImportFederationBlocklist {
+  pub csv: String,
+  /// Only return the changes, without applying them
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub dry_run: Option<bool>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// The change made by a line of an imported blocklist.
+pub struct FederationBlocklistImportEntry {
+  pub domain: String,
+  pub action: FederationBlocklistImportAction,
+  /// Whether the instance is limited after the import
+  pub limited: bool,
+  /// Whether media of the instance is rejected after the import
+  pub reject_media: bool,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub reason: Option<String>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub skip_reason: Option<FederationBlocklistSkipReason>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+pub struct ImportFederationBlocklistResponse {
+  pub entries: Vec<FederationBlocklistImportEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// The blocked and limited instances, in the Mastodon CSV format.
+pub struct ExportFederationBlocklistResponse {
+  pub csv: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// The outgoing federation queues of all instances, the ones furthest behind first.
+pub struct ListFederationQueuesResponse {
+  pub instances: Vec<InstanceWithFederationState>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Retry sending to an instance immediately, instead of waiting until the next retry after
+/// failures. Applied by the federation worker within a minute.
+pub struct ResetFederationBackoff {
+  pub instance_id: InstanceId,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Drop all activities which were not sent to an instance yet, and continue with the next new
+/// activity. Applied by the federation worker within a minute.
+pub struct SkipFederationBacklog {
+  pub instance_id: InstanceId,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Stop or continue sending activities to an instance. Unlike blocking, activities are still
+/// queued while paused, and sent after resuming.
+pub struct PauseFederation {
+  pub instance_id: InstanceId,
+  pub paused: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// The scheduled background jobs, with their current status.
+pub struct ListScheduledJobsResponse {
+  pub jobs: Vec<ScheduledJob>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Fetches the history of scheduled job runs, newest first.
+pub struct ListScheduledJobRuns {
+  /// Only show runs of this job
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub job_id: Option<ScheduledJobId>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub page: Option<i64>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+pub struct ListScheduledJobRunsResponse {
+  pub runs: Vec<ScheduledJobRun>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Run a scheduled job as soon as possible, without waiting for its next scheduled run.
+pub struct RunScheduledJob {
+  pub name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+pub struct ScheduledJobResponse {
+  pub job: ScheduledJob,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Fetches the emails which could not be sent, newest first.
+pub struct ListFailedEmails {
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub page: Option<i64>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+pub struct ListFailedEmailsResponse {
+  pub emails: Vec<OutgoingEmail>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Send a failed email again.
+pub struct RetryEmail {
+  pub email_id: OutgoingEmailId,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+pub struct OutgoingEmailResponse {
+  pub email: OutgoingEmail,
+}
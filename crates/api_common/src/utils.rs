@@ -1,12 +1,14 @@
 use crate::{
-  claims::Claims,
+  claims::{Claims, UnsubscribeClaims},
   context::LemmyContext,
+  email_queue::queue_email,
   request::{
+    check_url_is_public,
     delete_image_from_pictrs,
     fetch_pictrs_proxied_image_details,
     purge_image_from_pictrs,
   },
-  site::{FederatedInstances, InstanceWithFederationState},
+  site::{FederatedInstances, InstanceWithFederationState, ReadableFederationState},
 };
 use actix_web::{http::header::Header, HttpRequest};
 use actix_web_httpauth::headers::authorization::{Authorization, Bearer};
@@ -16,50 +18,64 @@ use lemmy_db_schema::{
   aggregates::structs::{PersonPostAggregates, PersonPostAggregatesForm},
   newtypes::{CommentId, CommunityId, DbUrl, InstanceId, PersonId, PostId, PostOrCommentId},
   source::{
+    activity::SentActivity,
+    api_token::ApiToken,
     comment::{Comment, CommentLike, CommentUpdateForm},
     community::{Community, CommunityModerator, CommunityUpdateForm},
     community_block::CommunityBlock,
     email_verification::{EmailVerification, EmailVerificationForm},
+    feed::Feed,
     images::{ImageDetails, RemoteImage},
     instance::Instance,
     instance_block::InstanceBlock,
     local_site::LocalSite,
     local_site_rate_limit::LocalSiteRateLimit,
     local_site_url_blocklist::LocalSiteUrlBlocklist,
+    local_user_email_digest::LocalUserEmailDigest,
+    local_user_keyword_filter::LocalUserKeywordFilter,
     mod_log::moderator::{
       ModRemoveComment,
       ModRemoveCommentForm,
       ModRemovePost,
       ModRemovePostForm,
     },
+    modmail::ModmailThread,
     oauth_account::OAuthAccount,
+    outgoing_email::OutgoingEmailInsertForm,
     password_reset_request::PasswordResetRequest,
     person::{Person, PersonUpdateForm},
     person_block::PersonBlock,
     post::{Post, PostLike},
+    push_subscription::PushSubscription,
+    queued_email_notification::{QueuedEmailNotification, QueuedEmailNotificationInsertForm},
     registration_application::RegistrationApplication,
     site::Site,
   },
   traits::{Crud, Likeable},
   utils::DbPool,
+  ApiTokenScope,
   CommunityVisibility,
+  EmailDigestMode,
   FederationMode,
+  KeywordFilterMode,
   RegistrationMode,
 };
 use lemmy_db_views::{
   comment::comment_view::CommentQuery,
   structs::{
+    ApiTokenView,
     CommunityFollowerView,
     CommunityModeratorView,
     CommunityPersonBanView,
     CommunityView,
     LocalImageView,
     LocalUserView,
+    PostView,
     SiteView,
   },
 };
 use lemmy_utils::{
-  email::{send_email, translations::Lang},
+  email::translations::Lang,
   error::{LemmyError, LemmyErrorExt, LemmyErrorExt2, LemmyErrorType, LemmyResult},
   rate_limit::{ActionType, BucketConfig},
   settings::{
@@ -70,14 +86,16 @@ use lemmy_utils::{
   utils::{
     markdown::{image_links::markdown_rewrite_image_links, markdown_check_for_blocked_urls},
     slurs::{build_slur_regex, remove_slurs},
-    validation::clean_urls_in_text,
+    validation::{clean_urls_in_text, is_valid_keyword_filter},
   },
+  web_push::{send_web_push, PushMessage, VapidKeys},
   CacheLock,
   CACHE_DURATION_FEDERATION,
 };
 use moka::future::Cache;
 use regex::{escape, Regex, RegexSet};
 use rosetta_i18n::{Language, LanguageId};
+use sha2::{Digest, Sha256};
 use std::sync::LazyLock;
 use tracing::{warn, Instrument};
 use url::{ParseError, Url};
@@ -350,8 +368,10 @@ pub fn check_private_messages_enabled(local_user_view: &LocalUserView) -> Result
   }
 }
 
+/// Admins additionally see how far the federation queue of each instance is behind.
 pub async fn build_federated_instances(
   local_site: &LocalSite,
+  admin: bool,
   pool: &mut DbPool<'_>,
 ) -> LemmyResult<Option<FederatedInstances>> {
   if local_site.federation_enabled {
@@ -359,12 +379,8 @@ pub async fn build_federated_instances(
     let mut allowed = Vec::new();
     let mut blocked = Vec::new();
 
-    let all = Instance::read_all_with_fed_state(pool).await?;
-    for (instance, federation_state, is_blocked, is_allowed) in all {
-      let i = InstanceWithFederationState {
-        instance,
-        federation_state: federation_state.map(std::convert::Into::into),
-      };
+    let all = read_instances_with_federation_state(admin, pool).await?;
+    for (i, is_blocked, is_allowed) in all {
       if is_blocked {
         // blocked instances will only have an entry here if they had been federated with in the
         // past.
@@ -388,6 +404,42 @@ pub async fn build_federated_instances(
   }
 }
 
+/// Returns (instance, blocked, allowed) tuples. The lag of the federation queues is only included
+/// for admins.
+pub async fn read_instances_with_federation_state(
+  admin: bool,
+  pool: &mut DbPool<'_>,
+) -> LemmyResult<Vec<(InstanceWithFederationState, bool, bool)>> {
+  let latest_activity_id = if admin {
+    Some(SentActivity::read_latest_id(pool).await?)
+  } else {
+    None
+  };
+  let all = Instance::read_all_with_fed_state(pool).await?;
+  Ok(
+    all
+      .into_iter()
+      .map(
+        |(instance, federation_state, is_blocked, is_allowed, limit)| {
+          let federation_state = federation_state.map(|state| {
+            let state = ReadableFederationState::from(state);
+            match latest_activity_id {
+              Some(latest_activity_id) => state.with_activities_behind(latest_activity_id),
+              None => state,
+            }
+          });
+          let i = InstanceWithFederationState {
+            instance,
+            federation_state,
+            limit,
+          };
+          (i, is_blocked, is_allowed)
+        },
+      )
+      .collect(),
+  )
+}
+
 /// Checks the password length
 pub fn password_length_check(pass: &str) -> LemmyResult<()> {
   if !(10..=60).contains(&pass.chars().count()) {
@@ -406,30 +458,129 @@ pub fn honeypot_check(honeypot: &Option<String>) -> LemmyResult<()> {
   }
 }
 
+/// Sends a notification email to the user, or queues it for the next digest if the user chose
+/// a digest mode.
 pub async fn send_email_to_user(
   local_user_view: &LocalUserView,
   subject: &str,
   body: &str,
-  settings: &Settings,
+  context: &LemmyContext,
 ) {
   if local_user_view.person.banned || !local_user_view.local_user.send_notifications_to_email {
     return;
   }
+  let Some(user_email) = &local_user_view.local_user.email else {
+    return;
+  };
 
-  if let Some(user_email) = &local_user_view.local_user.email {
-    match send_email(
-      subject,
-      user_email,
-      &local_user_view.person.name,
-      body,
-      settings,
-    )
+  let local_user_id = local_user_view.local_user.id;
+  let mode = LocalUserEmailDigest::read_mode(&mut context.pool(), local_user_id)
     .await
-    {
-      Ok(_o) => _o,
-      Err(e) => warn!("{}", e),
-    };
+    .unwrap_or_default();
+  if mode != EmailDigestMode::Instant {
+    let form =
+      QueuedEmailNotificationInsertForm::new(local_user_id, subject.to_string(), body.to_string());
+    if let Err(e) = QueuedEmailNotification::create(&mut context.pool(), &form).await {
+      warn!("Failed to queue notification email: {e}");
+    }
+    return;
+  }
+
+  let res =
+    send_notification_email_to_user(local_user_view, user_email, subject, body, context).await;
+  if let Err(e) = res {
+    warn!("{}", e);
+  }
+}
+
+/// Queues an email with a footer and header for unsubscribing from notification emails.
+pub async fn send_notification_email_to_user(
+  local_user_view: &LocalUserView,
+  user_email: &str,
+  subject: &str,
+  body: &str,
+  context: &LemmyContext,
+) -> LemmyResult<()> {
+  let token = UnsubscribeClaims::generate(local_user_view.local_user.id, context)?;
+  let unsubscribe_link = format!(
+    "{}/api/v4/account/unsubscribe?token={token}",
+    context.settings().get_protocol_and_hostname(),
+  );
+  let body = format!(
+    "{body}<br><br><a href=\"{unsubscribe_link}\">Unsubscribe from notification emails</a>"
+  );
+  let form = OutgoingEmailInsertForm::new(
+    user_email.to_string(),
+    local_user_view.person.name.clone(),
+    subject.to_string(),
+    body,
+    Some(unsubscribe_link),
+  );
+  queue_email(&form, &mut context.pool(), context.settings()).await
+}
+
+/// Sends a Web Push message to all push subscriptions of the user. This happens in the
+/// background, and subscriptions which are rejected by the push service are removed.
+pub fn send_push_to_user(
+  local_user_view: &LocalUserView,
+  title: &str,
+  body: &str,
+  url: String,
+  context: &LemmyContext,
+) {
+  if local_user_view.person.banned {
+    return;
   }
+  let (Some(private_key), Some(public_key)) = (
+    context.secret().vapid_private_key.clone(),
+    context.secret().vapid_public_key.clone(),
+  ) else {
+    return;
+  };
+  let vapid_keys = VapidKeys {
+    private_key: private_key.into_inner(),
+    public_key,
+  };
+  let message = PushMessage {
+    title: title.to_string(),
+    body: body.to_string(),
+    url,
+  };
+  let local_user_id = local_user_view.local_user.id;
+  let context = context.clone();
+  spawn_try_task(async move {
+    let subject = context.settings().get_protocol_and_hostname();
+    let subscriptions = PushSubscription::list(&mut context.pool(), local_user_id).await?;
+    for subscription in subscriptions {
+      // The endpoint may resolve to a different address than when it was registered
+      let is_public = match Url::parse(&subscription.endpoint) {
+        Ok(endpoint) => check_url_is_public(&endpoint).await.is_ok(),
+        Err(_) => false,
+      };
+      if !is_public {
+        warn!("Skipping push subscription with internal endpoint");
+        continue;
+      }
+      let res = send_web_push(
+        context.client(),
+        &subscription.endpoint,
+        &subscription.p256dh,
+        &subscription.auth,
+        &message,
+        &vapid_keys,
+        &subject,
+      )
+      .await;
+      match res {
+        Ok(true) => {}
+        Ok(false) => {
+          PushSubscription::delete(&mut context.pool(), subscription.id).await?;
+        }
+        Err(e) => warn!("Failed to send push message: {e}"),
+      }
+    }
+    Ok(())
+  });
 }
 
 pub async fn send_password_reset_email(
@@ -450,10 +601,15 @@ pub async fn send_password_reset_email(
   let protocol_and_hostname = settings.get_protocol_and_hostname();
   let reset_link = format!("{}/password_change/{}", protocol_and_hostname, &token);
   let body = &lang.password_reset_body(reset_link, &user.person.name);
-  send_email(subject, email, &user.person.name, body, settings).await?;
+  let form = OutgoingEmailInsertForm::new(
+    email.to_string(),
+    user.person.name.clone(),
+    subject.clone(),
+    body.clone(),
+    None,
+  );
+  queue_email(&form, pool, settings).await?;
 
-  // Insert the row after successful send, to avoid using daily reset limit while
-  // email sending is broken.
   let local_user_id = user.local_user.id;
   PasswordResetRequest::create(pool, local_user_id, token.clone()).await?;
   Ok(())
@@ -481,7 +637,14 @@ pub async fn send_verification_email(
   let lang = get_interface_language(user);
   let subject = lang.verify_email_subject(&settings.hostname);
   let body = lang.verify_email_body(&settings.hostname, &user.person.name, verify_link);
-  send_email(&subject, new_email, &user.person.name, &body, settings).await?;
+  let form = OutgoingEmailInsertForm::new(
+    new_email.to_string(),
+    user.person.name.clone(),
+    subject,
+    body,
+    None,
+  );
+  queue_email(&form, pool, settings).await?;
 
   Ok(())
 }
@@ -560,6 +723,7 @@ pub async fn get_url_blocklist(context: &LemmyContext) -> LemmyResult<RegexSet>
 
 pub async fn send_application_approved_email(
   user: &LocalUserView,
+  pool: &mut DbPool<'_>,
   settings: &Settings,
 ) -> LemmyResult<()> {
   let email = &user
@@ -570,7 +734,14 @@ pub async fn send_application_approved_email(
   let lang = get_interface_language(user);
   let subject = lang.registration_approved_subject(&user.person.actor_id);
   let body = lang.registration_approved_body(&settings.hostname);
-  send_email(&subject, email, &user.person.name, &body, settings).await
+  let form = OutgoingEmailInsertForm::new(
+    email.to_string(),
+    user.person.name.clone(),
+    subject,
+    body,
+    None,
+  );
+  queue_email(&form, pool, settings).await
 }
 
 /// Send a new applicant email notification to all admins
@@ -596,7 +767,14 @@ pub async fn send_new_applicant_email_to_admins(
     let lang = get_interface_language_from_settings(admin);
     let subject = lang.new_application_subject(&settings.hostname, applicant_username);
     let body = lang.new_application_body(applications_link);
-    send_email(&subject, email, &admin.person.name, &body, settings).await?;
+    let form = OutgoingEmailInsertForm::new(
+      email.to_string(),
+      admin.person.name.clone(),
+      subject,
+      body,
+      None,
+    );
+    queue_email(&form, pool, settings).await?;
   }
   Ok(())
 }
@@ -619,7 +797,14 @@ pub async fn send_new_report_email_to_admins(
       let subject =
         lang.new_report_subject(&settings.hostname, reported_username, reporter_username);
       let body = lang.new_report_body(reports_link);
-      send_email(&subject, email, &admin.person.name, &body, settings).await?;
+      let form = OutgoingEmailInsertForm::new(
+        email.to_string(),
+        admin.person.name.clone(),
+        subject,
+        body,
+        None,
+      );
+      queue_email(&form, pool, settings).await?;
     }
   }
   Ok(())
@@ -1027,6 +1212,85 @@ fn limit_expire_time(expires: DateTime<Utc>) -> LemmyResult<Option<DateTime<Utc>
   }
 }
 
+/// Private feeds are only visible to their creator.
+pub fn check_feed_visible(feed: &Feed, local_user_view: &Option<LocalUserView>) -> LemmyResult<()> {
+  let is_creator = local_user_view
+    .as_ref()
+    .is_some_and(|u| u.person.id == feed.creator_id);
+  if feed.public || is_creator {
+    Ok(())
+  } else {
+    Err(LemmyErrorType::NotFound)?
+  }
+}
+
+/// Modmail threads can be accessed by their creator and by the moderators of the community.
+/// Returns true if the user accesses the thread as a moderator.
+pub async fn check_modmail_thread_access(
+  thread: &ModmailThread,
+  local_user_view: &LocalUserView,
+  pool: &mut DbPool<'_>,
+) -> LemmyResult<bool> {
+  if thread.creator_id == local_user_view.person.id {
+    Ok(false)
+  } else {
+    is_mod_or_admin(pool, &local_user_view.person, thread.community_id).await?;
+    Ok(true)
+  }
+}
+
+/// Checks that the post of a comment thread is visible to the user, and that the comment belongs
+/// to the post.
+pub async fn check_comment_thread_access(
+  post_id: PostId,
+  comment_id: Option<CommentId>,
+  local_user_view: &LocalUserView,
+  pool: &mut DbPool<'_>,
+) -> LemmyResult<()> {
+  PostView::read(pool, post_id, Some(&local_user_view.local_user), false).await?;
+  if let Some(comment_id) = comment_id {
+    let comment = Comment::read(pool, comment_id).await?;
+    if comment.post_id != post_id {
+      Err(LemmyErrorType::NotFound)?
+    }
+  }
+  Ok(())
+}
+
+/// Maximum number of keyword filters per user. Each filter adds conditions to post and comment
+/// listings.
+pub const MAX_KEYWORD_FILTERS: usize = 50;
+
+/// Maximum number of push subscriptions per user, meaning browsers or devices which receive
+/// notifications.
+pub const MAX_PUSH_SUBSCRIPTIONS: usize = 20;
+
+/// Maximum number of other accounts which a person can declare as aliases (`alsoKnownAs`).
+pub const MAX_ACCOUNT_ALIASES: usize = 10;
+
+/// Maximum number of communities in a single feed.
+const MAX_FEED_COMMUNITIES: usize = 100;
+
+pub fn check_feed_communities(community_ids: &[CommunityId]) -> LemmyResult<()> {
+  if community_ids.len() > MAX_FEED_COMMUNITIES {
+    Err(LemmyErrorType::TooManyItems)?
+  }
+  Ok(())
+}
+
+/// Checks the keyword of a keyword filter, and for regex filters also that Postgres accepts the
+/// regex.
+pub async fn check_keyword_filter(
+  keyword: &str,
+  mode: KeywordFilterMode,
+  context: &LemmyContext,
+) -> LemmyResult<()> {
+  is_valid_keyword_filter(keyword)?;
+  LocalUserKeywordFilter::check_pattern(&mut context.pool(), &mode.pattern(keyword))
+    .await
+    .with_lemmy_type(LemmyErrorType::InvalidKeywordFilter)
+}
+
 pub fn check_conflicting_like_filters(
   liked_only: Option<bool>,
   disliked_only: Option<bool>,
@@ -1155,6 +1419,45 @@ pub async fn local_user_view_from_jwt(
   Ok(local_user_view)
 }
 
+/// Api tokens start with this prefix, so that they can be told apart from login jwts.
+pub const API_TOKEN_PREFIX: &str = "lemmy_";
+
+pub fn is_api_token(token: &str) -> bool {
+  token.starts_with(API_TOKEN_PREFIX)
+}
+
+/// Added to the request extensions when the user was authenticated with an api token, so that
+/// handlers can leave out account data.
+#[derive(Clone, Copy, Debug)]
+pub struct ApiTokenSession;
+
+/// Generates a new random api token. Returns the token itself, which is only shown to the user
+/// once, and its hash which is stored in the database.
+pub fn generate_api_token() -> (String, String) {
+  let token = format!("{API_TOKEN_PREFIX}{}", uuid::Uuid::new_v4().simple());
+  let hash = hash_api_token(&token);
+  (token, hash)
+}
+
+fn hash_api_token(token: &str) -> String {
+  hex::encode(Sha256::digest(token.as_bytes()))
+}
+
+/// Returns the user of a valid api token, together with the scopes of the token.
+pub async fn local_user_view_from_api_token(
+  token: &str,
+  context: &LemmyContext,
+) -> LemmyResult<(LocalUserView, Vec<ApiTokenScope>)> {
+  let api_token = ApiToken::validate(&mut context.pool(), &hash_api_token(token)).await?;
+  let local_user_view = LocalUserView::read(&mut context.pool(), api_token.local_user_id).await?;
+  let scopes = ApiTokenView::from_token(&mut context.pool(), api_token)
+    .await?
+    .scopes;
+  check_user_valid(&local_user_view.person)?;
+
+  Ok((local_user_view, scopes))
+}
+
 pub fn read_auth_token(req: &HttpRequest) -> LemmyResult<Option<String>> {
   // Try reading jwt from auth header
   if let Ok(header) = Authorization::<Bearer>::parse(req) {
@@ -0,0 +1,97 @@
+use lemmy_db_schema::{
+  newtypes::{CommunityId, WebhookId},
+  source::webhook::WebhookDelivery,
+  WebhookEventType,
+};
+use lemmy_db_views::structs::WebhookView;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+#[cfg(feature = "full")]
+use ts_rs::TS;
+use url::Url;
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Create a webhook. Without a community it receives events from the whole site, which requires
+/// admin permissions. Otherwise it requires moderator permissions in the community.
+pub struct CreateWebhook {
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub community_id: Option<CommunityId>,
+  #[cfg_attr(feature = "full", ts(type = "string"))]
+  pub url: Url,
+  /// Used to sign the request body with HMAC-SHA256. The signature is sent in the
+  /// `X-Lemmy-Signature` header.
+  pub secret: String,
+  pub events: Vec<WebhookEventType>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Update a webhook.
+pub struct UpdateWebhook {
+  pub webhook_id: WebhookId,
+  #[cfg_attr(feature = "full", ts(optional, type = "string"))]
+  pub url: Option<Url>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub secret: Option<String>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub events: Option<Vec<WebhookEventType>>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub enabled: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Delete a webhook.
+pub struct DeleteWebhook {
+  pub webhook_id: WebhookId,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Lists the webhooks of a community, or the site-wide webhooks if no community is given.
+pub struct ListWebhooks {
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub community_id: Option<CommunityId>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+pub struct WebhookResponse {
+  pub webhook: WebhookView,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+pub struct ListWebhooksResponse {
+  pub webhooks: Vec<WebhookView>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Fetches the delivery log of a webhook, newest first.
+pub struct ListWebhookDeliveries {
+  pub webhook_id: WebhookId,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub page: Option<i64>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+pub struct ListWebhookDeliveriesResponse {
+  pub deliveries: Vec<WebhookDelivery>,
+}
@@ -0,0 +1,48 @@
+use super::{check_automod_permission, check_automod_rule};
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_common::{
+  automod::{AutomodRuleResponse, CreateAutomodRule},
+  context::LemmyContext,
+  run_automod::add_automod_moderator,
+};
+use lemmy_db_schema::{
+  source::automod_rule::{AutomodRule, AutomodRuleInsertForm},
+  traits::Crud,
+};
+use lemmy_db_views::structs::LocalUserView;
+use lemmy_utils::error::LemmyResult;
+
+pub async fn create_automod_rule(
+  data: Json<CreateAutomodRule>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<AutomodRuleResponse>> {
+  let community = check_automod_permission(data.community_id, &local_user_view, &context).await?;
+
+  let data = data.into_inner();
+  let form = AutomodRuleInsertForm {
+    check_posts: data.check_posts,
+    check_comments: data.check_comments,
+    title_regex: data.title_regex,
+    body_regex: data.body_regex,
+    url_domain: data.url_domain,
+    max_author_age_days: data.max_author_age_days,
+    max_author_karma: data.max_author_karma,
+    language_id: data.language_id,
+    tag_id: data.tag_id,
+    message: data.message,
+    ..AutomodRuleInsertForm::new(
+      community.id,
+      local_user_view.person.id,
+      data.name,
+      data.action,
+    )
+  };
+  let form = check_automod_rule(form, &context).await?;
+  let rule = AutomodRule::create(&mut context.pool(), &form).await?;
+
+  add_automod_moderator(&community, &local_user_view.person, &context).await?;
+
+  Ok(Json(AutomodRuleResponse { rule }))
+}
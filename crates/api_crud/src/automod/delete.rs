@@ -0,0 +1,21 @@
+use super::check_automod_permission;
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_common::{automod::DeleteAutomodRule, context::LemmyContext, SuccessResponse};
+use lemmy_db_schema::{source::automod_rule::AutomodRule, traits::Crud};
+use lemmy_db_views::structs::LocalUserView;
+use lemmy_utils::error::LemmyResult;
+
+pub async fn delete_automod_rule(
+  data: Json<DeleteAutomodRule>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<SuccessResponse>> {
+  let rule = AutomodRule::read(&mut context.pool(), data.rule_id).await?;
+  check_automod_permission(rule.community_id, &local_user_view, &context).await?;
+
+  // The modlog entries of the rule are kept, they only reference it by name
+  AutomodRule::delete(&mut context.pool(), rule.id).await?;
+
+  Ok(Json(SuccessResponse::default()))
+}
@@ -0,0 +1,21 @@
+use super::check_automod_permission;
+use activitypub_federation::config::Data;
+use actix_web::web::{Json, Query};
+use lemmy_api_common::{
+  automod::{ListAutomodRules, ListAutomodRulesResponse},
+  context::LemmyContext,
+};
+use lemmy_db_schema::source::automod_rule::AutomodRule;
+use lemmy_db_views::structs::LocalUserView;
+use lemmy_utils::error::LemmyResult;
+
+pub async fn list_automod_rules(
+  data: Query<ListAutomodRules>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<ListAutomodRulesResponse>> {
+  check_automod_permission(data.community_id, &local_user_view, &context).await?;
+
+  let rules = AutomodRule::list(&mut context.pool(), data.community_id).await?;
+  Ok(Json(ListAutomodRulesResponse { rules }))
+}
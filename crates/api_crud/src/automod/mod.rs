@@ -0,0 +1,100 @@
+use lemmy_api_common::{context::LemmyContext, utils::check_community_mod_action};
+use lemmy_db_schema::{
+  newtypes::CommunityId,
+  source::{automod_rule::AutomodRuleInsertForm, community::Community, tag::Tag},
+  traits::Crud,
+  AutomodAction,
+};
+use lemmy_db_views::structs::LocalUserView;
+use lemmy_utils::{
+  error::{LemmyErrorType, LemmyResult},
+  utils::validation::{build_and_check_regex, is_valid_automod_rule_name, is_valid_body_field},
+};
+use url::Url;
+
+pub mod create;
+pub mod delete;
+pub mod list;
+pub mod update;
+
+/// Automod rules can only be managed by the moderators of the community. They are only applied
+/// by the instance of the community, so remote communities can't have rules.
+async fn check_automod_permission(
+  community_id: CommunityId,
+  local_user_view: &LocalUserView,
+  context: &LemmyContext,
+) -> LemmyResult<Community> {
+  let community = Community::read(&mut context.pool(), community_id).await?;
+  check_community_mod_action(
+    &local_user_view.person,
+    &community,
+    false,
+    &mut context.pool(),
+  )
+  .await?;
+  if !community.local {
+    Err(LemmyErrorType::AutomodRequiresLocalCommunity)?
+  }
+  Ok(community)
+}
+
+/// Validates a new or updated rule, and normalizes its optional fields so that empty strings
+/// are treated as unset.
+async fn check_automod_rule(
+  form: AutomodRuleInsertForm,
+  context: &LemmyContext,
+) -> LemmyResult<AutomodRuleInsertForm> {
+  let non_empty = |s: Option<String>| s.map(|s| s.trim().to_string()).filter(|s| !s.is_empty());
+  let form = AutomodRuleInsertForm {
+    name: form.name.trim().to_string(),
+    title_regex: non_empty(form.title_regex),
+    body_regex: non_empty(form.body_regex),
+    url_domain: non_empty(form.url_domain).map(|d| d.to_lowercase()),
+    message: non_empty(form.message),
+    tag_id: form.tag_id.filter(|_| form.action == AutomodAction::Tag),
+    ..form
+  };
+  is_valid_automod_rule_name(&form.name)?;
+
+  for regex in [&form.title_regex, &form.body_regex] {
+    if let Some(Err(e)) = build_and_check_regex(&regex.as_deref()) {
+      Err(e)?
+    }
+  }
+  if let Some(domain) = &form.url_domain {
+    let url = Url::parse(&format!("https://{domain}"));
+    if url.ok().and_then(|u| u.domain().map(ToString::to_string)) != Some(domain.clone()) {
+      Err(LemmyErrorType::InvalidAutomodRule)?
+    }
+  }
+  let has_condition = form.title_regex.is_some()
+    || form.body_regex.is_some()
+    || form.url_domain.is_some()
+    || form.max_author_age_days.is_some()
+    || form.max_author_karma.is_some()
+    || form.language_id.is_some();
+  let check_posts = form.check_posts.unwrap_or(true);
+  let check_comments = form.check_comments.unwrap_or(true);
+  // Comments can't be locked or tagged, so these rules need to check posts
+  let post_only = matches!(form.action, AutomodAction::Lock | AutomodAction::Tag);
+  if !has_condition
+    || form.max_author_age_days.is_some_and(|d| d < 0)
+    || !(check_posts || check_comments)
+    || (post_only && !check_posts)
+  {
+    Err(LemmyErrorType::InvalidAutomodRule)?
+  }
+
+  if let Some(message) = &form.message {
+    is_valid_body_field(message, false)?;
+  }
+  match form.action {
+    AutomodAction::Tag => {
+      let tag_id = form.tag_id.ok_or(LemmyErrorType::InvalidAutomodRule)?;
+      Tag::check_in_community(&mut context.pool(), form.community_id, &[tag_id]).await?;
+    }
+    AutomodAction::Reply if form.message.is_none() => Err(LemmyErrorType::InvalidAutomodRule)?,
+    _ => {}
+  }
+  Ok(form)
+}
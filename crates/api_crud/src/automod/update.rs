@@ -0,0 +1,59 @@
+use super::{check_automod_permission, check_automod_rule};
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use chrono::Utc;
+use lemmy_api_common::{
+  automod::{AutomodRuleResponse, UpdateAutomodRule},
+  context::LemmyContext,
+};
+use lemmy_db_schema::{
+  source::automod_rule::{AutomodRule, AutomodRuleInsertForm, AutomodRuleUpdateForm},
+  traits::Crud,
+};
+use lemmy_db_views::structs::LocalUserView;
+use lemmy_utils::error::LemmyResult;
+
+pub async fn update_automod_rule(
+  data: Json<UpdateAutomodRule>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<AutomodRuleResponse>> {
+  let rule = AutomodRule::read(&mut context.pool(), data.rule_id).await?;
+  check_automod_permission(rule.community_id, &local_user_view, &context).await?;
+
+  let data = data.into_inner();
+  let form = AutomodRuleInsertForm {
+    check_posts: data.check_posts.or(Some(rule.check_posts)),
+    check_comments: data.check_comments.or(Some(rule.check_comments)),
+    title_regex: data.title_regex,
+    body_regex: data.body_regex,
+    url_domain: data.url_domain,
+    max_author_age_days: data.max_author_age_days,
+    max_author_karma: data.max_author_karma,
+    language_id: data.language_id,
+    tag_id: data.tag_id,
+    message: data.message,
+    ..AutomodRuleInsertForm::new(rule.community_id, rule.creator_id, data.name, data.action)
+  };
+  let form = check_automod_rule(form, &context).await?;
+
+  let update_form = AutomodRuleUpdateForm {
+    name: Some(form.name),
+    enabled: data.enabled,
+    check_posts: form.check_posts,
+    check_comments: form.check_comments,
+    title_regex: Some(form.title_regex),
+    body_regex: Some(form.body_regex),
+    url_domain: Some(form.url_domain),
+    max_author_age_days: Some(form.max_author_age_days),
+    max_author_karma: Some(form.max_author_karma),
+    language_id: Some(form.language_id),
+    action: Some(form.action),
+    tag_id: Some(form.tag_id),
+    message: Some(form.message),
+    updated: Some(Some(Utc::now())),
+  };
+  let rule = AutomodRule::update(&mut context.pool(), rule.id, &update_form).await?;
+
+  Ok(Json(AutomodRuleResponse { rule }))
+}
@@ -4,6 +4,7 @@ use lemmy_api_common::{
   build_response::{build_comment_response, send_local_notifs},
   comment::{CommentResponse, CreateComment},
   context::LemmyContext,
+  run_automod::run_comment_automod,
   send_activity::{ActivityChannel, SendActivityData},
   utils::{
     check_community_user_action,
@@ -141,6 +142,8 @@ pub async fn create_comment(
     &context,
   )?;
 
+  run_comment_automod(&inserted_comment, true, &context).await;
+
   // Update the read comments, so your own new comment doesn't appear as a +1 unread
   update_read_comments(
     local_user_view.person.id,
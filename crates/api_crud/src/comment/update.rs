@@ -5,6 +5,7 @@ use lemmy_api_common::{
   build_response::{build_comment_response, send_local_notifs},
   comment::{CommentResponse, EditComment},
   context::LemmyContext,
+  run_automod::run_comment_automod,
   send_activity::{ActivityChannel, SendActivityData},
   utils::{
     check_community_user_action,
@@ -18,6 +19,7 @@ use lemmy_db_schema::{
   newtypes::PostOrCommentId,
   source::{
     comment::{Comment, CommentUpdateForm},
+    comment_revision::CommentRevision,
     local_site::LocalSite,
   },
   traits::Crud,
@@ -80,6 +82,13 @@ pub async fn update_comment(
   let updated_comment = Comment::update(&mut context.pool(), comment_id, &form)
     .await
     .with_lemmy_type(LemmyErrorType::CouldntUpdateComment)?;
+  CommentRevision::record(
+    &mut context.pool(),
+    &orig_comment.comment,
+    &updated_comment,
+    local_user_view.person.id,
+  )
+  .await?;
 
   // Do the mentions / recipients
   let updated_comment_content = updated_comment.content.clone();
@@ -99,6 +108,8 @@ pub async fn update_comment(
     &context,
   )?;
 
+  run_comment_automod(&updated_comment, false, &context).await;
+
   Ok(Json(
     build_comment_response(
       &context,
@@ -39,6 +39,7 @@ use lemmy_utils::{
     validation::{
       is_valid_actor_name,
       is_valid_body_field,
+      is_valid_duplicate_link_window,
       site_or_community_description_length_check,
     },
   },
@@ -80,6 +81,9 @@ pub async fn create_community(
   }
 
   check_community_visibility_allowed(data.visibility, &local_user_view)?;
+  if let Some(days) = data.duplicate_link_window_days {
+    is_valid_duplicate_link_window(days)?;
+  }
 
   // Double check for duplicate community actor_ids
   let community_actor_id = generate_local_apub_endpoint(
@@ -106,6 +110,8 @@ pub async fn create_community(
     inbox_url: Some(generate_inbox_url()?),
     posting_restricted_to_mods: data.posting_restricted_to_mods,
     visibility: data.visibility,
+    duplicate_link_policy: data.duplicate_link_policy,
+    duplicate_link_window_days: data.duplicate_link_window_days,
     ..CommunityInsertForm::new(
       site_view.site.instance_id,
       data.name.clone(),
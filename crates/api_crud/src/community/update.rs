@@ -26,7 +26,10 @@ use lemmy_db_schema::{
 use lemmy_db_views::structs::LocalUserView;
 use lemmy_utils::{
   error::{LemmyErrorExt, LemmyErrorType, LemmyResult},
-  utils::{slurs::check_slurs_opt, validation::is_valid_body_field},
+  utils::{
+    slurs::check_slurs_opt,
+    validation::{is_valid_body_field, is_valid_duplicate_link_window},
+  },
 };
 
 pub async fn update_community(
@@ -51,6 +54,9 @@ pub async fn update_community(
   }
 
   check_community_visibility_allowed(data.visibility, &local_user_view)?;
+  if let Some(days) = data.duplicate_link_window_days {
+    is_valid_duplicate_link_window(days)?;
+  }
   let description = diesel_string_update(data.description.as_deref());
 
   let old_community = Community::read(&mut context.pool(), data.community_id).await?;
@@ -83,6 +89,8 @@ pub async fn update_community(
     nsfw: data.nsfw,
     posting_restricted_to_mods: data.posting_restricted_to_mods,
     visibility: data.visibility,
+    duplicate_link_policy: data.duplicate_link_policy,
+    duplicate_link_window_days: data.duplicate_link_window_days,
     updated: Some(Some(Utc::now())),
     ..Default::default()
   };
@@ -0,0 +1,61 @@
+use super::{check_draft_content, MAX_DRAFTS};
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_common::{
+  context::LemmyContext,
+  draft::{CreateDraft, DraftResponse},
+};
+use lemmy_db_schema::{
+  source::draft::{Draft, DraftInsertForm},
+  traits::Crud,
+  utils::diesel_url_create,
+};
+use lemmy_db_views::structs::LocalUserView;
+use lemmy_utils::{
+  error::{LemmyErrorType, LemmyResult},
+  utils::validation::is_valid_url,
+};
+
+pub async fn create_draft(
+  data: Json<CreateDraft>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<DraftResponse>> {
+  let local_user_id = local_user_view.local_user.id;
+  if Draft::count(&mut context.pool(), local_user_id).await? >= MAX_DRAFTS {
+    Err(LemmyErrorType::TooManyItems)?
+  }
+
+  check_draft_content(
+    data.post_id,
+    data.parent_id,
+    data.name.as_deref(),
+    data.body.as_deref(),
+    data.alt_text.as_deref(),
+  )?;
+  let url = diesel_url_create(data.url.as_deref())?;
+  if let Some(url) = &url {
+    is_valid_url(url)?;
+  }
+  let custom_thumbnail = diesel_url_create(data.custom_thumbnail.as_deref())?;
+  if let Some(custom_thumbnail) = &custom_thumbnail {
+    is_valid_url(custom_thumbnail)?;
+  }
+
+  let form = DraftInsertForm {
+    community_id: data.community_id,
+    post_id: data.post_id,
+    parent_id: data.parent_id,
+    name: data.name.clone(),
+    url,
+    body: data.body.clone(),
+    alt_text: data.alt_text.clone(),
+    nsfw: data.nsfw,
+    language_id: data.language_id,
+    custom_thumbnail,
+    ..DraftInsertForm::new(local_user_id)
+  };
+  let draft = Draft::create(&mut context.pool(), &form).await?;
+
+  Ok(Json(DraftResponse { draft }))
+}
@@ -0,0 +1,20 @@
+use super::check_draft_creator;
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_common::{context::LemmyContext, draft::DeleteDraft, SuccessResponse};
+use lemmy_db_schema::{source::draft::Draft, traits::Crud};
+use lemmy_db_views::structs::LocalUserView;
+use lemmy_utils::error::LemmyResult;
+
+pub async fn delete_draft(
+  data: Json<DeleteDraft>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<SuccessResponse>> {
+  let draft = Draft::read(&mut context.pool(), data.draft_id).await?;
+  check_draft_creator(&draft, &local_user_view)?;
+
+  Draft::delete(&mut context.pool(), draft.id).await?;
+
+  Ok(Json(SuccessResponse::default()))
+}
@@ -0,0 +1,50 @@
+use lemmy_db_schema::{
+  newtypes::{CommentId, PostId},
+  source::draft::Draft,
+};
+use lemmy_db_views::structs::LocalUserView;
+use lemmy_utils::{
+  error::{LemmyErrorType, LemmyResult},
+  utils::validation::{is_valid_alt_text_field, is_valid_body_field, is_valid_draft_title},
+};
+
+pub mod create;
+pub mod delete;
+pub mod list;
+pub mod publish;
+pub mod update;
+
+/// Maximum number of drafts per user.
+const MAX_DRAFTS: i64 = 50;
+
+/// Drafts may be incomplete, so only the length limits are checked here. The full validation
+/// happens when the draft gets published.
+fn check_draft_content(
+  post_id: Option<PostId>,
+  parent_id: Option<CommentId>,
+  name: Option<&str>,
+  body: Option<&str>,
+  alt_text: Option<&str>,
+) -> LemmyResult<()> {
+  if parent_id.is_some() && post_id.is_none() {
+    Err(LemmyErrorType::InvalidDraft)?
+  }
+  if let Some(name) = name {
+    is_valid_draft_title(name)?;
+  }
+  if let Some(body) = body {
+    is_valid_body_field(body, post_id.is_none())?;
+  }
+  if let Some(alt_text) = alt_text {
+    is_valid_alt_text_field(alt_text)?;
+  }
+  Ok(())
+}
+
+/// Don't reveal drafts of other users.
+fn check_draft_creator(draft: &Draft, local_user_view: &LocalUserView) -> LemmyResult<()> {
+  if draft.local_user_id != local_user_view.local_user.id {
+    Err(LemmyErrorType::NotFound)?
+  }
+  Ok(())
+}
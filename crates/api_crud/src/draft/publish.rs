@@ -0,0 +1,45 @@
+use super::check_draft_creator;
+use crate::post::create::create_post;
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_common::{
+  context::LemmyContext,
+  draft::PublishDraft,
+  post::{CreatePost, PostResponse},
+};
+use lemmy_db_schema::{source::draft::Draft, traits::Crud};
+use lemmy_db_views::structs::LocalUserView;
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+
+pub async fn publish_draft(
+  data: Json<PublishDraft>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<PostResponse>> {
+  let draft = Draft::read(&mut context.pool(), data.draft_id).await?;
+  check_draft_creator(&draft, &local_user_view)?;
+
+  // Only post drafts can be published, comment drafts are submitted by the client as usual
+  let (None, Some(community_id)) = (draft.post_id, draft.community_id) else {
+    Err(LemmyErrorType::InvalidDraft)?
+  };
+
+  // Go through the normal post creation, so that all the usual checks are applied
+  let form = CreatePost {
+    name: draft.name.clone().unwrap_or_default(),
+    community_id,
+    url: draft.url.as_ref().map(ToString::to_string),
+    body: draft.body.clone(),
+    alt_text: draft.alt_text.clone(),
+    nsfw: draft.nsfw,
+    language_id: draft.language_id,
+    custom_thumbnail: draft.custom_thumbnail.as_ref().map(ToString::to_string),
+    scheduled_publish_time: data.scheduled_publish_time,
+    ..Default::default()
+  };
+  let res = create_post(Json(form), context.reset_request_count(), local_user_view).await?;
+
+  Draft::delete(&mut context.pool(), draft.id).await?;
+
+  Ok(res)
+}
@@ -0,0 +1,58 @@
+use super::{check_draft_content, check_draft_creator};
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use chrono::Utc;
+use lemmy_api_common::{
+  context::LemmyContext,
+  draft::{DraftResponse, UpdateDraft},
+};
+use lemmy_db_schema::{
+  source::draft::{Draft, DraftUpdateForm},
+  traits::Crud,
+  utils::diesel_url_create,
+};
+use lemmy_db_views::structs::LocalUserView;
+use lemmy_utils::{error::LemmyResult, utils::validation::is_valid_url};
+
+pub async fn update_draft(
+  data: Json<UpdateDraft>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<DraftResponse>> {
+  let draft = Draft::read(&mut context.pool(), data.draft_id).await?;
+  check_draft_creator(&draft, &local_user_view)?;
+
+  check_draft_content(
+    data.post_id,
+    data.parent_id,
+    data.name.as_deref(),
+    data.body.as_deref(),
+    data.alt_text.as_deref(),
+  )?;
+  let url = diesel_url_create(data.url.as_deref())?;
+  if let Some(url) = &url {
+    is_valid_url(url)?;
+  }
+  let custom_thumbnail = diesel_url_create(data.custom_thumbnail.as_deref())?;
+  if let Some(custom_thumbnail) = &custom_thumbnail {
+    is_valid_url(custom_thumbnail)?;
+  }
+
+  // Clients always send the whole draft, so missing fields are cleared
+  let form = DraftUpdateForm {
+    community_id: Some(data.community_id),
+    post_id: Some(data.post_id),
+    parent_id: Some(data.parent_id),
+    name: Some(data.name.clone()),
+    url: Some(url),
+    body: Some(data.body.clone()),
+    alt_text: Some(data.alt_text.clone()),
+    nsfw: Some(data.nsfw),
+    language_id: Some(data.language_id),
+    custom_thumbnail: Some(custom_thumbnail),
+    updated: Some(Some(Utc::now())),
+  };
+  let draft = Draft::update(&mut context.pool(), draft.id, &form).await?;
+
+  Ok(Json(DraftResponse { draft }))
+}
@@ -0,0 +1,59 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_common::{
+  context::LemmyContext,
+  feed::{CreateFeed, FeedResponse},
+  utils::{
+    check_feed_communities,
+    get_url_blocklist,
+    local_site_to_slur_regex,
+    process_markdown_opt,
+  },
+};
+use lemmy_db_schema::{
+  source::{
+    feed::{Feed, FeedInsertForm},
+    local_site::LocalSite,
+  },
+  traits::Crud,
+};
+use lemmy_db_views::structs::{FeedView, LocalUserView};
+use lemmy_utils::{
+  error::{LemmyErrorExt, LemmyErrorType, LemmyResult},
+  utils::{
+    slurs::check_slurs,
+    validation::{is_valid_body_field, is_valid_feed_name},
+  },
+};
+
+pub async fn create_feed(
+  data: Json<CreateFeed>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<FeedResponse>> {
+  let local_site = LocalSite::read(&mut context.pool()).await?;
+  let slur_regex = local_site_to_slur_regex(&local_site);
+  let url_blocklist = get_url_blocklist(&context).await?;
+  let name = data.name.trim().to_string();
+  is_valid_feed_name(&name)?;
+  check_slurs(&name, &slur_regex)?;
+  let description =
+    process_markdown_opt(&data.description, &slur_regex, &url_blocklist, &context).await?;
+  if let Some(description) = &description {
+    is_valid_body_field(description, false)?;
+  }
+  check_feed_communities(&data.community_ids)?;
+
+  let feed_form = FeedInsertForm {
+    description,
+    public: data.public,
+    ..FeedInsertForm::new(local_user_view.person.id, name)
+  };
+  let feed = Feed::create(&mut context.pool(), &feed_form)
+    .await
+    .with_lemmy_type(LemmyErrorType::FeedAlreadyExists)?;
+  Feed::set_communities(&mut context.pool(), feed.id, &data.community_ids).await?;
+
+  let feed_view = FeedView::read(&mut context.pool(), feed.id).await?;
+  Ok(Json(FeedResponse { feed_view }))
+}
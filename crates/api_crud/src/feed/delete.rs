@@ -0,0 +1,20 @@
+use super::check_feed_creator;
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_common::{context::LemmyContext, feed::DeleteFeed, SuccessResponse};
+use lemmy_db_schema::{source::feed::Feed, traits::Crud};
+use lemmy_db_views::structs::LocalUserView;
+use lemmy_utils::error::LemmyResult;
+
+pub async fn delete_feed(
+  data: Json<DeleteFeed>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<SuccessResponse>> {
+  let feed = Feed::read(&mut context.pool(), data.feed_id).await?;
+  check_feed_creator(&feed, &local_user_view)?;
+
+  Feed::delete(&mut context.pool(), feed.id).await?;
+
+  Ok(Json(SuccessResponse::default()))
+}
@@ -0,0 +1,30 @@
+use activitypub_federation::config::Data;
+use actix_web::web::{Json, Query};
+use lemmy_api_common::{
+  context::LemmyContext,
+  feed::{ListFeeds, ListFeedsResponse},
+  utils::check_private_instance,
+};
+use lemmy_db_schema::source::local_site::LocalSite;
+use lemmy_db_views::structs::{FeedView, LocalUserView};
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+
+pub async fn list_feeds(
+  data: Query<ListFeeds>,
+  context: Data<LemmyContext>,
+  local_user_view: Option<LocalUserView>,
+) -> LemmyResult<Json<ListFeedsResponse>> {
+  let local_site = LocalSite::read(&mut context.pool()).await?;
+  check_private_instance(&local_user_view, &local_site)?;
+
+  let my_person_id = local_user_view.as_ref().map(|u| u.person.id);
+  let creator_id = data
+    .creator_id
+    .or(my_person_id)
+    .ok_or(LemmyErrorType::NotLoggedIn)?;
+  // Private feeds are only listed for their creator
+  let include_private = Some(creator_id) == my_person_id;
+
+  let feeds = FeedView::list(&mut context.pool(), creator_id, include_private).await?;
+  Ok(Json(ListFeedsResponse { feeds }))
+}
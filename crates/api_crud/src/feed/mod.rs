@@ -0,0 +1,17 @@
+use lemmy_db_schema::source::feed::Feed;
+use lemmy_db_views::structs::LocalUserView;
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+
+pub mod create;
+pub mod delete;
+pub mod list;
+pub mod read;
+pub mod update;
+
+/// Feeds can only be changed by their creator.
+fn check_feed_creator(feed: &Feed, local_user_view: &LocalUserView) -> LemmyResult<()> {
+  if feed.creator_id != local_user_view.person.id {
+    Err(LemmyErrorType::NoFeedEditAllowed)?
+  }
+  Ok(())
+}
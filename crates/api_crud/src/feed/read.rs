@@ -0,0 +1,24 @@
+use activitypub_federation::config::Data;
+use actix_web::web::{Json, Query};
+use lemmy_api_common::{
+  context::LemmyContext,
+  feed::{FeedResponse, GetFeed},
+  utils::{check_feed_visible, check_private_instance},
+};
+use lemmy_db_schema::source::local_site::LocalSite;
+use lemmy_db_views::structs::{FeedView, LocalUserView};
+use lemmy_utils::error::LemmyResult;
+
+pub async fn get_feed(
+  data: Query<GetFeed>,
+  context: Data<LemmyContext>,
+  local_user_view: Option<LocalUserView>,
+) -> LemmyResult<Json<FeedResponse>> {
+  let local_site = LocalSite::read(&mut context.pool()).await?;
+  check_private_instance(&local_user_view, &local_site)?;
+
+  let feed_view = FeedView::read(&mut context.pool(), data.feed_id).await?;
+  check_feed_visible(&feed_view.feed, &local_user_view)?;
+
+  Ok(Json(FeedResponse { feed_view }))
+}
@@ -0,0 +1,75 @@
+use super::check_feed_creator;
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use chrono::Utc;
+use lemmy_api_common::{
+  context::LemmyContext,
+  feed::{FeedResponse, UpdateFeed},
+  utils::{
+    check_feed_communities,
+    get_url_blocklist,
+    local_site_to_slur_regex,
+    process_markdown_opt,
+  },
+};
+use lemmy_db_schema::{
+  source::{
+    feed::{Feed, FeedUpdateForm},
+    local_site::LocalSite,
+  },
+  traits::Crud,
+  utils::diesel_string_update,
+};
+use lemmy_db_views::structs::{FeedView, LocalUserView};
+use lemmy_utils::{
+  error::{LemmyErrorExt, LemmyErrorType, LemmyResult},
+  utils::{
+    slurs::check_slurs,
+    validation::{is_valid_body_field, is_valid_feed_name},
+  },
+};
+
+pub async fn update_feed(
+  data: Json<UpdateFeed>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<FeedResponse>> {
+  let feed = Feed::read(&mut context.pool(), data.feed_id).await?;
+  check_feed_creator(&feed, &local_user_view)?;
+
+  let local_site = LocalSite::read(&mut context.pool()).await?;
+  let slur_regex = local_site_to_slur_regex(&local_site);
+  let url_blocklist = get_url_blocklist(&context).await?;
+  let name = data.name.as_ref().map(|n| n.trim().to_string());
+  if let Some(name) = &name {
+    is_valid_feed_name(name)?;
+    check_slurs(name, &slur_regex)?;
+  }
+  let description = diesel_string_update(
+    process_markdown_opt(&data.description, &slur_regex, &url_blocklist, &context)
+      .await?
+      .as_deref(),
+  );
+  if let Some(Some(description)) = &description {
+    is_valid_body_field(description, false)?;
+  }
+  if let Some(community_ids) = &data.community_ids {
+    check_feed_communities(community_ids)?;
+  }
+
+  let feed_form = FeedUpdateForm {
+    name,
+    description,
+    public: data.public,
+    updated: Some(Some(Utc::now())),
+  };
+  Feed::update(&mut context.pool(), feed.id, &feed_form)
+    .await
+    .with_lemmy_type(LemmyErrorType::FeedAlreadyExists)?;
+  if let Some(community_ids) = &data.community_ids {
+    Feed::set_communities(&mut context.pool(), feed.id, community_ids).await?;
+  }
+
+  let feed_view = FeedView::read(&mut context.pool(), feed.id).await?;
+  Ok(Json(FeedResponse { feed_view }))
+}
@@ -1,9 +1,15 @@
+pub mod automod;
 pub mod comment;
 pub mod community;
 pub mod custom_emoji;
+pub mod draft;
+pub mod feed;
+pub mod modmail;
 pub mod oauth_provider;
 pub mod post;
 pub mod private_message;
 pub mod site;
+pub mod tag;
 pub mod tagline;
 pub mod user;
+pub mod webhook;
@@ -0,0 +1,41 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_common::{
+  context::LemmyContext,
+  modmail::{ArchiveModmailThread, ModmailThreadResponse},
+  utils::is_mod_or_admin,
+};
+use lemmy_db_schema::{
+  source::modmail::{ModmailThread, ModmailThreadUpdateForm},
+  traits::Crud,
+};
+use lemmy_db_views::structs::{LocalUserView, ModmailMessageView, ModmailThreadView};
+use lemmy_utils::error::LemmyResult;
+
+pub async fn archive_modmail_thread(
+  data: Json<ArchiveModmailThread>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<ModmailThreadResponse>> {
+  let thread = ModmailThread::read(&mut context.pool(), data.thread_id).await?;
+  is_mod_or_admin(
+    &mut context.pool(),
+    &local_user_view.person,
+    thread.community_id,
+  )
+  .await?;
+
+  let form = ModmailThreadUpdateForm {
+    archived: Some(data.archived),
+    ..Default::default()
+  };
+  ModmailThread::update(&mut context.pool(), thread.id, &form).await?;
+
+  let thread_view = ModmailThreadView::read(&mut context.pool(), thread.id, true).await?;
+  let messages = ModmailMessageView::list_for_thread(&mut context.pool(), thread.id).await?;
+
+  Ok(Json(ModmailThreadResponse {
+    thread_view,
+    messages,
+  }))
+}
@@ -0,0 +1,69 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_common::{
+  context::LemmyContext,
+  modmail::{CreateModmailThread, ModmailThreadResponse},
+  utils::{
+    check_community_deleted_removed,
+    get_url_blocklist,
+    local_site_to_slur_regex,
+    process_markdown,
+  },
+};
+use lemmy_db_schema::{
+  source::{
+    community::Community,
+    local_site::LocalSite,
+    modmail::{ModmailMessage, ModmailMessageInsertForm, ModmailThread, ModmailThreadInsertForm},
+  },
+  traits::Crud,
+};
+use lemmy_db_views::structs::{LocalUserView, ModmailMessageView, ModmailThreadView};
+use lemmy_utils::{
+  error::{LemmyErrorExt, LemmyErrorType, LemmyResult},
+  utils::{
+    slurs::check_slurs,
+    validation::{is_valid_body_field, is_valid_post_title},
+  },
+};
+
+pub async fn create_modmail_thread(
+  data: Json<CreateModmailThread>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<ModmailThreadResponse>> {
+  let community = Community::read(&mut context.pool(), data.community_id).await?;
+  // Modmail isn't federated, so the moderators of remote communities wouldn't see it
+  if !community.local {
+    Err(LemmyErrorType::ObjectNotLocal)?
+  }
+  check_community_deleted_removed(&community)?;
+
+  let local_site = LocalSite::read(&mut context.pool()).await?;
+  let slur_regex = local_site_to_slur_regex(&local_site);
+  let url_blocklist = get_url_blocklist(&context).await?;
+  let subject = data.subject.trim();
+  is_valid_post_title(subject)?;
+  check_slurs(subject, &slur_regex)?;
+  let content = process_markdown(&data.content, &slur_regex, &url_blocklist, &context).await?;
+  is_valid_body_field(&content, false)?;
+
+  let my_person_id = local_user_view.person.id;
+  let thread_form = ModmailThreadInsertForm::new(community.id, my_person_id, subject.to_string());
+  let thread = ModmailThread::create(&mut context.pool(), &thread_form)
+    .await
+    .with_lemmy_type(LemmyErrorType::CouldntCreateModmail)?;
+
+  let message_form = ModmailMessageInsertForm::new(thread.id, my_person_id, content, false);
+  ModmailMessage::create(&mut context.pool(), &message_form)
+    .await
+    .with_lemmy_type(LemmyErrorType::CouldntCreateModmail)?;
+
+  let thread_view = ModmailThreadView::read(&mut context.pool(), thread.id, false).await?;
+  let messages = ModmailMessageView::list_for_thread(&mut context.pool(), thread.id).await?;
+
+  Ok(Json(ModmailThreadResponse {
+    thread_view,
+    messages,
+  }))
+}
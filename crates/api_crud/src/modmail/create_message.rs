@@ -0,0 +1,65 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use chrono::Utc;
+use lemmy_api_common::{
+  context::LemmyContext,
+  modmail::{CreateModmailMessage, ModmailThreadResponse},
+  utils::{
+    check_modmail_thread_access,
+    get_url_blocklist,
+    local_site_to_slur_regex,
+    process_markdown,
+  },
+};
+use lemmy_db_schema::{
+  source::{
+    local_site::LocalSite,
+    modmail::{ModmailMessage, ModmailMessageInsertForm, ModmailThread, ModmailThreadUpdateForm},
+  },
+  traits::Crud,
+};
+use lemmy_db_views::structs::{LocalUserView, ModmailMessageView, ModmailThreadView};
+use lemmy_utils::{
+  error::{LemmyErrorExt, LemmyErrorType, LemmyResult},
+  utils::validation::is_valid_body_field,
+};
+
+pub async fn create_modmail_message(
+  data: Json<CreateModmailMessage>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<ModmailThreadResponse>> {
+  let thread = ModmailThread::read(&mut context.pool(), data.thread_id).await?;
+  let as_moderator =
+    check_modmail_thread_access(&thread, &local_user_view, &mut context.pool()).await?;
+
+  let local_site = LocalSite::read(&mut context.pool()).await?;
+  let slur_regex = local_site_to_slur_regex(&local_site);
+  let url_blocklist = get_url_blocklist(&context).await?;
+  let content = process_markdown(&data.content, &slur_regex, &url_blocklist, &context).await?;
+  is_valid_body_field(&content, false)?;
+
+  let message_form =
+    ModmailMessageInsertForm::new(thread.id, local_user_view.person.id, content, as_moderator);
+  ModmailMessage::create(&mut context.pool(), &message_form)
+    .await
+    .with_lemmy_type(LemmyErrorType::CouldntCreateModmail)?;
+
+  // A reply moves the thread to the top, and a reply of the creator reopens an archived thread
+  let thread_form = ModmailThreadUpdateForm {
+    archived: (!as_moderator).then_some(false),
+    updated: Some(Some(Utc::now())),
+  };
+  ModmailThread::update(&mut context.pool(), thread.id, &thread_form).await?;
+
+  // Replying means that the previous messages of the other side were read
+  ModmailMessage::mark_thread_as_read(&mut context.pool(), thread.id, as_moderator).await?;
+
+  let thread_view = ModmailThreadView::read(&mut context.pool(), thread.id, as_moderator).await?;
+  let messages = ModmailMessageView::list_for_thread(&mut context.pool(), thread.id).await?;
+
+  Ok(Json(ModmailThreadResponse {
+    thread_view,
+    messages,
+  }))
+}
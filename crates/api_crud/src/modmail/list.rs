@@ -0,0 +1,41 @@
+use activitypub_federation::config::Data;
+use actix_web::web::{Json, Query};
+use lemmy_api_common::{
+  context::LemmyContext,
+  modmail::{ListModmailThreads, ListModmailThreadsResponse},
+  utils::is_mod_or_admin,
+};
+use lemmy_db_views::{modmail::modmail_thread_view::ModmailThreadQuery, structs::LocalUserView};
+use lemmy_utils::error::LemmyResult;
+
+pub async fn list_modmail_threads(
+  data: Query<ListModmailThreads>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<ListModmailThreadsResponse>> {
+  let query = ModmailThreadQuery {
+    archived: data.archived,
+    page: data.page,
+    limit: data.limit,
+    ..Default::default()
+  };
+
+  let threads = if let Some(community_id) = data.community_id {
+    is_mod_or_admin(&mut context.pool(), &local_user_view.person, community_id).await?;
+    ModmailThreadQuery {
+      community_id: Some(community_id),
+      ..query
+    }
+    .list(&mut context.pool(), true)
+    .await?
+  } else {
+    ModmailThreadQuery {
+      creator_id: Some(local_user_view.person.id),
+      ..query
+    }
+    .list(&mut context.pool(), false)
+    .await?
+  };
+
+  Ok(Json(ListModmailThreadsResponse { threads }))
+}
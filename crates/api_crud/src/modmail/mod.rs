@@ -0,0 +1,5 @@
+pub mod archive;
+pub mod create;
+pub mod create_message;
+pub mod list;
+pub mod read;
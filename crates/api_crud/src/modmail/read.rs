@@ -0,0 +1,28 @@
+use activitypub_federation::config::Data;
+use actix_web::web::{Json, Query};
+use lemmy_api_common::{
+  context::LemmyContext,
+  modmail::{GetModmailThread, ModmailThreadResponse},
+  utils::check_modmail_thread_access,
+};
+use lemmy_db_schema::{source::modmail::ModmailThread, traits::Crud};
+use lemmy_db_views::structs::{LocalUserView, ModmailMessageView, ModmailThreadView};
+use lemmy_utils::error::LemmyResult;
+
+pub async fn get_modmail_thread(
+  data: Query<GetModmailThread>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<ModmailThreadResponse>> {
+  let thread = ModmailThread::read(&mut context.pool(), data.thread_id).await?;
+  let as_moderator =
+    check_modmail_thread_access(&thread, &local_user_view, &mut context.pool()).await?;
+
+  let thread_view = ModmailThreadView::read(&mut context.pool(), thread.id, as_moderator).await?;
+  let messages = ModmailMessageView::list_for_thread(&mut context.pool(), thread.id).await?;
+
+  Ok(Json(ModmailThreadResponse {
+    thread_view,
+    messages,
+  }))
+}
@@ -1,11 +1,13 @@
-use super::convert_published_time;
+use super::{check_poll, convert_published_time};
 use activitypub_federation::config::Data;
 use actix_web::web::Json;
+use chrono::{TimeDelta, Utc};
 use lemmy_api_common::{
   build_response::{build_post_response, send_local_notifs},
   context::LemmyContext,
   post::{CreatePost, PostResponse},
   request::generate_post_link_metadata,
+  run_automod::run_post_automod,
   send_activity::SendActivityData,
   utils::{
     check_community_user_action,
@@ -23,17 +25,22 @@ use lemmy_db_schema::{
     community::Community,
     local_site::LocalSite,
     post::{Post, PostInsertForm, PostLike, PostLikeForm, PostRead, PostReadForm},
+    post_poll::{PostPoll, PostPollInsertForm, PostPollOptionInsertForm},
+    tag::{PostTagInsertForm, Tag},
+    thread_subscription::{ThreadSubscription, ThreadSubscriptionForm},
   },
   traits::{Crud, Likeable},
   utils::diesel_url_create,
+  DuplicateLinkPolicy,
 };
-use lemmy_db_views::structs::{CommunityModeratorView, LocalUserView};
+use lemmy_db_views::structs::{CommunityModeratorView, LocalUserView, PostView};
 use lemmy_utils::{
   error::{LemmyErrorExt, LemmyErrorType, LemmyResult},
   utils::{
     mention::scrape_text_for_mentions,
     slurs::check_slurs,
     validation::{
+      equivalent_urls,
       is_url_blocked,
       is_valid_alt_text_field,
       is_valid_body_field,
@@ -57,7 +64,25 @@ pub async fn create_post(
   let url_blocklist = get_url_blocklist(&context).await?;
 
   let body = process_markdown_opt(&data.body, &slur_regex, &url_blocklist, &context).await?;
-  let url = diesel_url_create(data.url.as_deref())?;
+  let mut url = diesel_url_create(data.url.as_deref())?;
+
+  // Crossposts of crossposts point to the original post, so the whole chain shares one id
+  let crosspost_of_id = if let Some(crosspost_of_id) = data.crosspost_of_id {
+    let original = PostView::read(
+      &mut context.pool(),
+      crosspost_of_id,
+      Some(&local_user_view.local_user),
+      false,
+    )
+    .await?
+    .post;
+    if url.is_none() {
+      url = original.url;
+    }
+    Some(original.crosspost_of_id.unwrap_or(original.id))
+  } else {
+    None
+  };
   let custom_thumbnail = diesel_url_create(data.custom_thumbnail.as_deref())?;
 
   is_valid_post_title(&data.name)?;
@@ -79,9 +104,36 @@ pub async fn create_post(
     is_valid_body_field(body, true)?;
   }
 
+  let poll_end_time = data
+    .poll
+    .as_ref()
+    .map(|poll| check_poll(poll, &slur_regex))
+    .transpose()?
+    .flatten();
+
   let community = Community::read(&mut context.pool(), data.community_id).await?;
   check_community_user_action(&local_user_view.person, &community, &mut context.pool()).await?;
 
+  // Check if the link was already posted to the community recently
+  let duplicate_of = match (&url, community.duplicate_link_policy) {
+    (Some(url), DuplicateLinkPolicy::Warn | DuplicateLinkPolicy::Reject) => {
+      let urls = equivalent_urls(url)
+        .into_iter()
+        .map(Into::into)
+        .collect::<Vec<_>>();
+      let since = Utc::now() - TimeDelta::days(community.duplicate_link_window_days.into());
+      Post::read_duplicate_link(&mut context.pool(), community.id, &urls, since).await?
+    }
+    _ => None,
+  };
+  if let Some(duplicate_of) = &duplicate_of {
+    if community.duplicate_link_policy == DuplicateLinkPolicy::Reject {
+      Err(LemmyErrorType::DuplicateLink {
+        post_id: duplicate_of.id.0,
+      })?
+    }
+  }
+
   // If its an NSFW community, then use that as a default
   let nsfw = data.nsfw.or(Some(community.nsfw));
 
@@ -103,6 +155,10 @@ pub async fn create_post(
   )
   .await?;
 
+  if let Some(tags) = &data.tags {
+    Tag::check_in_community(&mut context.pool(), data.community_id, tags).await?;
+  }
+
   let scheduled_publish_time =
     convert_published_time(data.scheduled_publish_time, &local_user_view, &context).await?;
   let post_form = PostInsertForm {
@@ -112,6 +168,7 @@ pub async fn create_post(
     nsfw,
     language_id: Some(language_id),
     scheduled_publish_time,
+    crosspost_of_id,
     ..PostInsertForm::new(
       data.name.trim().to_string(),
       local_user_view.person.id,
@@ -123,8 +180,33 @@ pub async fn create_post(
     .await
     .with_lemmy_type(LemmyErrorType::CouldntCreatePost)?;
 
+  if let Some(tags) = &data.tags {
+    PostTagInsertForm::set_for_post(&mut context.pool(), inserted_post.id, tags).await?;
+  }
+
+  // The poll needs to exist before the post is federated
+  if let Some(poll) = &data.poll {
+    let poll_form = PostPollInsertForm::new(
+      inserted_post.id,
+      poll.multiple_choice.unwrap_or_default(),
+      poll_end_time,
+    );
+    let option_forms = poll
+      .options
+      .iter()
+      .zip(0..)
+      .map(|(name, position)| {
+        PostPollOptionInsertForm::new(inserted_post.id, name.trim().to_string(), position)
+      })
+      .collect::<Vec<_>>();
+    PostPoll::upsert(&mut context.pool(), &poll_form, &option_forms)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntCreatePost)?;
+  }
+
   let community_id = community.id;
-  let federate_post = if scheduled_publish_time.is_none() {
+  let is_scheduled = scheduled_publish_time.is_some();
+  let federate_post = if !is_scheduled {
     send_webmention(inserted_post.clone(), community);
     |post| Some(SendActivityData::CreatePost(post))
   } else {
@@ -147,6 +229,11 @@ pub async fn create_post(
     .await
     .with_lemmy_type(LemmyErrorType::CouldntLikePost)?;
 
+  if local_user_view.local_user.auto_subscribe_own_posts {
+    let form = ThreadSubscriptionForm::new(person_id, post_id, None, false);
+    ThreadSubscription::set(&mut context.pool(), &form).await?;
+  }
+
   // Scan the post body for user mentions, add those rows
   let mentions = scrape_text_for_mentions(&inserted_post.body.clone().unwrap_or_default());
   send_local_notifs(
@@ -162,5 +249,26 @@ pub async fn create_post(
   let read_form = PostReadForm::new(post_id, person_id);
   PostRead::mark_as_read(&mut context.pool(), &read_form).await?;
 
-  build_post_response(&context, community_id, local_user_view, post_id).await
+  // Scheduled posts are checked once they are published
+  if !is_scheduled {
+    run_post_automod(&inserted_post, true, &context).await;
+  }
+
+  // The post was already created at this point, so failing to read the duplicate must not fail
+  // the request
+  let duplicate_of = if let Some(duplicate_of) = duplicate_of {
+    PostView::read(
+      &mut context.pool(),
+      duplicate_of.id,
+      Some(&local_user_view.local_user),
+      false,
+    )
+    .await
+    .ok()
+  } else {
+    None
+  };
+  let mut res = build_post_response(&context, community_id, local_user_view, post_id).await?;
+  res.duplicate_of = duplicate_of;
+  Ok(res)
 }
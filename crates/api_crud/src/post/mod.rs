@@ -1,8 +1,12 @@
 use chrono::{DateTime, TimeZone, Utc};
-use lemmy_api_common::context::LemmyContext;
+use lemmy_api_common::{context::LemmyContext, post::CreatePostPoll};
 use lemmy_db_schema::source::post::Post;
 use lemmy_db_views::structs::LocalUserView;
-use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+use lemmy_utils::{
+  error::{LemmyErrorType, LemmyResult},
+  utils::{slurs::check_slurs, validation::is_valid_poll_options},
+};
+use regex::Regex;
 
 pub mod create;
 pub mod delete;
@@ -36,3 +40,27 @@ async fn convert_published_time(
     Ok(None)
   }
 }
+
+/// Validates the poll of a new post, and returns the time when it ends.
+fn check_poll(
+  poll: &CreatePostPoll,
+  slur_regex: &Option<LemmyResult<Regex>>,
+) -> LemmyResult<Option<DateTime<Utc>>> {
+  is_valid_poll_options(&poll.options)?;
+  for option in &poll.options {
+    check_slurs(option, slur_regex)?;
+  }
+  let end_time = poll
+    .end_time
+    .map(|end_time| {
+      Utc
+        .timestamp_opt(end_time, 0)
+        .single()
+        .ok_or(LemmyErrorType::InvalidUnixTime)
+    })
+    .transpose()?;
+  if end_time.is_some_and(|end_time| end_time < Utc::now()) {
+    Err(LemmyErrorType::InvalidPoll)?;
+  }
+  Ok(end_time)
+}
@@ -10,6 +10,7 @@ use lemmy_db_schema::{
     post::{Post, PostRead, PostReadForm},
   },
   traits::Crud,
+  ListingType,
 };
 use lemmy_db_views::{
   post::post_view::PostQuery,
@@ -86,23 +87,15 @@ pub async fn get_post(
 
   let moderators = CommunityModeratorView::for_community(&mut context.pool(), community_id).await?;
 
-  // Fetch the cross_posts
-  let cross_posts = if let Some(url) = &post_view.post.url {
-    let mut x_posts = PostQuery {
-      url_only: Some(true),
-      search_term: Some(url.inner().as_str().into()),
-      local_user: local_user.as_ref(),
-      ..Default::default()
-    }
-    .list(&local_site.site, &mut context.pool())
-    .await?;
-
-    // Don't return this post as one of the cross_posts
-    x_posts.retain(|x| x.post.id != post_id);
-    x_posts
-  } else {
-    Vec::new()
-  };
+  // Fetch the cross_posts, including those in remote communities
+  let cross_posts = PostQuery {
+    listing_type: Some(ListingType::All),
+    cross_posts_of: Some(&post_view.post),
+    local_user: local_user.as_ref(),
+    ..Default::default()
+  }
+  .list(&local_site.site, &mut context.pool())
+  .await?;
 
   // Return the jwt
   Ok(Json(GetPostResponse {
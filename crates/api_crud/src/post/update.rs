@@ -7,6 +7,7 @@ use lemmy_api_common::{
   context::LemmyContext,
   post::{EditPost, PostResponse},
   request::generate_post_link_metadata,
+  run_automod::run_post_automod,
   send_activity::SendActivityData,
   utils::{
     check_community_user_action,
@@ -23,6 +24,8 @@ use lemmy_db_schema::{
     community::Community,
     local_site::LocalSite,
     post::{Post, PostUpdateForm},
+    post_revision::PostRevision,
+    tag::{PostTagInsertForm, Tag},
   },
   traits::Crud,
   utils::{diesel_string_update, diesel_url_update},
@@ -112,6 +115,10 @@ pub async fn update_post(
   )
   .await?;
 
+  if let Some(tags) = &data.tags {
+    Tag::check_in_community(&mut context.pool(), orig_post.post.community_id, tags).await?;
+  }
+
   // handle changes to scheduled_publish_time
   let scheduled_publish_time = match (
     orig_post.post.scheduled_publish_time,
@@ -143,6 +150,17 @@ pub async fn update_post(
   let updated_post = Post::update(&mut context.pool(), post_id, &post_form)
     .await
     .with_lemmy_type(LemmyErrorType::CouldntUpdatePost)?;
+  PostRevision::record(
+    &mut context.pool(),
+    &orig_post.post,
+    &updated_post,
+    local_user_view.person.id,
+  )
+  .await?;
+
+  if let Some(tags) = &data.tags {
+    PostTagInsertForm::set_for_post(&mut context.pool(), post_id, tags).await?;
+  }
 
   // Scan the post body for user mentions, add those rows
   let mentions = scrape_text_for_mentions(&updated_post.body.clone().unwrap_or_default());
@@ -172,6 +190,7 @@ pub async fn update_post(
         context.reset_request_count(),
       )
       .await?;
+      run_post_automod(&updated_post, true, &context).await;
     }
     // post was already public, send update
     (None, _) => {
@@ -181,7 +200,8 @@ pub async fn update_post(
         |post| Some(SendActivityData::UpdatePost(post)),
         context.reset_request_count(),
       )
-      .await?
+      .await?;
+      run_post_automod(&updated_post, false, &context).await;
     }
     // schedule was changed, do nothing
     (Some(_), Some(_)) => {}
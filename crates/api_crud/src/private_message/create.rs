@@ -11,6 +11,7 @@ use lemmy_api_common::{
     local_site_to_slur_regex,
     process_markdown,
     send_email_to_user,
+    send_push_to_user,
   },
 };
 use lemmy_db_schema::{
@@ -75,14 +76,21 @@ pub async fn create_private_message(
     let lang = get_interface_language(&local_recipient);
     let inbox_link = format!("{}/inbox", context.settings().get_protocol_and_hostname());
     let sender_name = &local_user_view.person.name;
-    let content = markdown_to_html(&content);
+    let html_content = markdown_to_html(&content);
     send_email_to_user(
       &local_recipient,
       &lang.notification_private_message_subject(sender_name),
-      &lang.notification_private_message_body(inbox_link, &content, sender_name),
-      context.settings(),
+      &lang.notification_private_message_body(&inbox_link, &html_content, sender_name),
+      &context,
     )
     .await;
+    send_push_to_user(
+      &local_recipient,
+      &lang.notification_private_message_subject(sender_name),
+      &content,
+      inbox_link,
+      &context,
+    );
   }
 
   ActivityChannel::submit_activity(
@@ -71,6 +71,7 @@ pub async fn create_site(
     private_key: Some(Some(keypair.private_key)),
     public_key: Some(keypair.public_key),
     content_warning: diesel_string_update(data.content_warning.as_deref()),
+    edit_history_public: data.edit_history_public,
     ..Default::default()
   };
 
@@ -1,6 +1,10 @@
 use crate::user::my_user::get_my_user;
-use actix_web::web::{Data, Json};
-use lemmy_api_common::{context::LemmyContext, site::GetSiteResponse};
+use actix_web::{
+  web::{Data, Json},
+  HttpMessage,
+  HttpRequest,
+};
+use lemmy_api_common::{context::LemmyContext, site::GetSiteResponse, utils::ApiTokenSession};
 use lemmy_db_schema::source::{
   actor_language::SiteLanguage,
   language::Language,
@@ -13,11 +17,14 @@ use lemmy_utils::{build_cache, error::LemmyResult, CacheLock, VERSION};
 use std::sync::LazyLock;
 
 pub async fn get_site_v3(
+  req: HttpRequest,
   local_user_view: Option<LocalUserView>,
   context: Data<LemmyContext>,
 ) -> LemmyResult<Json<GetSiteResponse>> {
   let mut site = get_site_v4(local_user_view.clone(), context.clone()).await?;
-  if let Some(local_user_view) = local_user_view {
+  // Account data is not available to api tokens
+  let is_api_token = req.extensions().get::<ApiTokenSession>().is_some();
+  if let Some(local_user_view) = local_user_view.filter(|_| !is_api_token) {
     site.my_user = Some(get_my_user(local_user_view, context).await?.0);
   }
   Ok(site)
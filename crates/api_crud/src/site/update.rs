@@ -74,6 +74,7 @@ pub async fn update_site(
     sidebar,
     description: diesel_string_update(data.description.as_deref()),
     content_warning: diesel_string_update(data.content_warning.as_deref()),
+    edit_history_public: data.edit_history_public,
     updated: Some(Some(Utc::now())),
     ..Default::default()
   };
@@ -0,0 +1,71 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_common::{
+  community::{CommunityTagResponse, CreateCommunityTag},
+  context::LemmyContext,
+  send_activity::{ActivityChannel, SendActivityData},
+  utils::{check_community_mod_action, local_site_to_slur_regex},
+};
+use lemmy_db_schema::{
+  source::{
+    community::Community,
+    local_site::LocalSite,
+    tag::{Tag, TagInsertForm},
+  },
+  traits::Crud,
+};
+use lemmy_db_views::structs::LocalUserView;
+use lemmy_utils::{
+  error::{LemmyErrorType, LemmyResult},
+  utils::{slurs::check_slurs, validation::is_valid_tag_name},
+};
+use uuid::Uuid;
+
+pub async fn create_community_tag(
+  data: Json<CreateCommunityTag>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<CommunityTagResponse>> {
+  let local_site = LocalSite::read(&mut context.pool()).await?;
+  let slur_regex = local_site_to_slur_regex(&local_site);
+  let name = data.name.trim().to_string();
+  is_valid_tag_name(&name)?;
+  check_slurs(&name, &slur_regex)?;
+
+  let community = Community::read(&mut context.pool(), data.community_id).await?;
+  check_community_mod_action(
+    &local_user_view.person,
+    &community,
+    false,
+    &mut context.pool(),
+  )
+  .await?;
+  // Tags of remote communities are federated from their home instance
+  if !community.local {
+    Err(LemmyErrorType::ObjectNotLocal)?
+  }
+
+  // The id has a random part, as the name can be changed later
+  let mut ap_id = community.actor_id.inner().clone();
+  ap_id
+    .path_segments_mut()
+    .ok()
+    .ok_or(LemmyErrorType::InvalidUrl)?
+    .extend(["tag", &Uuid::new_v4().to_string()]);
+  let tag_form = TagInsertForm {
+    ap_id: ap_id.into(),
+    name,
+    community_id: community.id,
+    published: None,
+    updated: None,
+    deleted: false,
+  };
+  let tag = Tag::create(&mut context.pool(), &tag_form).await?;
+
+  ActivityChannel::submit_activity(
+    SendActivityData::UpdateCommunity(local_user_view.person.clone(), community),
+    &context,
+  )?;
+
+  Ok(Json(CommunityTagResponse { tag }))
+}
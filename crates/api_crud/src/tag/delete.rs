@@ -0,0 +1,53 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use chrono::Utc;
+use lemmy_api_common::{
+  community::DeleteCommunityTag,
+  context::LemmyContext,
+  send_activity::{ActivityChannel, SendActivityData},
+  utils::check_community_mod_action,
+  SuccessResponse,
+};
+use lemmy_db_schema::{
+  source::{
+    community::Community,
+    tag::{Tag, TagUpdateForm},
+  },
+  traits::Crud,
+};
+use lemmy_db_views::structs::LocalUserView;
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+
+pub async fn delete_community_tag(
+  data: Json<DeleteCommunityTag>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<SuccessResponse>> {
+  let tag = Tag::read(&mut context.pool(), data.tag_id).await?;
+  let community = Community::read(&mut context.pool(), tag.community_id).await?;
+  check_community_mod_action(
+    &local_user_view.person,
+    &community,
+    false,
+    &mut context.pool(),
+  )
+  .await?;
+  if !community.local {
+    Err(LemmyErrorType::ObjectNotLocal)?
+  }
+
+  // Deleted tags are hidden from posts and tag listings
+  let tag_form = TagUpdateForm {
+    deleted: Some(true),
+    updated: Some(Some(Utc::now())),
+    ..Default::default()
+  };
+  Tag::update(&mut context.pool(), data.tag_id, &tag_form).await?;
+
+  ActivityChannel::submit_activity(
+    SendActivityData::UpdateCommunity(local_user_view.person.clone(), community),
+    &context,
+  )?;
+
+  Ok(Json(SuccessResponse::default()))
+}
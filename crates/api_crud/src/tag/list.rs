@@ -0,0 +1,16 @@
+use actix_web::web::{Data, Json, Query};
+use lemmy_api_common::{
+  community::{ListCommunityTags, ListCommunityTagsResponse},
+  context::LemmyContext,
+};
+use lemmy_db_schema::source::tag::Tag;
+use lemmy_utils::error::LemmyResult;
+
+pub async fn list_community_tags(
+  data: Query<ListCommunityTags>,
+  context: Data<LemmyContext>,
+) -> LemmyResult<Json<ListCommunityTagsResponse>> {
+  let tags = Tag::read_for_community(&mut context.pool(), data.community_id).await?;
+
+  Ok(Json(ListCommunityTagsResponse { tags }))
+}
@@ -0,0 +1,61 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use chrono::Utc;
+use lemmy_api_common::{
+  community::{CommunityTagResponse, UpdateCommunityTag},
+  context::LemmyContext,
+  send_activity::{ActivityChannel, SendActivityData},
+  utils::{check_community_mod_action, local_site_to_slur_regex},
+};
+use lemmy_db_schema::{
+  source::{
+    community::Community,
+    local_site::LocalSite,
+    tag::{Tag, TagUpdateForm},
+  },
+  traits::Crud,
+};
+use lemmy_db_views::structs::LocalUserView;
+use lemmy_utils::{
+  error::{LemmyErrorType, LemmyResult},
+  utils::{slurs::check_slurs, validation::is_valid_tag_name},
+};
+
+pub async fn update_community_tag(
+  data: Json<UpdateCommunityTag>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<CommunityTagResponse>> {
+  let local_site = LocalSite::read(&mut context.pool()).await?;
+  let slur_regex = local_site_to_slur_regex(&local_site);
+  let name = data.name.trim().to_string();
+  is_valid_tag_name(&name)?;
+  check_slurs(&name, &slur_regex)?;
+
+  let tag = Tag::read(&mut context.pool(), data.tag_id).await?;
+  let community = Community::read(&mut context.pool(), tag.community_id).await?;
+  check_community_mod_action(
+    &local_user_view.person,
+    &community,
+    false,
+    &mut context.pool(),
+  )
+  .await?;
+  if !community.local {
+    Err(LemmyErrorType::ObjectNotLocal)?
+  }
+
+  let tag_form = TagUpdateForm {
+    name: Some(name),
+    updated: Some(Some(Utc::now())),
+    ..Default::default()
+  };
+  let tag = Tag::update(&mut context.pool(), data.tag_id, &tag_form).await?;
+
+  ActivityChannel::submit_activity(
+    SendActivityData::UpdateCommunity(local_user_view.person.clone(), community),
+    &context,
+  )?;
+
+  Ok(Json(CommunityTagResponse { tag }))
+}
@@ -5,6 +5,8 @@ use lemmy_api_common::{
   context::LemmyContext,
   oauth_provider::AuthenticateWithOauth,
   person::{LoginResponse, Register},
+  run_automod::AUTOMOD_NAME,
+  send_webhook::send_webhook_event,
   utils::{
     check_email_verified,
     check_registration_application,
@@ -36,6 +38,7 @@ use lemmy_db_schema::{
   },
   traits::Crud,
   RegistrationMode,
+  WebhookEventType,
 };
 use lemmy_db_views::structs::{LocalUserView, SiteView};
 use lemmy_utils::{
@@ -153,7 +156,13 @@ pub async fn register(
         answer,
       };
 
-      RegistrationApplication::create(&mut context.pool(), &form).await?;
+      let application = RegistrationApplication::create(&mut context.pool(), &form).await?;
+      send_webhook_event(
+        WebhookEventType::RegistrationApplicationCreated,
+        None,
+        &application,
+        &context,
+      )?;
     }
   }
 
@@ -379,7 +388,7 @@ pub async fn authenticate_with_oauth(
       {
         if let Some(answer) = data.answer.clone() {
           // Create the registration application
-          RegistrationApplication::create(
+          let application = RegistrationApplication::create(
             &mut context.pool(),
             &RegistrationApplicationInsertForm {
               local_user_id: local_user.id,
@@ -387,6 +396,12 @@ pub async fn authenticate_with_oauth(
             },
           )
           .await?;
+          send_webhook_event(
+            WebhookEventType::RegistrationApplicationCreated,
+            None,
+            &application,
+            &context,
+          )?;
 
           login_response.registration_created = true;
         }
@@ -414,6 +429,10 @@ async fn create_person(
 ) -> Result<Person, LemmyError> {
   let actor_keypair = generate_actor_keypair()?;
   is_valid_actor_name(&username, local_site.actor_name_max_length as usize)?;
+  // Reserved for the local automod account
+  if username.eq_ignore_ascii_case(AUTOMOD_NAME) {
+    Err(LemmyErrorType::UserAlreadyExists)?
+  }
   let actor_id = generate_local_apub_endpoint(
     EndpointType::Person,
     &username,
@@ -4,9 +4,15 @@ use lemmy_db_schema::source::{
   actor_language::LocalUserLanguage,
   community_block::CommunityBlock,
   instance_block::InstanceBlock,
+  local_user_email_digest::LocalUserEmailDigest,
   person_block::PersonBlock,
 };
-use lemmy_db_views::structs::{CommunityFollowerView, CommunityModeratorView, LocalUserView};
+use lemmy_db_views::structs::{
+  CommunityFollowerView,
+  CommunityModeratorView,
+  KeywordFilterView,
+  LocalUserView,
+};
 use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
 
 pub async fn get_my_user(
@@ -20,16 +26,26 @@ pub async fn get_my_user(
   let local_user_id = local_user_view.local_user.id;
   let pool = &mut context.pool();
 
-  let (follows, community_blocks, instance_blocks, person_blocks, moderates, discussion_languages) =
-    lemmy_db_schema::try_join_with_pool!(pool => (
-      |pool| CommunityFollowerView::for_person(pool, person_id),
-      |pool| CommunityBlock::for_person(pool, person_id),
-      |pool| InstanceBlock::for_person(pool, person_id),
-      |pool| PersonBlock::for_person(pool, person_id),
-      |pool| CommunityModeratorView::for_person(pool, person_id, Some(&local_user_view.local_user)),
-      |pool| LocalUserLanguage::read(pool, local_user_id)
-    ))
-    .with_lemmy_type(LemmyErrorType::SystemErrLogin)?;
+  let (
+    follows,
+    community_blocks,
+    instance_blocks,
+    person_blocks,
+    moderates,
+    discussion_languages,
+    keyword_filters,
+    email_digest_mode,
+  ) = lemmy_db_schema::try_join_with_pool!(pool => (
+    |pool| CommunityFollowerView::for_person(pool, person_id),
+    |pool| CommunityBlock::for_person(pool, person_id),
+    |pool| InstanceBlock::for_person(pool, person_id),
+    |pool| PersonBlock::for_person(pool, person_id),
+    |pool| CommunityModeratorView::for_person(pool, person_id, Some(&local_user_view.local_user)),
+    |pool| LocalUserLanguage::read(pool, local_user_id),
+    |pool| KeywordFilterView::list(pool, local_user_id),
+    |pool| LocalUserEmailDigest::read_mode(pool, local_user_id)
+  ))
+  .with_lemmy_type(LemmyErrorType::SystemErrLogin)?;
 
   Ok(Json(MyUserInfo {
     local_user_view: local_user_view.clone(),
@@ -39,5 +55,7 @@ pub async fn get_my_user(
     instance_blocks,
     person_blocks,
     discussion_languages,
+    keyword_filters,
+    email_digest_mode,
   }))
 }
@@ -0,0 +1,41 @@
+use super::{check_webhook_events, check_webhook_permission};
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_common::{
+  context::LemmyContext,
+  request::check_url_is_public,
+  webhook::{CreateWebhook, WebhookResponse},
+};
+use lemmy_db_schema::{
+  source::webhook::{Webhook, WebhookInsertForm},
+  traits::Crud,
+};
+use lemmy_db_views::structs::{LocalUserView, WebhookView};
+use lemmy_utils::{
+  error::LemmyResult,
+  utils::validation::{is_valid_webhook_secret, is_valid_webhook_url},
+};
+
+pub async fn create_webhook(
+  data: Json<CreateWebhook>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<WebhookResponse>> {
+  check_webhook_permission(data.community_id, &local_user_view, &context).await?;
+  is_valid_webhook_url(&data.url)?;
+  check_url_is_public(&data.url).await?;
+  is_valid_webhook_secret(&data.secret)?;
+  check_webhook_events(data.community_id, &data.events)?;
+
+  let webhook_form = WebhookInsertForm::new(
+    data.community_id,
+    local_user_view.person.id,
+    data.url.to_string(),
+    data.secret.clone(),
+  );
+  let webhook = Webhook::create(&mut context.pool(), &webhook_form).await?;
+  Webhook::set_events(&mut context.pool(), webhook.id, &data.events).await?;
+
+  let webhook = WebhookView::read(&mut context.pool(), webhook.id).await?;
+  Ok(Json(WebhookResponse { webhook }))
+}
@@ -0,0 +1,21 @@
+use super::check_webhook_permission;
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_common::{context::LemmyContext, webhook::DeleteWebhook, SuccessResponse};
+use lemmy_db_schema::{source::webhook::Webhook, traits::Crud};
+use lemmy_db_views::structs::LocalUserView;
+use lemmy_utils::error::LemmyResult;
+
+pub async fn delete_webhook(
+  data: Json<DeleteWebhook>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<SuccessResponse>> {
+  let webhook = Webhook::read(&mut context.pool(), data.webhook_id).await?;
+  check_webhook_permission(webhook.community_id, &local_user_view, &context).await?;
+
+  // The subscribed events and the delivery log are deleted as well
+  Webhook::delete(&mut context.pool(), webhook.id).await?;
+
+  Ok(Json(SuccessResponse::default()))
+}
@@ -0,0 +1,20 @@
+use super::check_webhook_permission;
+use activitypub_federation::config::Data;
+use actix_web::web::{Json, Query};
+use lemmy_api_common::{
+  context::LemmyContext,
+  webhook::{ListWebhooks, ListWebhooksResponse},
+};
+use lemmy_db_views::structs::{LocalUserView, WebhookView};
+use lemmy_utils::error::LemmyResult;
+
+pub async fn list_webhooks(
+  data: Query<ListWebhooks>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<ListWebhooksResponse>> {
+  check_webhook_permission(data.community_id, &local_user_view, &context).await?;
+
+  let webhooks = WebhookView::list(&mut context.pool(), data.community_id).await?;
+  Ok(Json(ListWebhooksResponse { webhooks }))
+}
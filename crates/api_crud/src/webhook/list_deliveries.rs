@@ -0,0 +1,26 @@
+use super::check_webhook_permission;
+use activitypub_federation::config::Data;
+use actix_web::web::{Json, Query};
+use lemmy_api_common::{
+  context::LemmyContext,
+  webhook::{ListWebhookDeliveries, ListWebhookDeliveriesResponse},
+};
+use lemmy_db_schema::{
+  source::webhook::{Webhook, WebhookDelivery},
+  traits::Crud,
+};
+use lemmy_db_views::structs::LocalUserView;
+use lemmy_utils::error::LemmyResult;
+
+pub async fn list_webhook_deliveries(
+  data: Query<ListWebhookDeliveries>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<ListWebhookDeliveriesResponse>> {
+  let webhook = Webhook::read(&mut context.pool(), data.webhook_id).await?;
+  check_webhook_permission(webhook.community_id, &local_user_view, &context).await?;
+
+  let deliveries =
+    WebhookDelivery::list(&mut context.pool(), webhook.id, data.page, data.limit).await?;
+  Ok(Json(ListWebhookDeliveriesResponse { deliveries }))
+}
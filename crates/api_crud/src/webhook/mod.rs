@@ -0,0 +1,52 @@
+use lemmy_api_common::{
+  context::LemmyContext,
+  utils::{check_community_mod_action, is_admin},
+};
+use lemmy_db_schema::{
+  newtypes::CommunityId,
+  source::community::Community,
+  traits::Crud,
+  WebhookEventType,
+};
+use lemmy_db_views::structs::LocalUserView;
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+
+pub mod create;
+pub mod delete;
+pub mod list;
+pub mod list_deliveries;
+pub mod update;
+
+/// Site-wide webhooks can only be managed by admins, community webhooks also by the moderators of
+/// the community.
+async fn check_webhook_permission(
+  community_id: Option<CommunityId>,
+  local_user_view: &LocalUserView,
+  context: &LemmyContext,
+) -> LemmyResult<()> {
+  match community_id {
+    Some(community_id) => {
+      let community = Community::read(&mut context.pool(), community_id).await?;
+      check_community_mod_action(
+        &local_user_view.person,
+        &community,
+        false,
+        &mut context.pool(),
+      )
+      .await
+    }
+    None => is_admin(local_user_view),
+  }
+}
+
+/// Registration applications are only visible to admins, so they can't be sent to community
+/// webhooks.
+fn check_webhook_events(
+  community_id: Option<CommunityId>,
+  events: &[WebhookEventType],
+) -> LemmyResult<()> {
+  if community_id.is_some() && events.contains(&WebhookEventType::RegistrationApplicationCreated) {
+    Err(LemmyErrorType::InvalidWebhookEvent)?
+  }
+  Ok(())
+}
@@ -0,0 +1,51 @@
+use super::{check_webhook_events, check_webhook_permission};
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use chrono::Utc;
+use lemmy_api_common::{
+  context::LemmyContext,
+  request::check_url_is_public,
+  webhook::{UpdateWebhook, WebhookResponse},
+};
+use lemmy_db_schema::{
+  source::webhook::{Webhook, WebhookUpdateForm},
+  traits::Crud,
+};
+use lemmy_db_views::structs::{LocalUserView, WebhookView};
+use lemmy_utils::{
+  error::LemmyResult,
+  utils::validation::{is_valid_webhook_secret, is_valid_webhook_url},
+};
+
+pub async fn update_webhook(
+  data: Json<UpdateWebhook>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<WebhookResponse>> {
+  let webhook = Webhook::read(&mut context.pool(), data.webhook_id).await?;
+  check_webhook_permission(webhook.community_id, &local_user_view, &context).await?;
+  if let Some(url) = &data.url {
+    is_valid_webhook_url(url)?;
+    check_url_is_public(url).await?;
+  }
+  if let Some(secret) = &data.secret {
+    is_valid_webhook_secret(secret)?;
+  }
+  if let Some(events) = &data.events {
+    check_webhook_events(webhook.community_id, events)?;
+  }
+
+  let webhook_form = WebhookUpdateForm {
+    url: data.url.as_ref().map(ToString::to_string),
+    secret: data.secret.clone(),
+    enabled: data.enabled,
+    updated: Some(Some(Utc::now())),
+  };
+  Webhook::update(&mut context.pool(), webhook.id, &webhook_form).await?;
+  if let Some(events) = &data.events {
+    Webhook::set_events(&mut context.pool(), webhook.id, events).await?;
+  }
+
+  let webhook = WebhookView::read(&mut context.pool(), webhook.id).await?;
+  Ok(Json(WebhookResponse { webhook }))
+}
@@ -25,6 +25,7 @@ use activitypub_federation::{
 use lemmy_api_common::{
   build_response::send_local_notifs,
   context::LemmyContext,
+  run_automod::run_comment_automod,
   utils::{check_post_deleted_or_removed, is_mod_or_admin},
 };
 use lemmy_db_schema::{
@@ -33,6 +34,7 @@ use lemmy_db_schema::{
   source::{
     activity::ActivitySendTargets,
     comment::{Comment, CommentLike, CommentLikeForm},
+    comment_revision::CommentRevision,
     community::Community,
     person::Person,
     post::Post,
@@ -47,6 +49,29 @@ use serde_json::{from_value, to_value};
 use url::Url;
 
 impl CreateOrUpdateNote {
+  pub(crate) async fn new(
+    comment: ApubComment,
+    actor: &ApubPerson,
+    community: &ApubCommunity,
+    kind: CreateOrUpdateType,
+    context: &Data<LemmyContext>,
+  ) -> LemmyResult<CreateOrUpdateNote> {
+    let id = generate_activity_id(
+      kind.clone(),
+      &context.settings().get_protocol_and_hostname(),
+    )?;
+    let note = comment.into_json(context).await?;
+    Ok(CreateOrUpdateNote {
+      actor: actor.id().into(),
+      to: generate_to(community)?,
+      cc: note.cc.clone(),
+      tag: note.tag.clone(),
+      object: note,
+      kind,
+      id,
+    })
+  }
+
   pub(crate) async fn send(
     comment: Comment,
     person_id: PersonId,
@@ -62,21 +87,8 @@ impl CreateOrUpdateNote {
       .await?
       .into();
 
-    let id = generate_activity_id(
-      kind.clone(),
-      &context.settings().get_protocol_and_hostname(),
-    )?;
-    let note = ApubComment(comment).into_json(&context).await?;
-
-    let create_or_update = CreateOrUpdateNote {
-      actor: person.id().into(),
-      to: generate_to(&community)?,
-      cc: note.cc.clone(),
-      tag: note.tag.clone(),
-      object: note,
-      kind,
-      id: id.clone(),
-    };
+    let create_or_update =
+      CreateOrUpdateNote::new(ApubComment(comment), &person, &community, kind, &context).await?;
 
     let tagged_users: Vec<ObjectId<ApubPerson>> = create_or_update
       .tag
@@ -140,7 +152,7 @@ impl ActivityHandler for CreateOrUpdateNote {
     // send the activity, not the comment author.
     let existing_comment = self.object.id.dereference_local(context).await.ok();
     if let (Some(distinguished), Some(existing_comment)) =
-      (self.object.distinguished, existing_comment)
+      (self.object.distinguished, &existing_comment)
     {
       if distinguished != existing_comment.distinguished {
         let creator = self.actor.dereference(context).await?;
@@ -150,6 +162,12 @@ impl ActivityHandler for CreateOrUpdateNote {
     }
 
     let comment = ApubComment::from_json(self.object, context).await?;
+    let actor = self.actor.dereference(context).await?;
+
+    // Keep the previous version of an edited comment
+    if let Some(existing_comment) = existing_comment {
+      CommentRevision::record(&mut context.pool(), &existing_comment, &comment, actor.id).await?;
+    }
 
     // author likes their own comment by default
     let like_form = CommentLikeForm {
@@ -162,8 +180,9 @@ impl ActivityHandler for CreateOrUpdateNote {
     // Calculate initial hot_rank
     CommentAggregates::update_hot_rank(&mut context.pool(), comment.id).await?;
 
+    run_comment_automod(&comment, self.kind == CreateOrUpdateType::Create, context).await;
+
     let do_send_email = self.kind == CreateOrUpdateType::Create;
-    let actor = self.actor.dereference(context).await?;
 
     // Note:
     // Although mentions could be gotten from the post tags (they are included there), or the ccs,
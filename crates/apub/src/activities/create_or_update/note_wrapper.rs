@@ -4,6 +4,7 @@ use crate::{
     activities::create_or_update::{
       note::CreateOrUpdateNote,
       note_wrapper::CreateOrUpdateNoteWrapper,
+      poll_vote::CreatePollVote,
       private_message::CreateOrUpdatePrivateMessage,
     },
     InCommunity,
@@ -15,9 +16,9 @@ use lemmy_utils::error::{LemmyError, LemmyResult};
 use serde_json::{from_value, to_value};
 use url::Url;
 
-/// In Activitypub, both private messages and comments are represented by `type: Note` which
-/// makes it difficult to distinguish them. This wrapper handles receiving of both types, and
-/// routes them to the correct handler.
+/// In Activitypub, private messages, comments and poll votes are all represented by `type: Note`
+/// which makes it difficult to distinguish them. This wrapper handles receiving of these types,
+/// and routes them to the correct handler.
 #[async_trait::async_trait]
 impl ActivityHandler for CreateOrUpdateNoteWrapper {
   type DataType = LemmyContext;
@@ -53,6 +54,14 @@ impl ActivityHandler for CreateOrUpdateNoteWrapper {
       }
     }
 
+    // Poll votes have the chosen option as name, and reply to the poll.
+    let poll_vote = from_value::<CreatePollVote>(val.clone());
+    if let Ok(poll_vote) = poll_vote {
+      CreatePollVote::verify(&poll_vote, context).await?;
+      CreatePollVote::receive(poll_vote, context).await?;
+      return Ok(());
+    }
+
     // If any of the previous checks failed, we are dealing with a private message.
     let private_message = from_value(val)?;
     CreateOrUpdatePrivateMessage::verify(&private_message, context).await?;
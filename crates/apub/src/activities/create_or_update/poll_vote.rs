@@ -0,0 +1,124 @@
+use crate::{
+  activities::{
+    generate_activity_id,
+    send_lemmy_activity,
+    verify_person,
+    verify_person_in_community,
+  },
+  insert_received_activity,
+  objects::person::ApubPerson,
+  protocol::{
+    activities::create_or_update::poll_vote::CreatePollVote,
+    objects::question::PollVote,
+  },
+};
+use activitypub_federation::{
+  config::Data,
+  kinds::{activity::CreateType, object::NoteType},
+  protocol::verification::{verify_domains_match, verify_urls_match},
+  traits::{ActivityHandler, Actor},
+};
+use chrono::Utc;
+use lemmy_api_common::context::LemmyContext;
+use lemmy_db_schema::{
+  source::{
+    activity::ActivitySendTargets,
+    community::Community,
+    person::Person,
+    post::Post,
+    post_poll::{PostPoll, PostPollOption, PostPollVote},
+  },
+  traits::Crud,
+};
+use lemmy_utils::error::{LemmyError, LemmyErrorType, LemmyResult};
+use url::Url;
+
+/// Sends the votes of a local user in a remote poll to the author of the poll. Like Mastodon, a
+/// separate activity is sent for each chosen option.
+pub(crate) async fn send_poll_vote(
+  post: Post,
+  voter: Person,
+  option_names: Vec<String>,
+  context: Data<LemmyContext>,
+) -> LemmyResult<()> {
+  let actor: ApubPerson = voter.into();
+  let poll_creator: ApubPerson = Person::read(&mut context.pool(), post.creator_id)
+    .await?
+    .into();
+  let protocol_and_hostname = context.settings().get_protocol_and_hostname();
+  for name in option_names {
+    let vote = CreatePollVote {
+      id: generate_activity_id(CreateType::Create, &protocol_and_hostname)?,
+      actor: actor.id().into(),
+      to: vec![poll_creator.id()],
+      object: PollVote {
+        kind: NoteType::Note,
+        id: generate_activity_id(NoteType::Note, &protocol_and_hostname)?,
+        attributed_to: actor.id().into(),
+        to: vec![poll_creator.id()],
+        name,
+        in_reply_to: post.ap_id.clone().into(),
+      },
+      kind: CreateType::Create,
+    };
+    let inbox = ActivitySendTargets::to_inbox(poll_creator.shared_inbox_or_inbox());
+    send_lemmy_activity(&context, vote, &actor, inbox, true).await?;
+  }
+  Ok(())
+}
+
+#[async_trait::async_trait]
+impl ActivityHandler for CreatePollVote {
+  type DataType = LemmyContext;
+  type Error = LemmyError;
+
+  fn id(&self) -> &Url {
+    &self.id
+  }
+
+  fn actor(&self) -> &Url {
+    self.actor.inner()
+  }
+
+  async fn verify(&self, context: &Data<Self::DataType>) -> LemmyResult<()> {
+    verify_person(&self.actor, context).await?;
+    verify_domains_match(self.actor.inner(), &self.object.id)?;
+    verify_urls_match(self.actor.inner(), self.object.attributed_to.inner())?;
+    let post = self.object.in_reply_to.dereference(context).await?;
+    // Only the instance of the poll counts the votes
+    if !post.local {
+      Err(LemmyErrorType::ObjectNotLocal)?
+    }
+    let community = Community::read(&mut context.pool(), post.community_id).await?;
+    verify_person_in_community(&self.actor, &community.into(), context).await?;
+    Ok(())
+  }
+
+  async fn receive(self, context: &Data<Self::DataType>) -> LemmyResult<()> {
+    insert_received_activity(&self.id, context).await?;
+    let voter = self.actor.dereference(context).await?;
+    let post = self.object.in_reply_to.dereference(context).await?;
+    let poll = PostPoll::read(&mut context.pool(), post.id).await?;
+    if poll.end_time.is_some_and(|end_time| end_time < Utc::now()) {
+      Err(LemmyErrorType::PollEnded)?
+    }
+    let option = PostPollOption::read_for_poll(&mut context.pool(), post.id)
+      .await?
+      .into_iter()
+      .find(|o| o.name == self.object.name)
+      .ok_or(LemmyErrorType::InvalidPoll)?;
+
+    // Mastodon sends a separate activity for each option, so only single choice polls are
+    // limited to the first vote
+    PostPollVote::vote(
+      &mut context.pool(),
+      post.id,
+      voter.id,
+      &[option.id],
+      !poll.multiple_choice,
+    )
+    .await?;
+    // The vote counts are sent out once the poll ends
+    Ok(())
+  }
+}
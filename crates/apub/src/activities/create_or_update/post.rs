@@ -20,7 +20,11 @@ use activitypub_federation::{
   protocol::verification::{verify_domains_match, verify_urls_match},
   traits::{ActivityHandler, Actor, Object},
 };
-use lemmy_api_common::{build_response::send_local_notifs, context::LemmyContext};
+use lemmy_api_common::{
+  build_response::send_local_notifs,
+  context::LemmyContext,
+  run_automod::run_post_automod,
+};
 use lemmy_db_schema::{
   aggregates::structs::PostAggregates,
   newtypes::{PersonId, PostOrCommentId},
@@ -29,6 +33,7 @@ use lemmy_db_schema::{
     community::Community,
     person::Person,
     post::{Post, PostLike, PostLikeForm},
+    post_revision::PostRevision,
   },
   traits::{Crud, Likeable},
 };
@@ -114,8 +119,15 @@ impl ActivityHandler for CreateOrUpdatePage {
 
   async fn receive(self, context: &Data<LemmyContext>) -> LemmyResult<()> {
     insert_received_activity(&self.id, context).await?;
+    let actor = self.actor.dereference(context).await?;
+    let existing_post = self.object.id.dereference_local(context).await.ok();
     let post = ApubPost::from_json(self.object, context).await?;
 
+    // Keep the previous version of an edited post
+    if let Some(existing_post) = existing_post {
+      PostRevision::record(&mut context.pool(), &existing_post, &post, actor.id).await?;
+    }
+
     // author likes their own post by default
     let like_form = PostLikeForm::new(post.id, post.creator_id, 1);
     PostLike::like(&mut context.pool(), &like_form).await?;
@@ -123,8 +135,9 @@ impl ActivityHandler for CreateOrUpdatePage {
     // Calculate initial hot_rank for post
     PostAggregates::update_ranks(&mut context.pool(), post.id).await?;
 
+    run_post_automod(&post, self.kind == CreateOrUpdateType::Create, context).await;
+
     let do_send_email = self.kind == CreateOrUpdateType::Create;
-    let actor = self.actor.dereference(context).await?;
 
     // Send the post body mentions
     let mentions = scrape_text_for_mentions(&post.body.clone().unwrap_or_default());
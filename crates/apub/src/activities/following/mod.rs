@@ -4,6 +4,7 @@ use crate::{
   protocol::activities::following::{
     accept::AcceptFollow,
     follow::Follow,
+    move_user::MoveUser,
     reject::RejectFollow,
     undo_follow::UndoFollow,
   },
@@ -19,6 +20,7 @@ use lemmy_utils::error::LemmyResult;
 
 pub(crate) mod accept;
 pub(crate) mod follow;
+pub(crate) mod move_user;
 pub(crate) mod reject;
 pub(crate) mod undo_follow;
 
@@ -37,6 +39,14 @@ pub async fn send_follow_community(
   }
 }
 
+pub async fn send_move_user(
+  person: Person,
+  target: Person,
+  context: &Data<LemmyContext>,
+) -> LemmyResult<()> {
+  MoveUser::send(person.into(), target.into(), context).await
+}
+
 pub async fn send_accept_or_reject_follow(
   community_id: CommunityId,
   person_id: PersonId,
@@ -0,0 +1,116 @@
+use crate::{
+  activities::{generate_activity_id, send_lemmy_activity, verify_person},
+  insert_received_activity,
+  objects::{community::ApubCommunity, person::ApubPerson},
+  protocol::activities::following::{follow::Follow, move_user::MoveUser, undo_follow::UndoFollow},
+};
+use activitypub_federation::{
+  config::Data,
+  kinds::{activity::MoveType, public},
+  protocol::verification::verify_urls_match,
+  traits::{ActivityHandler, Actor},
+};
+use lemmy_api_common::context::LemmyContext;
+use lemmy_db_schema::{
+  source::{
+    activity::ActivitySendTargets,
+    community::{Community, CommunityFollower},
+    person::{Person, PersonUpdateForm},
+    person_alias::PersonAlias,
+  },
+  traits::Crud,
+};
+use lemmy_utils::error::{LemmyError, LemmyErrorType, LemmyResult};
+use url::Url;
+
+impl MoveUser {
+  pub(crate) async fn send(
+    person: ApubPerson,
+    target: ApubPerson,
+    context: &Data<LemmyContext>,
+  ) -> LemmyResult<()> {
+    let move_ = MoveUser {
+      actor: person.id().into(),
+      to: vec![public()],
+      object: person.id().into(),
+      target: target.id().into(),
+      kind: MoveType::Move,
+      id: generate_activity_id(
+        MoveType::Move,
+        &context.settings().get_protocol_and_hostname(),
+      )?,
+    };
+
+    let inboxes = ActivitySendTargets::to_all_instances();
+    send_lemmy_activity(context, move_, &person, inboxes, true).await
+  }
+}
+
+#[async_trait::async_trait]
+impl ActivityHandler for MoveUser {
+  type DataType = LemmyContext;
+  type Error = LemmyError;
+
+  fn id(&self) -> &Url {
+    &self.id
+  }
+
+  fn actor(&self) -> &Url {
+    self.actor.inner()
+  }
+
+  async fn verify(&self, context: &Data<LemmyContext>) -> LemmyResult<()> {
+    verify_person(&self.actor, context).await?;
+    verify_urls_match(self.actor.inner(), self.object.inner())?;
+    Ok(())
+  }
+
+  async fn receive(self, context: &Data<LemmyContext>) -> LemmyResult<()> {
+    insert_received_activity(&self.id, context).await?;
+    let person = self.actor.dereference(context).await?;
+    // Refetch the target so that recently added aliases are known
+    let target = self.target.dereference_forced(context).await?;
+    migrate_account(&person, &target, context).await
+  }
+}
+
+/// Marks the person as moved to the target account, and migrates community follows to the target.
+/// Fails if the target doesn't list the person as alias. Old profiles are not redirected by the
+/// server, clients and other instances get the target as `moved_to` of the person.
+pub(crate) async fn migrate_account(
+  person: &ApubPerson,
+  target: &ApubPerson,
+  context: &Data<LemmyContext>,
+) -> LemmyResult<()> {
+  if person.id == target.id {
+    Err(LemmyErrorType::CantMoveToSelf)?
+  }
+  PersonAlias::check_is_alias(&mut context.pool(), target.id, &person.actor_id).await?;
+
+  let form = PersonUpdateForm {
+    moved_to: Some(Some(target.actor_id.clone())),
+    ..Default::default()
+  };
+  Person::update(&mut context.pool(), person.id, &form).await?;
+
+  // Follows of remote communities are migrated by the community's own instance. Only if the new
+  // account is local do we need to take care of them, by sending follows in its name. If the old
+  // account is also local, its follows are undone, as the community doesn't know about the move.
+  let community_ids =
+    CommunityFollower::transfer_follows(&mut context.pool(), person.id, target.id, !target.local)
+      .await?;
+  if target.local {
+    for community_id in community_ids {
+      let community: ApubCommunity = Community::read(&mut context.pool(), community_id)
+        .await?
+        .into();
+      if !community.local {
+        if person.local {
+          UndoFollow::send(person, &community, context).await?;
+        }
+        Follow::send(target, &community, context).await?;
+      }
+    }
+  }
+  Ok(())
+}
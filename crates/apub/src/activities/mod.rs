@@ -7,7 +7,7 @@ use crate::{
       lock_page::send_lock_post,
       update::send_update_community,
     },
-    create_or_update::private_message::send_create_or_update_pm,
+    create_or_update::{poll_vote::send_poll_vote, private_message::send_create_or_update_pm},
     deletion::{
       send_apub_delete_in_community,
       send_apub_delete_private_message,
@@ -30,10 +30,11 @@ use activitypub_federation::{
   traits::{ActivityHandler, Actor},
 };
 use anyhow::anyhow;
-use following::send_accept_or_reject_follow;
+use following::{send_accept_or_reject_follow, send_move_user};
 use lemmy_api_common::{
   context::LemmyContext,
   send_activity::{ActivityChannel, SendActivityData},
+  send_webhook::send_activity_webhooks,
 };
 use lemmy_db_schema::{
   source::{
@@ -233,6 +234,9 @@ pub async fn match_outgoing_activities(
   context: &Data<LemmyContext>,
 ) -> LemmyResult<()> {
   let context = context.reset_request_count();
+  if let Err(e) = send_activity_webhooks(&data, &context).await {
+    tracing::warn!("error while sending webhooks: {e}");
+  }
   let fed_task = async {
     use SendActivityData::*;
     match data {
@@ -307,6 +311,19 @@ pub async fn match_outgoing_activities(
         community,
         score,
       } => send_like_activity(object_id, actor, community, score, context).await,
+      VotePostPoll {
+        post,
+        voter,
+        option_names,
+      } => {
+        // Votes in local polls are counted already, and the counts are sent out once the poll
+        // ended
+        if post.local {
+          Ok(())
+        } else {
+          send_poll_vote(post, voter, option_names, context).await
+        }
+      }
       FollowCommunity(community, person, follow) => {
         send_follow_community(community, person, follow, &context).await
       }
@@ -373,6 +390,7 @@ pub async fn match_outgoing_activities(
         send_apub_delete_private_message(&person.into(), pm, deleted, context).await
       }
       DeleteUser(person, remove_data) => send_apub_delete_user(person, remove_data, context).await,
+      MoveUser(person, target) => send_move_user(person, target, &context).await,
       CreateReport {
         object_id,
         actor,
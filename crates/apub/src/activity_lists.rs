@@ -16,6 +16,7 @@ use crate::{
       following::{
         accept::AcceptFollow,
         follow::Follow,
+        move_user::MoveUser,
         reject::RejectFollow,
         undo_follow::UndoFollow,
       },
@@ -46,6 +47,7 @@ pub enum SharedInboxActivities {
   UndoFollow(UndoFollow),
   Report(Report),
   AnnounceActivity(AnnounceActivity),
+  MoveUser(MoveUser),
   /// This is a catch-all and needs to be last
   RawAnnouncableActivities(RawAnnouncableActivities),
 }
@@ -123,7 +125,9 @@ mod tests {
     test_parse_lemmy_item::<SharedInboxActivities>(
       "assets/lemmy/activities/create_or_update/create_comment.json",
     )?;
+    test_parse_lemmy_item::<SharedInboxActivities>("assets/lemmy/activities/following/move.json")?;
     test_json::<SharedInboxActivities>("assets/mastodon/activities/follow.json")?;
+    test_json::<SharedInboxActivities>("assets/mastodon/activities/move.json")?;
     Ok(())
   }
 }
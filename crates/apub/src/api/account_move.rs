@@ -0,0 +1,106 @@
+use crate::{
+  activities::following::move_user::migrate_account,
+  fetcher::resolve_actor_identifier,
+  objects::person::ApubPerson,
+};
+use activitypub_federation::{config::Data, fetch::object_id::ObjectId};
+use actix_web::web::Json;
+use bcrypt::verify;
+use lemmy_api_common::{
+  context::LemmyContext,
+  person::{AccountAliasesResponse, MoveAccount, UpdateAccountAliases},
+  send_activity::{ActivityChannel, SendActivityData},
+  utils::MAX_ACCOUNT_ALIASES,
+  SuccessResponse,
+};
+use lemmy_db_schema::source::{person::Person, person_alias::PersonAlias};
+use lemmy_db_views::structs::LocalUserView;
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+use url::Url;
+
+pub async fn list_account_aliases(
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<AccountAliasesResponse>> {
+  let aliases = PersonAlias::list(&mut context.pool(), local_user_view.person.id).await?;
+  Ok(Json(AccountAliasesResponse { aliases }))
+}
+
+pub async fn update_account_aliases(
+  data: Json<UpdateAccountAliases>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<AccountAliasesResponse>> {
+  if data.aliases.len() > MAX_ACCOUNT_ALIASES {
+    Err(LemmyErrorType::TooManyAliases)?
+  }
+
+  let mut aliases = vec![];
+  for identifier in &data.aliases {
+    let alias = resolve_person(identifier, &context, &local_user_view).await?;
+    if alias.id == local_user_view.person.id {
+      Err(LemmyErrorType::CantMoveToSelf)?
+    }
+    aliases.push(alias.actor_id.clone());
+  }
+  PersonAlias::replace(&mut context.pool(), local_user_view.person.id, aliases).await?;
+
+  let aliases = PersonAlias::list(&mut context.pool(), local_user_view.person.id).await?;
+  Ok(Json(AccountAliasesResponse { aliases }))
+}
+
+pub async fn move_account(
+  data: Json<MoveAccount>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<SuccessResponse>> {
+  // Verify the password
+  let valid: bool = local_user_view
+    .local_user
+    .password_encrypted
+    .as_ref()
+    .and_then(|password_encrypted| verify(&data.password, password_encrypted).ok())
+    .unwrap_or(false);
+  if !valid {
+    Err(LemmyErrorType::IncorrectLogin)?
+  }
+
+  let target = resolve_person(&data.target, &context, &local_user_view).await?;
+  // Refetch the target so that recently added aliases are known
+  let target = ObjectId::<ApubPerson>::from(target.actor_id.clone())
+    .dereference_forced(&context)
+    .await?;
+  let person: ApubPerson = local_user_view.person.clone().into();
+  migrate_account(&person, &target, &context).await?;
+
+  ActivityChannel::submit_activity(
+    SendActivityData::MoveUser(local_user_view.person, target.0),
+    &context,
+  )?;
+
+  Ok(Json(SuccessResponse::default()))
+}
+
+/// Resolves an account given as actor url or `name@instance`, fetching it if necessary.
+async fn resolve_person(
+  identifier: &str,
+  context: &Data<LemmyContext>,
+  local_user_view: &LocalUserView,
+) -> LemmyResult<ApubPerson> {
+  let identifier = identifier.trim().trim_start_matches('@');
+  if let Ok(url) = Url::parse(identifier) {
+    Ok(
+      ObjectId::<ApubPerson>::from(url)
+        .dereference(context)
+        .await?,
+    )
+  } else {
+    resolve_actor_identifier::<ApubPerson, Person>(
+      identifier,
+      context,
+      &Some(local_user_view.clone()),
+      false,
+    )
+    .await
+  }
+}
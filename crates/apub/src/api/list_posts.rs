@@ -8,11 +8,12 @@ use actix_web::web::{Json, Query};
 use lemmy_api_common::{
   context::LemmyContext,
   post::{GetPosts, GetPostsResponse},
-  utils::{check_conflicting_like_filters, check_private_instance},
+  utils::{check_conflicting_like_filters, check_feed_visible, check_private_instance},
 };
 use lemmy_db_schema::{
   newtypes::PostId,
-  source::{community::Community, post::PostRead},
+  source::{community::Community, feed::Feed, post::PostRead},
+  traits::Crud,
 };
 use lemmy_db_views::{
   post::post_view::PostQuery,
@@ -40,12 +41,18 @@ pub async fn list_posts(
   } else {
     data.community_id
   };
+  let feed_id = data.feed_id;
+  if let Some(feed_id) = feed_id {
+    let feed = Feed::read(&mut context.pool(), feed_id).await?;
+    check_feed_visible(&feed, &local_user_view)?;
+  }
   let read_only = data.read_only;
   let show_hidden = data.show_hidden;
   let show_read = data.show_read;
   let show_nsfw = data.show_nsfw;
   let hide_media = data.hide_media;
   let no_comments_only = data.no_comments_only;
+  let tag_ids = data.tag_ids.clone();
 
   let liked_only = data.liked_only;
   let disliked_only = data.disliked_only;
@@ -77,6 +84,7 @@ pub async fn list_posts(
     listing_type,
     sort,
     community_id,
+    feed_id,
     read_only,
     liked_only,
     disliked_only,
@@ -88,6 +96,7 @@ pub async fn list_posts(
     show_nsfw,
     hide_media,
     no_comments_only,
+    tag_ids,
     ..Default::default()
   }
   .list(&local_site.site, &mut context.pool())
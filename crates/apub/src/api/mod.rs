@@ -11,6 +11,7 @@ use lemmy_db_schema::{
 use lemmy_db_views::structs::LocalUserView;
 use lemmy_utils::error::LemmyResult;
 
+pub mod account_move;
 pub mod list_comments;
 pub mod list_person_content;
 pub mod list_posts;
@@ -1,34 +1,52 @@
-use crate::objects::{
-  comment::ApubComment,
-  community::ApubCommunity,
-  person::ApubPerson,
-  post::ApubPost,
+use crate::{
+  local_site_data_cached,
+  objects::{comment::ApubComment, community::ApubCommunity, person::ApubPerson, post::ApubPost},
 };
 use activitypub_federation::{config::Data, fetch::object_id::ObjectId, traits::Object};
 use actix_web::web::Json;
-use futures::{future::try_join_all, StreamExt};
+use futures::{
+  future::{join_all, try_join_all},
+  StreamExt,
+};
 use itertools::Itertools;
-use lemmy_api_common::{context::LemmyContext, SuccessResponse};
+use lemmy_api_common::{
+  context::LemmyContext,
+  utils::{
+    check_feed_communities,
+    check_keyword_filter,
+    local_site_opt_to_slur_regex,
+    MAX_KEYWORD_FILTERS,
+  },
+  SuccessResponse,
+};
 use lemmy_db_schema::{
-  newtypes::DbUrl,
+  newtypes::{DbUrl, LocalUserId, PersonId},
   source::{
     comment::{CommentSaved, CommentSavedForm},
     community::{CommunityFollower, CommunityFollowerForm, CommunityFollowerState},
     community_block::{CommunityBlock, CommunityBlockForm},
+    feed::{Feed, FeedInsertForm},
     instance::Instance,
     instance_block::{InstanceBlock, InstanceBlockForm},
     local_user::{LocalUser, LocalUserUpdateForm},
+    local_user_keyword_filter::{LocalUserKeywordFilter, LocalUserKeywordFilterInsertForm},
     local_user_vote_display_mode::{LocalUserVoteDisplayMode, LocalUserVoteDisplayModeUpdateForm},
     person::{Person, PersonUpdateForm},
     person_block::{PersonBlock, PersonBlockForm},
     post::{PostSaved, PostSavedForm},
   },
   traits::{Blockable, Crud, Followable, Saveable},
+  KeywordFilterMode,
+  ListingType,
 };
-use lemmy_db_views::structs::LocalUserView;
+use lemmy_db_views::structs::{FeedView, KeywordFilterView, LocalUserView};
 use lemmy_utils::{
   error::{LemmyErrorType, LemmyResult, MAX_API_PARAM_ELEMENTS},
   spawn_try_task,
+  utils::{
+    slurs::check_slurs,
+    validation::{is_valid_body_field, is_valid_feed_name},
+  },
 };
 use serde::{Deserialize, Serialize};
 use std::future::Future;
@@ -68,6 +86,35 @@ pub struct UserSettingsBackup {
   pub blocked_users: Vec<ObjectId<ApubPerson>>,
   #[serde(default)]
   pub blocked_instances: Vec<String>,
+  #[serde(default)]
+  pub feeds: Vec<FeedBackup>,
+  #[serde(default)]
+  pub keyword_filters: Vec<KeywordFilterBackup>,
+}
+
+/// A custom feed in the backup. Feeds are identified by their name, so importing a feed which
+/// already exists adds the communities to it.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FeedBackup {
+  pub name: String,
+  pub description: Option<String>,
+  #[serde(default)]
+  pub public: bool,
+  #[serde(default)]
+  pub communities: Vec<ObjectId<ApubCommunity>>,
+}
+
+/// A keyword filter in the backup. Filters which already exist are skipped on import.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct KeywordFilterBackup {
+  pub keyword: String,
+  #[serde(default)]
+  pub mode: KeywordFilterMode,
+  pub match_title: Option<bool>,
+  pub match_body: Option<bool>,
+  pub match_url: Option<bool>,
+  #[serde(default)]
+  pub listing_types: Vec<ListingType>,
 }
 
 pub async fn export_settings(
@@ -76,6 +123,34 @@ pub async fn export_settings(
 ) -> LemmyResult<Json<UserSettingsBackup>> {
   let lists = LocalUser::export_backup(&mut context.pool(), local_user_view.person.id).await?;
 
+  let feeds = FeedView::list(&mut context.pool(), local_user_view.person.id, true)
+    .await?
+    .into_iter()
+    .map(|f| FeedBackup {
+      name: f.feed.name,
+      description: f.feed.description,
+      public: f.feed.public,
+      communities: f
+        .communities
+        .into_iter()
+        .map(|c| c.actor_id.into())
+        .collect(),
+    })
+    .collect();
+
+  let keyword_filters = KeywordFilterView::list(&mut context.pool(), local_user_view.local_user.id)
+    .await?
+    .into_iter()
+    .map(|f| KeywordFilterBackup {
+      keyword: f.keyword_filter.keyword,
+      mode: f.keyword_filter.mode,
+      match_title: Some(f.keyword_filter.match_title),
+      match_body: Some(f.keyword_filter.match_body),
+      match_url: Some(f.keyword_filter.match_url),
+      listing_types: f.listing_types,
+    })
+    .collect();
+
   let vec_into = |vec: Vec<_>| vec.into_iter().map(Into::into).collect();
   Ok(Json(UserSettingsBackup {
     display_name: local_user_view.person.display_name,
@@ -92,6 +167,8 @@ pub async fn export_settings(
     blocked_users: lists.blocked_users.into_iter().map(Into::into).collect(),
     saved_posts: lists.saved_posts.into_iter().map(Into::into).collect(),
     saved_comments: lists.saved_comments.into_iter().map(Into::into).collect(),
+    feeds,
+    keyword_filters,
   }))
 }
 
@@ -165,13 +242,19 @@ pub async fn import_settings(
     + data.blocked_users.len()
     + data.blocked_instances.len()
     + data.saved_posts.len()
-    + data.saved_comments.len();
-  if url_count > MAX_API_PARAM_ELEMENTS {
+    + data.saved_comments.len()
+    + data
+      .feeds
+      .iter()
+      .map(|f| f.communities.len())
+      .sum::<usize>();
+  if url_count > MAX_API_PARAM_ELEMENTS || data.keyword_filters.len() > MAX_KEYWORD_FILTERS {
     Err(LemmyErrorType::TooManyItems)?;
   }
 
   spawn_try_task(async move {
     let person_id = local_user_view.person.id;
+    let local_user_id = local_user_view.local_user.id;
 
     info!(
       "Starting settings import for {}",
@@ -259,7 +342,26 @@ pub async fn import_settings(
     }))
     .await?;
 
-    info!("Settings import completed for {}, the following items failed: {failed_followed_communities}, {failed_saved_posts}, {failed_saved_comments}, {failed_community_blocks}, {failed_user_blocks}",
+    let mut failed_feeds = vec![];
+    for feed in &data.feeds {
+      if import_feed(feed, person_id, &context).await.is_err() {
+        failed_feeds.push(feed.name.clone());
+      }
+    }
+    let failed_feeds = failed_feeds.join(",");
+
+    let mut failed_keyword_filters = vec![];
+    for filter in &data.keyword_filters {
+      if import_keyword_filter(filter, local_user_id, &context)
+        .await
+        .is_err()
+      {
+        failed_keyword_filters.push(filter.keyword.clone());
+      }
+    }
+    let failed_keyword_filters = failed_keyword_filters.join(",");
+
+    info!("Settings import completed for {}, the following items failed: {failed_followed_communities}, {failed_saved_posts}, {failed_saved_comments}, {failed_community_blocks}, {failed_user_blocks}, {failed_feeds}, {failed_keyword_filters}",
     local_user_view.person.name);
 
     Ok(())
@@ -268,6 +370,91 @@ pub async fn import_settings(
   Ok(Json(Default::default()))
 }
 
+/// Creates the keyword filter, unless the user already has a filter with the same keyword and
+/// mode.
+async fn import_keyword_filter(
+  filter: &KeywordFilterBackup,
+  local_user_id: LocalUserId,
+  context: &Data<LemmyContext>,
+) -> LemmyResult<()> {
+  let keyword = filter.keyword.trim().to_string();
+  check_keyword_filter(&keyword, filter.mode, context).await?;
+  let existing = LocalUserKeywordFilter::list(&mut context.pool(), local_user_id).await?;
+  if existing
+    .iter()
+    .any(|f| f.keyword == keyword && f.mode == filter.mode)
+  {
+    return Ok(());
+  }
+  if existing.len() >= MAX_KEYWORD_FILTERS {
+    Err(LemmyErrorType::TooManyItems)?
+  }
+
+  let form = LocalUserKeywordFilterInsertForm {
+    mode: Some(filter.mode),
+    match_title: filter.match_title,
+    match_body: filter.match_body,
+    match_url: filter.match_url,
+    ..LocalUserKeywordFilterInsertForm::new(local_user_id, keyword)
+  };
+  let keyword_filter = LocalUserKeywordFilter::create(&mut context.pool(), &form).await?;
+  LocalUserKeywordFilter::set_listing_types(
+    &mut context.pool(),
+    keyword_filter.id,
+    &filter.listing_types,
+  )
+  .await?;
+  Ok(())
+}
+
+/// Creates the feed, or adds the communities to an existing feed with the same name. Communities
+/// which can't be fetched are skipped.
+async fn import_feed(
+  feed: &FeedBackup,
+  person_id: PersonId,
+  context: &Data<LemmyContext>,
+) -> LemmyResult<()> {
+  let local_site_data = local_site_data_cached(&mut context.pool()).await?;
+  let slur_regex = local_site_opt_to_slur_regex(&local_site_data.local_site);
+  is_valid_feed_name(&feed.name)?;
+  check_slurs(&feed.name, &slur_regex)?;
+  if let Some(description) = &feed.description {
+    check_slurs(description, &slur_regex)?;
+    is_valid_body_field(description, false)?;
+  }
+  let communities = join_all(
+    feed
+      .communities
+      .iter()
+      .map(|c| async { c.dereference(&context.reset_request_count()).await }),
+  )
+  .await;
+  let mut community_ids = communities
+    .into_iter()
+    .filter_map(|c| c.ok().map(|c| c.id))
+    .collect::<Vec<_>>();
+
+  let existing = Feed::read_from_name(&mut context.pool(), person_id, &feed.name).await?;
+  if let Some(existing) = &existing {
+    community_ids.extend(Feed::read_community_ids(&mut context.pool(), existing.id).await?);
+  }
+  let community_ids = community_ids.into_iter().unique().collect::<Vec<_>>();
+  check_feed_communities(&community_ids)?;
+
+  let feed_id = if let Some(existing) = existing {
+    existing.id
+  } else {
+    let form = FeedInsertForm {
+      description: feed.description.clone(),
+      public: Some(feed.public),
+      ..FeedInsertForm::new(person_id, feed.name.clone())
+    };
+    Feed::create(&mut context.pool(), &form).await?.id
+  };
+  Feed::set_communities(&mut context.pool(), feed_id, &community_ids).await?;
+  Ok(())
+}
+
 async fn fetch_and_import<Kind, Fut>(
   objects: Vec<ObjectId<Kind>>,
   context: &Data<LemmyContext>,
@@ -317,9 +504,13 @@ pub(crate) mod tests {
         CommunityFollowerState,
         CommunityInsertForm,
       },
+      feed::{Feed, FeedInsertForm},
+      local_user_keyword_filter::{LocalUserKeywordFilter, LocalUserKeywordFilterInsertForm},
       person::Person,
     },
     traits::{Crud, Followable},
+    KeywordFilterMode,
+    ListingType,
   };
   use lemmy_db_views::structs::{CommunityFollowerView, LocalUserView};
   use lemmy_utils::error::{LemmyErrorType, LemmyResult};
@@ -347,6 +538,15 @@ pub(crate) mod tests {
       ..CommunityFollowerForm::new(community.id, export_user.person.id)
     };
     CommunityFollower::follow(pool, &follower_form).await?;
+    let feed_form = FeedInsertForm::new(export_user.person.id, "my feed".to_string());
+    let feed = Feed::create(pool, &feed_form).await?;
+    Feed::set_communities(pool, feed.id, &[community.id]).await?;
+    let filter_form = LocalUserKeywordFilterInsertForm {
+      mode: Some(KeywordFilterMode::Substring),
+      ..LocalUserKeywordFilterInsertForm::new(export_user.local_user.id, "spoiler".to_string())
+    };
+    let filter = LocalUserKeywordFilter::create(pool, &filter_form).await?;
+    LocalUserKeywordFilter::set_listing_types(pool, filter.id, &[ListingType::All]).await?;
 
     let backup = export_settings(export_user.clone(), context.reset_request_count()).await?;
 
@@ -370,6 +570,23 @@ pub(crate) mod tests {
     assert_eq!(follows.len(), 1);
     assert_eq!(follows[0].community.actor_id, community.actor_id);
 
+    let imported_feed = Feed::read_from_name(pool, import_user.person.id, "my feed")
+      .await?
+      .ok_or(LemmyErrorType::NotFound)?;
+    assert_eq!(
+      vec![community.id],
+      Feed::read_community_ids(pool, imported_feed.id).await?
+    );
+
+    let imported_filters = LocalUserKeywordFilter::list(pool, import_user.local_user.id).await?;
+    assert_eq!(1, imported_filters.len());
+    assert_eq!("spoiler", imported_filters[0].keyword);
+    assert_eq!(KeywordFilterMode::Substring, imported_filters[0].mode);
+    assert_eq!(
+      vec![(imported_filters[0].id, ListingType::All)],
+      LocalUserKeywordFilter::read_listing_types(pool, &[imported_filters[0].id]).await?
+    );
+
     Person::delete(pool, export_user.person.id).await?;
     Person::delete(pool, import_user.person.id).await?;
     Ok(())
@@ -1,26 +1,35 @@
 use crate::{
   activity_lists::AnnouncableActivities,
-  objects::community::ApubCommunity,
+  collections::{outbox_page_before_url, outbox_page_url, OUTBOX_PAGE_SIZE},
+  objects::{comment::ApubComment, community::ApubCommunity, post::ApubPost},
   protocol::{
     activities::{
       community::announce::AnnounceActivity,
-      create_or_update::page::CreateOrUpdatePage,
+      create_or_update::{note::CreateOrUpdateNote, page::CreateOrUpdatePage},
       CreateOrUpdateType,
     },
-    collections::group_outbox::GroupOutbox,
+    collections::group_outbox::{GroupOutbox, GroupOutboxPage},
+    InCommunity,
   },
 };
 use activitypub_federation::{
   config::Data,
-  kinds::collection::OrderedCollectionType,
+  kinds::collection::{OrderedCollectionPageType, OrderedCollectionType},
   protocol::verification::verify_domains_match,
-  traits::{ActivityHandler, Collection},
+  traits::{ActivityHandler, Collection, Object},
 };
 use futures::future::join_all;
 use lemmy_api_common::{context::LemmyContext, utils::generate_outbox_url};
-use lemmy_db_schema::{source::site::Site, utils::FETCH_LIMIT_MAX, PostSortType};
+use lemmy_db_schema::{
+  aggregates::structs::CommunityAggregates,
+  newtypes::PersonContentCombinedId,
+  source::{community::Community, site::Site},
+  utils::FETCH_LIMIT_MAX,
+  PostSortType,
+};
 use lemmy_db_views::post::post_view::PostQuery;
-use lemmy_utils::error::{LemmyError, LemmyResult};
+use lemmy_utils::error::{FederationError, LemmyError, LemmyResult};
+use serde_json::{from_value, to_value};
 use url::Url;
 
 #[derive(Clone, Debug)]
@@ -60,10 +69,15 @@ impl Collection for ApubCommunityOutbox {
       ordered_items.push(announce);
     }
 
+    // Use the stored counts, as counting the items on each request is too expensive
+    let counts = CommunityAggregates::read(&mut data.pool(), owner.id).await?;
+    let total_items = counts.posts + counts.comments;
+    let id: Url = generate_outbox_url(&owner.actor_id)?.into();
     Ok(GroupOutbox {
       r#type: OrderedCollectionType::OrderedCollection,
-      id: generate_outbox_url(&owner.actor_id)?.into(),
-      total_items: ordered_items.len() as i32,
+      first: Some(outbox_page_url(&id, 1)),
+      id,
+      total_items: i32::try_from(total_items).unwrap_or(i32::MAX),
       ordered_items,
     })
   }
@@ -115,3 +129,99 @@ impl Collection for ApubCommunityOutbox {
     Ok(ApubCommunityOutbox(()))
   }
 }
+
+impl GroupOutboxPage {
+  /// Reads the page with the posts and comments which are older than `before`, or the first page
+  /// with the newest ones.
+  pub(crate) async fn read_local(
+    community: &ApubCommunity,
+    before: Option<PersonContentCombinedId>,
+    context: &Data<LemmyContext>,
+  ) -> LemmyResult<GroupOutboxPage> {
+    // Read one more item to find out if there is a next page
+    let mut content = Community::list_outbox_content(
+      &mut context.pool(),
+      community.id,
+      OUTBOX_PAGE_SIZE + 1,
+      before,
+    )
+    .await?;
+    let has_next = content.len() as i64 > OUTBOX_PAGE_SIZE;
+    content.truncate(OUTBOX_PAGE_SIZE as usize);
+    let last_id = content.iter().map(|(id, ..)| *id).next_back();
+
+    let mut ordered_items = vec![];
+    for (_, post, comment, creator) in content {
+      let creator = creator.into();
+      let kind = CreateOrUpdateType::Create;
+      let announcable = match comment {
+        Some(comment) => {
+          let create =
+            CreateOrUpdateNote::new(comment.into(), &creator, community, kind, context).await?;
+          // Comments are announced as NoteWrapper, see CreateOrUpdateNote::send()
+          AnnouncableActivities::CreateOrUpdateNoteWrapper(from_value(to_value(create)?)?)
+        }
+        None => AnnouncableActivities::CreateOrUpdatePost(
+          CreateOrUpdatePage::new(post.into(), &creator, community, kind, context).await?,
+        ),
+      };
+      let announce = AnnounceActivity::new(announcable.try_into()?, community, context)?;
+      ordered_items.push(announce);
+    }
+
+    let part_of: Url = generate_outbox_url(&community.actor_id)?.into();
+    let id = match before {
+      Some(before) => outbox_page_before_url(&part_of, before),
+      None => outbox_page_url(&part_of, 1),
+    };
+    Ok(GroupOutboxPage {
+      r#type: OrderedCollectionPageType::OrderedCollectionPage,
+      id,
+      next: last_id
+        .filter(|_| has_next)
+        .map(|last_id| outbox_page_before_url(&part_of, last_id)),
+      part_of,
+      ordered_items,
+    })
+  }
+}
+
+/// Stores a post or comment from the outbox of a remote community. Only the object is stored,
+/// without side effects of receiving the activity such as notifications, as it is usually old.
+/// Returns the url of its replies collection, if it has one.
+pub(crate) async fn receive_outbox_item(
+  announce: AnnounceActivity,
+  community: &ApubCommunity,
+  context: &Data<LemmyContext>,
+) -> LemmyResult<Option<Url>> {
+  let inner: AnnouncableActivities = announce.object.object(context).await?.try_into()?;
+  if inner.community(context).await?.id != community.id {
+    Err(FederationError::InvalidCommunity)?
+  }
+  let replies = match inner {
+    AnnouncableActivities::CreateOrUpdatePost(create) => {
+      create.verify(context).await?;
+      let replies = create
+        .object
+        .replies
+        .as_ref()
+        .and_then(|r| r.collection_url(create.object.id.inner()));
+      ApubPost::from_json(create.object, context).await?;
+      replies
+    }
+    AnnouncableActivities::CreateOrUpdateNoteWrapper(wrapper) => {
+      let create: CreateOrUpdateNote = from_value(to_value(wrapper)?)?;
+      create.verify(context).await?;
+      let replies = create
+        .object
+        .replies
+        .as_ref()
+        .and_then(|r| r.collection_url(create.object.id.inner()));
+      ApubComment::from_json(create.object, context).await?;
+      replies
+    }
+    // Other activities are not included in outboxes, ignore them
+    _ => None,
+  };
+  Ok(replies)
+}
@@ -1,4 +1,27 @@
+use lemmy_db_schema::newtypes::PersonContentCombinedId;
+use url::Url;
+
 pub(crate) mod community_featured;
 pub(crate) mod community_follower;
 pub(crate) mod community_moderators;
 pub(crate) mod community_outbox;
+pub(crate) mod person_outbox;
+
+/// Number of activities on each page of an outbox
+pub(crate) const OUTBOX_PAGE_SIZE: i64 = 20;
+
+/// Url of the outbox page with the given number, which starts at 1 for the newest activities.
+pub(crate) fn outbox_page_url(outbox_id: &Url, page: i64) -> Url {
+  let mut url = outbox_id.clone();
+  url.query_pairs_mut().append_pair("page", &page.to_string());
+  url
+}
+
+/// Url of the community outbox page with the items which are older than the given item.
+pub(crate) fn outbox_page_before_url(outbox_id: &Url, before: PersonContentCombinedId) -> Url {
+  let mut url = outbox_id.clone();
+  url
+    .query_pairs_mut()
+    .append_pair("before", &before.0.to_string());
+  url
+}
@@ -0,0 +1,76 @@
+use crate::{
+  collections::{outbox_page_url, OUTBOX_PAGE_SIZE},
+  objects::{community::ApubCommunity, person::ApubPerson},
+  protocol::{
+    activities::{
+      create_or_update::{note::CreateOrUpdateNote, page::CreateOrUpdatePage},
+      CreateOrUpdateType,
+    },
+    collections::person_outbox::{PersonOutbox, PersonOutboxActivity, PersonOutboxPage},
+  },
+};
+use activitypub_federation::{
+  config::Data,
+  kinds::collection::{OrderedCollectionPageType, OrderedCollectionType},
+};
+use lemmy_api_common::{context::LemmyContext, utils::generate_outbox_url};
+use lemmy_db_schema::source::person::Person;
+use lemmy_utils::error::LemmyResult;
+use url::Url;
+
+impl PersonOutbox {
+  pub(crate) async fn read_local(
+    person: &ApubPerson,
+    context: &Data<LemmyContext>,
+  ) -> LemmyResult<PersonOutbox> {
+    let (total_items, _) =
+      Person::list_public_content(&mut context.pool(), person.id, 0, 0).await?;
+    let id: Url = generate_outbox_url(&person.actor_id)?.into();
+    Ok(PersonOutbox {
+      r#type: OrderedCollectionType::OrderedCollection,
+      first: outbox_page_url(&id, 1),
+      id,
+      total_items,
+    })
+  }
+}
+
+impl PersonOutboxPage {
+  /// Reads the page with the given number, starting at 1 for the newest activities.
+  pub(crate) async fn read_local(
+    person: &ApubPerson,
+    page: i64,
+    context: &Data<LemmyContext>,
+  ) -> LemmyResult<PersonOutboxPage> {
+    let page = page.max(1);
+    let offset = (page - 1) * OUTBOX_PAGE_SIZE;
+    let (total_items, content) =
+      Person::list_public_content(&mut context.pool(), person.id, OUTBOX_PAGE_SIZE, offset).await?;
+    let has_next = offset + (content.len() as i64) < total_items;
+
+    let mut ordered_items = vec![];
+    for (post, comment, community) in content {
+      let community: ApubCommunity = community.into();
+      let kind = CreateOrUpdateType::Create;
+      let activity = match comment {
+        Some(comment) => PersonOutboxActivity::CreateComment(
+          CreateOrUpdateNote::new(comment.into(), person, &community, kind, context).await?,
+        ),
+        None => PersonOutboxActivity::CreatePost(
+          CreateOrUpdatePage::new(post.into(), person, &community, kind, context).await?,
+        ),
+      };
+      ordered_items.push(activity);
+    }
+
+    let part_of: Url = generate_outbox_url(&person.actor_id)?.into();
+    Ok(PersonOutboxPage {
+      r#type: OrderedCollectionPageType::OrderedCollectionPage,
+      id: outbox_page_url(&part_of, page),
+      next: has_next.then(|| outbox_page_url(&part_of, page + 1)),
+      prev: (page > 1).then(|| outbox_page_url(&part_of, page - 1)),
+      part_of,
+      ordered_items,
+    })
+  }
+}
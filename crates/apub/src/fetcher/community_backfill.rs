@@ -0,0 +1,221 @@
+use crate::{
+  collections::community_outbox::receive_outbox_item,
+  objects::{comment::ApubComment, community::ApubCommunity},
+  protocol::{
+    activities::community::announce::AnnounceActivity,
+    collections::{
+      group_outbox::{GroupOutbox, GroupOutboxPage},
+      replies::{Replies, RepliesOrUrl},
+    },
+    objects::{group::Group, note::Note},
+  },
+};
+use activitypub_federation::{
+  config::Data,
+  fetch::fetch_object_http,
+  protocol::verification::verify_domains_match,
+  traits::Object,
+};
+use chrono::TimeDelta;
+use futures::future::join_all;
+use lemmy_api_common::context::LemmyContext;
+use lemmy_db_schema::{
+  source::{community::Community, community_backfill::CommunityBackfill},
+  traits::Crud,
+};
+use lemmy_utils::error::LemmyResult;
+use std::time::Duration;
+use tracing::{debug, warn};
+use url::Url;
+
+/// How often to check for backfills which are due
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Delay before fetching each item of an outbox page or replies collection, so that a step doesn't
+/// send a burst of requests to the instance.
+const ITEM_DELAY: Duration = Duration::from_secs(1);
+
+/// Fetches the history of remote communities which are queued for backfill, forever. Each step
+/// fetches one outbox page or replies collection and is spaced out from the previous step for the
+/// same instance by `backfill_interval_secs`. Within a step, items are fetched one by one with
+/// `ITEM_DELAY` in between.
+pub async fn backfill_communities(context: Data<LemmyContext>) -> LemmyResult<()> {
+  let interval = TimeDelta::seconds(context.settings().federation.backfill_interval_secs.into());
+  loop {
+    match CommunityBackfill::claim_due(&mut context.pool(), interval).await {
+      Ok(backfills) if !backfills.is_empty() => {
+        // Each backfill belongs to a different instance, so they can run in parallel
+        join_all(
+          backfills
+            .iter()
+            .map(|backfill| backfill_step(backfill, &context)),
+        )
+        .await;
+      }
+      Ok(_) => tokio::time::sleep(POLL_INTERVAL).await,
+      Err(e) => {
+        warn!("Failed to read community backfills: {e}");
+        tokio::time::sleep(POLL_INTERVAL).await
+      }
+    }
+  }
+}
+
+async fn backfill_step(backfill: &CommunityBackfill, context: &Data<LemmyContext>) {
+  let max_pages = context.settings().federation.backfill_max_pages;
+  let res = match fetch_next(backfill, context).await {
+    Ok((next_page, mut pending_replies)) => {
+      // Collections which are left over when the page limit is reached would never be fetched
+      let remaining = max_pages - backfill.pages_fetched - 1;
+      pending_replies.truncate(usize::try_from(remaining).unwrap_or_default());
+      CommunityBackfill::advance(
+        &mut context.pool(),
+        backfill,
+        next_page.map(Into::into),
+        pending_replies.into_iter().map(Into::into).collect(),
+        max_pages,
+      )
+      .await
+    }
+    Err(e) => {
+      warn!(
+        "Failed to backfill community {}: {e}",
+        backfill.community_id.0
+      );
+      CommunityBackfill::fail(&mut context.pool(), backfill).await
+    }
+  };
+  if let Err(e) = res {
+    warn!(
+      "Failed to store backfill of community {}: {e}",
+      backfill.community_id.0
+    );
+  }
+}
+
+/// Fetches the next outbox page of the community and stores its posts and comments. Older versions
+/// only provide posts in the outbox, so their comments are fetched from the replies collections
+/// afterwards, one per step. Returns the url of the outbox page after it, and the replies
+/// collections which are still pending.
+async fn fetch_next(
+  backfill: &CommunityBackfill,
+  context: &Data<LemmyContext>,
+) -> LemmyResult<(Option<Url>, Vec<Url>)> {
+  let mut pending_replies = backfill
+    .pending_replies
+    .iter()
+    .flatten()
+    .cloned()
+    .map(Into::into)
+    .collect::<Vec<Url>>();
+  if backfill.next_page.is_none() && backfill.pages_fetched > 0 {
+    // The outbox was fetched already, continue with the oldest pending replies collection
+    if !pending_replies.is_empty() {
+      let url = pending_replies.remove(0);
+      match fetch_replies(&url, context).await {
+        Ok(replies) => pending_replies.extend(replies),
+        // The collection may be gone or unsupported, which shouldn't stop the other ones
+        Err(e) => debug!("Failed to backfill replies {url}: {e}"),
+      }
+    }
+    return Ok((None, pending_replies));
+  }
+
+  let community: ApubCommunity = Community::read(&mut context.pool(), backfill.community_id)
+    .await?
+    .into();
+  let (items, next_page) = match &backfill.next_page {
+    Some(page_url) => {
+      verify_domains_match(page_url.inner(), community.actor_id.inner())?;
+      let page: GroupOutboxPage = fetch_object_http(page_url.inner(), context).await?.object;
+      (page.ordered_items, page.next)
+    }
+    None => {
+      // The outbox url is only known from the community json
+      let group: Group = fetch_object_http(community.actor_id.inner(), context)
+        .await?
+        .object;
+      let outbox_url: Url = group.outbox.into();
+      verify_domains_match(&outbox_url, community.actor_id.inner())?;
+      let outbox: GroupOutbox = fetch_object_http(&outbox_url, context).await?.object;
+      match outbox.first {
+        // The newest posts were already fetched together with the community, so continue with
+        // the pages which contain the full history.
+        Some(first) => (vec![], Some(first)),
+        // Older versions only provide the posts in the outbox itself
+        None => {
+          let replies = store_items(outbox.ordered_items, &community, context).await;
+          return Ok((None, replies));
+        }
+      }
+    }
+  };
+
+  // Outbox pages include the comments, so there is no need to fetch replies separately
+  store_items(items, &community, context).await;
+  Ok((next_page, pending_replies))
+}
+
+/// Stores the items oldest first, so that parent comments are usually known before their replies.
+/// Errors are ignored, because the outbox may contain content which can't be parsed, or which was
+/// deleted in the meantime. Returns the replies collections of the stored items.
+async fn store_items(
+  items: Vec<AnnounceActivity>,
+  community: &ApubCommunity,
+  context: &Data<LemmyContext>,
+) -> Vec<Url> {
+  let mut replies = vec![];
+  for item in items.into_iter().rev() {
+    tokio::time::sleep(ITEM_DELAY).await;
+    let id = item.id.clone();
+    // Each item may need several requests, e.g. for the creator and parent comments
+    match receive_outbox_item(item, community, &context.reset_request_count()).await {
+      Ok(r) => replies.extend(r),
+      Err(e) => debug!("Failed to backfill {id}: {e}"),
+    }
+  }
+  replies
+}
+
+/// Fetches a replies collection or one of its pages, and stores the comments in it. Returns the
+/// replies collections of these comments, and the next page of the collection.
+async fn fetch_replies(url: &Url, context: &Data<LemmyContext>) -> LemmyResult<Vec<Url>> {
+  let replies: Replies = fetch_object_http(url, context).await?.object;
+  let mut pending = vec![];
+  // Pages may be embedded in the collection, e.g. Mastodon includes the first one
+  let mut pages = vec![replies];
+  while let Some(page) = pages.pop() {
+    for item in page.items {
+      tokio::time::sleep(ITEM_DELAY).await;
+      let id = item.id();
+      match store_reply(id, &context.reset_request_count()).await {
+        Ok(r) => pending.extend(r),
+        Err(e) => debug!("Failed to backfill {id}: {e}"),
+      }
+    }
+    for link in [page.first, page.next].into_iter().flatten() {
+      match link {
+        RepliesOrUrl::Url(u) => {
+          if verify_domains_match(&u, url).is_ok() {
+            pending.push(u);
+          }
+        }
+        RepliesOrUrl::Replies(r) => pages.push(*r),
+      }
+    }
+  }
+  Ok(pending)
+}
+
+/// Fetches and stores a single comment from a replies collection. Returns its own replies
+/// collection.
+async fn store_reply(id: &Url, context: &Data<LemmyContext>) -> LemmyResult<Option<Url>> {
+  let note: Note = fetch_object_http(id, context).await?.object;
+  ApubComment::verify(&note, id, context).await?;
+  let replies = note
+    .replies
+    .as_ref()
+    .and_then(|r| r.collection_url(note.id.inner()));
+  ApubComment::from_json(note, context).await?;
+  Ok(replies)
+}
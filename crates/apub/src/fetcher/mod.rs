@@ -10,6 +10,7 @@ use lemmy_db_schema::traits::ApubActor;
 use lemmy_db_views::structs::LocalUserView;
 use lemmy_utils::error::{LemmyError, LemmyResult};
 
+pub mod community_backfill;
 pub(crate) mod markdown_links;
 pub mod post_or_comment;
 pub mod search;
@@ -7,8 +7,14 @@ use crate::{
     community_outbox::ApubCommunityOutbox,
   },
   fetcher::site_or_community_or_user::SiteOrCommunityOrUser,
-  http::{check_community_fetchable, create_apub_response, create_apub_tombstone_response},
+  http::{
+    check_community_fetchable,
+    create_apub_response,
+    create_apub_tombstone_response,
+    OutboxQuery,
+  },
   objects::community::ApubCommunity,
+  protocol::collections::group_outbox::GroupOutboxPage,
 };
 use activitypub_federation::{
   actix_web::signing_actor,
@@ -108,10 +114,12 @@ async fn check_is_follower(
   }
 }
 
-/// Returns the community outbox, which is populated by a maximum of 20 posts (but no other
-/// activities like votes or comments).
+/// Returns the community outbox. Without `page` or `before` parameter, this contains the newest
+/// posts for older Lemmy versions, and links to the first page. The pages contain all posts and
+/// comments.
 pub(crate) async fn get_apub_community_outbox(
   info: Path<CommunityPath>,
+  query: Query<OutboxQuery>,
   context: Data<LemmyContext>,
   request: HttpRequest,
 ) -> LemmyResult<HttpResponse> {
@@ -121,8 +129,13 @@ pub(crate) async fn get_apub_community_outbox(
       .ok_or(LemmyErrorType::NotFound)?
       .into();
   check_community_content_fetchable(&community, &request, &context).await?;
-  let outbox = ApubCommunityOutbox::read_local(&community, &context).await?;
-  create_apub_response(&outbox)
+  if query.page.is_some() || query.before.is_some() {
+    let outbox_page = GroupOutboxPage::read_local(&community, query.before, &context).await?;
+    create_apub_response(&outbox_page)
+  } else {
+    let outbox = ApubCommunityOutbox::read_local(&community, &context).await?;
+    create_apub_response(&outbox)
+  }
 }
 
 pub(crate) async fn get_apub_community_moderators(
@@ -266,8 +279,24 @@ pub(crate) mod tests {
     let res =
       get_apub_community_moderators(path.clone().into(), context.reset_request_count()).await?;
     assert_eq!(200, res.status());
-    let res =
-      get_apub_community_outbox(path.into(), context.reset_request_count(), request).await?;
+    let res = get_apub_community_outbox(
+      path.clone().into(),
+      Query(OutboxQuery::default()),
+      context.reset_request_count(),
+      request.clone(),
+    )
+    .await?;
+    assert_eq!(200, res.status());
+    let res = get_apub_community_outbox(
+      path.into(),
+      Query(OutboxQuery {
+        page: Some(1),
+        ..Default::default()
+      }),
+      context.reset_request_count(),
+      request,
+    )
+    .await?;
     assert_eq!(200, res.status());
 
     Instance::delete(&mut context.pool(), instance.id).await?;
@@ -310,7 +339,13 @@ pub(crate) mod tests {
     let res =
       get_apub_community_moderators(path.clone().into(), context.reset_request_count()).await;
     assert!(res.is_err());
-    let res = get_apub_community_outbox(path, context.reset_request_count(), request).await;
+    let res = get_apub_community_outbox(
+      path,
+      Query(OutboxQuery::default()),
+      context.reset_request_count(),
+      request,
+    )
+    .await;
     assert!(res.is_err());
 
     //Community::delete(&mut context.pool(), community.id).await?;
@@ -350,7 +385,13 @@ pub(crate) mod tests {
     let res =
       get_apub_community_moderators(path.clone().into(), context.reset_request_count()).await;
     assert!(res.is_err());
-    let res = get_apub_community_outbox(path, context.reset_request_count(), request).await;
+    let res = get_apub_community_outbox(
+      path,
+      Query(OutboxQuery::default()),
+      context.reset_request_count(),
+      request,
+    )
+    .await;
     assert!(res.is_err());
 
     Instance::delete(&mut context.pool(), instance.id).await?;
@@ -14,7 +14,7 @@ use activitypub_federation::{
 use actix_web::{web, web::Bytes, HttpRequest, HttpResponse};
 use lemmy_api_common::context::LemmyContext;
 use lemmy_db_schema::{
-  newtypes::DbUrl,
+  newtypes::{DbUrl, PersonContentCombinedId},
   source::{activity::SentActivity, community::Community},
   CommunityVisibility,
 };
@@ -88,6 +88,14 @@ fn redirect_remote_object(url: &DbUrl) -> HttpResponse {
   res.finish()
 }
 
+/// Page number for paginated outboxes, starting at 1. The community outbox is paged with `before`
+/// instead, which is the id of the last item on the previous page.
+#[derive(Deserialize, Clone, Default)]
+pub struct OutboxQuery {
+  pub(crate) page: Option<i64>,
+  pub(crate) before: Option<PersonContentCombinedId>,
+}
+
 #[derive(Deserialize)]
 pub struct ActivityQuery {
   type_: String,
@@ -1,11 +1,11 @@
 use crate::{
-  http::{create_apub_response, create_apub_tombstone_response},
+  http::{create_apub_response, create_apub_tombstone_response, OutboxQuery},
   objects::person::ApubPerson,
-  protocol::collections::empty_outbox::EmptyOutbox,
+  protocol::collections::person_outbox::{PersonOutbox, PersonOutboxPage},
 };
 use activitypub_federation::{config::Data, traits::Object};
 use actix_web::{web, HttpResponse};
-use lemmy_api_common::{context::LemmyContext, utils::generate_outbox_url};
+use lemmy_api_common::context::LemmyContext;
 use lemmy_db_schema::{source::person::Person, traits::ApubActor};
 use lemmy_utils::error::{LemmyErrorType, LemmyResult};
 use serde::Deserialize;
@@ -36,14 +36,22 @@ pub(crate) async fn get_apub_person_http(
   }
 }
 
+/// Returns the public posts and comments of a local person as `Create` activities. Without `page`
+/// parameter only the collection is returned, which links to the first page.
 pub(crate) async fn get_apub_person_outbox(
   info: web::Path<PersonQuery>,
+  query: web::Query<OutboxQuery>,
   context: Data<LemmyContext>,
 ) -> LemmyResult<HttpResponse> {
-  let person = Person::read_from_name(&mut context.pool(), &info.user_name, false)
+  let person: ApubPerson = Person::read_from_name(&mut context.pool(), &info.user_name, false)
     .await?
-    .ok_or(LemmyErrorType::NotFound)?;
-  let outbox_id = generate_outbox_url(&person.actor_id)?.into();
-  let outbox = EmptyOutbox::new(outbox_id)?;
-  create_apub_response(&outbox)
+    .ok_or(LemmyErrorType::NotFound)?
+    .into();
+  if let Some(page) = query.page {
+    let outbox_page = PersonOutboxPage::read_local(&person, page, &context).await?;
+    create_apub_response(&outbox_page)
+  } else {
+    let outbox = PersonOutbox::read_local(&person, &context).await?;
+    create_apub_response(&outbox)
+  }
 }
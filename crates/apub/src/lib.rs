@@ -6,7 +6,13 @@ use activitypub_federation::{
 use async_trait::async_trait;
 use lemmy_api_common::context::LemmyContext;
 use lemmy_db_schema::{
-  source::{activity::ReceivedActivity, instance::Instance, local_site::LocalSite},
+  newtypes::InstanceId,
+  source::{
+    activity::ReceivedActivity,
+    federation_limit::FederationLimit,
+    instance::Instance,
+    local_site::LocalSite,
+  },
   utils::{ActualDbPool, DbPool},
 };
 use lemmy_utils::{
@@ -130,6 +136,7 @@ pub(crate) struct LocalSiteData {
   local_site: Option<LocalSite>,
   allowed_instances: Vec<Instance>,
   blocked_instances: Vec<Instance>,
+  federation_limits: Vec<FederationLimit>,
 }
 
 pub(crate) async fn local_site_data_cached(
@@ -148,26 +155,55 @@ pub(crate) async fn local_site_data_cached(
   Ok(
     CACHE
       .try_get_with((), async {
-        let (local_site, allowed_instances, blocked_instances) =
+        let (local_site, allowed_instances, blocked_instances, federation_limits) =
           lemmy_db_schema::try_join_with_pool!(pool => (
             // LocalSite may be missing
             |pool| async {
               Ok(LocalSite::read(pool).await.ok())
             },
             Instance::allowlist,
-            Instance::blocklist
+            Instance::blocklist,
+            FederationLimit::read_all
           ))?;
 
         Ok::<_, diesel::result::Error>(Arc::new(LocalSiteData {
           local_site,
           allowed_instances,
           blocked_instances,
+          federation_limits,
         }))
       })
       .await?,
   )
 }
 
+/// The restrictions which admins placed on remote instances, combined for all instances involved
+/// in an object, e.g. those of the post creator and of the community.
+#[derive(Clone, Copy, Default)]
+pub(crate) struct InstanceLimits {
+  pub(crate) limited: bool,
+  pub(crate) reject_media: bool,
+  pub(crate) force_nsfw: bool,
+}
+
+pub(crate) async fn instance_limits(
+  instance_ids: &[InstanceId],
+  context: &LemmyContext,
+) -> LemmyResult<InstanceLimits> {
+  let local_site_data = local_site_data_cached(&mut context.pool()).await?;
+  Ok(
+    local_site_data
+      .federation_limits
+      .iter()
+      .filter(|l| instance_ids.contains(&l.instance_id))
+      .fold(InstanceLimits::default(), |acc, l| InstanceLimits {
+        limited: acc.limited || l.limited,
+        reject_media: acc.reject_media || l.reject_media,
+        force_nsfw: acc.force_nsfw || l.force_nsfw,
+      }),
+  )
+}
+
 pub(crate) async fn check_apub_id_valid_with_strictness(
   apub_id: &Url,
   is_strict: bool,
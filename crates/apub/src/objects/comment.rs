@@ -123,6 +123,7 @@ impl Object for ApubComment {
       distinguished: Some(self.distinguished),
       language,
       attachment: vec![],
+      replies: None,
     };
 
     Ok(note)
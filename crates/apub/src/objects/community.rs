@@ -1,9 +1,14 @@
 use crate::{
   activities::GetActorType,
   fetcher::markdown_links::markdown_rewrite_remote_links_opt,
-  objects::{instance::fetch_instance_actor_for_object, read_from_string_or_source_opt},
+  instance_limits,
+  objects::{
+    instance::fetch_instance_actor_for_object,
+    proxy_image_link_limited,
+    read_from_string_or_source_opt,
+  },
   protocol::{
-    objects::{group::Group, LanguageTag},
+    objects::{group::Group, Hashtag, LanguageTag},
     ImageObject,
     Source,
   },
@@ -11,7 +16,7 @@ use crate::{
 use activitypub_federation::{
   config::Data,
   kinds::actor::GroupType,
-  protocol::values::MediaTypeHtml,
+  protocol::{values::MediaTypeHtml, verification::verify_domains_match},
   traits::{Actor, Object},
 };
 use chrono::{DateTime, Utc};
@@ -24,7 +29,6 @@ use lemmy_api_common::{
     get_url_blocklist,
     local_site_opt_to_slur_regex,
     process_markdown_opt,
-    proxy_image_link_opt_apub,
   },
 };
 use lemmy_db_schema::{
@@ -34,6 +38,7 @@ use lemmy_db_schema::{
     actor_language::CommunityLanguage,
     community::{Community, CommunityInsertForm, CommunityUpdateForm},
     local_site::LocalSite,
+    tag::{Tag, TagInsertForm},
   },
   traits::{ApubActor, Crud},
   CommunityVisibility,
@@ -41,7 +46,7 @@ use lemmy_db_schema::{
 use lemmy_utils::{
   error::{LemmyError, LemmyResult},
   spawn_try_task,
-  utils::markdown::markdown_to_html,
+  utils::{markdown::markdown_to_html, validation::is_valid_tag_name},
 };
 use std::ops::Deref;
 use url::Url;
@@ -96,6 +101,11 @@ impl Object for ApubCommunity {
     let community_id = self.id;
     let langs = CommunityLanguage::read(&mut data.pool(), community_id).await?;
     let language = LanguageTag::new_multiple(langs, &mut data.pool()).await?;
+    let tag = Tag::read_for_community(&mut data.pool(), community_id)
+      .await?
+      .into_iter()
+      .map(Into::into)
+      .collect();
 
     let group = Group {
       kind: GroupType::Group,
@@ -121,6 +131,7 @@ impl Object for ApubCommunity {
       posting_restricted_to_mods: Some(self.posting_restricted_to_mods),
       attributed_to: Some(generate_moderators_url(&self.actor_id)?.into()),
       manually_approves_followers: Some(self.visibility == CommunityVisibility::Private),
+      tag,
     };
     Ok(group)
   }
@@ -144,8 +155,9 @@ impl Object for ApubCommunity {
     let sidebar = read_from_string_or_source_opt(&group.content, &None, &group.source);
     let sidebar = process_markdown_opt(&sidebar, slur_regex, &url_blocklist, context).await?;
     let sidebar = markdown_rewrite_remote_links_opt(sidebar, context).await;
-    let icon = proxy_image_link_opt_apub(group.icon.map(|i| i.url), context).await?;
-    let banner = proxy_image_link_opt_apub(group.image.map(|i| i.url), context).await?;
+    let limits = instance_limits(&[instance_id], context).await?;
+    let icon = proxy_image_link_limited(group.icon.map(|i| i.url), limits, context).await?;
+    let banner = proxy_image_link_limited(group.image.map(|i| i.url), limits, context).await?;
     let visibility = Some(if group.manually_approves_followers.unwrap_or_default() {
       CommunityVisibility::Private
     } else {
@@ -188,6 +200,7 @@ impl Object for ApubCommunity {
     let timestamp = group.updated.or(group.published).unwrap_or_else(Utc::now);
     let community = Community::insert_apub(&mut context.pool(), timestamp, &form).await?;
     CommunityLanguage::update(&mut context.pool(), languages, community.id).await?;
+    update_community_tags(&group.tag, &community, context).await?;
 
     let community: ApubCommunity = community.into();
 
@@ -212,6 +225,35 @@ impl Object for ApubCommunity {
   }
 }
 
+/// Mirrors the post tags of a remote community. Tags which are no longer listed get deleted.
+async fn update_community_tags(
+  tags: &[Hashtag],
+  community: &Community,
+  context: &Data<LemmyContext>,
+) -> LemmyResult<()> {
+  let mut ap_ids = vec![];
+  for tag in tags {
+    // Ignore invalid tags and those which don't belong to the community
+    if verify_domains_match(&tag.href, community.actor_id.inner()).is_err()
+      || is_valid_tag_name(&tag.name).is_err()
+    {
+      continue;
+    }
+    let form = TagInsertForm {
+      ap_id: tag.href.clone().into(),
+      name: tag.name.trim().to_string(),
+      community_id: community.id,
+      published: None,
+      updated: None,
+      deleted: false,
+    };
+    Tag::upsert(&mut context.pool(), &form).await?;
+    ap_ids.push(form.ap_id);
+  }
+  Tag::delete_for_community_except(&mut context.pool(), community.id, &ap_ids).await?;
+  Ok(())
+}
+
 impl Actor for ApubCommunity {
   fn id(&self) -> Url {
     self.actor_id.inner().clone()
@@ -291,6 +333,12 @@ pub(crate) mod tests {
       Some("A description of ten forward.".into())
     );
 
+    let tags = Tag::read_for_community(&mut context.pool(), community.id).await?;
+    assert_eq!(
+      vec!["Discussion", "Meta"],
+      tags.iter().map(|t| t.name.as_str()).collect::<Vec<_>>()
+    );
+
     Community::delete(&mut context.pool(), community.id).await?;
     Site::delete(&mut context.pool(), site.id).await?;
     Ok(())
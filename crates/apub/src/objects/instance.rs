@@ -2,8 +2,9 @@ use crate::{
   activities::GetActorType,
   check_apub_id_valid_with_strictness,
   fetcher::markdown_links::markdown_rewrite_remote_links_opt,
+  instance_limits,
   local_site_data_cached,
-  objects::read_from_string_or_source_opt,
+  objects::{proxy_image_link_limited, read_from_string_or_source_opt},
   protocol::{
     objects::{instance::Instance, LanguageTag},
     ImageObject,
@@ -23,12 +24,7 @@ use activitypub_federation::{
 use chrono::{DateTime, Utc};
 use lemmy_api_common::{
   context::LemmyContext,
-  utils::{
-    get_url_blocklist,
-    local_site_opt_to_slur_regex,
-    process_markdown_opt,
-    proxy_image_link_opt_apub,
-  },
+  utils::{get_url_blocklist, local_site_opt_to_slur_regex, process_markdown_opt},
 };
 use lemmy_db_schema::{
   newtypes::InstanceId,
@@ -149,8 +145,9 @@ impl Object for ApubSite {
     let sidebar = read_from_string_or_source_opt(&apub.content, &None, &apub.source);
     let sidebar = process_markdown_opt(&sidebar, slur_regex, &url_blocklist, context).await?;
     let sidebar = markdown_rewrite_remote_links_opt(sidebar, context).await;
-    let icon = proxy_image_link_opt_apub(apub.icon.map(|i| i.url), context).await?;
-    let banner = proxy_image_link_opt_apub(apub.image.map(|i| i.url), context).await?;
+    let limits = instance_limits(&[instance.id], context).await?;
+    let icon = proxy_image_link_limited(apub.icon.map(|i| i.url), limits, context).await?;
+    let banner = proxy_image_link_limited(apub.image.map(|i| i.url), limits, context).await?;
 
     let site_form = SiteInsertForm {
       name: apub.name.clone(),
@@ -166,6 +163,7 @@ impl Object for ApubSite {
       private_key: None,
       instance_id: instance.id,
       content_warning: apub.content_warning,
+      edit_history_public: None,
     };
     let languages =
       LanguageTag::to_language_id_multiple(apub.language, &mut context.pool()).await?;
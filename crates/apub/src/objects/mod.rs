@@ -1,8 +1,13 @@
-use crate::protocol::{objects::page::Attachment, Source};
+use crate::{
+  protocol::{objects::page::Attachment, Source},
+  InstanceLimits,
+};
 use activitypub_federation::{config::Data, protocol::values::MediaTypeMarkdownOrHtml};
 use html2md::parse_html;
-use lemmy_api_common::context::LemmyContext;
+use lemmy_api_common::{context::LemmyContext, utils::proxy_image_link_opt_apub};
+use lemmy_db_schema::newtypes::DbUrl;
 use lemmy_utils::error::LemmyResult;
+use url::Url;
 
 pub mod comment;
 pub mod community;
@@ -54,3 +59,19 @@ pub(crate) async fn append_attachments_to_comment(
 
   Ok(content)
 }
+
+/// Proxies an image of a remote object. Images of limited instances are stored without proxying,
+/// and those of instances whose media is rejected are dropped.
+pub(crate) async fn proxy_image_link_limited(
+  link: Option<Url>,
+  limits: InstanceLimits,
+  context: &LemmyContext,
+) -> LemmyResult<Option<DbUrl>> {
+  if limits.reject_media {
+    Ok(None)
+  } else if limits.limited {
+    Ok(link.map(Into::into))
+  } else {
+    proxy_image_link_opt_apub(link, context).await
+  }
+}
@@ -2,8 +2,13 @@ use crate::{
   activities::GetActorType,
   check_apub_id_valid_with_strictness,
   fetcher::markdown_links::markdown_rewrite_remote_links_opt,
+  instance_limits,
   local_site_data_cached,
-  objects::{instance::fetch_instance_actor_for_object, read_from_string_or_source_opt},
+  objects::{
+    instance::fetch_instance_actor_for_object,
+    proxy_image_link_limited,
+    read_from_string_or_source_opt,
+  },
   protocol::{
     objects::person::{Person, UserTypes},
     ImageObject,
@@ -23,7 +28,7 @@ use lemmy_api_common::{
     get_url_blocklist,
     local_site_opt_to_slur_regex,
     process_markdown_opt,
-    proxy_image_link_opt_apub,
+    MAX_ACCOUNT_ALIASES,
   },
 };
 use lemmy_db_schema::{
@@ -32,6 +37,7 @@ use lemmy_db_schema::{
     activity::ActorType,
     local_site::LocalSite,
     person::{Person as DbPerson, PersonInsertForm, PersonUpdateForm},
+    person_alias::PersonAlias,
   },
   traits::{ApubActor, Crud},
 };
@@ -91,7 +97,7 @@ impl Object for ApubPerson {
     Ok(())
   }
 
-  async fn into_json(self, _context: &Data<Self::DataType>) -> LemmyResult<Person> {
+  async fn into_json(self, context: &Data<Self::DataType>) -> LemmyResult<Person> {
     let kind = if self.bot_account {
       UserTypes::Service
     } else {
@@ -114,6 +120,12 @@ impl Object for ApubPerson {
       public_key: self.public_key(),
       updated: self.updated,
       inbox: self.inbox_url.clone().into(),
+      also_known_as: PersonAlias::list(&mut context.pool(), self.id)
+        .await?
+        .into_iter()
+        .map(Into::into)
+        .collect(),
+      moved_to: self.moved_to.clone().map(Into::into),
     };
     Ok(person)
   }
@@ -146,8 +158,9 @@ impl Object for ApubPerson {
     let bio = read_from_string_or_source_opt(&person.summary, &None, &person.source);
     let bio = process_markdown_opt(&bio, slur_regex, &url_blocklist, context).await?;
     let bio = markdown_rewrite_remote_links_opt(bio, context).await;
-    let avatar = proxy_image_link_opt_apub(person.icon.map(|i| i.url), context).await?;
-    let banner = proxy_image_link_opt_apub(person.image.map(|i| i.url), context).await?;
+    let limits = instance_limits(&[instance_id], context).await?;
+    let avatar = proxy_image_link_limited(person.icon.map(|i| i.url), limits, context).await?;
+    let banner = proxy_image_link_limited(person.image.map(|i| i.url), limits, context).await?;
 
     // Some Mastodon users have `name: ""` (empty string), need to convert that to `None`
     // https://github.com/mastodon/mastodon/issues/25233
@@ -179,8 +192,16 @@ impl Object for ApubPerson {
       ),
       matrix_user_id: person.matrix_user_id,
       instance_id,
+      moved_to: person.moved_to.map(Into::into),
     };
+    let aliases = person
+      .also_known_as
+      .into_iter()
+      .take(MAX_ACCOUNT_ALIASES)
+      .map(Into::into)
+      .collect();
     let person = DbPerson::upsert(&mut context.pool(), &person_form).await?;
+    PersonAlias::replace(&mut context.pool(), person.id, aliases).await?;
 
     Ok(person.into())
   }
@@ -2,11 +2,15 @@ use crate::{
   activities::{generate_to, verify_person_in_community, verify_visibility},
   check_apub_id_valid_with_strictness,
   fetcher::markdown_links::{markdown_rewrite_remote_links_opt, to_local_url},
+  instance_limits,
   local_site_data_cached,
   objects::read_from_string_or_source_opt,
   protocol::{
     objects::{
-      page::{Attachment, AttributedTo, Hashtag, HashtagType, Page, PageType},
+      page::{Attachment, AttributedTo, Page, PageType},
+      question::QuestionOption,
+      Hashtag,
+      HashtagType,
       LanguageTag,
     },
     ImageObject,
@@ -36,6 +40,8 @@ use lemmy_db_schema::{
     local_site::LocalSite,
     person::Person,
     post::{Post, PostInsertForm, PostUpdateForm},
+    post_poll::{PostPoll, PostPollInsertForm, PostPollOption, PostPollOptionInsertForm},
+    tag::{PostTagInsertForm, Tag},
   },
   traits::Crud,
 };
@@ -45,8 +51,8 @@ use lemmy_utils::{
   spawn_try_task,
   utils::{
     markdown::markdown_to_html,
-    slurs::check_slurs_opt,
-    validation::{is_url_blocked, is_valid_url},
+    slurs::{check_slurs, check_slurs_opt},
+    validation::{is_url_blocked, is_valid_poll_options, is_valid_url},
   },
 };
 use std::ops::Deref;
@@ -129,8 +135,21 @@ impl Object for ApubPost {
       name: format!("#{}", &community.name),
       kind: HashtagType::Hashtag,
     };
+    let crosspost_of = if let Some(crosspost_of_id) = self.crosspost_of_id {
+      let original = Post::read(&mut context.pool(), crosspost_of_id).await?;
+      Some(original.ap_id.into())
+    } else {
+      None
+    };
+    let mut tag = vec![hashtag];
+    tag.extend(
+      Tag::read_for_post(&mut context.pool(), self.id)
+        .await?
+        .into_iter()
+        .map(Into::into),
+    );
 
-    let page = Page {
+    let mut page = Page {
       kind: PageType::Page,
       id: self.ap_id.clone().into(),
       attributed_to: AttributedTo::Lemmy(creator.actor_id.into()),
@@ -147,8 +166,34 @@ impl Object for ApubPost {
       published: Some(self.published),
       updated: self.updated,
       in_reply_to: None,
-      tag: vec![hashtag],
+      tag,
+      one_of: None,
+      any_of: None,
+      end_time: None,
+      voters_count: None,
+      crosspost_of,
+      replies: None,
     };
+
+    // Posts with a poll are sent as `Question`, which is understood by Mastodon and others
+    if let Ok(poll) = PostPoll::read(&mut context.pool(), self.id).await {
+      // Vote counts are hidden until the poll ended. Polls without end time never end, so their
+      // counts are always included, and are up to date whenever the post is fetched.
+      let with_counts = poll.end_time.is_none_or(|end_time| end_time < Utc::now());
+      let options = PostPollOption::read_for_poll(&mut context.pool(), self.id)
+        .await?
+        .into_iter()
+        .map(|option| QuestionOption::new(option, with_counts))
+        .collect();
+      page.kind = PageType::Question;
+      if poll.multiple_choice {
+        page.any_of = Some(options);
+      } else {
+        page.one_of = Some(options);
+      }
+      page.end_time = poll.end_time;
+      page.voters_count = with_counts.then_some(poll.voters_count);
+    }
     Ok(page)
   }
 
@@ -167,6 +212,13 @@ impl Object for ApubPost {
     let local_site_data = local_site_data_cached(&mut context.pool()).await?;
     let slur_regex = &local_site_opt_to_slur_regex(&local_site_data.local_site);
     check_slurs_opt(&page.name, slur_regex)?;
+    if let Some((options, _)) = page.poll_options() {
+      let names = options.iter().map(|o| o.name.clone()).collect::<Vec<_>>();
+      is_valid_poll_options(&names)?;
+      for name in &names {
+        check_slurs(name, slur_regex)?;
+      }
+    }
 
     verify_domains_match(page.creator()?.inner(), page.id.inner())?;
     verify_visibility(&page.to, &page.cc, &community)?;
@@ -213,7 +265,13 @@ impl Object for ApubPost {
       name = name.chars().take(MAX_TITLE_LENGTH).collect();
     }
 
-    let first_attachment = page.attachment.first();
+    let limits = instance_limits(&[creator.instance_id, community.instance_id], context).await?;
+
+    // Media attachments of instances with rejected media are dropped, but links are kept
+    let first_attachment = page
+      .attachment
+      .first()
+      .filter(|a| !limits.reject_media || matches!(a, Attachment::Link(_)));
     let local_site = LocalSite::read(&mut context.pool()).await.ok();
 
     let url = if let Some(attachment) = first_attachment.cloned() {
@@ -243,34 +301,92 @@ impl Object for ApubPost {
     let body = process_markdown_opt(&body, slur_regex, &url_blocklist, context).await?;
     let body = markdown_rewrite_remote_links_opt(body, context).await;
     let language_id = Some(
-      LanguageTag::to_language_id_single(page.language.unwrap_or_default(), &mut context.pool())
-        .await?,
+      LanguageTag::to_language_id_single(
+        page.language.clone().unwrap_or_default(),
+        &mut context.pool(),
+      )
+      .await?,
     );
 
+    // Only link the crosspost if the original post can be fetched, it's not worth rejecting the
+    // post otherwise
+    let crosspost_of_id = match &page.crosspost_of {
+      Some(crosspost_of) if crosspost_of != &page.id => crosspost_of
+        .dereference(context)
+        .await
+        .ok()
+        .map(|original| original.crosspost_of_id.unwrap_or(original.id)),
+      _ => None,
+    };
+
+    // Instead of generating a thumbnail, the one sent by a limited instance is used as is
+    let thumbnail_url = if limits.limited && !limits.reject_media {
+      page.image.clone().map(|i| i.url.into())
+    } else {
+      None
+    };
+
     let form = PostInsertForm {
       url: url.map(Into::into),
+      thumbnail_url,
       body,
       alt_text,
       published: page.published,
       updated: page.updated,
       deleted: Some(false),
-      nsfw: page.sensitive,
+      nsfw: if limits.force_nsfw {
+        Some(true)
+      } else {
+        page.sensitive
+      },
       ap_id: Some(page.id.clone().into()),
       local: Some(false),
       language_id,
+      crosspost_of_id,
       ..PostInsertForm::new(name, creator.id, community.id)
     };
 
     let timestamp = page.updated.or(page.published).unwrap_or_else(Utc::now);
     let post = Post::insert_apub(&mut context.pool(), timestamp, &form).await?;
-    let post_ = post.clone();
-    let context_ = context.reset_request_count();
 
+    // Assign the community post tags which are known locally, other hashtags are ignored
+    let tag_ap_ids = page
+      .tag
+      .iter()
+      .map(|t| t.href.clone().into())
+      .collect::<Vec<_>>();
+    let tag_ids = Tag::read_from_apub_ids(&mut context.pool(), community.id, &tag_ap_ids)
+      .await?
+      .into_iter()
+      .map(|t| t.id)
+      .collect::<Vec<_>>();
+    PostTagInsertForm::set_for_post(&mut context.pool(), post.id, &tag_ids).await?;
+
+    // Vote counts of remote polls are taken over as they are
+    if let Some((options, multiple_choice)) = page.poll_options() {
+      let poll_form = PostPollInsertForm {
+        voters_count: page.voters_count,
+        ..PostPollInsertForm::new(post.id, multiple_choice, page.end_time)
+      };
+      let option_forms = options
+        .iter()
+        .zip(0..)
+        .map(|(option, position)| PostPollOptionInsertForm {
+          vote_count: option.replies.as_ref().map(|r| r.total_items),
+          ..PostPollOptionInsertForm::new(post.id, option.name.clone(), position)
+        })
+        .collect::<Vec<_>>();
+      PostPoll::upsert(&mut context.pool(), &poll_form, &option_forms).await?;
+    }
     // Generates a post thumbnail in background task, because some sites can be very slow to
-    // respond.
-    spawn_try_task(
-      async move { generate_post_link_metadata(post_, None, |_| None, context_).await },
-    );
+    // respond. Limited instances don't get thumbnails generated.
+    if !limits.limited && !limits.reject_media {
+      let post_ = post.clone();
+      let context_ = context.reset_request_count();
+      spawn_try_task(
+        async move { generate_post_link_metadata(post_, None, |_| None, context_).await },
+      );
+    }
 
     Ok(post.into())
   }
@@ -308,7 +424,15 @@ mod tests {
     assert_eq!(post.body.as_ref().map(std::string::String::len), Some(45));
     assert!(!post.locked);
     assert!(!post.featured_community);
-    assert_eq!(context.request_count(), 1);
+    // The original of the crosspost is fetched as well
+    assert_eq!(context.request_count(), 2);
+
+    // Only the hashtag matching a community tag is assigned
+    let tags = Tag::read_for_post(&mut context.pool(), post.id).await?;
+    assert_eq!(
+      vec!["Meta"],
+      tags.iter().map(|t| t.name.as_str()).collect::<Vec<_>>()
+    );
 
     Post::delete(&mut context.pool(), post.id).await?;
     Person::delete(&mut context.pool(), person.id).await?;
@@ -336,4 +460,40 @@ mod tests {
     Community::delete(&mut context.pool(), community.id).await?;
     Ok(())
   }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_parse_mastodon_question() -> LemmyResult<()> {
+    let context = LemmyContext::init_test_context().await;
+    let community = parse_lemmy_community(&context).await?;
+
+    let json = file_to_json_object("assets/mastodon/objects/person.json")?;
+    let person = ApubPerson::from_json(json, &context).await?;
+
+    let json = file_to_json_object("assets/mastodon/objects/question.json")?;
+    let post = ApubPost::from_json(json, &context).await?;
+
+    let poll = PostPoll::read(&mut context.pool(), post.id).await?;
+    assert!(!poll.multiple_choice);
+    assert_eq!(3, poll.voters_count);
+    let options = PostPollOption::read_for_poll(&mut context.pool(), post.id).await?;
+    assert_eq!(
+      vec![("Names", 2), ("Dungeons", 1)],
+      options
+        .iter()
+        .map(|o| (o.name.as_str(), o.vote_count))
+        .collect::<Vec<_>>()
+    );
+
+    // The poll is sent out as question again
+    let page = post.clone().into_json(&context).await?;
+    assert_eq!(PageType::Question, page.kind);
+    assert_eq!(Some(2), page.one_of.map(|o| o.len()));
+    assert!(page.any_of.is_none());
+
+    Post::delete(&mut context.pool(), post.id).await?;
+    Person::delete(&mut context.pool(), person.id).await?;
+    Community::delete(&mut context.pool(), community.id).await?;
+    Ok(())
+  }
 }
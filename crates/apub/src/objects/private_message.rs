@@ -1,6 +1,7 @@
 use crate::{
   check_apub_id_valid_with_strictness,
   fetcher::markdown_links::markdown_rewrite_remote_links,
+  instance_limits,
   objects::read_from_string_or_source,
   protocol::{
     objects::private_message::{PrivateMessage, PrivateMessageType},
@@ -29,7 +30,7 @@ use lemmy_db_schema::{
   source::{
     instance::Instance,
     local_site::LocalSite,
-    person::Person,
+    person::{Person, PersonFollower},
     person_block::PersonBlock,
     private_message::{PrivateMessage as DbPrivateMessage, PrivateMessageInsertForm},
   },
@@ -152,6 +153,15 @@ impl Object for ApubPrivateMessage {
     {
       check_private_messages_enabled(&recipient_local_user)?;
     }
+
+    // Users of limited instances can only message local users who follow them
+    let limits = instance_limits(&[creator.instance_id], context).await?;
+    if limits.limited
+      && recipient.local
+      && !PersonFollower::is_following(&mut context.pool(), recipient.id, creator.id).await?
+    {
+      Err(FederationError::InstanceIsLimited)?
+    }
     let local_site = LocalSite::read(&mut context.pool()).await.ok();
     let slur_regex = &local_site_opt_to_slur_regex(&local_site);
     let url_blocklist = get_url_blocklist(context).await?;
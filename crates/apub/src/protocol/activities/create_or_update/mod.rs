@@ -1,6 +1,7 @@
 pub mod note;
 pub(crate) mod note_wrapper;
 pub mod page;
+pub mod poll_vote;
 pub mod private_message;
 
 #[cfg(test)]
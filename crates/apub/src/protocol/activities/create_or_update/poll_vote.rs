@@ -0,0 +1,20 @@
+use crate::{objects::person::ApubPerson, protocol::objects::question::PollVote};
+use activitypub_federation::{
+  fetch::object_id::ObjectId,
+  kinds::activity::CreateType,
+  protocol::helpers::deserialize_one_or_many,
+};
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreatePollVote {
+  pub(crate) id: Url,
+  pub(crate) actor: ObjectId<ApubPerson>,
+  #[serde(deserialize_with = "deserialize_one_or_many")]
+  pub(crate) to: Vec<Url>,
+  pub(crate) object: PollVote,
+  #[serde(rename = "type")]
+  pub(crate) kind: CreateType,
+}
@@ -1,12 +1,18 @@
 pub(crate) mod accept;
 pub mod follow;
+pub mod move_user;
 pub(crate) mod reject;
 pub mod undo_follow;
 
 #[cfg(test)]
 mod tests {
   use crate::protocol::{
-    activities::following::{accept::AcceptFollow, follow::Follow, undo_follow::UndoFollow},
+    activities::following::{
+      accept::AcceptFollow,
+      follow::Follow,
+      move_user::MoveUser,
+      undo_follow::UndoFollow,
+    },
     tests::test_parse_lemmy_item,
   };
   use lemmy_utils::error::LemmyResult;
@@ -16,6 +22,7 @@ mod tests {
     test_parse_lemmy_item::<Follow>("assets/lemmy/activities/following/follow.json")?;
     test_parse_lemmy_item::<AcceptFollow>("assets/lemmy/activities/following/accept.json")?;
     test_parse_lemmy_item::<UndoFollow>("assets/lemmy/activities/following/undo_follow.json")?;
+    test_parse_lemmy_item::<MoveUser>("assets/lemmy/activities/following/move.json")?;
     Ok(())
   }
 }
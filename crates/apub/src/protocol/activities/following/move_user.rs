@@ -0,0 +1,26 @@
+use crate::objects::person::ApubPerson;
+use activitypub_federation::{
+  fetch::object_id::ObjectId,
+  kinds::activity::MoveType,
+  protocol::helpers::deserialize_one_or_many,
+};
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+/// Announces that a person has moved to another account, in the same way as Mastodon. The target
+/// needs to list the moved account in its `alsoKnownAs`.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MoveUser {
+  pub(crate) actor: ObjectId<ApubPerson>,
+  #[serde(deserialize_with = "deserialize_one_or_many", default)]
+  #[serde(skip_serializing_if = "Vec::is_empty")]
+  pub(crate) to: Vec<Url>,
+  /// The old account, needs to be identical to `actor`
+  pub(crate) object: ObjectId<ApubPerson>,
+  /// The new account
+  pub(crate) target: ObjectId<ApubPerson>,
+  #[serde(rename = "type")]
+  pub(crate) kind: MoveType,
+  pub(crate) id: Url,
+}
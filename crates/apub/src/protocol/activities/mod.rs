@@ -19,9 +19,18 @@ mod tests {
   use crate::protocol::{
     activities::{
       community::{announce::AnnounceActivity, report::Report},
-      create_or_update::{note::CreateOrUpdateNote, page::CreateOrUpdatePage},
+      create_or_update::{
+        note::CreateOrUpdateNote,
+        page::CreateOrUpdatePage,
+        poll_vote::CreatePollVote,
+      },
       deletion::delete::Delete,
-      following::{accept::AcceptFollow, follow::Follow, undo_follow::UndoFollow},
+      following::{
+        accept::AcceptFollow,
+        follow::Follow,
+        move_user::MoveUser,
+        undo_follow::UndoFollow,
+      },
       voting::{undo_vote::UndoVote, vote::Vote},
     },
     tests::test_json,
@@ -51,6 +60,8 @@ mod tests {
     test_json::<Vote>("assets/mastodon/activities/like_page.json")?;
     test_json::<UndoVote>("assets/mastodon/activities/undo_like_page.json")?;
     test_json::<Report>("assets/mastodon/activities/flag.json")?;
+    test_json::<CreatePollVote>("assets/mastodon/activities/poll_vote.json")?;
+    test_json::<MoveUser>("assets/mastodon/activities/move.json")?;
     Ok(())
   }
 
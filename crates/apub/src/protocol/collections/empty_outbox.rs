@@ -3,7 +3,7 @@ use lemmy_utils::error::LemmyResult;
 use serde::{Deserialize, Serialize};
 use url::Url;
 
-/// Empty placeholder outbox used for Person, Instance, which dont implement a proper outbox yet.
+/// Empty placeholder outbox used for Instance, which doesnt implement a proper outbox yet.
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub(crate) struct EmptyOutbox {
@@ -1,8 +1,12 @@
 use crate::protocol::activities::community::announce::AnnounceActivity;
-use activitypub_federation::kinds::collection::OrderedCollectionType;
+use activitypub_federation::kinds::collection::{OrderedCollectionPageType, OrderedCollectionType};
 use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
 use url::Url;
 
+/// Outbox of a community. The newest posts are included directly in `ordered_items` for older Lemmy
+/// versions, while the full history of posts and comments is split into pages, starting at `first`.
+#[skip_serializing_none]
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GroupOutbox {
@@ -10,4 +14,16 @@ pub struct GroupOutbox {
   pub(crate) id: Url,
   pub(crate) total_items: i32,
   pub(crate) ordered_items: Vec<AnnounceActivity>,
+  pub(crate) first: Option<Url>,
+}
+
+#[skip_serializing_none]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GroupOutboxPage {
+  pub(crate) r#type: OrderedCollectionPageType,
+  pub(crate) id: Url,
+  pub(crate) part_of: Url,
+  pub(crate) ordered_items: Vec<AnnounceActivity>,
+  pub(crate) next: Option<Url>,
 }
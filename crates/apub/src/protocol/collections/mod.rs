@@ -3,16 +3,18 @@ pub(crate) mod group_featured;
 pub(crate) mod group_followers;
 pub(crate) mod group_moderators;
 pub(crate) mod group_outbox;
+pub(crate) mod person_outbox;
+pub(crate) mod replies;
 
 #[cfg(test)]
 mod tests {
   use crate::protocol::{
     collections::{
-      empty_outbox::EmptyOutbox,
       group_featured::GroupFeatured,
       group_followers::GroupFollowers,
       group_moderators::GroupModerators,
-      group_outbox::GroupOutbox,
+      group_outbox::{GroupOutbox, GroupOutboxPage},
+      person_outbox::{PersonOutbox, PersonOutboxActivity, PersonOutboxPage},
     },
     tests::{test_json, test_parse_lemmy_item},
   };
@@ -25,9 +27,23 @@ mod tests {
     let outbox =
       test_parse_lemmy_item::<GroupOutbox>("assets/lemmy/collections/group_outbox.json")?;
     assert_eq!(outbox.ordered_items.len() as i32, outbox.total_items);
+    let page =
+      test_parse_lemmy_item::<GroupOutboxPage>("assets/lemmy/collections/group_outbox_page.json")?;
+    assert_eq!(2, page.ordered_items.len());
     test_parse_lemmy_item::<GroupFeatured>("assets/lemmy/collections/group_featured_posts.json")?;
     test_parse_lemmy_item::<GroupModerators>("assets/lemmy/collections/group_moderators.json")?;
-    test_parse_lemmy_item::<EmptyOutbox>("assets/lemmy/collections/person_outbox.json")?;
+    test_parse_lemmy_item::<PersonOutbox>("assets/lemmy/collections/person_outbox.json")?;
+    let page = test_parse_lemmy_item::<PersonOutboxPage>(
+      "assets/lemmy/collections/person_outbox_page.json",
+    )?;
+    assert!(matches!(
+      page.ordered_items.first(),
+      Some(PersonOutboxActivity::CreateComment(_))
+    ));
+    assert!(matches!(
+      page.ordered_items.get(1),
+      Some(PersonOutboxActivity::CreatePost(_))
+    ));
     Ok(())
   }
 
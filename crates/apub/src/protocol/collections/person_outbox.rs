@@ -0,0 +1,38 @@
+use crate::protocol::activities::create_or_update::{
+  note::CreateOrUpdateNote,
+  page::CreateOrUpdatePage,
+};
+use activitypub_federation::kinds::collection::{OrderedCollectionPageType, OrderedCollectionType};
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+use url::Url;
+
+/// Outbox of a person. The activities are split into pages, starting at `first`.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PersonOutbox {
+  pub(crate) r#type: OrderedCollectionType,
+  pub(crate) id: Url,
+  pub(crate) total_items: i64,
+  pub(crate) first: Url,
+}
+
+#[skip_serializing_none]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PersonOutboxPage {
+  pub(crate) r#type: OrderedCollectionPageType,
+  pub(crate) id: Url,
+  pub(crate) part_of: Url,
+  pub(crate) ordered_items: Vec<PersonOutboxActivity>,
+  pub(crate) next: Option<Url>,
+  pub(crate) prev: Option<Url>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(untagged)]
+pub(crate) enum PersonOutboxActivity {
+  // Comments need to come first, because a `Note` can also be parsed as `Page`
+  CreateComment(CreateOrUpdateNote),
+  CreatePost(CreateOrUpdatePage),
+}
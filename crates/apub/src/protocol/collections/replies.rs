@@ -0,0 +1,58 @@
+use crate::protocol::{Id, IdOrNestedObject};
+use activitypub_federation::protocol::verification::verify_domains_match;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+use url::Url;
+
+/// Replies to a post or comment, as provided by Mastodon and other platforms. Lemmy doesn't
+/// provide this collection itself, but uses it to backfill comments from communities whose outbox
+/// only contains posts. The same type is used for the collection and for its pages, which are
+/// either referenced by url or embedded.
+#[skip_serializing_none]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Replies {
+  pub(crate) id: Option<Url>,
+  #[serde(default, alias = "orderedItems")]
+  pub(crate) items: Vec<IdOrNestedObject<Reply>>,
+  pub(crate) first: Option<RepliesOrUrl>,
+  pub(crate) next: Option<RepliesOrUrl>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(untagged)]
+pub(crate) enum RepliesOrUrl {
+  Url(Url),
+  Replies(Box<Replies>),
+}
+
+impl RepliesOrUrl {
+  pub(crate) fn id(&self) -> Option<&Url> {
+    match self {
+      RepliesOrUrl::Url(u) => Some(u),
+      RepliesOrUrl::Replies(r) => r.id.as_ref(),
+    }
+  }
+
+  /// The url of the collection, if it is on the same domain as the post or comment which it
+  /// belongs to.
+  pub(crate) fn collection_url(&self, object_id: &Url) -> Option<Url> {
+    self
+      .id()
+      .filter(|id| verify_domains_match(id, object_id).is_ok())
+      .cloned()
+  }
+}
+
+/// An item of the replies collection. Embedded objects are fetched again from their id, so only
+/// the id is needed.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub(crate) struct Reply {
+  id: Url,
+}
+
+impl Id for Reply {
+  fn object_id(&self) -> &Url {
+    &self.id
+  }
+}
@@ -9,7 +9,7 @@ use crate::{
   local_site_data_cached,
   objects::community::ApubCommunity,
   protocol::{
-    objects::{Endpoints, LanguageTag},
+    objects::{Endpoints, Hashtag, LanguageTag},
     ImageObject,
     Source,
   },
@@ -77,6 +77,9 @@ pub struct Group {
   pub(crate) manually_approves_followers: Option<bool>,
   pub(crate) published: Option<DateTime<Utc>>,
   pub(crate) updated: Option<DateTime<Utc>>,
+  /// Tags which can be assigned to posts in this community
+  #[serde(deserialize_with = "deserialize_skip_error", default)]
+  pub(crate) tag: Vec<Hashtag>,
 }
 
 impl Group {
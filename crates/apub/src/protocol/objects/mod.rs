@@ -1,7 +1,7 @@
 use lemmy_db_schema::{
   impls::actor_language::UNDETERMINED_ID,
   newtypes::LanguageId,
-  source::language::Language,
+  source::{language::Language, tag::Tag},
   utils::DbPool,
 };
 use lemmy_utils::error::LemmyResult;
@@ -14,6 +14,7 @@ pub(crate) mod note;
 pub(crate) mod page;
 pub(crate) mod person;
 pub(crate) mod private_message;
+pub(crate) mod question;
 pub(crate) mod tombstone;
 
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
@@ -22,6 +23,30 @@ pub struct Endpoints {
   pub shared_inbox: Url,
 }
 
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct Hashtag {
+  pub(crate) href: Url,
+  pub(crate) name: String,
+  #[serde(rename = "type")]
+  pub(crate) kind: HashtagType,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub enum HashtagType {
+  Hashtag,
+}
+
+/// Community post tags are federated as hashtags, identified by their `href`.
+impl From<Tag> for Hashtag {
+  fn from(tag: Tag) -> Self {
+    Hashtag {
+      href: tag.ap_id.into(),
+      name: tag.name,
+      kind: HashtagType::Hashtag,
+    }
+  }
+}
+
 /// As specified in https://schema.org/Language
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
@@ -115,6 +140,8 @@ mod tests {
     tests::{test_json, test_parse_lemmy_item},
   };
   use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+  use url::Url;
 
   #[test]
   fn test_parse_objects_lemmy() -> LemmyResult<()> {
@@ -147,9 +174,20 @@ mod tests {
   #[test]
   fn test_parse_objects_mastodon() -> LemmyResult<()> {
     test_json::<Person>("assets/mastodon/objects/person.json")?;
-    test_json::<Note>("assets/mastodon/objects/note_1.json")?;
+    let note = test_json::<Note>("assets/mastodon/objects/note_1.json")?;
+    // The replies collection is used to backfill comments
+    let replies = note
+      .inner()
+      .replies
+      .as_ref()
+      .and_then(|r| r.collection_url(note.inner().id.inner()));
+    assert_eq!(
+      Some("https://mastodon.madrid/users/felix/statuses/107224289116410645/replies"),
+      replies.as_ref().map(Url::as_str)
+    );
     test_json::<Note>("assets/mastodon/objects/note_2.json")?;
     test_json::<Page>("assets/mastodon/objects/page.json")?;
+    test_json::<Page>("assets/mastodon/objects/question.json")?;
     Ok(())
   }
 
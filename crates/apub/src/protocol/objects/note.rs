@@ -3,6 +3,7 @@ use crate::{
   mentions::MentionOrValue,
   objects::{comment::ApubComment, community::ApubCommunity, person::ApubPerson, post::ApubPost},
   protocol::{
+    collections::replies::RepliesOrUrl,
     objects::{page::Attachment, LanguageTag},
     InCommunity,
     Source,
@@ -57,6 +58,8 @@ pub struct Note {
   pub(crate) language: Option<LanguageTag>,
   #[serde(default)]
   pub(crate) attachment: Vec<Attachment>,
+  #[serde(deserialize_with = "deserialize_skip_error", default)]
+  pub(crate) replies: Option<RepliesOrUrl>,
 }
 
 impl Note {
@@ -1,7 +1,13 @@
 use crate::{
   fetcher::user_or_community::{PersonOrGroupType, UserOrCommunity},
   objects::{community::ApubCommunity, person::ApubPerson, post::ApubPost},
-  protocol::{objects::LanguageTag, ImageObject, InCommunity, Source},
+  protocol::{
+    collections::replies::RepliesOrUrl,
+    objects::{question::QuestionOption, Hashtag, LanguageTag},
+    ImageObject,
+    InCommunity,
+    Source,
+  },
 };
 use activitypub_federation::{
   config::Data,
@@ -31,6 +37,8 @@ pub enum PageType {
   Note,
   Video,
   Event,
+  /// A post with a poll
+  Question,
 }
 
 #[skip_serializing_none]
@@ -65,6 +73,19 @@ pub struct Page {
   pub(crate) language: Option<LanguageTag>,
   #[serde(deserialize_with = "deserialize_skip_error", default)]
   pub(crate) tag: Vec<Hashtag>,
+  /// Poll options if only one of them can be chosen
+  pub(crate) one_of: Option<Vec<QuestionOption>>,
+  /// Poll options if any number of them can be chosen
+  pub(crate) any_of: Option<Vec<QuestionOption>>,
+  /// When the poll ends
+  pub(crate) end_time: Option<DateTime<Utc>>,
+  pub(crate) voters_count: Option<i32>,
+  /// The original post, if this is a crosspost. Defined in the Lemmy context on join-lemmy.org
+  /// as `"crosspostOf": {"@id": "lemmy:crosspostOf", "@type": "@id"}`, so it's a reference to
+  /// another object.
+  pub(crate) crosspost_of: Option<ObjectId<ApubPost>>,
+  #[serde(deserialize_with = "deserialize_skip_error", default)]
+  pub(crate) replies: Option<RepliesOrUrl>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -158,20 +179,16 @@ pub(crate) struct AttributedToPeertube {
   pub id: ObjectId<UserOrCommunity>,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
-pub struct Hashtag {
-  pub(crate) href: Url,
-  pub(crate) name: String,
-  #[serde(rename = "type")]
-  pub(crate) kind: HashtagType,
-}
-
-#[derive(Clone, Debug, Deserialize, Serialize)]
-pub enum HashtagType {
-  Hashtag,
-}
-
 impl Page {
+  /// The poll options and whether multiple of them can be chosen, if this is a poll.
+  pub(crate) fn poll_options(&self) -> Option<(&Vec<QuestionOption>, bool)> {
+    match (&self.one_of, &self.any_of) {
+      (Some(options), _) => Some((options, false)),
+      (None, Some(options)) => Some((options, true)),
+      (None, None) => None,
+    }
+  }
+
   pub(crate) fn creator(&self) -> LemmyResult<ObjectId<ApubPerson>> {
     match &self.attributed_to {
       AttributedTo::Lemmy(l) => Ok(l.clone()),
@@ -272,9 +289,21 @@ where
 #[cfg(test)]
 mod tests {
   use crate::protocol::{objects::page::Page, tests::test_parse_lemmy_item};
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
 
   #[test]
   fn test_not_parsing_note_as_page() {
     assert!(test_parse_lemmy_item::<Page>("assets/lemmy/objects/note.json").is_err());
   }
+
+  #[test]
+  fn test_parse_crosspost() -> LemmyResult<()> {
+    let page = test_parse_lemmy_item::<Page>("assets/lemmy/objects/page.json")?;
+    assert_eq!(
+      Some("https://enterprise.lemmy.ml/post/55140"),
+      page.crosspost_of.as_ref().map(|c| c.inner().as_str())
+    );
+    Ok(())
+  }
 }
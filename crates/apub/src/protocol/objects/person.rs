@@ -4,7 +4,10 @@ use crate::{
 };
 use activitypub_federation::{
   fetch::object_id::ObjectId,
-  protocol::{helpers::deserialize_skip_error, public_key::PublicKey},
+  protocol::{
+    helpers::{deserialize_one_or_many, deserialize_skip_error},
+    public_key::PublicKey,
+  },
 };
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -28,7 +31,7 @@ pub struct Person {
   /// username, set at account creation and usually fixed after that
   pub(crate) preferred_username: String,
   pub(crate) inbox: Url,
-  /// mandatory field in activitypub, lemmy currently serves an empty outbox
+  /// mandatory field in activitypub, contains the public posts and comments of the person
   pub(crate) outbox: Url,
   pub(crate) public_key: PublicKey,
 
@@ -46,4 +49,11 @@ pub struct Person {
   pub(crate) endpoints: Option<Endpoints>,
   pub(crate) published: Option<DateTime<Utc>>,
   pub(crate) updated: Option<DateTime<Utc>>,
+  /// Other accounts of the same person, which are allowed to move to this one
+  #[serde(deserialize_with = "deserialize_one_or_many", default)]
+  #[serde(skip_serializing_if = "Vec::is_empty")]
+  pub(crate) also_known_as: Vec<Url>,
+  /// Account which this person has moved to
+  #[serde(deserialize_with = "deserialize_skip_error", default)]
+  pub(crate) moved_to: Option<ObjectId<ApubPerson>>,
 }
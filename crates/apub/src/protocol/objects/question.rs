@@ -0,0 +1,62 @@
+use crate::objects::{person::ApubPerson, post::ApubPost};
+use activitypub_federation::{
+  fetch::object_id::ObjectId,
+  kinds::{collection::CollectionType, object::NoteType},
+  protocol::helpers::deserialize_one_or_many,
+};
+use lemmy_db_schema::source::post_poll::PostPollOption;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+use url::Url;
+
+/// An option of a poll. Polls are represented as `Question`, which has the options in `oneOf`
+/// for single choice polls, or in `anyOf` for multiple choice polls.
+#[skip_serializing_none]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct QuestionOption {
+  #[serde(rename = "type")]
+  pub(crate) kind: NoteType,
+  pub(crate) name: String,
+  pub(crate) replies: Option<QuestionOptionReplies>,
+}
+
+/// Contains the number of votes for a poll option.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct QuestionOptionReplies {
+  #[serde(rename = "type")]
+  pub(crate) kind: CollectionType,
+  pub(crate) total_items: i32,
+}
+
+/// A vote for a single poll option. It has the option as `name`, and no content. Votes are only
+/// sent to the author of the poll, which publishes the vote counts once the poll ended, or all
+/// the time for polls without end time.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PollVote {
+  #[serde(rename = "type")]
+  pub(crate) kind: NoteType,
+  pub(crate) id: Url,
+  pub(crate) attributed_to: ObjectId<ApubPerson>,
+  #[serde(deserialize_with = "deserialize_one_or_many")]
+  pub(crate) to: Vec<Url>,
+  pub(crate) name: String,
+  pub(crate) in_reply_to: ObjectId<ApubPost>,
+}
+
+impl QuestionOption {
+  /// The vote count is left out while a poll with end time is still open, so that the results
+  /// can't be seen before voting.
+  pub(crate) fn new(option: PostPollOption, with_count: bool) -> Self {
+    QuestionOption {
+      kind: NoteType::Note,
+      name: option.name,
+      replies: with_count.then_some(QuestionOptionReplies {
+        kind: CollectionType::Collection,
+        total_items: option.vote_count,
+      }),
+    }
+  }
+}
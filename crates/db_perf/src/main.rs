@@ -199,5 +199,6 @@ fn site() -> LemmyResult<Site> {
     public_key: String::new(),
     instance_id: Default::default(),
     content_warning: None,
+    edit_history_public: false,
   })
 }
@@ -782,15 +782,17 @@ CALL r.create_person_saved_combined_trigger ('post');
 
 CALL r.create_person_saved_combined_trigger ('comment');
 
--- modlog: (17 tables)
+-- modlog: (20 tables)
 -- admin_allow_instance
 -- admin_block_instance
+-- admin_limit_instance
 -- admin_purge_comment
 -- admin_purge_community
 -- admin_purge_person
 -- admin_purge_post
 -- mod_add
 -- mod_add_community
+-- mod_automod_action
 -- mod_ban
 -- mod_ban_from_community
 -- mod_feature_post
@@ -800,6 +802,7 @@ CALL r.create_person_saved_combined_trigger ('comment');
 -- mod_remove_community
 -- mod_remove_post
 -- mod_transfer_community
+-- mod_warn_person
 CREATE PROCEDURE r.create_modlog_combined_trigger (table_name text)
 LANGUAGE plpgsql
 AS $a$
@@ -827,6 +830,8 @@ CALL r.create_modlog_combined_trigger ('admin_allow_instance');
 
 CALL r.create_modlog_combined_trigger ('admin_block_instance');
 
+CALL r.create_modlog_combined_trigger ('admin_limit_instance');
+
 CALL r.create_modlog_combined_trigger ('admin_purge_comment');
 
 CALL r.create_modlog_combined_trigger ('admin_purge_community');
@@ -839,6 +844,8 @@ CALL r.create_modlog_combined_trigger ('mod_add');
 
 CALL r.create_modlog_combined_trigger ('mod_add_community');
 
+CALL r.create_modlog_combined_trigger ('mod_automod_action');
+
 CALL r.create_modlog_combined_trigger ('mod_ban');
 
 CALL r.create_modlog_combined_trigger ('mod_ban_from_community');
@@ -857,7 +864,9 @@ CALL r.create_modlog_combined_trigger ('mod_remove_post');
 
 CALL r.create_modlog_combined_trigger ('mod_transfer_community');
 
--- Inbox: (replies, comment mentions, post mentions, and private_messages)
+CALL r.create_modlog_combined_trigger ('mod_warn_person');
+
+-- Inbox: (replies, comment mentions, post mentions, private_messages, warnings, modmail messages, and thread replies)
 CREATE PROCEDURE r.create_inbox_combined_trigger (table_name text)
 LANGUAGE plpgsql
 AS $a$
@@ -889,6 +898,12 @@ CALL r.create_inbox_combined_trigger ('person_post_mention');
 
 CALL r.create_inbox_combined_trigger ('private_message');
 
+CALL r.create_inbox_combined_trigger ('mod_warn_person');
+
+CALL r.create_inbox_combined_trigger ('modmail_message');
+
+CALL r.create_inbox_combined_trigger ('thread_reply');
+
 -- Prevent using delete instead of uplete on action tables
 CREATE FUNCTION r.require_uplete ()
     RETURNS TRIGGER
@@ -928,37 +943,57 @@ CREATE TRIGGER require_uplete
     EXECUTE FUNCTION r.require_uplete ();
 
 -- search: (post, comment, community, person)
-CREATE PROCEDURE r.create_search_combined_trigger (table_name text)
+-- The `search_vector` is recalculated whenever one of the given columns changes.
+CREATE PROCEDURE r.create_search_combined_trigger (table_name text, search_columns text)
 LANGUAGE plpgsql
 AS $a$
 BEGIN
-    EXECUTE replace($b$ CREATE FUNCTION r.search_combined_thing_insert ( )
+    EXECUTE replace(replace($b$ CREATE FUNCTION r.search_combined_thing_insert ( )
             RETURNS TRIGGER
             LANGUAGE plpgsql
             AS $$
             BEGIN
                 -- TODO need to figure out how to do the other columns here
-                INSERT INTO search_combined (published, thing_id)
-                    VALUES (NEW.published, NEW.id);
+                INSERT INTO search_combined (published, thing_id, search_vector)
+                    VALUES (NEW.published, NEW.id, r.search_vector (NEW));
                 RETURN NEW;
             END $$;
     CREATE TRIGGER search_combined
         AFTER INSERT ON thing
         FOR EACH ROW
         EXECUTE FUNCTION r.search_combined_thing_insert ( );
+    CREATE FUNCTION r.search_combined_thing_search_vector_update ( )
+            RETURNS TRIGGER
+            LANGUAGE plpgsql
+            AS $$
+            BEGIN
+                UPDATE
+                    search_combined
+                SET
+                    search_vector = r.search_vector (NEW)
+                WHERE
+                    thing_id = NEW.id;
+                RETURN NULL;
+            END $$;
+    CREATE TRIGGER search_combined_search_vector
+        AFTER UPDATE OF search_columns ON thing
+        FOR EACH ROW
+        EXECUTE FUNCTION r.search_combined_thing_search_vector_update ( );
         $b$,
         'thing',
-        table_name);
+        table_name),
+    'search_columns',
+    search_columns);
 END;
 $a$;
 
-CALL r.create_search_combined_trigger ('post');
+CALL r.create_search_combined_trigger ('post', 'name, body, language_id');
 
-CALL r.create_search_combined_trigger ('comment');
+CALL r.create_search_combined_trigger ('comment', 'content, language_id');
 
-CALL r.create_search_combined_trigger ('community');
+CALL r.create_search_combined_trigger ('community', 'name, title, description');
 
-CALL r.create_search_combined_trigger ('person');
+CALL r.create_search_combined_trigger ('person', 'name, display_name');
 
 -- You also need to triggers to update the `score` column.
 -- post | post_aggregates::score
@@ -57,6 +57,86 @@ BEGIN
 END;
 $$;
 
+-- The number of posts and comments of a person which were removed by moderators
+CREATE FUNCTION r.person_removal_count (person_id int)
+    RETURNS bigint
+    LANGUAGE sql
+    STABLE PARALLEL SAFE RETURN (
+        SELECT
+            count(*)
+        FROM
+            mod_remove_post
+            INNER JOIN post ON post.id = mod_remove_post.post_id
+        WHERE
+            post.creator_id = person_id
+            AND mod_remove_post.removed) + (
+        SELECT
+            count(*)
+        FROM
+            mod_remove_comment
+            INNER JOIN comment ON comment.id = mod_remove_comment.comment_id
+        WHERE
+            comment.creator_id = person_id
+            AND mod_remove_comment.removed);
+
+-- The number of times a person was banned from the site or from a community
+CREATE FUNCTION r.person_ban_count (person_id int)
+    RETURNS bigint
+    LANGUAGE sql
+    STABLE PARALLEL SAFE RETURN (
+        SELECT
+            count(*)
+        FROM
+            mod_ban
+        WHERE
+            other_person_id = person_id
+            AND banned) + (
+        SELECT
+            count(*)
+        FROM
+            mod_ban_from_community
+        WHERE
+            other_person_id = person_id
+            AND banned);
+
+-- The poll of a post as json, or null if the post has no poll. Vote counts are only included
+-- once the given person voted, or after the poll ended.
+CREATE FUNCTION r.post_poll (poll_post_id int, voter_id int)
+    RETURNS json
+    LANGUAGE sql
+    STABLE PARALLEL SAFE RETURN (
+        SELECT
+            json_build_object('multiple_choice', post_poll.multiple_choice, 'end_time', post_poll.end_time, 'voters_count', CASE WHEN results_visible THEN
+                    post_poll.voters_count
+                END, 'options', (
+                    SELECT
+                        coalesce(json_agg(json_build_object('id', post_poll_option.id, 'name', post_poll_option.name, 'vote_count', CASE WHEN results_visible THEN
+                                    post_poll_option.vote_count
+                                END) ORDER BY post_poll_option.position), '[]')
+                    FROM post_poll_option
+                    WHERE
+                        post_poll_option.post_id = poll_post_id), 'my_votes', (
+                    SELECT
+                        coalesce(json_agg(post_poll_vote.option_id), '[]')
+                    FROM post_poll_vote
+                    WHERE
+                        post_poll_vote.post_id = poll_post_id
+                        AND post_poll_vote.person_id = voter_id))
+        FROM
+            post_poll,
+            LATERAL (
+                SELECT
+                    coalesce(post_poll.end_time < now(), FALSE)
+                    OR EXISTS (
+                        SELECT
+                        FROM
+                            post_poll_vote
+                        WHERE
+                            post_poll_vote.post_id = poll_post_id
+                            AND post_poll_vote.person_id = voter_id) AS results_visible) AS visibility
+        WHERE
+            post_poll.post_id = poll_post_id);
+
 CREATE FUNCTION r.local_url (url_path text)
     RETURNS text
     LANGUAGE sql
@@ -266,3 +346,119 @@ BEGIN
 END;
 $$;
 
+
+-- Text search configuration used for content in the given language. Languages without a built-in
+-- configuration (including "undetermined") fall back to `simple`, which does no stemming.
+CREATE FUNCTION r.text_search_config (language_id int)
+    RETURNS regconfig
+    LANGUAGE sql
+    STABLE PARALLEL SAFE RETURN coalesce((
+        SELECT
+            CASE code
+            WHEN 'ar' THEN
+                'arabic'
+            WHEN 'da' THEN
+                'danish'
+            WHEN 'de' THEN
+                'german'
+            WHEN 'el' THEN
+                'greek'
+            WHEN 'en' THEN
+                'english'
+            WHEN 'es' THEN
+                'spanish'
+            WHEN 'fi' THEN
+                'finnish'
+            WHEN 'fr' THEN
+                'french'
+            WHEN 'ga' THEN
+                'irish'
+            WHEN 'hu' THEN
+                'hungarian'
+            WHEN 'id' THEN
+                'indonesian'
+            WHEN 'it' THEN
+                'italian'
+            WHEN 'lt' THEN
+                'lithuanian'
+            WHEN 'nb' THEN
+                'norwegian'
+            WHEN 'ne' THEN
+                'nepali'
+            WHEN 'nl' THEN
+                'dutch'
+            WHEN 'nn' THEN
+                'norwegian'
+            WHEN 'no' THEN
+                'norwegian'
+            WHEN 'pt' THEN
+                'portuguese'
+            WHEN 'ro' THEN
+                'romanian'
+            WHEN 'ru' THEN
+                'russian'
+            WHEN 'sv' THEN
+                'swedish'
+            WHEN 'ta' THEN
+                'tamil'
+            WHEN 'tr' THEN
+                'turkish'
+            END::regconfig
+        FROM language
+        WHERE
+            id = language_id), 'simple');
+
+-- The title gets weight A and the body weight B, which is used by `ts_rank` for relevance sorting.
+-- The `simple` configuration is always added, so that exact words still match in content with a
+-- stemming configuration.
+CREATE FUNCTION r.search_vector (config regconfig, title text, body text)
+    RETURNS tsvector
+    LANGUAGE sql
+    IMMUTABLE PARALLEL SAFE RETURN setweight(to_tsvector(config, coalesce(title, '')), 'A') || setweight(to_tsvector(config, coalesce(body, '')), 'B') || setweight(to_tsvector('simple', coalesce(title, '')), 'A') || setweight(to_tsvector('simple', coalesce(body, '')), 'B');
+
+CREATE FUNCTION r.search_vector (item post)
+    RETURNS tsvector
+    LANGUAGE sql
+    STABLE PARALLEL SAFE RETURN r.search_vector (r.text_search_config (item.language_id), item.name, item.body);
+
+CREATE FUNCTION r.search_vector (item comment)
+    RETURNS tsvector
+    LANGUAGE sql
+    STABLE PARALLEL SAFE RETURN r.search_vector (r.text_search_config (item.language_id), item.content, NULL);
+
+CREATE FUNCTION r.search_vector (item community)
+    RETURNS tsvector
+    LANGUAGE sql
+    IMMUTABLE PARALLEL SAFE RETURN r.search_vector ('simple', item.name || ' ' || item.title, item.description);
+
+CREATE FUNCTION r.search_vector (item person)
+    RETURNS tsvector
+    LANGUAGE sql
+    IMMUTABLE PARALLEL SAFE RETURN r.search_vector ('simple', item.name || ' ' || coalesce(item.display_name, ''), NULL);
+
+-- Parses a search term into a query where every word also matches as a prefix, for example
+-- "tim" matches "timmy". Posts and comments are also indexed with the configuration of their
+-- language, so the term is parsed with each configuration of `r.text_search_config` as well, and
+-- "dogs" matches "dog" in English content.
+CREATE FUNCTION r.search_query (search_term text)
+    RETURNS tsquery
+    LANGUAGE sql
+    IMMUTABLE PARALLEL SAFE RETURN (
+        SELECT
+            string_agg('(' || query::text || ')', ' | ')::tsquery
+        FROM (
+            SELECT
+                regexp_replace(websearch_to_tsquery('simple', search_term)::text, '(''(?:[^'']|'''')*'')', '\1:*', 'g')::tsquery AS query
+            UNION
+            SELECT
+                websearch_to_tsquery(config, search_term)
+            FROM
+                unnest('{arabic,danish,german,greek,english,spanish,finnish,french,irish,hungarian,indonesian,italian,lithuanian,norwegian,nepali,dutch,portuguese,romanian,russian,swedish,tamil,turkish}'::regconfig[]) AS config) AS queries
+        WHERE
+            numnode(query) > 0);
+
+-- Only the parts of a search vector which come from the title
+CREATE FUNCTION r.title_search_vector (search_vector tsvector)
+    RETURNS tsvector
+    LANGUAGE sql
+    IMMUTABLE PARALLEL SAFE RETURN ts_filter(search_vector, '{a}');
@@ -32,6 +32,16 @@ impl SentActivity {
     let conn = &mut get_conn(pool).await?;
     sent_activity.find(object_id).first(conn).await
   }
+  /// The id of the newest activity, or 0 if there are none.
+  pub async fn read_latest_id(pool: &mut DbPool<'_>) -> Result<ActivityId, Error> {
+    use crate::schema::sent_activity::dsl::{id, sent_activity};
+    let conn = &mut get_conn(pool).await?;
+    let latest_id = sent_activity
+      .select(diesel::dsl::max(id))
+      .get_result::<Option<ActivityId>>(conn)
+      .await?;
+    Ok(latest_id.unwrap_or_default())
+  }
 }
 
 impl ReceivedActivity {
@@ -0,0 +1,192 @@
+use crate::{
+  newtypes::{ApiTokenId, LocalUserId},
+  schema::{api_token, api_token_scope},
+  source::api_token::{ApiToken, ApiTokenInsertForm, ApiTokenScopeForm},
+  utils::{get_conn, now, DbPool},
+  ApiTokenScope,
+};
+use diesel::{
+  delete,
+  insert_into,
+  result::Error,
+  BoolExpressionMethods,
+  ExpressionMethods,
+  NullableExpressionMethods,
+  OptionalExtension,
+  QueryDsl,
+};
+use diesel_async::RunQueryDsl;
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+
+impl ApiToken {
+  /// Creates the token together with its scopes.
+  pub async fn create(
+    pool: &mut DbPool<'_>,
+    form: &ApiTokenInsertForm,
+    scopes: &[ApiTokenScope],
+  ) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    let form = form.clone();
+    let scopes = scopes.to_vec();
+    conn
+      .build_transaction()
+      .run(|conn| {
+        Box::pin(async move {
+          let token = insert_into(api_token::table)
+            .values(form)
+            .get_result::<Self>(conn)
+            .await?;
+          let scope_forms = scopes
+            .into_iter()
+            .map(|scope| ApiTokenScopeForm {
+              api_token_id: token.id,
+              scope,
+            })
+            .collect::<Vec<_>>();
+          insert_into(api_token_scope::table)
+            .values(scope_forms)
+            .on_conflict_do_nothing()
+            .execute(conn)
+            .await?;
+          Ok(token)
+        }) as _
+      })
+      .await
+  }
+
+  /// Reads the token with the given hash, if it exists and is not expired.
+  pub async fn validate(pool: &mut DbPool<'_>, token_hash: &str) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    api_token::table
+      .filter(api_token::token_hash.eq(token_hash))
+      .filter(
+        api_token::expires
+          .is_null()
+          .or(api_token::expires.gt(now().nullable())),
+      )
+      .first::<Self>(conn)
+      .await
+      .optional()?
+      .ok_or(LemmyErrorType::NotLoggedIn.into())
+  }
+
+  /// All tokens of the user, newest first. Includes expired tokens.
+  pub async fn list(pool: &mut DbPool<'_>, local_user_id: LocalUserId) -> Result<Vec<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    api_token::table
+      .filter(api_token::local_user_id.eq(local_user_id))
+      .order_by(api_token::published.desc())
+      .load::<Self>(conn)
+      .await
+  }
+
+  /// Reads the scopes of all the given tokens.
+  pub async fn read_scopes(
+    pool: &mut DbPool<'_>,
+    token_ids: &[ApiTokenId],
+  ) -> Result<Vec<(ApiTokenId, ApiTokenScope)>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    api_token_scope::table
+      .filter(api_token_scope::api_token_id.eq_any(token_ids))
+      .order_by(api_token_scope::scope)
+      .select((api_token_scope::api_token_id, api_token_scope::scope))
+      .load(conn)
+      .await
+  }
+
+  /// Revokes the token. Only deletes it if it belongs to the given user.
+  pub async fn delete_for_user(
+    pool: &mut DbPool<'_>,
+    token_id: ApiTokenId,
+    local_user_id: LocalUserId,
+  ) -> Result<usize, Error> {
+    let conn = &mut get_conn(pool).await?;
+    delete(
+      api_token::table
+        .filter(api_token::id.eq(token_id))
+        .filter(api_token::local_user_id.eq(local_user_id)),
+    )
+    .execute(conn)
+    .await
+  }
+}
+
+#[cfg(test)]
+mod tests {
+
+  use super::*;
+  use crate::{
+    source::{
+      instance::Instance,
+      local_user::{LocalUser, LocalUserInsertForm},
+      person::{Person, PersonInsertForm},
+    },
+    traits::Crud,
+    utils::build_db_pool_for_tests,
+  };
+  use chrono::{TimeDelta, Utc};
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_api_token() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let instance = Instance::read_or_create(pool, "my_domain.tld".to_string()).await?;
+    let person_form = PersonInsertForm::test_form(instance.id, "api_token_user");
+    let person = Person::create(pool, &person_form).await?;
+    let user_form = LocalUserInsertForm::test_form(person.id);
+    let user = LocalUser::create(pool, &user_form, vec![]).await?;
+    let other_person_form = PersonInsertForm::test_form(instance.id, "api_token_other");
+    let other_person = Person::create(pool, &other_person_form).await?;
+    let other_user_form = LocalUserInsertForm::test_form(other_person.id);
+    let other_user = LocalUser::create(pool, &other_user_form, vec![]).await?;
+
+    let form = ApiTokenInsertForm::new(user.id, "bot".to_string(), "hash1".to_string(), None);
+    let token = ApiToken::create(
+      pool,
+      &form,
+      &[ApiTokenScope::WritePost, ApiTokenScope::Read],
+    )
+    .await?;
+    let expired_form = ApiTokenInsertForm::new(
+      user.id,
+      "expired".to_string(),
+      "hash2".to_string(),
+      Some(Utc::now() - TimeDelta::days(1)),
+    );
+    let expired = ApiToken::create(pool, &expired_form, &[ApiTokenScope::Read]).await?;
+
+    assert_eq!(token, ApiToken::validate(pool, "hash1").await?);
+    assert!(ApiToken::validate(pool, "hash2").await.is_err());
+    assert!(ApiToken::validate(pool, "unknown").await.is_err());
+
+    let scopes = ApiToken::read_scopes(pool, &[token.id]).await?;
+    assert_eq!(
+      vec![
+        (token.id, ApiTokenScope::Read),
+        (token.id, ApiTokenScope::WritePost)
+      ],
+      scopes
+    );
+
+    let tokens = ApiToken::list(pool, user.id).await?;
+    assert_eq!(vec![expired.clone(), token.clone()], tokens);
+
+    // Tokens can only be revoked by their owner
+    assert_eq!(
+      0,
+      ApiToken::delete_for_user(pool, token.id, other_user.id).await?
+    );
+    assert_eq!(1, ApiToken::delete_for_user(pool, token.id, user.id).await?);
+    assert!(ApiToken::validate(pool, "hash1").await.is_err());
+    assert!(ApiToken::read_scopes(pool, &[token.id]).await?.is_empty());
+
+    Person::delete(pool, person.id).await?;
+    Person::delete(pool, other_person.id).await?;
+    Instance::delete(pool, instance.id).await?;
+    Ok(())
+  }
+}
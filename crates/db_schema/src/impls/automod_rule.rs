@@ -0,0 +1,155 @@
+use crate::{
+  newtypes::{AutomodRuleId, CommunityId},
+  schema::automod_rule,
+  source::automod_rule::{AutomodRule, AutomodRuleInsertForm, AutomodRuleUpdateForm},
+  traits::Crud,
+  utils::{get_conn, DbPool},
+};
+use diesel::{insert_into, result::Error, ExpressionMethods, QueryDsl};
+use diesel_async::RunQueryDsl;
+
+#[async_trait]
+impl Crud for AutomodRule {
+  type InsertForm = AutomodRuleInsertForm;
+  type UpdateForm = AutomodRuleUpdateForm;
+  type IdType = AutomodRuleId;
+
+  async fn create(pool: &mut DbPool<'_>, form: &Self::InsertForm) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(automod_rule::table)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+
+  async fn update(
+    pool: &mut DbPool<'_>,
+    rule_id: AutomodRuleId,
+    form: &Self::UpdateForm,
+  ) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(automod_rule::table.find(rule_id))
+      .set(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+}
+
+impl AutomodRule {
+  /// All rules of the community, in the order they were created.
+  pub async fn list(pool: &mut DbPool<'_>, community_id: CommunityId) -> Result<Vec<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    automod_rule::table
+      .filter(automod_rule::community_id.eq(community_id))
+      .order_by(automod_rule::id)
+      .load::<Self>(conn)
+      .await
+  }
+
+  /// The enabled rules of the community, in the order in which they are applied.
+  pub async fn list_enabled(
+    pool: &mut DbPool<'_>,
+    community_id: CommunityId,
+  ) -> Result<Vec<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    automod_rule::table
+      .filter(automod_rule::community_id.eq(community_id))
+      .filter(automod_rule::enabled)
+      .order_by(automod_rule::id)
+      .load::<Self>(conn)
+      .await
+  }
+}
+
+#[cfg(test)]
+mod tests {
+
+  use super::*;
+  use crate::{
+    source::{
+      community::{Community, CommunityInsertForm},
+      instance::Instance,
+      person::{Person, PersonInsertForm},
+    },
+    utils::build_db_pool_for_tests,
+    AutomodAction,
+  };
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_automod_rules() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let instance = Instance::read_or_create(pool, "my_domain.tld".to_string()).await?;
+    let person_form = PersonInsertForm::test_form(instance.id, "automod_rule_mod");
+    let person = Person::create(pool, &person_form).await?;
+    let community_form = CommunityInsertForm::new(
+      instance.id,
+      "automod_rule_community".into(),
+      "nada".to_owned(),
+      "pubkey".to_string(),
+    );
+    let community = Community::create(pool, &community_form).await?;
+
+    let spam_form = AutomodRuleInsertForm {
+      body_regex: Some("buy cheap".to_string()),
+      ..AutomodRuleInsertForm::new(
+        community.id,
+        person.id,
+        "spam".to_string(),
+        AutomodAction::Remove,
+      )
+    };
+    let spam = AutomodRule::create(pool, &spam_form).await?;
+    let new_account_form = AutomodRuleInsertForm {
+      max_author_age_days: Some(3),
+      message: Some("new account".to_string()),
+      ..AutomodRuleInsertForm::new(
+        community.id,
+        person.id,
+        "new accounts".to_string(),
+        AutomodAction::Report,
+      )
+    };
+    let new_account = AutomodRule::create(pool, &new_account_form).await?;
+    assert!(spam.enabled && spam.check_posts && spam.check_comments);
+
+    // Disabled rules are listed, but not applied
+    let disable_form = AutomodRuleUpdateForm {
+      enabled: Some(false),
+      body_regex: Some(None),
+      title_regex: Some(Some("cheap".to_string())),
+      ..Default::default()
+    };
+    let spam = AutomodRule::update(pool, spam.id, &disable_form).await?;
+    assert_eq!(None, spam.body_regex);
+    assert_eq!(Some("cheap".to_string()), spam.title_regex);
+
+    let ids = |rules: Vec<AutomodRule>| rules.into_iter().map(|r| r.id).collect::<Vec<_>>();
+    assert_eq!(
+      vec![spam.id, new_account.id],
+      ids(AutomodRule::list(pool, community.id).await?)
+    );
+    assert_eq!(
+      vec![new_account.id],
+      ids(AutomodRule::list_enabled(pool, community.id).await?)
+    );
+
+    AutomodRule::delete(pool, new_account.id).await?;
+    assert_eq!(
+      vec![spam.id],
+      ids(AutomodRule::list(pool, community.id).await?)
+    );
+
+    Community::delete(pool, community.id).await?;
+    assert!(AutomodRule::list(pool, community.id).await?.is_empty());
+    Person::delete(pool, person.id).await?;
+    Instance::delete(pool, instance.id).await?;
+
+    Ok(())
+  }
+}
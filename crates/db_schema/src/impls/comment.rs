@@ -1,7 +1,7 @@
 use crate::{
   diesel::{DecoratableTarget, OptionalExtension},
   newtypes::{CommentId, DbUrl, PersonId},
-  schema::{comment, comment_actions},
+  schema::{comment, comment_actions, comment_revision},
   source::comment::{
     Comment,
     CommentInsertForm,
@@ -33,14 +33,30 @@ impl Comment {
     for_creator_id: PersonId,
   ) -> Result<Vec<Self>, Error> {
     let conn = &mut get_conn(pool).await?;
-
-    diesel::update(comment::table.filter(comment::creator_id.eq(for_creator_id)))
-      .set((
-        comment::content.eq(DELETED_REPLACEMENT_TEXT),
-        comment::deleted.eq(true),
-        comment::updated.eq(Utc::now()),
-      ))
-      .get_results::<Self>(conn)
+    conn
+      .build_transaction()
+      .run(|conn| {
+        Box::pin(async move {
+          // Otherwise the previous versions could still be read from the edit history
+          let creator_comments = comment::table
+            .filter(comment::creator_id.eq(for_creator_id))
+            .select(comment::id);
+          diesel::delete(
+            comment_revision::table.filter(comment_revision::comment_id.eq_any(creator_comments)),
+          )
+          .execute(conn)
+          .await?;
+
+          diesel::update(comment::table.filter(comment::creator_id.eq(for_creator_id)))
+            .set((
+              comment::content.eq(DELETED_REPLACEMENT_TEXT),
+              comment::deleted.eq(true),
+              comment::updated.eq(Utc::now()),
+            ))
+            .get_results::<Self>(conn)
+            .await
+        }) as _
+      })
       .await
   }
 
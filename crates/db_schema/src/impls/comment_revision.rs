@@ -0,0 +1,33 @@
+use crate::{
+  newtypes::PersonId,
+  schema::comment_revision,
+  source::{
+    comment::Comment,
+    comment_revision::{CommentRevision, CommentRevisionInsertForm},
+  },
+  utils::{get_conn, DbPool},
+};
+use diesel::{insert_into, result::Error};
+use diesel_async::RunQueryDsl;
+
+impl CommentRevision {
+  /// Stores the previous version of an edited comment. Nothing is stored if the content didn't
+  /// change.
+  pub async fn record(
+    pool: &mut DbPool<'_>,
+    previous: &Comment,
+    current: &Comment,
+    editor_id: PersonId,
+  ) -> Result<Option<Self>, Error> {
+    if previous.content == current.content {
+      return Ok(None);
+    }
+    let form = CommentRevisionInsertForm::new(previous.id, editor_id, previous.content.clone());
+    let conn = &mut get_conn(pool).await?;
+    insert_into(comment_revision::table)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+      .map(Some)
+  }
+}
@@ -1,9 +1,18 @@
 use crate::{
   diesel::{DecoratableTarget, OptionalExtension},
-  newtypes::{CommunityId, DbUrl, PersonId},
-  schema::{community, community_actions, instance, post},
+  newtypes::{CommunityId, DbUrl, PersonContentCombinedId, PersonId},
+  schema::{
+    comment,
+    community,
+    community_actions,
+    instance,
+    person,
+    person_content_combined,
+    post,
+  },
   source::{
     actor_language::CommunityLanguage,
+    comment::Comment,
     community::{
       Community,
       CommunityFollower,
@@ -16,6 +25,7 @@ use crate::{
       CommunityPersonBanForm,
       CommunityUpdateForm,
     },
+    person::Person,
     post::Post,
   },
   traits::{ApubActor, Bannable, Crud, Followable, Joinable},
@@ -44,12 +54,13 @@ use diesel::{
   update,
   BoolExpressionMethods,
   ExpressionMethods,
+  JoinOnDsl,
   NullableExpressionMethods,
   QueryDsl,
   Queryable,
 };
 use diesel_async::RunQueryDsl;
-use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+use lemmy_utils::error::{LemmyError, LemmyErrorType, LemmyResult};
 
 #[async_trait]
 impl Crud for Community {
@@ -207,6 +218,77 @@ impl Community {
     Ok(())
   }
 
+  /// Lists posts and comments in the community, newest first. Each item consists of its id for
+  /// paging, the post, the comment if it is a comment, and the creator. With `before`, only items
+  /// which are older than the item with this id are listed. Deleted, removed and scheduled content
+  /// is excluded.
+  pub async fn list_outbox_content(
+    pool: &mut DbPool<'_>,
+    community_id: CommunityId,
+    limit: i64,
+    before: Option<PersonContentCombinedId>,
+  ) -> Result<Vec<(PersonContentCombinedId, Post, Option<Comment>, Person)>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    let mut query = person_content_combined::table
+      .left_join(comment::table.on(person_content_combined::comment_id.eq(comment::id.nullable())))
+      .inner_join(
+        post::table.on(
+          person_content_combined::post_id
+            .eq(post::id.nullable())
+            .or(comment::post_id.eq(post::id)),
+        ),
+      )
+      .inner_join(
+        person::table.on(
+          comment::creator_id.eq(person::id).or(
+            person_content_combined::post_id
+              .is_not_null()
+              .and(post::creator_id.eq(person::id)),
+          ),
+        ),
+      )
+      .filter(post::community_id.eq(community_id))
+      .filter(not(post::deleted.or(post::removed)))
+      .filter(post::scheduled_publish_time.is_null())
+      .filter(
+        comment::id
+          .is_null()
+          .or(not(comment::deleted.or(comment::removed))),
+      )
+      .into_boxed();
+
+    // Keyset pagination, so that reading old pages doesn't get slower with each page
+    if let Some(before) = before {
+      let published = person_content_combined::table
+        .find(before)
+        .select(person_content_combined::published)
+        .first::<DateTime<Utc>>(conn)
+        .await?;
+      query = query.filter(
+        person_content_combined::published.lt(published).or(
+          person_content_combined::published
+            .eq(published)
+            .and(person_content_combined::id.lt(before)),
+        ),
+      );
+    }
+
+    query
+      .select((
+        person_content_combined::id,
+        post::all_columns,
+        comment::all_columns.nullable(),
+        person::all_columns,
+      ))
+      .order_by((
+        person_content_combined::published.desc(),
+        person_content_combined::id.desc(),
+      ))
+      .limit(limit)
+      .load::<(PersonContentCombinedId, Post, Option<Comment>, Person)>(conn)
+      .await
+  }
+
   pub async fn get_random_community_id(
     pool: &mut DbPool<'_>,
     type_: &Option<ListingType>,
@@ -422,6 +504,57 @@ impl CommunityFollower {
     .await?;
     Ok(())
   }
+
+  /// Moves the community follows of a person to the account which they moved to. With
+  /// `only_local_communities`, follows of remote communities are left alone as these are migrated
+  /// by the community's instance. Otherwise they are transferred as pending, until the community
+  /// accepts the follow of the new account. Returns the ids of communities whose follow was
+  /// migrated.
+  pub async fn transfer_follows(
+    pool: &mut DbPool<'_>,
+    old_person_id: PersonId,
+    new_person_id: PersonId,
+    only_local_communities: bool,
+  ) -> LemmyResult<Vec<CommunityId>> {
+    let conn = &mut get_conn(pool).await?;
+    conn
+      .build_transaction()
+      .run(|conn| {
+        Box::pin(async move {
+          let mut query = community_actions::table
+            .inner_join(community::table)
+            .filter(community_actions::followed.is_not_null())
+            .filter(community_actions::person_id.eq(old_person_id))
+            .select((Self::as_select(), community::local))
+            .into_boxed();
+          if only_local_communities {
+            query = query.filter(community::local.eq(true));
+          }
+          let follows = query.load::<(Self, bool)>(conn).await?;
+
+          for (follow, community_local) in &follows {
+            let new_form = CommunityFollowerForm::new(follow.community_id, new_person_id);
+            let form = if *community_local {
+              CommunityFollowerForm {
+                state: Some(follow.state),
+                approver_id: follow.approver_id,
+                ..new_form
+              }
+            } else {
+              CommunityFollowerForm {
+                state: Some(CommunityFollowerState::Pending),
+                ..new_form
+              }
+            };
+            Self::follow(&mut conn.into(), &form).await?;
+            let form = CommunityFollowerForm::new(follow.community_id, old_person_id);
+            Self::unfollow(&mut conn.into(), &form).await?;
+          }
+          Ok::<_, LemmyError>(follows.into_iter().map(|(f, _)| f.community_id).collect())
+        }) as _
+      })
+      .await
+  }
 }
 
 impl Queryable<sql_types::Nullable<crate::schema::sql_types::CommunityFollowerState>, Pg>
@@ -537,7 +670,9 @@ impl ApubActor for Community {
 #[cfg(test)]
 mod tests {
   use crate::{
+    schema::community_actions,
     source::{
+      comment::{Comment, CommentInsertForm},
       community::{
         Community,
         CommunityFollower,
@@ -553,11 +688,15 @@ mod tests {
       instance::Instance,
       local_user::LocalUser,
       person::{Person, PersonInsertForm},
+      post::{Post, PostInsertForm, PostUpdateForm},
     },
     traits::{Bannable, Crud, Followable, Joinable},
-    utils::{build_db_pool_for_tests, uplete},
+    utils::{build_db_pool_for_tests, get_conn, uplete},
     CommunityVisibility,
+    DuplicateLinkPolicy,
   };
+  use diesel::{ExpressionMethods, QueryDsl};
+  use diesel_async::RunQueryDsl;
   use lemmy_utils::error::LemmyResult;
   use pretty_assertions::assert_eq;
   use serial_test::serial;
@@ -611,6 +750,8 @@ mod tests {
       instance_id: inserted_instance.id,
       visibility: CommunityVisibility::Public,
       random_number: inserted_community.random_number,
+      duplicate_link_policy: DuplicateLinkPolicy::Allow,
+      duplicate_link_window_days: 30,
     };
 
     let community_follower_form = CommunityFollowerForm {
@@ -731,4 +872,143 @@ mod tests {
 
     Ok(())
   }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_transfer_follows() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let instance = Instance::read_or_create(pool, "my_domain.tld".to_string()).await?;
+    let old_form = PersonInsertForm::test_form(instance.id, "old_account");
+    let old_person = Person::create(pool, &old_form).await?;
+    let new_form = PersonInsertForm::test_form(instance.id, "new_account");
+    let new_person = Person::create(pool, &new_form).await?;
+
+    let community_form = CommunityInsertForm::new(
+      instance.id,
+      "moving".into(),
+      "nada".to_owned(),
+      "pubkey".to_string(),
+    );
+    let community = Community::create(pool, &community_form).await?;
+    let follow_form = CommunityFollowerForm {
+      state: Some(CommunityFollowerState::Accepted),
+      ..CommunityFollowerForm::new(community.id, old_person.id)
+    };
+    CommunityFollower::follow(pool, &follow_form).await?;
+    let remote_community_form = CommunityInsertForm {
+      local: Some(false),
+      ..CommunityInsertForm::new(
+        instance.id,
+        "moving_remote".into(),
+        "nada".to_owned(),
+        "pubkey".to_string(),
+      )
+    };
+    let remote_community = Community::create(pool, &remote_community_form).await?;
+    let follow_form = CommunityFollowerForm {
+      state: Some(CommunityFollowerState::Accepted),
+      ..CommunityFollowerForm::new(remote_community.id, old_person.id)
+    };
+    CommunityFollower::follow(pool, &follow_form).await?;
+
+    let moved =
+      CommunityFollower::transfer_follows(pool, old_person.id, new_person.id, true).await?;
+    assert_eq!(vec![community.id], moved);
+    let moved =
+      CommunityFollower::transfer_follows(pool, old_person.id, new_person.id, false).await?;
+    assert_eq!(vec![remote_community.id], moved);
+
+    // The follows now belong to the new account, nothing is left to transfer
+    let moved =
+      CommunityFollower::transfer_follows(pool, old_person.id, new_person.id, false).await?;
+    assert!(moved.is_empty());
+    // The remote community needs to accept the follow of the new account first
+    let states = community_actions::table
+      .filter(community_actions::person_id.eq(new_person.id))
+      .order_by(community_actions::community_id)
+      .select(community_actions::follow_state)
+      .load::<Option<CommunityFollowerState>>(&mut get_conn(pool).await?)
+      .await?;
+    assert_eq!(
+      vec![
+        Some(CommunityFollowerState::Accepted),
+        Some(CommunityFollowerState::Pending)
+      ],
+      states
+    );
+    let unfollow_form = CommunityFollowerForm::new(community.id, new_person.id);
+    let unfollowed = CommunityFollower::unfollow(pool, &unfollow_form).await?;
+    assert_eq!(uplete::Count::only_deleted(1), unfollowed);
+
+    Community::delete(pool, community.id).await?;
+    Community::delete(pool, remote_community.id).await?;
+    Person::delete(pool, old_person.id).await?;
+    Person::delete(pool, new_person.id).await?;
+    Instance::delete(pool, instance.id).await?;
+    Ok(())
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_list_outbox_content() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+    let instance = Instance::read_or_create(pool, "my_domain.tld".to_string()).await?;
+
+    let post_creator =
+      Person::create(pool, &PersonInsertForm::test_form(instance.id, "op")).await?;
+    let commenter =
+      Person::create(pool, &PersonInsertForm::test_form(instance.id, "commenter")).await?;
+    let community_form = CommunityInsertForm::new(
+      instance.id,
+      "outbox_content".into(),
+      "nada".to_owned(),
+      "pubkey".to_string(),
+    );
+    let community = Community::create(pool, &community_form).await?;
+
+    let post_form = PostInsertForm::new("post".into(), post_creator.id, community.id);
+    let post = Post::create(pool, &post_form).await?;
+    let post_form = PostInsertForm::new("removed".into(), post_creator.id, community.id);
+    let removed_post = Post::create(pool, &post_form).await?;
+    let post_form = PostUpdateForm {
+      removed: Some(true),
+      ..Default::default()
+    };
+    Post::update(pool, removed_post.id, &post_form).await?;
+    let comment_form = CommentInsertForm::new(commenter.id, post.id, "comment".into());
+    let comment = Comment::create(pool, &comment_form, None).await?;
+
+    let items = Community::list_outbox_content(pool, community.id, 10, None).await?;
+    // Newest first, each with its own creator
+    let ids = items
+      .iter()
+      .map(|(_, post, comment, creator)| (post.id, comment.as_ref().map(|c| c.id), creator.id))
+      .collect::<Vec<_>>();
+    assert_eq!(
+      vec![
+        (post.id, Some(comment.id), commenter.id),
+        (post.id, None, post_creator.id)
+      ],
+      ids
+    );
+
+    // The next page starts after the last item of the previous one
+    let first_page = Community::list_outbox_content(pool, community.id, 1, None).await?;
+    let before = first_page.iter().map(|(id, ..)| *id).next_back();
+    let items = Community::list_outbox_content(pool, community.id, 10, before).await?;
+    let ids = items
+      .iter()
+      .map(|(_, post, comment, _)| (post.id, comment.as_ref().map(|c| c.id)))
+      .collect::<Vec<_>>();
+    assert_eq!(vec![(post.id, None)], ids);
+
+    Community::delete(pool, community.id).await?;
+    Person::delete(pool, post_creator.id).await?;
+    Person::delete(pool, commenter.id).await?;
+    Instance::delete(pool, instance.id).await?;
+    Ok(())
+  }
 }
@@ -0,0 +1,222 @@
+use crate::{
+  newtypes::{CommunityId, DbUrl, InstanceId},
+  schema::community_backfill,
+  source::community_backfill::{CommunityBackfill, CommunityBackfillInsertForm},
+  utils::{get_conn, now, DbPool},
+};
+use chrono::{TimeDelta, Utc};
+use diesel::{delete, insert_into, result::Error, ExpressionMethods, QueryDsl};
+use diesel_async::RunQueryDsl;
+
+/// Backfills are given up after this many failed fetches in a row.
+const MAX_FAILURES: i32 = 3;
+
+impl CommunityBackfill {
+  /// Queues the backfill of a community. Does nothing if the community was backfilled before and
+  /// is still in the queue.
+  pub async fn create(
+    pool: &mut DbPool<'_>,
+    form: &CommunityBackfillInsertForm,
+  ) -> Result<(), Error> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(community_backfill::table)
+      .values(form)
+      .on_conflict_do_nothing()
+      .execute(conn)
+      .await?;
+    Ok(())
+  }
+
+  /// Takes the backfills which are due, at most one per remote instance. All backfills of these
+  /// instances are postponed by `interval`, which limits the rate of requests to each instance.
+  pub async fn claim_due(pool: &mut DbPool<'_>, interval: TimeDelta) -> Result<Vec<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    conn
+      .build_transaction()
+      .run(|conn| {
+        Box::pin(async move {
+          let mut due = community_backfill::table
+            .filter(community_backfill::next_fetch.le(now()))
+            .order_by((
+              community_backfill::instance_id,
+              community_backfill::published,
+            ))
+            .for_update()
+            .skip_locked()
+            .load::<Self>(conn)
+            .await?;
+          due.dedup_by_key(|b| b.instance_id);
+
+          let instance_ids = due
+            .iter()
+            .map(|b| b.instance_id)
+            .collect::<Vec<InstanceId>>();
+          diesel::update(
+            community_backfill::table.filter(community_backfill::instance_id.eq_any(instance_ids)),
+          )
+          .set(community_backfill::next_fetch.eq(Utc::now() + interval))
+          .execute(conn)
+          .await?;
+          Ok(due)
+        }) as _
+      })
+      .await
+  }
+
+  /// Stores the outbox page and replies collections to fetch next, after a fetch was successful.
+  /// The backfill is finished if there is nothing left to fetch, or if `max_pages` were fetched.
+  pub async fn advance(
+    pool: &mut DbPool<'_>,
+    backfill: &Self,
+    next_page: Option<DbUrl>,
+    pending_replies: Vec<DbUrl>,
+    max_pages: i32,
+  ) -> Result<(), Error> {
+    let pages_fetched = backfill.pages_fetched + 1;
+    if (next_page.is_none() && pending_replies.is_empty()) || pages_fetched >= max_pages {
+      return Self::delete(pool, backfill.community_id).await;
+    }
+    let pending_replies = pending_replies
+      .into_iter()
+      .map(Some)
+      .collect::<Vec<Option<DbUrl>>>();
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(community_backfill::table.find(backfill.community_id))
+      .set((
+        community_backfill::next_page.eq(next_page),
+        community_backfill::pending_replies.eq(pending_replies),
+        community_backfill::pages_fetched.eq(pages_fetched),
+        community_backfill::failures.eq(0),
+      ))
+      .execute(conn)
+      .await?;
+    Ok(())
+  }
+
+  /// Counts a failed fetch. The same page is attempted again later, unless it failed too often.
+  pub async fn fail(pool: &mut DbPool<'_>, backfill: &Self) -> Result<(), Error> {
+    let failures = backfill.failures + 1;
+    if failures >= MAX_FAILURES {
+      return Self::delete(pool, backfill.community_id).await;
+    }
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(community_backfill::table.find(backfill.community_id))
+      .set(community_backfill::failures.eq(failures))
+      .execute(conn)
+      .await?;
+    Ok(())
+  }
+
+  pub async fn delete(pool: &mut DbPool<'_>, community_id: CommunityId) -> Result<(), Error> {
+    let conn = &mut get_conn(pool).await?;
+    delete(community_backfill::table.find(community_id))
+      .execute(conn)
+      .await?;
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+
+  use super::*;
+  use crate::{
+    source::{
+      community::{Community, CommunityInsertForm},
+      instance::Instance,
+    },
+    traits::Crud,
+    utils::build_db_pool_for_tests,
+  };
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+  use url::Url;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_community_backfill() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+    let instance = Instance::read_or_create(pool, "backfill.tld".to_string()).await?;
+
+    let mut communities = vec![];
+    for name in ["backfill_1", "backfill_2"] {
+      let form = CommunityInsertForm::new(
+        instance.id,
+        name.into(),
+        "nada".to_owned(),
+        "pubkey".to_string(),
+      );
+      let community = Community::create(pool, &form).await?;
+      let form = CommunityBackfillInsertForm::new(community.id, instance.id);
+      CommunityBackfill::create(pool, &form).await?;
+      // Queuing the same community again has no effect
+      CommunityBackfill::create(pool, &form).await?;
+      communities.push(community.id);
+    }
+
+    // Only one backfill per instance is handed out, and the others wait for the interval
+    let claimed = CommunityBackfill::claim_due(pool, TimeDelta::minutes(1)).await?;
+    let claimed_ids = claimed
+      .iter()
+      .map(|b| b.community_id)
+      .collect::<Vec<CommunityId>>();
+    assert_eq!(communities.get(..1), Some(claimed_ids.as_slice()));
+    assert!(CommunityBackfill::claim_due(pool, TimeDelta::minutes(1))
+      .await?
+      .is_empty());
+
+    let next_page: DbUrl = Url::parse("https://backfill.tld/c/backfill_1/outbox?page=2")?.into();
+    for backfill in claimed {
+      CommunityBackfill::advance(pool, &backfill, Some(next_page.clone()), vec![], 2).await?;
+      let backfill = CommunityBackfill {
+        pages_fetched: 1,
+        ..backfill
+      };
+      // The page limit was reached
+      CommunityBackfill::advance(pool, &backfill, Some(next_page.clone()), vec![], 2).await?;
+    }
+
+    Instance::delete(pool, instance.id).await?;
+    Ok(())
+  }
+  #[tokio::test]
+  #[serial]
+  async fn test_community_backfill_replies() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+    let instance = Instance::read_or_create(pool, "backfill-replies.tld".to_string()).await?;
+    let form = CommunityInsertForm::new(
+      instance.id,
+      "backfill_replies".into(),
+      "nada".to_owned(),
+      "pubkey".to_string(),
+    );
+    let community = Community::create(pool, &form).await?;
+    let form = CommunityBackfillInsertForm::new(community.id, instance.id);
+    CommunityBackfill::create(pool, &form).await?;
+
+    // Without a next page, the backfill continues as long as there are replies to fetch
+    let backfill = claim_one(pool).await?;
+    let replies: DbUrl = Url::parse("https://backfill-replies.tld/post/1/replies")?.into();
+    CommunityBackfill::advance(pool, &backfill, None, vec![replies.clone()], 10).await?;
+    let backfill = claim_one(pool).await?;
+    assert_eq!(None, backfill.next_page);
+    assert_eq!(vec![Some(replies)], backfill.pending_replies);
+
+    CommunityBackfill::advance(pool, &backfill, None, vec![], 10).await?;
+    assert!(CommunityBackfill::claim_due(pool, TimeDelta::zero())
+      .await?
+      .is_empty());
+
+    Instance::delete(pool, instance.id).await?;
+    Ok(())
+  }
+
+  async fn claim_one(pool: &mut DbPool<'_>) -> LemmyResult<CommunityBackfill> {
+    let mut claimed = CommunityBackfill::claim_due(pool, TimeDelta::zero()).await?;
+    assert_eq!(1, claimed.len());
+    Ok(claimed.remove(0))
+  }
+}
@@ -0,0 +1,133 @@
+use crate::{
+  newtypes::{DraftId, LocalUserId},
+  schema::draft,
+  source::draft::{Draft, DraftInsertForm, DraftUpdateForm},
+  traits::Crud,
+  utils::{functions::coalesce, get_conn, DbPool},
+};
+use diesel::{dsl::insert_into, result::Error, ExpressionMethods, QueryDsl};
+use diesel_async::RunQueryDsl;
+
+#[async_trait]
+impl Crud for Draft {
+  type InsertForm = DraftInsertForm;
+  type UpdateForm = DraftUpdateForm;
+  type IdType = DraftId;
+
+  async fn create(pool: &mut DbPool<'_>, form: &Self::InsertForm) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(draft::table)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+
+  async fn update(
+    pool: &mut DbPool<'_>,
+    draft_id: DraftId,
+    form: &Self::UpdateForm,
+  ) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(draft::table.find(draft_id))
+      .set(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+}
+
+impl Draft {
+  /// Lists the drafts of a user, most recently changed first.
+  pub async fn list(pool: &mut DbPool<'_>, local_user_id: LocalUserId) -> Result<Vec<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    draft::table
+      .filter(draft::local_user_id.eq(local_user_id))
+      .order_by(coalesce(draft::updated, draft::published).desc())
+      .then_order_by(draft::id.desc())
+      .load::<Self>(conn)
+      .await
+  }
+
+  pub async fn count(pool: &mut DbPool<'_>, local_user_id: LocalUserId) -> Result<i64, Error> {
+    let conn = &mut get_conn(pool).await?;
+    draft::table
+      .filter(draft::local_user_id.eq(local_user_id))
+      .count()
+      .get_result(conn)
+      .await
+  }
+}
+
+#[cfg(test)]
+mod tests {
+
+  use super::*;
+  use crate::{
+    source::{
+      community::{Community, CommunityInsertForm},
+      instance::Instance,
+      local_user::{LocalUser, LocalUserInsertForm},
+      person::{Person, PersonInsertForm},
+    },
+    utils::build_db_pool_for_tests,
+  };
+  use chrono::Utc;
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_drafts() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let instance = Instance::read_or_create(pool, "my_domain.tld".to_string()).await?;
+    let person_form = PersonInsertForm::test_form(instance.id, "draft_user");
+    let person = Person::create(pool, &person_form).await?;
+    let user_form = LocalUserInsertForm::test_form(person.id);
+    let user = LocalUser::create(pool, &user_form, vec![]).await?;
+    let community_form = CommunityInsertForm::new(
+      instance.id,
+      "draft_community".into(),
+      "nada".to_owned(),
+      "pubkey".to_string(),
+    );
+    let community = Community::create(pool, &community_form).await?;
+
+    let form = DraftInsertForm {
+      community_id: Some(community.id),
+      name: Some("unfinished".to_string()),
+      ..DraftInsertForm::new(user.id)
+    };
+    let first = Draft::create(pool, &form).await?;
+    let form = DraftInsertForm {
+      body: Some("more text".to_string()),
+      ..DraftInsertForm::new(user.id)
+    };
+    let second = Draft::create(pool, &form).await?;
+    assert_eq!(2, Draft::count(pool, user.id).await?);
+    assert_eq!(
+      vec![second.clone(), first.clone()],
+      Draft::list(pool, user.id).await?
+    );
+
+    // editing a draft moves it to the top
+    let form = DraftUpdateForm {
+      name: Some(Some("almost done".to_string())),
+      updated: Some(Some(Utc::now())),
+      ..Default::default()
+    };
+    let first = Draft::update(pool, first.id, &form).await?;
+    assert_eq!(Some("almost done".to_string()), first.name);
+    assert_eq!(Some(community.id), first.community_id);
+    assert_eq!(vec![first, second], Draft::list(pool, user.id).await?);
+
+    // drafts are removed together with the community
+    Community::delete(pool, community.id).await?;
+    assert_eq!(1, Draft::count(pool, user.id).await?);
+
+    Person::delete(pool, person.id).await?;
+    Instance::delete(pool, instance.id).await?;
+    Ok(())
+  }
+}
@@ -0,0 +1,98 @@
+use crate::{
+  newtypes::InstanceId,
+  schema::federation_limit,
+  source::federation_limit::{FederationLimit, FederationLimitForm},
+  utils::{get_conn, DbPool},
+};
+use diesel::{
+  delete,
+  dsl::insert_into,
+  result::Error,
+  ExpressionMethods,
+  OptionalExtension,
+  QueryDsl,
+};
+use diesel_async::RunQueryDsl;
+
+impl FederationLimit {
+  /// Sets the restrictions for an instance, replacing any previous ones.
+  pub async fn upsert(pool: &mut DbPool<'_>, form: &FederationLimitForm) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(federation_limit::table)
+      .values(form)
+      .on_conflict(federation_limit::instance_id)
+      .do_update()
+      .set(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+  pub async fn delete(pool: &mut DbPool<'_>, instance_id_: InstanceId) -> Result<usize, Error> {
+    let conn = &mut get_conn(pool).await?;
+    delete(federation_limit::table.filter(federation_limit::instance_id.eq(instance_id_)))
+      .execute(conn)
+      .await
+  }
+  pub async fn read(
+    pool: &mut DbPool<'_>,
+    instance_id_: InstanceId,
+  ) -> Result<Option<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    federation_limit::table
+      .find(instance_id_)
+      .first(conn)
+      .await
+      .optional()
+  }
+  pub async fn read_all(pool: &mut DbPool<'_>) -> Result<Vec<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    federation_limit::table.load(conn).await
+  }
+}
+
+#[cfg(test)]
+mod tests {
+
+  use super::*;
+  use crate::{source::instance::Instance, utils::build_db_pool_for_tests};
+  use chrono::Utc;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_federation_limit() -> Result<(), Error> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+    let instance = Instance::read_or_create(pool, "limited.xyz".to_string()).await?;
+
+    let form = FederationLimitForm {
+      instance_id: instance.id,
+      limited: true,
+      ..Default::default()
+    };
+    FederationLimit::upsert(pool, &form).await?;
+
+    // Changing the limits replaces the existing row
+    let form = FederationLimitForm {
+      force_nsfw: true,
+      updated: Some(Utc::now()),
+      ..form
+    };
+    let limit = FederationLimit::upsert(pool, &form).await?;
+    assert!(limit.limited);
+    assert!(limit.force_nsfw);
+    assert!(!limit.reject_media);
+    assert_eq!(
+      Some(&limit),
+      FederationLimit::read(pool, instance.id).await?.as_ref()
+    );
+    assert_eq!(vec![limit], FederationLimit::read_all(pool).await?);
+
+    FederationLimit::delete(pool, instance.id).await?;
+    assert!(FederationLimit::read(pool, instance.id).await?.is_none());
+
+    Instance::delete(pool, instance.id).await?;
+
+    Ok(())
+  }
+}
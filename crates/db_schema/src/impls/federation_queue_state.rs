@@ -1,9 +1,11 @@
 use crate::{
-  newtypes::InstanceId,
+  newtypes::{ActivityId, InstanceId},
+  schema::{federation_queue_state, sent_activity},
   source::federation_queue_state::FederationQueueState,
   utils::{get_conn, DbPool},
 };
-use diesel::{prelude::*, result::Error};
+use chrono::{DateTime, Utc};
+use diesel::{insert_into, prelude::*, result::Error};
 use diesel_async::RunQueryDsl;
 
 impl FederationQueueState {
@@ -27,20 +29,207 @@ impl FederationQueueState {
           last_retry: None,
           last_successful_id: None, // this value is set to the most current id for new instances
           last_successful_published_time: None,
+          paused: false,
+          reset_backoff_requested: false,
+          skip_backlog_requested: false,
         }),
     )
   }
+  /// Saves the progress of the federation worker. The admin controls are left as they are, so that
+  /// changes made while the worker is running don't get lost.
   pub async fn upsert(pool: &mut DbPool<'_>, state: &FederationQueueState) -> Result<(), Error> {
-    use crate::schema::federation_queue_state::dsl::{federation_queue_state, instance_id};
+    use crate::schema::federation_queue_state::dsl::{
+      fail_count,
+      federation_queue_state,
+      instance_id,
+      last_retry,
+      last_successful_id,
+      last_successful_published_time,
+    };
     let conn = &mut get_conn(pool).await?;
 
     state
       .insert_into(federation_queue_state)
       .on_conflict(instance_id)
       .do_update()
-      .set(state)
+      .set((
+        last_successful_id.eq(state.last_successful_id),
+        last_successful_published_time.eq(state.last_successful_published_time),
+        fail_count.eq(state.fail_count),
+        last_retry.eq(state.last_retry),
+      ))
+      .execute(conn)
+      .await?;
+    Ok(())
+  }
+
+  /// Pauses or resumes sending activities to the instance.
+  pub async fn set_paused(
+    pool: &mut DbPool<'_>,
+    instance_id: InstanceId,
+    paused: bool,
+  ) -> Result<(), Error> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(federation_queue_state::table)
+      .values((
+        federation_queue_state::instance_id.eq(instance_id),
+        federation_queue_state::fail_count.eq(0),
+        federation_queue_state::paused.eq(paused),
+      ))
+      .on_conflict(federation_queue_state::instance_id)
+      .do_update()
+      .set(federation_queue_state::paused.eq(paused))
+      .execute(conn)
+      .await?;
+    Ok(())
+  }
+
+  /// Requests to retry sending to the instance immediately, instead of waiting for the backoff.
+  pub async fn request_reset_backoff(
+    pool: &mut DbPool<'_>,
+    instance_id: InstanceId,
+  ) -> Result<(), Error> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(federation_queue_state::table)
+      .values((
+        federation_queue_state::instance_id.eq(instance_id),
+        federation_queue_state::fail_count.eq(0),
+        federation_queue_state::reset_backoff_requested.eq(true),
+      ))
+      .on_conflict(federation_queue_state::instance_id)
+      .do_update()
+      .set(federation_queue_state::reset_backoff_requested.eq(true))
+      .execute(conn)
+      .await?;
+    Ok(())
+  }
+
+  /// Requests to skip all activities which were not sent to the instance yet.
+  pub async fn request_skip_backlog(
+    pool: &mut DbPool<'_>,
+    instance_id: InstanceId,
+  ) -> Result<(), Error> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(federation_queue_state::table)
+      .values((
+        federation_queue_state::instance_id.eq(instance_id),
+        federation_queue_state::fail_count.eq(0),
+        federation_queue_state::skip_backlog_requested.eq(true),
+      ))
+      .on_conflict(federation_queue_state::instance_id)
+      .do_update()
+      .set(federation_queue_state::skip_backlog_requested.eq(true))
+      .execute(conn)
+      .await?;
+    Ok(())
+  }
+
+  /// Reads the queues with a reset or skip requested by an admin.
+  pub async fn list_requested(pool: &mut DbPool<'_>) -> Result<Vec<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    federation_queue_state::table
+      .filter(
+        federation_queue_state::reset_backoff_requested
+          .or(federation_queue_state::skip_backlog_requested),
+      )
+      .select(Self::as_select())
+      .load(conn)
+      .await
+  }
+
+  /// Applies the reset or skip requested for the queue. This must only be called while no
+  /// federation worker is running for the instance, as it would overwrite the changes.
+  pub async fn apply_requested(pool: &mut DbPool<'_>, state: &Self) -> Result<(), Error> {
+    let conn = &mut get_conn(pool).await?;
+    // Skipping the backlog also skips the activity which failed, so the backoff is reset either way
+    diesel::update(federation_queue_state::table.find(state.instance_id))
+      .set((
+        federation_queue_state::fail_count.eq(0),
+        federation_queue_state::last_retry.eq(None::<DateTime<Utc>>),
+        federation_queue_state::reset_backoff_requested.eq(false),
+      ))
       .execute(conn)
       .await?;
+    if state.skip_backlog_requested {
+      let latest = sent_activity::table
+        .select((sent_activity::id, sent_activity::published))
+        .order_by(sent_activity::id.desc())
+        .first::<(ActivityId, DateTime<Utc>)>(conn)
+        .await
+        .optional()?;
+      diesel::update(federation_queue_state::table.find(state.instance_id))
+        .set((
+          federation_queue_state::last_successful_id.eq(latest.map(|l| l.0)),
+          federation_queue_state::last_successful_published_time.eq(latest.map(|l| l.1)),
+          federation_queue_state::skip_backlog_requested.eq(false),
+        ))
+        .execute(conn)
+        .await?;
+    }
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+
+  use super::*;
+  use crate::{source::instance::Instance, utils::build_db_pool_for_tests};
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_federation_queue_requests() -> Result<(), Error> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+    let instance = Instance::read_or_create(pool, "queue.tld".to_string()).await?;
+
+    let state = FederationQueueState {
+      fail_count: 5,
+      last_retry: Some(Utc::now()),
+      ..FederationQueueState::load(pool, instance.id).await?
+    };
+    FederationQueueState::upsert(pool, &state).await?;
+    FederationQueueState::set_paused(pool, instance.id, true).await?;
+    FederationQueueState::request_reset_backoff(pool, instance.id).await?;
+
+    // Progress saved by the worker doesn't overwrite the admin controls
+    FederationQueueState::upsert(pool, &state).await?;
+    let requested = FederationQueueState::list_requested(pool).await?;
+    assert_eq!(1, requested.len());
+    let state = FederationQueueState::load(pool, instance.id).await?;
+    assert!(state.paused);
+    assert!(state.reset_backoff_requested);
+    assert!(!state.skip_backlog_requested);
+
+    FederationQueueState::apply_requested(pool, &state).await?;
+    let state = FederationQueueState::load(pool, instance.id).await?;
+    assert_eq!(0, state.fail_count);
+    assert_eq!(None, state.last_retry);
+    assert!(state.paused);
+    assert!(FederationQueueState::list_requested(pool).await?.is_empty());
+
+    // The admin controls also work for instances which were never sent to
+    let new_instance = Instance::read_or_create(pool, "queue-new.tld".to_string()).await?;
+    FederationQueueState::set_paused(pool, new_instance.id, true).await?;
+    FederationQueueState::request_skip_backlog(pool, new_instance.id).await?;
+    let state = FederationQueueState::load(pool, new_instance.id).await?;
+    assert!(state.paused);
+    assert!(state.skip_backlog_requested);
+    assert_eq!(0, state.fail_count);
+
+    // The queue state doesn't cascade on delete
+    let instance_ids = [instance.id, new_instance.id];
+    diesel::delete(
+      federation_queue_state::table
+        .filter(federation_queue_state::instance_id.eq_any(instance_ids)),
+    )
+    .execute(&mut get_conn(pool).await?)
+    .await?;
+    Instance::delete(pool, instance.id).await?;
+    Instance::delete(pool, new_instance.id).await?;
+
     Ok(())
   }
 }
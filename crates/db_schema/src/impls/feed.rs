@@ -0,0 +1,171 @@
+use crate::{
+  newtypes::{CommunityId, FeedId, PersonId},
+  schema::{feed, feed_community},
+  source::feed::{Feed, FeedCommunity, FeedInsertForm, FeedUpdateForm},
+  traits::Crud,
+  utils::{get_conn, DbPool},
+};
+use diesel::{delete, insert_into, result::Error, ExpressionMethods, OptionalExtension, QueryDsl};
+use diesel_async::RunQueryDsl;
+
+#[async_trait]
+impl Crud for Feed {
+  type InsertForm = FeedInsertForm;
+  type UpdateForm = FeedUpdateForm;
+  type IdType = FeedId;
+
+  async fn create(pool: &mut DbPool<'_>, form: &Self::InsertForm) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(feed::table)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+
+  async fn update(
+    pool: &mut DbPool<'_>,
+    feed_id: FeedId,
+    form: &Self::UpdateForm,
+  ) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(feed::table.find(feed_id))
+      .set(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+}
+
+impl Feed {
+  /// Feed names are unique per creator.
+  pub async fn read_from_name(
+    pool: &mut DbPool<'_>,
+    creator_id: PersonId,
+    name: &str,
+  ) -> Result<Option<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    feed::table
+      .filter(feed::creator_id.eq(creator_id))
+      .filter(feed::name.eq(name))
+      .first::<Self>(conn)
+      .await
+      .optional()
+  }
+
+  /// Replaces the communities which are part of the feed.
+  pub async fn set_communities(
+    pool: &mut DbPool<'_>,
+    feed_id: FeedId,
+    community_ids: &[CommunityId],
+  ) -> Result<(), Error> {
+    let conn = &mut get_conn(pool).await?;
+    let forms = community_ids
+      .iter()
+      .map(|&community_id| FeedCommunity {
+        feed_id,
+        community_id,
+      })
+      .collect::<Vec<_>>();
+    conn
+      .build_transaction()
+      .run(|conn| {
+        Box::pin(async move {
+          delete(feed_community::table.filter(feed_community::feed_id.eq(feed_id)))
+            .execute(conn)
+            .await?;
+          insert_into(feed_community::table)
+            .values(forms)
+            .on_conflict_do_nothing()
+            .execute(conn)
+            .await?;
+          Ok::<_, Error>(())
+        }) as _
+      })
+      .await
+  }
+
+  pub async fn read_community_ids(
+    pool: &mut DbPool<'_>,
+    feed_id: FeedId,
+  ) -> Result<Vec<CommunityId>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    feed_community::table
+      .filter(feed_community::feed_id.eq(feed_id))
+      .order_by(feed_community::community_id)
+      .select(feed_community::community_id)
+      .load(conn)
+      .await
+  }
+}
+
+#[cfg(test)]
+mod tests {
+
+  use super::*;
+  use crate::{
+    source::{
+      community::{Community, CommunityInsertForm},
+      instance::Instance,
+      person::{Person, PersonInsertForm},
+    },
+    utils::build_db_pool_for_tests,
+  };
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_feed_communities() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let instance = Instance::read_or_create(pool, "my_domain.tld".to_string()).await?;
+    let person_form = PersonInsertForm::test_form(instance.id, "feed_creator");
+    let person = Person::create(pool, &person_form).await?;
+    let community_form = CommunityInsertForm::new(
+      instance.id,
+      "feed_community_1".into(),
+      "nada".to_owned(),
+      "pubkey".to_string(),
+    );
+    let community = Community::create(pool, &community_form).await?;
+    let other_community_form = CommunityInsertForm::new(
+      instance.id,
+      "feed_community_2".into(),
+      "nada".to_owned(),
+      "pubkey".to_string(),
+    );
+    let other_community = Community::create(pool, &other_community_form).await?;
+
+    let form = FeedInsertForm::new(person.id, "news".to_string());
+    let feed = Feed::create(pool, &form).await?;
+    assert!(!feed.public);
+    // names are unique per creator
+    assert!(Feed::create(pool, &form).await.is_err());
+    assert_eq!(
+      Some(feed.clone()),
+      Feed::read_from_name(pool, person.id, "news").await?
+    );
+
+    Feed::set_communities(pool, feed.id, &[community.id, other_community.id]).await?;
+    assert_eq!(
+      vec![community.id, other_community.id],
+      Feed::read_community_ids(pool, feed.id).await?
+    );
+
+    Feed::set_communities(pool, feed.id, &[other_community.id]).await?;
+    assert_eq!(
+      vec![other_community.id],
+      Feed::read_community_ids(pool, feed.id).await?
+    );
+
+    // deleting a community removes it from the feed
+    Community::delete(pool, other_community.id).await?;
+    assert!(Feed::read_community_ids(pool, feed.id).await?.is_empty());
+
+    Community::delete(pool, community.id).await?;
+    Person::delete(pool, person.id).await?;
+    Instance::delete(pool, instance.id).await?;
+    Ok(())
+  }
+}
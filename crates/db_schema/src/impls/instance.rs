@@ -4,12 +4,14 @@ use crate::{
   schema::{
     federation_allowlist,
     federation_blocklist,
+    federation_limit,
     federation_queue_state,
     instance,
     local_site,
     site,
   },
   source::{
+    federation_limit::FederationLimit,
     federation_queue_state::FederationQueueState,
     instance::{Instance, InstanceForm},
   },
@@ -126,13 +128,14 @@ impl Instance {
       .await
   }
 
-  /// returns a list of all instances, each with a flag of whether the instance is allowed or not
-  /// and dead or not ordered by id
+  /// returns a list of all instances, each with a flag of whether the instance is allowed or not,
+  /// dead or not and paused by an admin or not, ordered by id
   pub async fn read_federated_with_blocked_and_dead(
     pool: &mut DbPool<'_>,
-  ) -> Result<Vec<(Self, bool, bool)>, Error> {
+  ) -> Result<Vec<(Self, bool, bool, bool)>, Error> {
     let conn = &mut get_conn(pool).await?;
     let is_dead_expr = coalesce(instance::updated, instance::published).lt(now() - 3.days());
+    let is_paused_expr = coalesce(federation_queue_state::paused.nullable(), false);
     // this needs to be done in two steps because the meaning of the "blocked" column depends on the
     // existence of any value at all in the allowlist. (so a normal join wouldn't work)
     let use_allowlist = federation_allowlist::table
@@ -142,32 +145,46 @@ impl Instance {
     if use_allowlist {
       instance::table
         .left_join(federation_allowlist::table)
+        .left_join(federation_queue_state::table)
         .select((
           Self::as_select(),
           federation_allowlist::instance_id.nullable().is_not_null(),
           is_dead_expr,
+          is_paused_expr,
         ))
         .order_by(instance::id)
-        .get_results::<(Self, bool, bool)>(conn)
+        .get_results::<(Self, bool, bool, bool)>(conn)
         .await
     } else {
       instance::table
         .left_join(federation_blocklist::table)
+        .left_join(federation_queue_state::table)
         .select((
           Self::as_select(),
           federation_blocklist::instance_id.nullable().is_null(),
           is_dead_expr,
+          is_paused_expr,
         ))
         .order_by(instance::id)
-        .get_results::<(Self, bool, bool)>(conn)
+        .get_results::<(Self, bool, bool, bool)>(conn)
         .await
     }
   }
 
-  /// returns (instance, blocked, allowed, fed queue state) tuples
+  /// returns (instance, fed queue state, blocked, allowed, limits) tuples
+  #[allow(clippy::type_complexity)]
   pub async fn read_all_with_fed_state(
     pool: &mut DbPool<'_>,
-  ) -> Result<Vec<(Self, Option<FederationQueueState>, bool, bool)>, Error> {
+  ) -> Result<
+    Vec<(
+      Self,
+      Option<FederationQueueState>,
+      bool,
+      bool,
+      Option<FederationLimit>,
+    )>,
+    Error,
+  > {
     let conn = &mut get_conn(pool).await?;
     instance::table
       // omit instance representing the local site
@@ -176,11 +193,13 @@ impl Instance {
       .left_join(federation_blocklist::table)
       .left_join(federation_allowlist::table)
       .left_join(federation_queue_state::table)
+      .left_join(federation_limit::table)
       .select((
         Self::as_select(),
         Option::<FederationQueueState>::as_select(),
         federation_blocklist::instance_id.nullable().is_not_null(),
         federation_allowlist::instance_id.nullable().is_not_null(),
+        Option::<FederationLimit>::as_select(),
       ))
       .get_results(conn)
       .await
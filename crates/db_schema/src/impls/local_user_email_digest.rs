@@ -0,0 +1,78 @@
+use crate::{
+  diesel::OptionalExtension,
+  newtypes::LocalUserId,
+  schema::local_user_email_digest,
+  source::local_user_email_digest::LocalUserEmailDigest,
+  utils::{get_conn, now, DbPool},
+  EmailDigestMode,
+};
+use chrono::TimeDelta;
+use diesel::{dsl::insert_into, result::Error, ExpressionMethods, QueryDsl};
+use diesel_async::RunQueryDsl;
+
+impl LocalUserEmailDigest {
+  /// The digest mode of the user, which is instant if they never changed it.
+  pub async fn read_mode(
+    pool: &mut DbPool<'_>,
+    local_user_id: LocalUserId,
+  ) -> Result<EmailDigestMode, Error> {
+    let conn = &mut get_conn(pool).await?;
+    let mode = local_user_email_digest::table
+      .find(local_user_id)
+      .select(local_user_email_digest::mode)
+      .first(conn)
+      .await
+      .optional()?;
+    Ok(mode.unwrap_or_default())
+  }
+
+  /// Changes the digest mode. If it is different from before, the period of the first digest
+  /// starts now.
+  pub async fn set_mode(
+    pool: &mut DbPool<'_>,
+    local_user_id: LocalUserId,
+    mode: EmailDigestMode,
+  ) -> Result<(), Error> {
+    if Self::read_mode(pool, local_user_id).await? == mode {
+      return Ok(());
+    }
+    let conn = &mut get_conn(pool).await?;
+    let values = (
+      local_user_email_digest::local_user_id.eq(local_user_id),
+      local_user_email_digest::mode.eq(mode),
+      local_user_email_digest::last_sent.eq(now()),
+    );
+    insert_into(local_user_email_digest::table)
+      .values(values)
+      .on_conflict(local_user_email_digest::local_user_id)
+      .do_update()
+      .set((
+        local_user_email_digest::mode.eq(mode),
+        local_user_email_digest::last_sent.eq(now()),
+      ))
+      .execute(conn)
+      .await?;
+    Ok(())
+  }
+
+  pub async fn mark_sent(pool: &mut DbPool<'_>, local_user_id: LocalUserId) -> Result<(), Error> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(local_user_email_digest::table.find(local_user_id))
+      .set(local_user_email_digest::last_sent.eq(now()))
+      .execute(conn)
+      .await?;
+    Ok(())
+  }
+}
+
+impl EmailDigestMode {
+  /// Time between two digests, or `None` if each notification is sent right away.
+  pub fn interval(self) -> Option<TimeDelta> {
+    match self {
+      EmailDigestMode::Instant => None,
+      EmailDigestMode::Hourly => Some(TimeDelta::hours(1)),
+      EmailDigestMode::Daily => Some(TimeDelta::days(1)),
+      EmailDigestMode::Weekly => Some(TimeDelta::weeks(1)),
+    }
+  }
+}
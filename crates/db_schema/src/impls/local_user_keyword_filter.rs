@@ -0,0 +1,262 @@
+use crate::{
+  newtypes::{KeywordFilterId, LocalUserId},
+  schema::{local_user_keyword_filter, local_user_keyword_filter_listing},
+  source::local_user_keyword_filter::{
+    LocalUserKeywordFilter,
+    LocalUserKeywordFilterInsertForm,
+    LocalUserKeywordFilterListingForm,
+    LocalUserKeywordFilterUpdateForm,
+  },
+  traits::Crud,
+  utils::{get_conn, regex_match_insensitive, DbPool},
+  KeywordFilterMode,
+  ListingType,
+};
+use diesel::{
+  delete,
+  dsl::{exists, not},
+  insert_into,
+  result::Error,
+  select,
+  sql_types::Text,
+  BoolExpressionMethods,
+  ExpressionMethods,
+  IntoSql,
+  QueryDsl,
+};
+use diesel_async::RunQueryDsl;
+
+#[async_trait]
+impl Crud for LocalUserKeywordFilter {
+  type InsertForm = LocalUserKeywordFilterInsertForm;
+  type UpdateForm = LocalUserKeywordFilterUpdateForm;
+  type IdType = KeywordFilterId;
+
+  async fn create(pool: &mut DbPool<'_>, form: &Self::InsertForm) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(local_user_keyword_filter::table)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+
+  async fn update(
+    pool: &mut DbPool<'_>,
+    keyword_filter_id: KeywordFilterId,
+    form: &Self::UpdateForm,
+  ) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(local_user_keyword_filter::table.find(keyword_filter_id))
+      .set(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+}
+
+impl LocalUserKeywordFilter {
+  /// All filters of the user, oldest first.
+  pub async fn list(pool: &mut DbPool<'_>, local_user_id: LocalUserId) -> Result<Vec<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    local_user_keyword_filter::table
+      .filter(local_user_keyword_filter::local_user_id.eq(local_user_id))
+      .order_by(local_user_keyword_filter::id)
+      .load::<Self>(conn)
+      .await
+  }
+
+  /// The filters of the user which apply to the given listing. Filters which are not limited to
+  /// any listings always apply. Without a listing type, only those are returned.
+  pub async fn list_for_listing(
+    pool: &mut DbPool<'_>,
+    local_user_id: LocalUserId,
+    listing_type: Option<ListingType>,
+  ) -> Result<Vec<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    let listings = local_user_keyword_filter_listing::table.filter(
+      local_user_keyword_filter_listing::keyword_filter_id.eq(local_user_keyword_filter::id),
+    );
+    let mut query = local_user_keyword_filter::table
+      .filter(local_user_keyword_filter::local_user_id.eq(local_user_id))
+      .into_boxed();
+    query = if let Some(listing_type) = listing_type {
+      query.filter(not(exists(listings)).or(exists(
+        listings.filter(local_user_keyword_filter_listing::listing_type.eq(listing_type)),
+      )))
+    } else {
+      query.filter(not(exists(listings)))
+    };
+    query
+      .order_by(local_user_keyword_filter::id)
+      .load::<Self>(conn)
+      .await
+  }
+
+  /// Replaces the listings to which the filter is limited.
+  pub async fn set_listing_types(
+    pool: &mut DbPool<'_>,
+    keyword_filter_id: KeywordFilterId,
+    listing_types: &[ListingType],
+  ) -> Result<(), Error> {
+    let conn = &mut get_conn(pool).await?;
+    let forms = listing_types
+      .iter()
+      .map(|&listing_type| LocalUserKeywordFilterListingForm {
+        keyword_filter_id,
+        listing_type,
+      })
+      .collect::<Vec<_>>();
+    conn
+      .build_transaction()
+      .run(|conn| {
+        Box::pin(async move {
+          delete(
+            local_user_keyword_filter_listing::table
+              .filter(local_user_keyword_filter_listing::keyword_filter_id.eq(keyword_filter_id)),
+          )
+          .execute(conn)
+          .await?;
+          insert_into(local_user_keyword_filter_listing::table)
+            .values(forms)
+            .on_conflict_do_nothing()
+            .execute(conn)
+            .await?;
+          Ok::<_, Error>(())
+        }) as _
+      })
+      .await
+  }
+
+  /// Reads the listing types of all the given filters.
+  pub async fn read_listing_types(
+    pool: &mut DbPool<'_>,
+    keyword_filter_ids: &[KeywordFilterId],
+  ) -> Result<Vec<(KeywordFilterId, ListingType)>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    local_user_keyword_filter_listing::table
+      .filter(local_user_keyword_filter_listing::keyword_filter_id.eq_any(keyword_filter_ids))
+      .order_by(local_user_keyword_filter_listing::listing_type)
+      .select((
+        local_user_keyword_filter_listing::keyword_filter_id,
+        local_user_keyword_filter_listing::listing_type,
+      ))
+      .load(conn)
+      .await
+  }
+
+  /// The POSIX regular expression which is matched case insensitively against the text.
+  pub fn pattern(&self) -> String {
+    self.mode.pattern(&self.keyword)
+  }
+
+  /// Checks that Postgres accepts the pattern as a regular expression.
+  pub async fn check_pattern(pool: &mut DbPool<'_>, pattern: &str) -> Result<(), Error> {
+    let conn = &mut get_conn(pool).await?;
+    select(regex_match_insensitive(
+      "".into_sql::<Text>(),
+      pattern.into_sql::<Text>(),
+    ))
+    .get_result::<bool>(conn)
+    .await?;
+    Ok(())
+  }
+}
+
+impl KeywordFilterMode {
+  /// Converts the keyword into a POSIX regular expression for this mode.
+  pub fn pattern(self, keyword: &str) -> String {
+    let escaped = regex::escape(keyword);
+    match self {
+      // Not using `\y` word boundaries, so that keywords which start or end with punctuation also
+      // work
+      KeywordFilterMode::WholeWord => format!(r"(^|\W){escaped}(\W|$)"),
+      KeywordFilterMode::Substring => escaped,
+      KeywordFilterMode::Regex => keyword.to_string(),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+
+  use super::*;
+  use crate::{
+    source::{
+      instance::Instance,
+      local_user::{LocalUser, LocalUserInsertForm},
+      person::{Person, PersonInsertForm},
+    },
+    utils::build_db_pool_for_tests,
+  };
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_keyword_filter_listings() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let instance = Instance::read_or_create(pool, "my_domain.tld".to_string()).await?;
+    let person_form = PersonInsertForm::test_form(instance.id, "keyword_filter_user");
+    let person = Person::create(pool, &person_form).await?;
+    let user_form = LocalUserInsertForm::test_form(person.id);
+    let user = LocalUser::create(pool, &user_form, vec![]).await?;
+
+    let everywhere_form = LocalUserKeywordFilterInsertForm::new(user.id, "politics".to_string());
+    let everywhere = LocalUserKeywordFilter::create(pool, &everywhere_form).await?;
+    let all_form = LocalUserKeywordFilterInsertForm {
+      mode: Some(KeywordFilterMode::Regex),
+      ..LocalUserKeywordFilterInsertForm::new(user.id, "elect(ion|ed)".to_string())
+    };
+    let all_only = LocalUserKeywordFilter::create(pool, &all_form).await?;
+    LocalUserKeywordFilter::set_listing_types(
+      pool,
+      all_only.id,
+      &[ListingType::All, ListingType::Local],
+    )
+    .await?;
+
+    assert_eq!(
+      vec![everywhere.clone(), all_only.clone()],
+      LocalUserKeywordFilter::list(pool, user.id).await?
+    );
+    assert_eq!(
+      vec![everywhere.clone(), all_only.clone()],
+      LocalUserKeywordFilter::list_for_listing(pool, user.id, Some(ListingType::All)).await?
+    );
+    assert_eq!(
+      vec![everywhere.clone()],
+      LocalUserKeywordFilter::list_for_listing(pool, user.id, Some(ListingType::Subscribed))
+        .await?
+    );
+    assert_eq!(
+      vec![everywhere.clone()],
+      LocalUserKeywordFilter::list_for_listing(pool, user.id, None).await?
+    );
+    assert_eq!(
+      vec![
+        (all_only.id, ListingType::All),
+        (all_only.id, ListingType::Local)
+      ],
+      LocalUserKeywordFilter::read_listing_types(pool, &[everywhere.id, all_only.id]).await?
+    );
+
+    assert_eq!(r"(^|\W)politics(\W|$)", everywhere.pattern());
+    assert_eq!("elect(ion|ed)", all_only.pattern());
+    let substring = LocalUserKeywordFilter {
+      keyword: "c++".to_string(),
+      mode: KeywordFilterMode::Substring,
+      ..everywhere.clone()
+    };
+    assert_eq!(r"c\+\+", substring.pattern());
+    LocalUserKeywordFilter::check_pattern(pool, &all_only.pattern()).await?;
+    assert!(LocalUserKeywordFilter::check_pattern(pool, "(unclosed")
+      .await
+      .is_err());
+
+    Person::delete(pool, person.id).await?;
+    Instance::delete(pool, instance.id).await?;
+    Ok(())
+  }
+}
@@ -2,6 +2,7 @@ use crate::{
   newtypes::{
     AdminAllowInstanceId,
     AdminBlockInstanceId,
+    AdminLimitInstanceId,
     AdminPurgeCommentId,
     AdminPurgeCommunityId,
     AdminPurgePersonId,
@@ -10,6 +11,7 @@ use crate::{
   schema::{
     admin_allow_instance,
     admin_block_instance,
+    admin_limit_instance,
     admin_purge_comment,
     admin_purge_community,
     admin_purge_person,
@@ -20,6 +22,8 @@ use crate::{
     AdminAllowInstanceForm,
     AdminBlockInstance,
     AdminBlockInstanceForm,
+    AdminLimitInstance,
+    AdminLimitInstanceForm,
     AdminPurgeComment,
     AdminPurgeCommentForm,
     AdminPurgeCommunity,
@@ -32,7 +36,7 @@ use crate::{
   traits::Crud,
   utils::{get_conn, DbPool},
 };
-use diesel::{dsl::insert_into, result::Error, QueryDsl};
+use diesel::{dsl::insert_into, result::Error, ExpressionMethods, QueryDsl};
 use diesel_async::RunQueryDsl;
 
 #[async_trait]
@@ -196,3 +200,60 @@ impl Crud for AdminBlockInstance {
       .await
   }
 }
+
+#[async_trait]
+impl Crud for AdminLimitInstance {
+  type InsertForm = AdminLimitInstanceForm;
+  type UpdateForm = AdminLimitInstanceForm;
+  type IdType = AdminLimitInstanceId;
+
+  async fn create(pool: &mut DbPool<'_>, form: &Self::InsertForm) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(admin_limit_instance::table)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+
+  async fn update(
+    pool: &mut DbPool<'_>,
+    from_id: Self::IdType,
+    form: &Self::InsertForm,
+  ) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(admin_limit_instance::table.find(from_id))
+      .set(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+}
+
+impl AdminBlockInstance {
+  /// Reads the newest entry for each instance.
+  pub async fn read_latest(pool: &mut DbPool<'_>) -> Result<Vec<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    admin_block_instance::table
+      .distinct_on(admin_block_instance::instance_id)
+      .order_by((
+        admin_block_instance::instance_id,
+        admin_block_instance::published.desc(),
+      ))
+      .load::<Self>(conn)
+      .await
+  }
+}
+
+impl AdminLimitInstance {
+  /// Reads the newest entry for each instance.
+  pub async fn read_latest(pool: &mut DbPool<'_>) -> Result<Vec<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    admin_limit_instance::table
+      .distinct_on(admin_limit_instance::instance_id)
+      .order_by((
+        admin_limit_instance::instance_id,
+        admin_limit_instance::published.desc(),
+      ))
+      .load::<Self>(conn)
+      .await
+  }
+}
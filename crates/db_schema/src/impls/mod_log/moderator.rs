@@ -2,6 +2,7 @@ use crate::{
   newtypes::{
     ModAddCommunityId,
     ModAddId,
+    ModAutomodActionId,
     ModBanFromCommunityId,
     ModBanId,
     ModFeaturePostId,
@@ -11,10 +12,13 @@ use crate::{
     ModRemoveCommunityId,
     ModRemovePostId,
     ModTransferCommunityId,
+    ModWarnPersonId,
+    PersonId,
   },
   schema::{
     mod_add,
     mod_add_community,
+    mod_automod_action,
     mod_ban,
     mod_ban_from_community,
     mod_feature_post,
@@ -24,12 +28,15 @@ use crate::{
     mod_remove_community,
     mod_remove_post,
     mod_transfer_community,
+    mod_warn_person,
   },
   source::mod_log::moderator::{
     ModAdd,
     ModAddCommunity,
     ModAddCommunityForm,
     ModAddForm,
+    ModAutomodAction,
+    ModAutomodActionForm,
     ModBan,
     ModBanForm,
     ModBanFromCommunity,
@@ -48,11 +55,14 @@ use crate::{
     ModRemovePostForm,
     ModTransferCommunity,
     ModTransferCommunityForm,
+    ModWarnPerson,
+    ModWarnPersonForm,
+    ModWarnPersonUpdateForm,
   },
   traits::Crud,
   utils::{get_conn, DbPool},
 };
-use diesel::{dsl::insert_into, result::Error, QueryDsl};
+use diesel::{dsl::insert_into, result::Error, ExpressionMethods, QueryDsl};
 use diesel_async::RunQueryDsl;
 
 #[async_trait]
@@ -378,6 +388,77 @@ impl Crud for ModAdd {
   }
 }
 
+#[async_trait]
+impl Crud for ModAutomodAction {
+  type InsertForm = ModAutomodActionForm;
+  type UpdateForm = ModAutomodActionForm;
+  type IdType = ModAutomodActionId;
+
+  async fn create(pool: &mut DbPool<'_>, form: &Self::InsertForm) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(mod_automod_action::table)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+
+  async fn update(
+    pool: &mut DbPool<'_>,
+    from_id: Self::IdType,
+    form: &Self::UpdateForm,
+  ) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(mod_automod_action::table.find(from_id))
+      .set(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+}
+
+#[async_trait]
+impl Crud for ModWarnPerson {
+  type InsertForm = ModWarnPersonForm;
+  type UpdateForm = ModWarnPersonUpdateForm;
+  type IdType = ModWarnPersonId;
+
+  async fn create(pool: &mut DbPool<'_>, form: &Self::InsertForm) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(mod_warn_person::table)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+
+  async fn update(
+    pool: &mut DbPool<'_>,
+    from_id: Self::IdType,
+    form: &Self::UpdateForm,
+  ) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(mod_warn_person::table.find(from_id))
+      .set(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+}
+
+impl ModWarnPerson {
+  pub async fn mark_all_as_read(
+    pool: &mut DbPool<'_>,
+    for_recipient_id: PersonId,
+  ) -> Result<Vec<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(
+      mod_warn_person::table
+        .filter(mod_warn_person::other_person_id.eq(for_recipient_id))
+        .filter(mod_warn_person::read.eq(false)),
+    )
+    .set(mod_warn_person::read.eq(true))
+    .get_results::<Self>(conn)
+    .await
+  }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -0,0 +1,36 @@
+use crate::{
+  newtypes::ModNoteId,
+  schema::mod_note,
+  source::mod_note::{ModNote, ModNoteInsertForm},
+  traits::Crud,
+  utils::{get_conn, DbPool},
+};
+use diesel::{insert_into, result::Error, QueryDsl};
+use diesel_async::RunQueryDsl;
+
+#[async_trait]
+impl Crud for ModNote {
+  type InsertForm = ModNoteInsertForm;
+  type UpdateForm = ModNoteInsertForm;
+  type IdType = ModNoteId;
+
+  async fn create(pool: &mut DbPool<'_>, form: &Self::InsertForm) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(mod_note::table)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+
+  async fn update(
+    pool: &mut DbPool<'_>,
+    note_id: ModNoteId,
+    form: &Self::UpdateForm,
+  ) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(mod_note::table.find(note_id))
+      .set(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+}
@@ -0,0 +1,197 @@
+use crate::{
+  newtypes::{ModmailMessageId, ModmailThreadId, PersonId},
+  schema::{community_actions, modmail_message, modmail_thread},
+  source::modmail::{
+    ModmailMessage,
+    ModmailMessageInsertForm,
+    ModmailMessageUpdateForm,
+    ModmailThread,
+    ModmailThreadInsertForm,
+    ModmailThreadUpdateForm,
+  },
+  traits::Crud,
+  utils::{action_query, get_conn, DbPool},
+};
+use diesel::{dsl::insert_into, result::Error, ExpressionMethods, QueryDsl};
+use diesel_async::RunQueryDsl;
+
+#[async_trait]
+impl Crud for ModmailThread {
+  type InsertForm = ModmailThreadInsertForm;
+  type UpdateForm = ModmailThreadUpdateForm;
+  type IdType = ModmailThreadId;
+
+  async fn create(pool: &mut DbPool<'_>, form: &Self::InsertForm) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(modmail_thread::table)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+
+  async fn update(
+    pool: &mut DbPool<'_>,
+    thread_id: ModmailThreadId,
+    form: &Self::UpdateForm,
+  ) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(modmail_thread::table.find(thread_id))
+      .set(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+}
+
+#[async_trait]
+impl Crud for ModmailMessage {
+  type InsertForm = ModmailMessageInsertForm;
+  type UpdateForm = ModmailMessageUpdateForm;
+  type IdType = ModmailMessageId;
+
+  async fn create(pool: &mut DbPool<'_>, form: &Self::InsertForm) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(modmail_message::table)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+
+  async fn update(
+    pool: &mut DbPool<'_>,
+    message_id: ModmailMessageId,
+    form: &Self::UpdateForm,
+  ) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(modmail_message::table.find(message_id))
+      .set(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+}
+
+impl ModmailMessage {
+  /// Marks the messages of a thread as read. Moderators read the messages of the thread creator,
+  /// and the thread creator reads the messages of the moderators.
+  pub async fn mark_thread_as_read(
+    pool: &mut DbPool<'_>,
+    thread_id: ModmailThreadId,
+    as_moderator: bool,
+  ) -> Result<usize, Error> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(
+      modmail_message::table
+        .filter(modmail_message::thread_id.eq(thread_id))
+        .filter(modmail_message::from_moderator.eq(!as_moderator))
+        .filter(modmail_message::read.eq(false)),
+    )
+    .set(modmail_message::read.eq(true))
+    .execute(conn)
+    .await
+  }
+
+  /// Marks all messages to the person as read, both in their own threads and in the threads of
+  /// the communities which they moderate.
+  pub async fn mark_all_as_read(
+    pool: &mut DbPool<'_>,
+    for_person_id: PersonId,
+  ) -> Result<usize, Error> {
+    let conn = &mut get_conn(pool).await?;
+    let own_threads = modmail_thread::table
+      .filter(modmail_thread::creator_id.eq(for_person_id))
+      .select(modmail_thread::id);
+    let own_read = diesel::update(
+      modmail_message::table
+        .filter(modmail_message::thread_id.eq_any(own_threads))
+        .filter(modmail_message::from_moderator)
+        .filter(modmail_message::read.eq(false)),
+    )
+    .set(modmail_message::read.eq(true))
+    .execute(conn)
+    .await?;
+
+    let moderated_communities = action_query(community_actions::became_moderator)
+      .filter(community_actions::person_id.eq(for_person_id))
+      .select(community_actions::community_id);
+    let moderated_threads = modmail_thread::table
+      .filter(modmail_thread::community_id.eq_any(moderated_communities))
+      .select(modmail_thread::id);
+    let moderated_read = diesel::update(
+      modmail_message::table
+        .filter(modmail_message::thread_id.eq_any(moderated_threads))
+        .filter(modmail_message::from_moderator.eq(false))
+        .filter(modmail_message::read.eq(false)),
+    )
+    .set(modmail_message::read.eq(true))
+    .execute(conn)
+    .await?;
+
+    Ok(own_read + moderated_read)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+
+  use crate::{
+    source::{
+      community::{Community, CommunityInsertForm, CommunityModerator, CommunityModeratorForm},
+      instance::Instance,
+      modmail::{ModmailMessage, ModmailMessageInsertForm, ModmailThread, ModmailThreadInsertForm},
+      person::{Person, PersonInsertForm},
+    },
+    traits::{Crud, Joinable},
+    utils::build_db_pool_for_tests,
+  };
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_mark_as_read() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let instance = Instance::read_or_create(pool, "my_domain.tld".to_string()).await?;
+    let user_form = PersonInsertForm::test_form(instance.id, "modmail_user");
+    let user = Person::create(pool, &user_form).await?;
+    let moderator_form = PersonInsertForm::test_form(instance.id, "modmail_mod");
+    let moderator = Person::create(pool, &moderator_form).await?;
+
+    let community_form = CommunityInsertForm::new(
+      instance.id,
+      "test_modmail".to_string(),
+      "nada".to_owned(),
+      "pubkey".to_string(),
+    );
+    let community = Community::create(pool, &community_form).await?;
+    let moderator_form = CommunityModeratorForm {
+      community_id: community.id,
+      person_id: moderator.id,
+    };
+    CommunityModerator::join(pool, &moderator_form).await?;
+
+    let thread_form = ModmailThreadInsertForm::new(community.id, user.id, "question".to_string());
+    let thread = ModmailThread::create(pool, &thread_form).await?;
+    let question_form =
+      ModmailMessageInsertForm::new(thread.id, user.id, "hello?".to_string(), false);
+    ModmailMessage::create(pool, &question_form).await?;
+    let answer_form =
+      ModmailMessageInsertForm::new(thread.id, moderator.id, "hi".to_string(), true);
+    ModmailMessage::create(pool, &answer_form).await?;
+
+    // The moderators only read the message of the user
+    let read = ModmailMessage::mark_thread_as_read(pool, thread.id, true).await?;
+    assert_eq!(1, read);
+
+    // Marking everything as read only affects the message of the moderator now
+    let read = ModmailMessage::mark_all_as_read(pool, moderator.id).await?;
+    assert_eq!(0, read);
+    let read = ModmailMessage::mark_all_as_read(pool, user.id).await?;
+    assert_eq!(1, read);
+
+    Instance::delete(pool, instance.id).await?;
+
+    Ok(())
+  }
+}
@@ -0,0 +1,212 @@
+use crate::{
+  newtypes::OutgoingEmailId,
+  schema::outgoing_email,
+  source::outgoing_email::{OutgoingEmail, OutgoingEmailInsertForm},
+  utils::{get_conn, limit_and_offset, now, DbPool},
+};
+use chrono::{TimeDelta, Utc};
+use diesel::{
+  delete,
+  dsl::IntervalDsl,
+  insert_into,
+  result::Error,
+  ExpressionMethods,
+  NullableExpressionMethods,
+  QueryDsl,
+};
+use diesel_async::RunQueryDsl;
+
+/// Emails which can't be sent after this many attempts are given up on, until an admin retries
+/// them.
+const MAX_SEND_ATTEMPTS: i32 = 8;
+
+/// Delay before the first retry of a failed email. It doubles with each further attempt.
+const RETRY_BASE_DELAY_SECS: i64 = 60;
+
+impl OutgoingEmail {
+  /// Adds the email to the queue. It is due right away.
+  pub async fn create(
+    pool: &mut DbPool<'_>,
+    form: &OutgoingEmailInsertForm,
+  ) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(outgoing_email::table)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+
+  /// Takes up to `limit` emails which are due to be sent. Their next attempt is moved back by
+  /// `lease`, so that they are sent later if the process dies while sending.
+  pub async fn claim_due(
+    pool: &mut DbPool<'_>,
+    limit: i64,
+    lease: TimeDelta,
+  ) -> Result<Vec<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    conn
+      .build_transaction()
+      .run(|conn| {
+        Box::pin(async move {
+          let ids = outgoing_email::table
+            .filter(outgoing_email::next_attempt.le(now().nullable()))
+            .order_by(outgoing_email::next_attempt)
+            .select(outgoing_email::id)
+            .limit(limit)
+            .for_update()
+            .skip_locked()
+            .load::<OutgoingEmailId>(conn)
+            .await?;
+          diesel::update(outgoing_email::table.filter(outgoing_email::id.eq_any(ids)))
+            .set(outgoing_email::next_attempt.eq(Utc::now() + lease))
+            .get_results::<Self>(conn)
+            .await
+        }) as _
+      })
+      .await
+  }
+
+  /// Stores the result of a send attempt. Failed emails are retried with exponential backoff,
+  /// until the maximum number of attempts is reached.
+  pub async fn finish_attempt(
+    pool: &mut DbPool<'_>,
+    email: &Self,
+    error: Option<String>,
+  ) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    let attempts = email.attempts + 1;
+    let (next_attempt, sent) = match error {
+      None => (None, Some(Utc::now())),
+      Some(_) if attempts >= MAX_SEND_ATTEMPTS => (None, None),
+      Some(_) => (Some(Utc::now() + Self::retry_delay(attempts)), None),
+    };
+    diesel::update(outgoing_email::table.find(email.id))
+      .set((
+        outgoing_email::attempts.eq(attempts),
+        outgoing_email::next_attempt.eq(next_attempt),
+        outgoing_email::sent.eq(sent),
+        outgoing_email::last_error.eq(error),
+      ))
+      .get_result::<Self>(conn)
+      .await
+  }
+
+  /// Lists the emails which failed too often and are not retried anymore, newest first.
+  pub async fn list_failed(
+    pool: &mut DbPool<'_>,
+    page: Option<i64>,
+    limit: Option<i64>,
+  ) -> Result<Vec<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    let (limit, offset) = limit_and_offset(page, limit)?;
+    outgoing_email::table
+      .filter(outgoing_email::sent.is_null())
+      .filter(outgoing_email::next_attempt.is_null())
+      .order_by(outgoing_email::published.desc())
+      .offset(offset)
+      .limit(limit)
+      .load::<Self>(conn)
+      .await
+  }
+
+  /// Schedules a failed email to be sent again right away, with the full number of attempts.
+  pub async fn retry(pool: &mut DbPool<'_>, email_id: OutgoingEmailId) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(
+      outgoing_email::table
+        .find(email_id)
+        .filter(outgoing_email::sent.is_null()),
+    )
+    .set((
+      outgoing_email::attempts.eq(0),
+      outgoing_email::next_attempt.eq(now().nullable()),
+    ))
+    .get_result::<Self>(conn)
+    .await
+  }
+
+  /// Delete emails after a week, unless they are still being retried. This also removes the
+  /// password reset and verification links which they contain.
+  pub async fn delete_old(pool: &mut DbPool<'_>) -> Result<usize, Error> {
+    let conn = &mut get_conn(pool).await?;
+    delete(
+      outgoing_email::table
+        .filter(outgoing_email::published.lt(now() - 7.days()))
+        .filter(outgoing_email::next_attempt.is_null()),
+    )
+    .execute(conn)
+    .await
+  }
+
+  fn retry_delay(attempts: i32) -> TimeDelta {
+    let factor = 2_i64.saturating_pow((attempts - 1).clamp(0, 30).unsigned_abs());
+    TimeDelta::seconds(RETRY_BASE_DELAY_SECS.saturating_mul(factor))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+
+  use super::*;
+  use crate::utils::build_db_pool_for_tests;
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_outgoing_email_retries() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let form = OutgoingEmailInsertForm::new(
+      "user@example.com".to_string(),
+      "user".to_string(),
+      "subject".to_string(),
+      "<p>body</p>".to_string(),
+      None,
+    );
+    let email = OutgoingEmail::create(pool, &form).await?;
+
+    // A claimed email is not handed out a second time
+    let claimed = OutgoingEmail::claim_due(pool, 10, TimeDelta::minutes(5)).await?;
+    assert_eq!(
+      vec![email.id],
+      claimed.iter().map(|e| e.id).collect::<Vec<_>>()
+    );
+    assert!(OutgoingEmail::claim_due(pool, 10, TimeDelta::minutes(5))
+      .await?
+      .is_empty());
+
+    let mut email = email;
+    for _ in 0..MAX_SEND_ATTEMPTS {
+      email = OutgoingEmail::finish_attempt(pool, &email, Some("smtp down".to_string())).await?;
+    }
+    assert_eq!(MAX_SEND_ATTEMPTS, email.attempts);
+    assert_eq!(None, email.next_attempt);
+    let failed = OutgoingEmail::list_failed(pool, None, None).await?;
+    assert_eq!(vec![email.clone()], failed);
+
+    // After a manual retry, it is sent successfully
+    let email = OutgoingEmail::retry(pool, email.id).await?;
+    assert_eq!(0, email.attempts);
+    let claimed = OutgoingEmail::claim_due(pool, 10, TimeDelta::minutes(5)).await?;
+    assert_eq!(1, claimed.len());
+    let email = OutgoingEmail::finish_attempt(pool, &email, None).await?;
+    assert!(email.sent.is_some());
+    assert!(OutgoingEmail::list_failed(pool, None, None)
+      .await?
+      .is_empty());
+
+    delete(outgoing_email::table.find(email.id))
+      .execute(&mut get_conn(pool).await?)
+      .await?;
+    Ok(())
+  }
+
+  #[test]
+  fn test_retry_delay() {
+    assert_eq!(TimeDelta::minutes(1), OutgoingEmail::retry_delay(1));
+    assert_eq!(TimeDelta::minutes(4), OutgoingEmail::retry_delay(3));
+  }
+}
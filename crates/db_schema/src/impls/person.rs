@@ -1,22 +1,32 @@
 use crate::{
   diesel::OptionalExtension,
   newtypes::{CommunityId, DbUrl, InstanceId, PersonId},
-  schema::{comment, community, instance, local_user, person, person_actions, post},
-  source::person::{
-    Person,
-    PersonFollower,
-    PersonFollowerForm,
-    PersonInsertForm,
-    PersonUpdateForm,
+  schema::{
+    comment,
+    community,
+    instance,
+    local_user,
+    person,
+    person_actions,
+    person_content_combined,
+    post,
+  },
+  source::{
+    comment::Comment,
+    community::Community,
+    person::{Person, PersonFollower, PersonFollowerForm, PersonInsertForm, PersonUpdateForm},
+    post::Post,
   },
   traits::{ApubActor, Crud, Followable},
-  utils::{action_query, functions::lower, get_conn, now, uplete, DbPool},
+  utils::{action_query, find_action, functions::lower, get_conn, now, uplete, DbPool},
+  CommunityVisibility,
 };
 use chrono::Utc;
 use diesel::{
-  dsl::{insert_into, not},
+  dsl::{exists, insert_into, not},
   expression::SelectableHelper,
   result::Error,
+  BoolExpressionMethods,
   CombineDsl,
   ExpressionMethods,
   JoinOnDsl,
@@ -138,6 +148,66 @@ impl Person {
     .then_some(())
     .ok_or(LemmyErrorType::UsernameAlreadyExists.into())
   }
+
+  /// Lists posts and comments of the person which are publicly visible, newest first, together
+  /// with the total number of such items. Each item consists of the post, the comment if it is a
+  /// comment, and the community. Content in private or local-only communities, and deleted or
+  /// removed content is excluded.
+  pub async fn list_public_content(
+    pool: &mut DbPool<'_>,
+    person_id: PersonId,
+    limit: i64,
+    offset: i64,
+  ) -> Result<(i64, Vec<(Post, Option<Comment>, Community)>), Error> {
+    let conn = &mut get_conn(pool).await?;
+    let query = || {
+      person_content_combined::table
+        .left_join(
+          comment::table.on(person_content_combined::comment_id.eq(comment::id.nullable())),
+        )
+        .inner_join(
+          post::table.on(
+            person_content_combined::post_id
+              .eq(post::id.nullable())
+              .or(comment::post_id.eq(post::id)),
+          ),
+        )
+        .inner_join(community::table.on(post::community_id.eq(community::id)))
+        .filter(
+          comment::creator_id.eq(person_id).or(
+            person_content_combined::post_id
+              .is_not_null()
+              .and(post::creator_id.eq(person_id)),
+          ),
+        )
+        .filter(community::visibility.eq(CommunityVisibility::Public))
+        .filter(not(community::deleted.or(community::removed)))
+        .filter(not(post::deleted.or(post::removed)))
+        .filter(
+          comment::id
+            .is_null()
+            .or(not(comment::deleted.or(comment::removed))),
+        )
+        .into_boxed()
+    };
+
+    let total = query().count().get_result::<i64>(conn).await?;
+    let items = query()
+      .select((
+        post::all_columns,
+        comment::all_columns.nullable(),
+        community::all_columns,
+      ))
+      .order_by((
+        person_content_combined::published.desc(),
+        person_content_combined::id.desc(),
+      ))
+      .limit(limit)
+      .offset(offset)
+      .load::<(Post, Option<Comment>, Community)>(conn)
+      .await?;
+    Ok((total, items))
+  }
 }
 
 impl PersonInsertForm {
@@ -242,6 +312,21 @@ impl PersonFollower {
       .load(conn)
       .await
   }
+
+  /// Returns true if `follower_id` follows `person_id`.
+  pub async fn is_following(
+    pool: &mut DbPool<'_>,
+    follower_id: PersonId,
+    person_id: PersonId,
+  ) -> Result<bool, Error> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::select(exists(find_action(
+      person_actions::followed,
+      (follower_id, person_id),
+    )))
+    .get_result(conn)
+    .await
+  }
 }
 
 #[cfg(test)]
@@ -249,11 +334,15 @@ mod tests {
 
   use crate::{
     source::{
+      comment::{Comment, CommentInsertForm},
+      community::{Community, CommunityInsertForm},
       instance::Instance,
       person::{Person, PersonFollower, PersonFollowerForm, PersonInsertForm, PersonUpdateForm},
+      post::{Post, PostInsertForm, PostUpdateForm},
     },
     traits::{Crud, Followable},
     utils::{build_db_pool_for_tests, uplete},
+    CommunityVisibility,
   };
   use lemmy_utils::error::LemmyResult;
   use pretty_assertions::assert_eq;
@@ -292,6 +381,7 @@ mod tests {
       matrix_user_id: None,
       ban_expires: None,
       instance_id: inserted_instance.id,
+      moved_to: None,
     };
 
     let read_person = Person::read(pool, inserted_person.id).await?;
@@ -343,4 +433,68 @@ mod tests {
 
     Ok(())
   }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_list_public_content() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+    let instance = Instance::read_or_create(pool, "my_domain.tld".to_string()).await?;
+
+    let person_form = PersonInsertForm::test_form(instance.id, "outbox_person");
+    let person = Person::create(pool, &person_form).await?;
+
+    let community_form = CommunityInsertForm::new(
+      instance.id,
+      "outbox_public".into(),
+      "nada".to_owned(),
+      "pubkey".to_string(),
+    );
+    let public_community = Community::create(pool, &community_form).await?;
+    let community_form = CommunityInsertForm {
+      visibility: Some(CommunityVisibility::LocalOnly),
+      ..CommunityInsertForm::new(
+        instance.id,
+        "outbox_local_only".into(),
+        "nada".to_owned(),
+        "pubkey".to_string(),
+      )
+    };
+    let local_only_community = Community::create(pool, &community_form).await?;
+
+    let post_form = PostInsertForm::new("public".into(), person.id, public_community.id);
+    let public_post = Post::create(pool, &post_form).await?;
+    let post_form = PostInsertForm::new("local only".into(), person.id, local_only_community.id);
+    Post::create(pool, &post_form).await?;
+    let post_form = PostInsertForm::new("deleted".into(), person.id, public_community.id);
+    let deleted_post = Post::create(pool, &post_form).await?;
+    let post_form = PostUpdateForm {
+      deleted: Some(true),
+      ..Default::default()
+    };
+    Post::update(pool, deleted_post.id, &post_form).await?;
+    let comment_form = CommentInsertForm::new(person.id, public_post.id, "comment".into());
+    let comment = Comment::create(pool, &comment_form, None).await?;
+
+    let (total, items) = Person::list_public_content(pool, person.id, 10, 0).await?;
+    assert_eq!(2, total);
+    // Newest first
+    let ids = items
+      .iter()
+      .map(|(post, comment, _)| (post.id, comment.as_ref().map(|c| c.id)))
+      .collect::<Vec<_>>();
+    assert_eq!(
+      vec![(public_post.id, Some(comment.id)), (public_post.id, None)],
+      ids
+    );
+
+    let (_, items) = Person::list_public_content(pool, person.id, 10, 1).await?;
+    assert_eq!(1, items.len());
+
+    Person::delete(pool, person.id).await?;
+    Community::delete(pool, public_community.id).await?;
+    Community::delete(pool, local_only_community.id).await?;
+    Instance::delete(pool, instance.id).await?;
+    Ok(())
+  }
 }
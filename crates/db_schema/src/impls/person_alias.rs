@@ -0,0 +1,130 @@
+use crate::{
+  newtypes::{DbUrl, PersonId},
+  schema::person_alias,
+  source::person_alias::{PersonAlias, PersonAliasForm},
+  utils::{get_conn, DbPool},
+};
+use diesel::{
+  delete,
+  dsl::{exists, select},
+  insert_into,
+  result::Error,
+  ExpressionMethods,
+  QueryDsl,
+};
+use diesel_async::RunQueryDsl;
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+
+impl PersonAlias {
+  pub async fn list(pool: &mut DbPool<'_>, person_id: PersonId) -> Result<Vec<DbUrl>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    person_alias::table
+      .filter(person_alias::person_id.eq(person_id))
+      .order_by(person_alias::published)
+      .select(person_alias::alias)
+      .load::<DbUrl>(conn)
+      .await
+  }
+
+  /// Fails unless the person lists `alias` as one of their aliases, which is required for moving
+  /// an account to the person.
+  pub async fn check_is_alias(
+    pool: &mut DbPool<'_>,
+    person_id: PersonId,
+    alias: &DbUrl,
+  ) -> LemmyResult<()> {
+    let conn = &mut get_conn(pool).await?;
+    select(exists(
+      person_alias::table
+        .filter(person_alias::person_id.eq(person_id))
+        .filter(person_alias::alias.eq(alias)),
+    ))
+    .get_result::<bool>(conn)
+    .await?
+    .then_some(())
+    .ok_or(LemmyErrorType::MoveTargetMissingAlias.into())
+  }
+
+  /// Replaces all aliases of the person with the given ones.
+  pub async fn replace(
+    pool: &mut DbPool<'_>,
+    person_id: PersonId,
+    aliases: Vec<DbUrl>,
+  ) -> Result<(), Error> {
+    let conn = &mut get_conn(pool).await?;
+    let forms = aliases
+      .into_iter()
+      .map(|alias| PersonAliasForm::new(person_id, alias))
+      .collect::<Vec<_>>();
+    conn
+      .build_transaction()
+      .run(|conn| {
+        Box::pin(async move {
+          delete(person_alias::table.filter(person_alias::person_id.eq(person_id)))
+            .execute(conn)
+            .await?;
+          insert_into(person_alias::table)
+            .values(forms)
+            .on_conflict_do_nothing()
+            .execute(conn)
+            .await?;
+          Ok(())
+        }) as _
+      })
+      .await
+  }
+}
+
+#[cfg(test)]
+mod tests {
+
+  use crate::{
+    source::{
+      instance::Instance,
+      person::{Person, PersonInsertForm},
+      person_alias::PersonAlias,
+    },
+    traits::Crud,
+    utils::build_db_pool_for_tests,
+  };
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+  use url::Url;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_person_alias() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let instance = Instance::read_or_create(pool, "my_domain.tld".to_string()).await?;
+    let person_form = PersonInsertForm::test_form(instance.id, "alias_user");
+    let person = Person::create(pool, &person_form).await?;
+    assert!(PersonAlias::list(pool, person.id).await?.is_empty());
+
+    let first = Url::parse("https://example.com/u/first")?.into();
+    let second = Url::parse("https://example.net/users/second")?.into();
+    PersonAlias::replace(pool, person.id, vec![first, second]).await?;
+    assert_eq!(2, PersonAlias::list(pool, person.id).await?.len());
+
+    // Replacing drops aliases which are not listed anymore
+    let second = Url::parse("https://example.net/users/second")?.into();
+    PersonAlias::replace(pool, person.id, vec![second]).await?;
+    let aliases = PersonAlias::list(pool, person.id).await?;
+    assert_eq!(
+      vec!["https://example.net/users/second".to_string()],
+      aliases.iter().map(ToString::to_string).collect::<Vec<_>>()
+    );
+    let first = Url::parse("https://example.com/u/first")?.into();
+    assert!(PersonAlias::check_is_alias(pool, person.id, &first)
+      .await
+      .is_err());
+    let second = Url::parse("https://example.net/users/second")?.into();
+    PersonAlias::check_is_alias(pool, person.id, &second).await?;
+
+    Person::delete(pool, person.id).await?;
+    Instance::delete(pool, instance.id).await?;
+    Ok(())
+  }
+}
@@ -1,7 +1,7 @@
 use crate::{
   diesel::{BoolExpressionMethods, NullableExpressionMethods, OptionalExtension},
   newtypes::{CommunityId, DbUrl, PersonId, PostId},
-  schema::{community, person, post, post_actions},
+  schema::{community, person, post, post_actions, post_revision},
   source::post::{
     Post,
     PostActionsCursor,
@@ -107,6 +107,27 @@ impl Post {
       .await
   }
 
+  /// The newest post in the community which links to one of the urls, and was published after
+  /// the given time.
+  pub async fn read_duplicate_link(
+    pool: &mut DbPool<'_>,
+    the_community_id: CommunityId,
+    urls: &[DbUrl],
+    since: DateTime<Utc>,
+  ) -> Result<Option<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    post::table
+      .filter(post::community_id.eq(the_community_id))
+      .filter(post::url.eq_any(urls))
+      .filter(post::deleted.eq(false))
+      .filter(post::removed.eq(false))
+      .filter(post::published.gt(since))
+      .order_by(post::published.desc())
+      .first::<Self>(conn)
+      .await
+      .optional()
+  }
+
   pub async fn list_for_sitemap(
     pool: &mut DbPool<'_>,
   ) -> Result<Vec<(DbUrl, chrono::DateTime<Utc>)>, Error> {
@@ -128,16 +149,30 @@ impl Post {
     for_creator_id: PersonId,
   ) -> Result<Vec<Self>, Error> {
     let conn = &mut get_conn(pool).await?;
-
-    diesel::update(post::table.filter(post::creator_id.eq(for_creator_id)))
-      .set((
-        post::name.eq(DELETED_REPLACEMENT_TEXT),
-        post::url.eq(Option::<&str>::None),
-        post::body.eq(DELETED_REPLACEMENT_TEXT),
-        post::deleted.eq(true),
-        post::updated.eq(Utc::now()),
-      ))
-      .get_results::<Self>(conn)
+    conn
+      .build_transaction()
+      .run(|conn| {
+        Box::pin(async move {
+          // Otherwise the previous versions could still be read from the edit history
+          let creator_posts = post::table
+            .filter(post::creator_id.eq(for_creator_id))
+            .select(post::id);
+          diesel::delete(post_revision::table.filter(post_revision::post_id.eq_any(creator_posts)))
+            .execute(conn)
+            .await?;
+
+          diesel::update(post::table.filter(post::creator_id.eq(for_creator_id)))
+            .set((
+              post::name.eq(DELETED_REPLACEMENT_TEXT),
+              post::url.eq(Option::<&str>::None),
+              post::body.eq(DELETED_REPLACEMENT_TEXT),
+              post::deleted.eq(true),
+              post::updated.eq(Utc::now()),
+            ))
+            .get_results::<Self>(conn)
+            .await
+        }) as _
+      })
       .await
   }
 
@@ -436,6 +471,7 @@ impl PostActionsCursor {
 mod tests {
 
   use crate::{
+    newtypes::DbUrl,
     source::{
       community::{Community, CommunityInsertForm},
       instance::Instance,
@@ -455,7 +491,7 @@ mod tests {
     traits::{Crud, Likeable, Saveable},
     utils::{build_db_pool_for_tests, uplete},
   };
-  use chrono::DateTime;
+  use chrono::{DateTime, TimeDelta, Utc};
   use lemmy_utils::error::LemmyResult;
   use pretty_assertions::assert_eq;
   use serial_test::serial;
@@ -527,6 +563,7 @@ mod tests {
       featured_local: false,
       url_content_type: None,
       scheduled_publish_time: None,
+      crosspost_of_id: None,
     };
 
     // Post Like
@@ -600,4 +637,44 @@ mod tests {
 
     Ok(())
   }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_duplicate_link() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let instance = Instance::read_or_create(pool, "my_domain.tld".to_string()).await?;
+    let person_form = PersonInsertForm::test_form(instance.id, "duplicate_poster");
+    let person = Person::create(pool, &person_form).await?;
+    let community_form = CommunityInsertForm::new(
+      instance.id,
+      "duplicate_links".to_string(),
+      "nada".to_owned(),
+      "pubkey".to_string(),
+    );
+    let community = Community::create(pool, &community_form).await?;
+
+    let url: DbUrl = Url::parse("https://example.com/article")?.into();
+    let post_form = PostInsertForm {
+      url: Some(url.clone()),
+      ..PostInsertForm::new("article".into(), person.id, community.id)
+    };
+    let post = Post::create(pool, &post_form).await?;
+
+    let other_url: DbUrl = Url::parse("https://example.com/other")?.into();
+    let day_ago = Utc::now() - TimeDelta::days(1);
+    let duplicate =
+      Post::read_duplicate_link(pool, community.id, &[other_url, url.clone()], day_ago).await?;
+    assert_eq!(Some(post.id), duplicate.map(|p| p.id));
+
+    // Posts before the time window are ignored
+    let duplicate = Post::read_duplicate_link(pool, community.id, &[url], Utc::now()).await?;
+    assert_eq!(None, duplicate);
+
+    Community::delete(pool, community.id).await?;
+    Person::delete(pool, person.id).await?;
+    Instance::delete(pool, instance.id).await?;
+    Ok(())
+  }
 }
@@ -0,0 +1,299 @@
+use crate::{
+  newtypes::{PersonId, PostId, PostPollOptionId},
+  schema::{post, post_poll, post_poll_option, post_poll_vote},
+  source::post_poll::{
+    PostPoll,
+    PostPollInsertForm,
+    PostPollOption,
+    PostPollOptionInsertForm,
+    PostPollVote,
+    PostPollVoteForm,
+  },
+  utils::{get_conn, now, DbPool},
+};
+use diesel::{
+  delete,
+  dsl::{count_distinct, exists, insert_into},
+  result::Error,
+  select,
+  ExpressionMethods,
+  NullableExpressionMethods,
+  QueryDsl,
+};
+use diesel_async::RunQueryDsl;
+use lemmy_utils::error::{LemmyError, LemmyErrorType, LemmyResult};
+
+impl PostPoll {
+  pub async fn read(pool: &mut DbPool<'_>, post_id: PostId) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    post_poll::table.find(post_id).first(conn).await
+  }
+
+  /// Marks the local polls which ended since the last call as federated, and returns their
+  /// posts, so that the final vote counts can be sent out.
+  pub async fn mark_ended_as_federated(pool: &mut DbPool<'_>) -> Result<Vec<PostId>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    let local_posts = post::table.filter(post::local).select(post::id);
+    diesel::update(
+      post_poll::table
+        .filter(post_poll::results_federated.eq(false))
+        .filter(post_poll::end_time.lt(now().nullable()))
+        .filter(post_poll::post_id.eq_any(local_posts)),
+    )
+    .set(post_poll::results_federated.eq(true))
+    .returning(post_poll::post_id)
+    .get_results(conn)
+    .await
+  }
+
+  /// Creates the poll, or updates the existing poll of the post. Options are matched by name,
+  /// options which are not in the given list are removed together with their votes, and the
+  /// voters of local polls are counted again.
+  pub async fn upsert(
+    pool: &mut DbPool<'_>,
+    form: &PostPollInsertForm,
+    options: &[PostPollOptionInsertForm],
+  ) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    conn
+      .build_transaction()
+      .run(|conn| {
+        Box::pin(async move {
+          let poll = insert_into(post_poll::table)
+            .values(form)
+            .on_conflict(post_poll::post_id)
+            .do_update()
+            .set(form)
+            .get_result::<Self>(conn)
+            .await?;
+          for option in options {
+            insert_into(post_poll_option::table)
+              .values(option)
+              .on_conflict((post_poll_option::post_id, post_poll_option::name))
+              .do_update()
+              .set(option)
+              .execute(conn)
+              .await?;
+          }
+          let names = options.iter().map(|o| o.name.clone()).collect::<Vec<_>>();
+          delete(
+            post_poll_option::table
+              .filter(post_poll_option::post_id.eq(form.post_id))
+              .filter(post_poll_option::name.ne_all(names)),
+          )
+          .execute(conn)
+          .await?;
+          // Remote polls provide their own count
+          if form.voters_count.is_some() {
+            return Ok(poll);
+          }
+          // Votes of removed options are deleted with them, so the voters need to be counted again
+          let voters = post_poll_vote::table
+            .filter(post_poll_vote::post_id.eq(form.post_id))
+            .select(count_distinct(post_poll_vote::person_id))
+            .get_result::<i64>(conn)
+            .await?;
+          diesel::update(post_poll::table.find(form.post_id))
+            .set(post_poll::voters_count.eq(i32::try_from(voters).unwrap_or(i32::MAX)))
+            .get_result::<Self>(conn)
+            .await
+        }) as _
+      })
+      .await
+  }
+}
+
+impl PostPollOption {
+  /// All options of the poll, in the order in which they are displayed.
+  pub async fn read_for_poll(pool: &mut DbPool<'_>, post_id: PostId) -> Result<Vec<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    post_poll_option::table
+      .filter(post_poll_option::post_id.eq(post_id))
+      .order_by(post_poll_option::position)
+      .load::<Self>(conn)
+      .await
+  }
+}
+
+impl PostPollVote {
+  /// Adds the votes of a person and updates the vote counts. Votes which already exist are
+  /// ignored, and the person is only counted as a new voter on their first vote. With
+  /// `only_first_vote`, nothing is added if the person voted before. Returns the number of added
+  /// votes.
+  pub async fn vote(
+    pool: &mut DbPool<'_>,
+    post_id: PostId,
+    person_id: PersonId,
+    option_ids: &[PostPollOptionId],
+    only_first_vote: bool,
+  ) -> LemmyResult<usize> {
+    let conn = &mut get_conn(pool).await?;
+    let forms = option_ids
+      .iter()
+      .map(|&option_id| PostPollVoteForm {
+        option_id,
+        person_id,
+        post_id,
+      })
+      .collect::<Vec<_>>();
+    conn
+      .build_transaction()
+      .run(|conn| {
+        Box::pin(async move {
+          // Lock the poll, so that concurrent votes of the same person can't both pass the check
+          post_poll::table
+            .find(post_id)
+            .select(post_poll::post_id)
+            .for_update()
+            .get_result::<PostId>(conn)
+            .await?;
+          let voted_before = select(exists(
+            post_poll_vote::table
+              .filter(post_poll_vote::post_id.eq(post_id))
+              .filter(post_poll_vote::person_id.eq(person_id)),
+          ))
+          .get_result::<bool>(conn)
+          .await?;
+          if voted_before && only_first_vote {
+            Err(LemmyErrorType::AlreadyVotedInPoll)?
+          }
+          let added = insert_into(post_poll_vote::table)
+            .values(forms)
+            .on_conflict_do_nothing()
+            .returning(post_poll_vote::option_id)
+            .get_results::<PostPollOptionId>(conn)
+            .await?;
+          diesel::update(post_poll_option::table.filter(post_poll_option::id.eq_any(&added)))
+            .set(post_poll_option::vote_count.eq(post_poll_option::vote_count + 1))
+            .execute(conn)
+            .await?;
+          if !voted_before && !added.is_empty() {
+            diesel::update(post_poll::table.find(post_id))
+              .set(post_poll::voters_count.eq(post_poll::voters_count + 1))
+              .execute(conn)
+              .await?;
+          }
+          Ok::<_, LemmyError>(added.len())
+        }) as _
+      })
+      .await
+  }
+
+  /// The options which the person voted for.
+  pub async fn read_for_person(
+    pool: &mut DbPool<'_>,
+    post_id: PostId,
+    person_id: PersonId,
+  ) -> Result<Vec<PostPollOptionId>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    post_poll_vote::table
+      .filter(post_poll_vote::post_id.eq(post_id))
+      .filter(post_poll_vote::person_id.eq(person_id))
+      .select(post_poll_vote::option_id)
+      .load(conn)
+      .await
+  }
+}
+
+#[cfg(test)]
+mod tests {
+
+  use crate::{
+    source::{
+      community::{Community, CommunityInsertForm},
+      instance::Instance,
+      person::{Person, PersonInsertForm},
+      post::{Post, PostInsertForm},
+      post_poll::{
+        PostPoll,
+        PostPollInsertForm,
+        PostPollOption,
+        PostPollOptionInsertForm,
+        PostPollVote,
+      },
+    },
+    traits::Crud,
+    utils::build_db_pool_for_tests,
+  };
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_poll_votes() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let instance = Instance::read_or_create(pool, "my_domain.tld".to_string()).await?;
+    let person_form = PersonInsertForm::test_form(instance.id, "poll_voter");
+    let person = Person::create(pool, &person_form).await?;
+    let community_form = CommunityInsertForm::new(
+      instance.id,
+      "test_poll".to_string(),
+      "nada".to_owned(),
+      "pubkey".to_string(),
+    );
+    let community = Community::create(pool, &community_form).await?;
+    let post_form = PostInsertForm::new("poll".into(), person.id, community.id);
+    let post = Post::create(pool, &post_form).await?;
+
+    let poll_form = PostPollInsertForm::new(post.id, true, None);
+    let option_forms = ["a", "b", "c"]
+      .iter()
+      .zip(0..)
+      .map(|(name, position)| PostPollOptionInsertForm::new(post.id, name.to_string(), position))
+      .collect::<Vec<_>>();
+    PostPoll::upsert(pool, &poll_form, &option_forms).await?;
+    let option_ids = PostPollOption::read_for_poll(pool, post.id)
+      .await?
+      .into_iter()
+      .map(|o| o.id)
+      .collect::<Vec<_>>();
+    assert_eq!(3, option_ids.len());
+
+    let first_votes = option_ids.get(0..2).unwrap_or_default();
+    let added = PostPollVote::vote(pool, post.id, person.id, first_votes, true).await?;
+    assert_eq!(2, added);
+    // Votes from the api must be cast at once
+    let second_votes = option_ids.get(1..3).unwrap_or_default();
+    let res = PostPollVote::vote(pool, post.id, person.id, second_votes, true).await;
+    assert!(res.is_err());
+    // Voting again for an existing option is ignored, and the voter is only counted once
+    let added = PostPollVote::vote(pool, post.id, person.id, second_votes, false).await?;
+    assert_eq!(1, added);
+    let poll = PostPoll::read(pool, post.id).await?;
+    assert_eq!(1, poll.voters_count);
+    let counts = PostPollOption::read_for_poll(pool, post.id)
+      .await?
+      .into_iter()
+      .map(|o| o.vote_count)
+      .collect::<Vec<_>>();
+    assert_eq!(vec![1, 1, 1], counts);
+    let my_votes = PostPollVote::read_for_person(pool, post.id, person.id).await?;
+    assert_eq!(3, my_votes.len());
+
+    let other_person_form = PersonInsertForm::test_form(instance.id, "poll_voter_2");
+    let other_person = Person::create(pool, &other_person_form).await?;
+    let last_vote = option_ids.get(2..3).unwrap_or_default();
+    PostPollVote::vote(pool, post.id, other_person.id, last_vote, true).await?;
+    let poll = PostPoll::read(pool, post.id).await?;
+    assert_eq!(2, poll.voters_count);
+
+    // Updating the poll removes options which are missing, together with their votes, and the
+    // people who only voted for them are no longer counted as voters
+    let poll =
+      PostPoll::upsert(pool, &poll_form, option_forms.get(0..2).unwrap_or_default()).await?;
+    assert_eq!(1, poll.voters_count);
+    let options = PostPollOption::read_for_poll(pool, post.id).await?;
+    assert_eq!(
+      vec!["a", "b"],
+      options.iter().map(|o| o.name.as_str()).collect::<Vec<_>>()
+    );
+    let my_votes = PostPollVote::read_for_person(pool, post.id, person.id).await?;
+    assert_eq!(2, my_votes.len());
+
+    Instance::delete(pool, instance.id).await?;
+    Ok(())
+  }
+}
@@ -0,0 +1,120 @@
+use crate::{
+  newtypes::PersonId,
+  schema::post_revision,
+  source::{
+    post::Post,
+    post_revision::{PostRevision, PostRevisionInsertForm},
+  },
+  utils::{get_conn, DbPool},
+};
+use diesel::{insert_into, result::Error};
+use diesel_async::RunQueryDsl;
+
+impl PostRevision {
+  /// Stores the previous version of an edited post. Nothing is stored if the name, url and body
+  /// didn't change, for example when only the language was edited.
+  pub async fn record(
+    pool: &mut DbPool<'_>,
+    previous: &Post,
+    current: &Post,
+    editor_id: PersonId,
+  ) -> Result<Option<Self>, Error> {
+    if previous.name == current.name && previous.url == current.url && previous.body == current.body
+    {
+      return Ok(None);
+    }
+    let form = PostRevisionInsertForm::new(
+      previous.id,
+      editor_id,
+      previous.name.clone(),
+      previous.url.clone(),
+      previous.body.clone(),
+    );
+    let conn = &mut get_conn(pool).await?;
+    insert_into(post_revision::table)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+      .map(Some)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+
+  use crate::{
+    schema::post_revision,
+    source::{
+      community::{Community, CommunityInsertForm},
+      instance::Instance,
+      person::{Person, PersonInsertForm},
+      post::{Post, PostInsertForm, PostUpdateForm},
+      post_revision::PostRevision,
+    },
+    traits::Crud,
+    utils::{build_db_pool_for_tests, get_conn},
+  };
+  use diesel::{ExpressionMethods, QueryDsl};
+  use diesel_async::RunQueryDsl;
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_record_revision() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let instance = Instance::read_or_create(pool, "my_domain.tld".to_string()).await?;
+    let person_form = PersonInsertForm::test_form(instance.id, "post_reviser");
+    let person = Person::create(pool, &person_form).await?;
+    let community_form = CommunityInsertForm::new(
+      instance.id,
+      "test_revision".to_string(),
+      "nada".to_owned(),
+      "pubkey".to_string(),
+    );
+    let community = Community::create(pool, &community_form).await?;
+    let post_form = PostInsertForm {
+      body: Some("first body".to_string()),
+      ..PostInsertForm::new("first title".into(), person.id, community.id)
+    };
+    let post = Post::create(pool, &post_form).await?;
+
+    let update_form = PostUpdateForm {
+      body: Some(Some("second body".to_string())),
+      ..Default::default()
+    };
+    let updated_post = Post::update(pool, post.id, &update_form).await?;
+    let revision = PostRevision::record(pool, &post, &updated_post, person.id)
+      .await?
+      .expect("body changed");
+    assert_eq!(post.id, revision.post_id);
+    assert_eq!(person.id, revision.editor_id);
+    assert_eq!("first title", revision.name);
+    assert_eq!(Some("first body".to_string()), revision.body);
+
+    // Edits which don't change the content are not recorded
+    let update_form = PostUpdateForm {
+      nsfw: Some(true),
+      ..Default::default()
+    };
+    let nsfw_post = Post::update(pool, post.id, &update_form).await?;
+    let revision = PostRevision::record(pool, &updated_post, &nsfw_post, person.id).await?;
+    assert_eq!(None, revision);
+
+    // Deleting the account also removes the edit history
+    Post::permadelete_for_creator(pool, person.id).await?;
+    let conn = &mut get_conn(pool).await?;
+    let revisions: i64 = post_revision::table
+      .filter(post_revision::post_id.eq(post.id))
+      .count()
+      .get_result(conn)
+      .await?;
+    assert_eq!(0, revisions);
+
+    Instance::delete(pool, instance.id).await?;
+    Ok(())
+  }
+}
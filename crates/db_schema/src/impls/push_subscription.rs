@@ -0,0 +1,131 @@
+use crate::{
+  newtypes::{LocalUserId, PushSubscriptionId},
+  schema::push_subscription,
+  source::push_subscription::{PushSubscription, PushSubscriptionInsertForm},
+  utils::{get_conn, DbPool},
+};
+use diesel::{delete, insert_into, result::Error, ExpressionMethods, QueryDsl};
+use diesel_async::RunQueryDsl;
+
+impl PushSubscription {
+  /// Stores the subscription. If the endpoint is already known, its keys and owner are replaced,
+  /// as browsers keep the same endpoint when the subscription is renewed.
+  pub async fn create(
+    pool: &mut DbPool<'_>,
+    form: &PushSubscriptionInsertForm,
+  ) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(push_subscription::table)
+      .values(form)
+      .on_conflict(push_subscription::endpoint)
+      .do_update()
+      .set(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+
+  pub async fn list(pool: &mut DbPool<'_>, local_user_id: LocalUserId) -> Result<Vec<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    push_subscription::table
+      .filter(push_subscription::local_user_id.eq(local_user_id))
+      .order_by(push_subscription::published.desc())
+      .load::<Self>(conn)
+      .await
+  }
+
+  /// Removes the subscription with the given endpoint, if it belongs to the user.
+  pub async fn delete_by_endpoint(
+    pool: &mut DbPool<'_>,
+    local_user_id: LocalUserId,
+    endpoint: &str,
+  ) -> Result<usize, Error> {
+    let conn = &mut get_conn(pool).await?;
+    delete(
+      push_subscription::table
+        .filter(push_subscription::local_user_id.eq(local_user_id))
+        .filter(push_subscription::endpoint.eq(endpoint)),
+    )
+    .execute(conn)
+    .await
+  }
+
+  /// Removes a subscription which was rejected by the push service.
+  pub async fn delete(pool: &mut DbPool<'_>, id: PushSubscriptionId) -> Result<usize, Error> {
+    let conn = &mut get_conn(pool).await?;
+    delete(push_subscription::table.find(id))
+      .execute(conn)
+      .await
+  }
+}
+
+#[cfg(test)]
+mod tests {
+
+  use super::*;
+  use crate::{
+    source::{
+      instance::Instance,
+      local_user::{LocalUser, LocalUserInsertForm},
+      person::{Person, PersonInsertForm},
+    },
+    traits::Crud,
+    utils::build_db_pool_for_tests,
+  };
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_push_subscription() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let instance = Instance::read_or_create(pool, "my_domain.tld".to_string()).await?;
+    let person_form = PersonInsertForm::test_form(instance.id, "push_user");
+    let person = Person::create(pool, &person_form).await?;
+    let user_form = LocalUserInsertForm::test_form(person.id);
+    let user = LocalUser::create(pool, &user_form, vec![]).await?;
+    let other_person_form = PersonInsertForm::test_form(instance.id, "push_other");
+    let other_person = Person::create(pool, &other_person_form).await?;
+    let other_user_form = LocalUserInsertForm::test_form(other_person.id);
+    let other_user = LocalUser::create(pool, &other_user_form, vec![]).await?;
+
+    let endpoint = "https://push.example.com/abc".to_string();
+    let form = PushSubscriptionInsertForm::new(
+      user.id,
+      endpoint.clone(),
+      "key1".to_string(),
+      "auth1".to_string(),
+    );
+    let first = PushSubscription::create(pool, &form).await?;
+
+    // Renewing the subscription replaces the keys instead of adding a second row
+    let form = PushSubscriptionInsertForm::new(
+      user.id,
+      endpoint.clone(),
+      "key2".to_string(),
+      "auth2".to_string(),
+    );
+    let renewed = PushSubscription::create(pool, &form).await?;
+    assert_eq!(first.id, renewed.id);
+    assert_eq!("key2", renewed.p256dh);
+    assert_eq!(vec![renewed], PushSubscription::list(pool, user.id).await?);
+
+    // Subscriptions can only be removed by their owner
+    assert_eq!(
+      0,
+      PushSubscription::delete_by_endpoint(pool, other_user.id, &endpoint).await?
+    );
+    assert_eq!(
+      1,
+      PushSubscription::delete_by_endpoint(pool, user.id, &endpoint).await?
+    );
+    assert!(PushSubscription::list(pool, user.id).await?.is_empty());
+
+    Person::delete(pool, person.id).await?;
+    Person::delete(pool, other_person.id).await?;
+    Instance::delete(pool, instance.id).await?;
+    Ok(())
+  }
+}
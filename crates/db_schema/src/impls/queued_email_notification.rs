@@ -0,0 +1,163 @@
+use crate::{
+  newtypes::{LocalUserId, QueuedEmailNotificationId},
+  schema::{local_user_email_digest, queued_email_notification},
+  source::queued_email_notification::{QueuedEmailNotification, QueuedEmailNotificationInsertForm},
+  utils::{get_conn, DbPool},
+  EmailDigestMode,
+};
+use chrono::{DateTime, Utc};
+use diesel::{
+  delete,
+  insert_into,
+  result::Error,
+  ExpressionMethods,
+  JoinOnDsl,
+  NullableExpressionMethods,
+  QueryDsl,
+};
+use diesel_async::RunQueryDsl;
+
+impl QueuedEmailNotification {
+  pub async fn create(
+    pool: &mut DbPool<'_>,
+    form: &QueuedEmailNotificationInsertForm,
+  ) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(queued_email_notification::table)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+
+  /// All queued notifications of the user, oldest first.
+  pub async fn list(pool: &mut DbPool<'_>, local_user_id: LocalUserId) -> Result<Vec<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    queued_email_notification::table
+      .filter(queued_email_notification::local_user_id.eq(local_user_id))
+      .order_by(queued_email_notification::published.asc())
+      .then_order_by(queued_email_notification::id.asc())
+      .load::<Self>(conn)
+      .await
+  }
+
+  pub async fn delete(
+    pool: &mut DbPool<'_>,
+    ids: &[QueuedEmailNotificationId],
+  ) -> Result<usize, Error> {
+    let conn = &mut get_conn(pool).await?;
+    delete(queued_email_notification::table.filter(queued_email_notification::id.eq_any(ids)))
+      .execute(conn)
+      .await
+  }
+
+  /// Users with queued notifications whose digest period is over. This includes users who
+  /// switched back to instant emails while they had notifications in the queue.
+  pub async fn list_due_users(pool: &mut DbPool<'_>) -> Result<Vec<LocalUserId>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    let users: Vec<(LocalUserId, Option<EmailDigestMode>, Option<DateTime<Utc>>)> =
+      queued_email_notification::table
+        .left_join(
+          local_user_email_digest::table.on(
+            local_user_email_digest::local_user_id.eq(queued_email_notification::local_user_id),
+          ),
+        )
+        .select((
+          queued_email_notification::local_user_id,
+          local_user_email_digest::mode.nullable(),
+          local_user_email_digest::last_sent.nullable(),
+        ))
+        .distinct()
+        .load(conn)
+        .await?;
+
+    let now = Utc::now();
+    Ok(
+      users
+        .into_iter()
+        .filter(|(_, mode, last_sent)| {
+          match (mode.and_then(EmailDigestMode::interval), last_sent) {
+            (Some(interval), Some(last_sent)) => *last_sent + interval <= now,
+            _ => true,
+          }
+        })
+        .map(|(local_user_id, _, _)| local_user_id)
+        .collect(),
+    )
+  }
+}
+
+#[cfg(test)]
+mod tests {
+
+  use super::*;
+  use crate::{
+    source::{
+      instance::Instance,
+      local_user::{LocalUser, LocalUserInsertForm},
+      local_user_email_digest::LocalUserEmailDigest,
+      person::{Person, PersonInsertForm},
+    },
+    traits::Crud,
+    utils::build_db_pool_for_tests,
+  };
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_email_digest_queue() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let instance = Instance::read_or_create(pool, "my_domain.tld".to_string()).await?;
+    let person_form = PersonInsertForm::test_form(instance.id, "digest_user");
+    let person = Person::create(pool, &person_form).await?;
+    let user_form = LocalUserInsertForm::test_form(person.id);
+    let user = LocalUser::create(pool, &user_form, vec![]).await?;
+
+    assert_eq!(
+      EmailDigestMode::Instant,
+      LocalUserEmailDigest::read_mode(pool, user.id).await?
+    );
+    LocalUserEmailDigest::set_mode(pool, user.id, EmailDigestMode::Daily).await?;
+    assert_eq!(
+      EmailDigestMode::Daily,
+      LocalUserEmailDigest::read_mode(pool, user.id).await?
+    );
+
+    let form =
+      QueuedEmailNotificationInsertForm::new(user.id, "first".to_string(), "<p>1</p>".to_string());
+    let first = QueuedEmailNotification::create(pool, &form).await?;
+    let form =
+      QueuedEmailNotificationInsertForm::new(user.id, "second".to_string(), "<p>2</p>".to_string());
+    let second = QueuedEmailNotification::create(pool, &form).await?;
+    assert_eq!(
+      vec![first.clone(), second.clone()],
+      QueuedEmailNotification::list(pool, user.id).await?
+    );
+
+    // The daily period only just started
+    assert!(!QueuedEmailNotification::list_due_users(pool)
+      .await?
+      .contains(&user.id));
+
+    // Switching back to instant emails sends the queue right away
+    LocalUserEmailDigest::set_mode(pool, user.id, EmailDigestMode::Instant).await?;
+    assert!(QueuedEmailNotification::list_due_users(pool)
+      .await?
+      .contains(&user.id));
+
+    assert_eq!(
+      2,
+      QueuedEmailNotification::delete(pool, &[first.id, second.id]).await?
+    );
+    assert!(!QueuedEmailNotification::list_due_users(pool)
+      .await?
+      .contains(&user.id));
+
+    Person::delete(pool, person.id).await?;
+    Instance::delete(pool, instance.id).await?;
+    Ok(())
+  }
+}
@@ -0,0 +1,270 @@
+use crate::{
+  newtypes::ScheduledJobId,
+  schema::{scheduled_job, scheduled_job_run},
+  source::scheduled_job::{ScheduledJob, ScheduledJobForm, ScheduledJobRun, ScheduledJobRunForm},
+  utils::{get_conn, limit_and_offset, now, DbPool},
+};
+use chrono::{DateTime, TimeDelta, Utc};
+use diesel::{
+  dsl::IntervalDsl,
+  insert_into,
+  result::Error,
+  upsert::excluded,
+  BoolExpressionMethods,
+  ExpressionMethods,
+  NullableExpressionMethods,
+  OptionalExtension,
+  QueryDsl,
+};
+use diesel_async::RunQueryDsl;
+use std::cmp::min;
+
+/// Delay before the first retry of a failed job. It doubles with each consecutive failure, but is
+/// never longer than the regular job interval.
+const RETRY_BASE_DELAY_SECS: i64 = 30;
+
+impl ScheduledJob {
+  /// Creates the jobs if they don't exist yet, and updates their intervals. The schedule of
+  /// existing jobs is kept, so that a restart doesn't delay them.
+  pub async fn register(pool: &mut DbPool<'_>, forms: &[ScheduledJobForm]) -> Result<(), Error> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(scheduled_job::table)
+      .values(forms)
+      .on_conflict(scheduled_job::name)
+      .do_update()
+      .set(scheduled_job::interval_secs.eq(excluded(scheduled_job::interval_secs)))
+      .execute(conn)
+      .await?;
+    Ok(())
+  }
+
+  pub async fn list(pool: &mut DbPool<'_>) -> Result<Vec<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    scheduled_job::table
+      .order_by(scheduled_job::name)
+      .load::<Self>(conn)
+      .await
+  }
+
+  /// Schedules the job to run as soon as possible.
+  pub async fn run_now(pool: &mut DbPool<'_>, name: &str) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(scheduled_job::table.filter(scheduled_job::name.eq(name)))
+      .set(scheduled_job::next_run.eq(now()))
+      .get_result::<Self>(conn)
+      .await
+  }
+
+  /// Takes a lease on a job which is due, so that no other process runs it at the same time. Only
+  /// jobs with one of the given names are considered, as those are the ones which the calling
+  /// process knows how to run. Returns `None` if there is no job to run right now.
+  ///
+  /// If the process dies while running the job, other processes can pick it up again after the
+  /// lease expires.
+  pub async fn acquire(
+    pool: &mut DbPool<'_>,
+    names: &[String],
+    worker: &str,
+    lease: TimeDelta,
+  ) -> Result<Option<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    let names = names.to_vec();
+    let worker = worker.to_string();
+    conn
+      .build_transaction()
+      .run(|conn| {
+        Box::pin(async move {
+          let job_id = scheduled_job::table
+            .filter(scheduled_job::name.eq_any(names))
+            .filter(scheduled_job::next_run.le(now()))
+            .filter(
+              scheduled_job::locked_until
+                .is_null()
+                .or(scheduled_job::locked_until.lt(now().nullable())),
+            )
+            .order_by(scheduled_job::next_run)
+            .select(scheduled_job::id)
+            .for_update()
+            .skip_locked()
+            .first::<ScheduledJobId>(conn)
+            .await
+            .optional()?;
+          let Some(job_id) = job_id else {
+            return Ok(None);
+          };
+          diesel::update(scheduled_job::table.find(job_id))
+            .set((
+              scheduled_job::locked_until.eq(Utc::now() + lease),
+              scheduled_job::locked_by.eq(worker),
+              scheduled_job::last_started.eq(now().nullable()),
+            ))
+            .get_result::<Self>(conn)
+            .await
+            .map(Some)
+        }) as _
+      })
+      .await
+  }
+
+  /// Releases the lease after a run, records the run in the history and schedules the next run.
+  /// Failed jobs are retried with exponential backoff.
+  pub async fn finish(
+    pool: &mut DbPool<'_>,
+    job: &Self,
+    started: DateTime<Utc>,
+    error: Option<String>,
+  ) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    let (next_run, failure_count) = if error.is_some() {
+      (Utc::now() + job.retry_delay(), job.failure_count + 1)
+    } else {
+      (Utc::now() + job.interval(), 0)
+    };
+    let run_form = ScheduledJobRunForm {
+      job_id: job.id,
+      started,
+      error: error.clone(),
+    };
+    let job_id = job.id;
+    conn
+      .build_transaction()
+      .run(|conn| {
+        Box::pin(async move {
+          insert_into(scheduled_job_run::table)
+            .values(run_form)
+            .execute(conn)
+            .await?;
+          diesel::update(scheduled_job::table.find(job_id))
+            .set((
+              scheduled_job::next_run.eq(next_run),
+              scheduled_job::failure_count.eq(failure_count),
+              scheduled_job::last_error.eq(error),
+              scheduled_job::last_finished.eq(now().nullable()),
+              scheduled_job::locked_until.eq(None::<DateTime<Utc>>),
+              scheduled_job::locked_by.eq(None::<String>),
+            ))
+            .get_result::<Self>(conn)
+            .await
+        }) as _
+      })
+      .await
+  }
+
+  fn interval(&self) -> TimeDelta {
+    TimeDelta::seconds(self.interval_secs.into())
+  }
+
+  /// Delay before retrying after a failed run
+  fn retry_delay(&self) -> TimeDelta {
+    let factor = 2_i64.saturating_pow(self.failure_count.clamp(0, 30).unsigned_abs());
+    min(
+      TimeDelta::seconds(RETRY_BASE_DELAY_SECS.saturating_mul(factor)),
+      self.interval(),
+    )
+  }
+}
+
+impl ScheduledJobRun {
+  /// Lists past runs, newest first. Optionally only for a single job.
+  pub async fn list(
+    pool: &mut DbPool<'_>,
+    job_id: Option<ScheduledJobId>,
+    page: Option<i64>,
+    limit: Option<i64>,
+  ) -> Result<Vec<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    let (limit, offset) = limit_and_offset(page, limit)?;
+    let mut query = scheduled_job_run::table.into_boxed();
+    if let Some(job_id) = job_id {
+      query = query.filter(scheduled_job_run::job_id.eq(job_id));
+    }
+    query
+      .order_by(scheduled_job_run::started.desc())
+      .offset(offset)
+      .limit(limit)
+      .load::<Self>(conn)
+      .await
+  }
+
+  /// Delete the run history after a week
+  pub async fn delete_old(pool: &mut DbPool<'_>) -> Result<usize, Error> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::delete(scheduled_job_run::table.filter(scheduled_job_run::started.lt(now() - 7.days())))
+      .execute(conn)
+      .await
+  }
+}
+
+#[cfg(test)]
+mod tests {
+
+  use super::*;
+  use crate::utils::build_db_pool_for_tests;
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_acquire_and_finish() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let forms = vec![ScheduledJobForm {
+      name: "test_job".to_string(),
+      interval_secs: 3600,
+    }];
+    let names = vec!["test_job".to_string()];
+    ScheduledJob::register(pool, &forms).await?;
+    // Registering again must not create a duplicate
+    ScheduledJob::register(pool, &forms).await?;
+    assert_eq!(1, ScheduledJob::list(pool).await?.len());
+
+    // New jobs are due immediately, and can only be taken by one worker
+    let lease = TimeDelta::minutes(5);
+    assert!(ScheduledJob::acquire(pool, &[], "worker_1", lease)
+      .await?
+      .is_none());
+    let job = ScheduledJob::acquire(pool, &names, "worker_1", lease).await?;
+    let Some(job) = job else {
+      panic!("job should be due");
+    };
+    assert_eq!(Some("worker_1".to_string()), job.locked_by);
+    assert!(ScheduledJob::acquire(pool, &names, "worker_2", lease)
+      .await?
+      .is_none());
+
+    // A failed run is retried with backoff
+    let started = Utc::now();
+    let failed = ScheduledJob::finish(pool, &job, started, Some("broken".to_string())).await?;
+    assert_eq!(1, failed.failure_count);
+    assert_eq!(Some("broken".to_string()), failed.last_error);
+    assert!(failed.locked_until.is_none());
+    assert!(failed.next_run > started);
+    assert!(failed.next_run < started + TimeDelta::minutes(1));
+    assert!(ScheduledJob::acquire(pool, &names, "worker_2", lease)
+      .await?
+      .is_none());
+
+    // Triggering the job manually makes it due again
+    ScheduledJob::run_now(pool, "test_job").await?;
+    let job = ScheduledJob::acquire(pool, &names, "worker_2", lease).await?;
+    let Some(job) = job else {
+      panic!("job should be due");
+    };
+    let succeeded = ScheduledJob::finish(pool, &job, Utc::now(), None).await?;
+    assert_eq!(0, succeeded.failure_count);
+    assert!(succeeded.last_error.is_none());
+    assert!(succeeded.next_run > Utc::now() + TimeDelta::minutes(59));
+
+    let runs = ScheduledJobRun::list(pool, Some(job.id), None, None).await?;
+    assert_eq!(
+      vec![None, Some("broken".to_string())],
+      runs.into_iter().map(|r| r.error).collect::<Vec<_>>()
+    );
+
+    let conn = &mut get_conn(pool).await?;
+    diesel::delete(scheduled_job::table).execute(conn).await?;
+    Ok(())
+  }
+}
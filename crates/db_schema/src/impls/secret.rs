@@ -1,9 +1,9 @@
 use crate::{
-  schema::secret::dsl::secret,
+  schema::secret::dsl::{secret, vapid_private_key, vapid_public_key},
   source::secret::Secret,
   utils::{get_conn, DbPool},
 };
-use diesel::result::Error;
+use diesel::{result::Error, ExpressionMethods, QueryDsl};
 use diesel_async::RunQueryDsl;
 
 impl Secret {
@@ -13,6 +13,24 @@ impl Secret {
     Self::read_secrets(pool).await
   }
 
+  /// Stores the keys for Web Push, unless they were already set in the meantime. Returns the
+  /// secrets as they are stored in the database afterwards.
+  pub async fn set_vapid_keys(
+    pool: &mut DbPool<'_>,
+    private_key: String,
+    public_key: String,
+  ) -> Result<Secret, Error> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(secret.filter(vapid_private_key.is_null()))
+      .set((
+        vapid_private_key.eq(private_key),
+        vapid_public_key.eq(public_key),
+      ))
+      .execute(conn)
+      .await?;
+    secret.first(conn).await
+  }
+
   async fn read_secrets(pool: &mut DbPool<'_>) -> Result<Self, Error> {
     let conn = &mut get_conn(pool).await?;
     secret.first(conn).await
@@ -1,19 +1,19 @@
 use crate::{
-  newtypes::TagId,
+  newtypes::{CommunityId, DbUrl, PostId, TagId},
   schema::{post_tag, tag},
-  source::tag::{PostTagInsertForm, Tag, TagInsertForm},
+  source::tag::{PostTagInsertForm, Tag, TagInsertForm, TagUpdateForm},
   traits::Crud,
   utils::{get_conn, DbPool},
 };
-use diesel::{insert_into, result::Error, QueryDsl};
+use diesel::{delete, insert_into, result::Error, ExpressionMethods, QueryDsl};
 use diesel_async::RunQueryDsl;
-use lemmy_utils::error::LemmyResult;
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
 
 #[async_trait]
 impl Crud for Tag {
   type InsertForm = TagInsertForm;
 
-  type UpdateForm = TagInsertForm;
+  type UpdateForm = TagUpdateForm;
 
   type IdType = TagId;
 
@@ -38,6 +38,106 @@ impl Crud for Tag {
   }
 }
 
+impl Tag {
+  /// Inserts the tag, or updates the existing tag with the same `ap_id`. This is used for
+  /// federated tags.
+  pub async fn upsert(pool: &mut DbPool<'_>, form: &TagInsertForm) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(tag::table)
+      .values(form)
+      .on_conflict(tag::ap_id)
+      .do_update()
+      .set(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+
+  /// All tags of a community which are not deleted, ordered by name.
+  pub async fn read_for_community(
+    pool: &mut DbPool<'_>,
+    community_id: CommunityId,
+  ) -> Result<Vec<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    tag::table
+      .filter(tag::community_id.eq(community_id))
+      .filter(tag::deleted.eq(false))
+      .order_by(tag::name)
+      .load::<Self>(conn)
+      .await
+  }
+
+  /// All tags which are assigned to the given post and not deleted.
+  pub async fn read_for_post(pool: &mut DbPool<'_>, post_id: PostId) -> Result<Vec<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    post_tag::table
+      .inner_join(tag::table)
+      .filter(post_tag::post_id.eq(post_id))
+      .filter(tag::deleted.eq(false))
+      .select(tag::all_columns)
+      .order_by(tag::name)
+      .load::<Self>(conn)
+      .await
+  }
+
+  /// Reads the tags of a community which have one of the given activitypub ids.
+  pub async fn read_from_apub_ids(
+    pool: &mut DbPool<'_>,
+    community_id: CommunityId,
+    ap_ids: &[DbUrl],
+  ) -> Result<Vec<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    tag::table
+      .filter(tag::community_id.eq(community_id))
+      .filter(tag::deleted.eq(false))
+      .filter(tag::ap_id.eq_any(ap_ids))
+      .load::<Self>(conn)
+      .await
+  }
+
+  /// Marks all tags of the community as deleted, except those with the given activitypub ids.
+  /// Used to mirror tag removals from remote communities.
+  pub async fn delete_for_community_except(
+    pool: &mut DbPool<'_>,
+    community_id: CommunityId,
+    keep_ap_ids: &[DbUrl],
+  ) -> Result<usize, Error> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(
+      tag::table
+        .filter(tag::community_id.eq(community_id))
+        .filter(tag::deleted.eq(false))
+        .filter(tag::ap_id.ne_all(keep_ap_ids)),
+    )
+    .set(tag::deleted.eq(true))
+    .execute(conn)
+    .await
+  }
+
+  /// Ensures that all the given tags belong to the community and are not deleted.
+  pub async fn check_in_community(
+    pool: &mut DbPool<'_>,
+    community_id: CommunityId,
+    tag_ids: &[TagId],
+  ) -> LemmyResult<()> {
+    let conn = &mut get_conn(pool).await?;
+    let count = tag::table
+      .filter(tag::community_id.eq(community_id))
+      .filter(tag::deleted.eq(false))
+      .filter(tag::id.eq_any(tag_ids))
+      .count()
+      .get_result::<i64>(conn)
+      .await?;
+    let mut unique_ids = tag_ids.to_vec();
+    unique_ids.sort_by_key(|t| t.0);
+    unique_ids.dedup();
+    if usize::try_from(count).unwrap_or_default() == unique_ids.len() {
+      Ok(())
+    } else {
+      Err(LemmyErrorType::TagNotInCommunity.into())
+    }
+  }
+}
+
 impl PostTagInsertForm {
   pub async fn insert_tag_associations(
     pool: &mut DbPool<'_>,
@@ -50,4 +150,49 @@ impl PostTagInsertForm {
       .await?;
     Ok(())
   }
+
+  /// Adds a single tag to the post. Returns false if the post already had the tag.
+  pub async fn add_to_post(
+    pool: &mut DbPool<'_>,
+    post_id: PostId,
+    tag_id: TagId,
+  ) -> LemmyResult<bool> {
+    let conn = &mut get_conn(pool).await?;
+    let inserted = insert_into(post_tag::table)
+      .values(PostTagInsertForm { post_id, tag_id })
+      .on_conflict_do_nothing()
+      .execute(conn)
+      .await?;
+    Ok(inserted > 0)
+  }
+
+  /// Replaces all tag associations of the post with the given tags.
+  pub async fn set_for_post(
+    pool: &mut DbPool<'_>,
+    post_id: PostId,
+    tag_ids: &[TagId],
+  ) -> LemmyResult<()> {
+    let conn = &mut get_conn(pool).await?;
+    let forms = tag_ids
+      .iter()
+      .map(|&tag_id| PostTagInsertForm { post_id, tag_id })
+      .collect::<Vec<_>>();
+    conn
+      .build_transaction()
+      .run(|conn| {
+        Box::pin(async move {
+          delete(post_tag::table.filter(post_tag::post_id.eq(post_id)))
+            .execute(conn)
+            .await?;
+          insert_into(post_tag::table)
+            .values(forms)
+            .on_conflict_do_nothing()
+            .execute(conn)
+            .await?;
+          Ok::<_, Error>(())
+        }) as _
+      })
+      .await?;
+    Ok(())
+  }
 }
@@ -0,0 +1,282 @@
+use crate::{
+  newtypes::{CommentId, PersonId, PostId, ThreadReplyId},
+  schema::{thread_reply, thread_subscription},
+  source::{
+    comment::Comment,
+    thread_subscription::{
+      ThreadReply,
+      ThreadReplyInsertForm,
+      ThreadReplyUpdateForm,
+      ThreadSubscription,
+      ThreadSubscriptionForm,
+    },
+  },
+  traits::Crud,
+  utils::{get_conn, DbPool},
+};
+use diesel::{
+  delete,
+  dsl::insert_into,
+  result::Error,
+  BoolExpressionMethods,
+  ExpressionMethods,
+  PgExpressionMethods,
+  QueryDsl,
+};
+use diesel_async::RunQueryDsl;
+use std::collections::HashMap;
+
+impl ThreadSubscription {
+  /// Subscribes to or mutes a thread, replacing a previous subscription of the same thread.
+  pub async fn set(pool: &mut DbPool<'_>, form: &ThreadSubscriptionForm) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    let form = form.clone();
+    conn
+      .build_transaction()
+      .run(|conn| {
+        Box::pin(async move {
+          delete(
+            thread_subscription::table
+              .filter(thread_subscription::person_id.eq(form.person_id))
+              .filter(thread_subscription::post_id.eq(form.post_id))
+              .filter(thread_subscription::comment_id.is_not_distinct_from(form.comment_id)),
+          )
+          .execute(conn)
+          .await?;
+          insert_into(thread_subscription::table)
+            .values(form)
+            .get_result::<Self>(conn)
+            .await
+        }) as _
+      })
+      .await
+  }
+
+  /// Removes a subscription, or a mute if `muted` is true.
+  pub async fn remove(
+    pool: &mut DbPool<'_>,
+    person_id: PersonId,
+    post_id: PostId,
+    comment_id: Option<CommentId>,
+    muted: bool,
+  ) -> Result<usize, Error> {
+    let conn = &mut get_conn(pool).await?;
+    delete(
+      thread_subscription::table
+        .filter(thread_subscription::person_id.eq(person_id))
+        .filter(thread_subscription::post_id.eq(post_id))
+        .filter(thread_subscription::comment_id.is_not_distinct_from(comment_id))
+        .filter(thread_subscription::muted.eq(muted)),
+    )
+    .execute(conn)
+    .await
+  }
+
+  pub async fn list(pool: &mut DbPool<'_>, person_id: PersonId) -> Result<Vec<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    thread_subscription::table
+      .filter(thread_subscription::person_id.eq(person_id))
+      .order_by(thread_subscription::published.desc())
+      .load(conn)
+      .await
+  }
+
+  /// Returns everyone who should be notified about a new comment, meaning they are subscribed to
+  /// the post or to one of the parent comments. If there are multiple subscriptions in the
+  /// thread, the one closest to the new comment decides whether it is muted.
+  pub async fn list_subscribers(
+    pool: &mut DbPool<'_>,
+    comment: &Comment,
+  ) -> Result<Vec<PersonId>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    // The first element of the path is always 0, and the last one is the comment itself
+    let parent_ids: Vec<CommentId> = comment
+      .path
+      .0
+      .split('.')
+      .skip(1)
+      .filter_map(|id| id.parse().ok().map(CommentId))
+      .filter(|id| *id != comment.id)
+      .collect();
+
+    let subscriptions: Vec<(PersonId, Option<CommentId>, bool)> = thread_subscription::table
+      .filter(thread_subscription::post_id.eq(comment.post_id))
+      .filter(
+        thread_subscription::comment_id
+          .is_null()
+          .or(thread_subscription::comment_id.eq_any(&parent_ids)),
+      )
+      .select((
+        thread_subscription::person_id,
+        thread_subscription::comment_id,
+        thread_subscription::muted,
+      ))
+      .load(conn)
+      .await?;
+
+    // The depth of the subscribed comment in the thread, with 0 for the post
+    let depth = |comment_id: Option<CommentId>| {
+      comment_id
+        .and_then(|id| parent_ids.iter().position(|p| *p == id))
+        .map(|pos| pos + 1)
+        .unwrap_or_default()
+    };
+    let mut closest: HashMap<PersonId, (usize, bool)> = HashMap::new();
+    for (person_id, comment_id, muted) in subscriptions {
+      let depth = depth(comment_id);
+      let entry = closest.entry(person_id).or_insert((depth, muted));
+      if depth > entry.0 {
+        *entry = (depth, muted);
+      }
+    }
+
+    let mut subscribers: Vec<PersonId> = closest
+      .into_iter()
+      .filter(|(_, (_, muted))| !muted)
+      .map(|(person_id, _)| person_id)
+      .collect();
+    subscribers.sort_by_key(|p| p.0);
+    Ok(subscribers)
+  }
+}
+
+#[async_trait]
+impl Crud for ThreadReply {
+  type InsertForm = ThreadReplyInsertForm;
+  type UpdateForm = ThreadReplyUpdateForm;
+  type IdType = ThreadReplyId;
+
+  async fn create(pool: &mut DbPool<'_>, form: &Self::InsertForm) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    // Comment edits send the notifications again, so update the existing row in that case
+    insert_into(thread_reply::table)
+      .values(form)
+      .on_conflict((thread_reply::recipient_id, thread_reply::comment_id))
+      .do_update()
+      .set(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+
+  async fn update(
+    pool: &mut DbPool<'_>,
+    thread_reply_id: ThreadReplyId,
+    form: &Self::UpdateForm,
+  ) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(thread_reply::table.find(thread_reply_id))
+      .set(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+}
+
+impl ThreadReply {
+  pub async fn mark_all_as_read(
+    pool: &mut DbPool<'_>,
+    for_recipient_id: PersonId,
+  ) -> Result<Vec<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(
+      thread_reply::table
+        .filter(thread_reply::recipient_id.eq(for_recipient_id))
+        .filter(thread_reply::read.eq(false)),
+    )
+    .set(thread_reply::read.eq(true))
+    .get_results::<Self>(conn)
+    .await
+  }
+}
+
+#[cfg(test)]
+mod tests {
+
+  use super::*;
+  use crate::{
+    source::{
+      comment::CommentInsertForm,
+      community::{Community, CommunityInsertForm},
+      instance::Instance,
+      person::{Person, PersonInsertForm},
+      post::{Post, PostInsertForm},
+    },
+    utils::build_db_pool_for_tests,
+  };
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_thread_subscribers() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let instance = Instance::read_or_create(pool, "my_domain.tld".to_string()).await?;
+    let alice_form = PersonInsertForm::test_form(instance.id, "thread_alice");
+    let alice = Person::create(pool, &alice_form).await?;
+    let bob_form = PersonInsertForm::test_form(instance.id, "thread_bob");
+    let bob = Person::create(pool, &bob_form).await?;
+    let community_form = CommunityInsertForm::new(
+      instance.id,
+      "thread_community".into(),
+      "nada".to_owned(),
+      "pubkey".to_string(),
+    );
+    let community = Community::create(pool, &community_form).await?;
+    let post_form = PostInsertForm::new("thread post".into(), alice.id, community.id);
+    let post = Post::create(pool, &post_form).await?;
+
+    let comment_form = CommentInsertForm::new(bob.id, post.id, "top".into());
+    let top = Comment::create(pool, &comment_form, None).await?;
+    let comment_form = CommentInsertForm::new(bob.id, post.id, "child".into());
+    let child = Comment::create(pool, &comment_form, Some(&top.path)).await?;
+    let comment_form = CommentInsertForm::new(alice.id, post.id, "grandchild".into());
+    let grandchild = Comment::create(pool, &comment_form, Some(&child.path)).await?;
+
+    // Alice follows the whole post, bob only the replies to his top comment
+    let form = ThreadSubscriptionForm::new(alice.id, post.id, None, false);
+    ThreadSubscription::set(pool, &form).await?;
+    let form = ThreadSubscriptionForm::new(bob.id, post.id, Some(top.id), false);
+    ThreadSubscription::set(pool, &form).await?;
+    assert_eq!(
+      vec![alice.id],
+      ThreadSubscription::list_subscribers(pool, &top).await?
+    );
+    assert_eq!(
+      vec![alice.id, bob.id],
+      ThreadSubscription::list_subscribers(pool, &grandchild).await?
+    );
+
+    // Muting the child comment silences it and everything below for alice
+    let form = ThreadSubscriptionForm::new(alice.id, post.id, Some(child.id), true);
+    ThreadSubscription::set(pool, &form).await?;
+    assert_eq!(
+      vec![alice.id, bob.id],
+      ThreadSubscription::list_subscribers(pool, &child).await?
+    );
+    assert_eq!(
+      vec![bob.id],
+      ThreadSubscription::list_subscribers(pool, &grandchild).await?
+    );
+
+    // Subscribing again replaces the mute
+    let form = ThreadSubscriptionForm::new(alice.id, post.id, Some(child.id), false);
+    ThreadSubscription::set(pool, &form).await?;
+    assert_eq!(2, ThreadSubscription::list(pool, alice.id).await?.len());
+    assert_eq!(
+      vec![alice.id, bob.id],
+      ThreadSubscription::list_subscribers(pool, &grandchild).await?
+    );
+
+    ThreadSubscription::remove(pool, bob.id, post.id, Some(top.id), false).await?;
+    ThreadSubscription::remove(pool, alice.id, post.id, None, false).await?;
+    assert_eq!(
+      Vec::<PersonId>::new(),
+      ThreadSubscription::list_subscribers(pool, &top).await?
+    );
+
+    Instance::delete(pool, instance.id).await?;
+    Ok(())
+  }
+}
@@ -0,0 +1,387 @@
+use crate::{
+  newtypes::{CommunityId, WebhookId},
+  schema::{webhook, webhook_delivery, webhook_event},
+  source::webhook::{
+    Webhook,
+    WebhookDelivery,
+    WebhookDeliveryInsertForm,
+    WebhookEvent,
+    WebhookInsertForm,
+    WebhookUpdateForm,
+  },
+  traits::Crud,
+  utils::{get_conn, limit_and_offset, now, DbPool},
+  WebhookEventType,
+};
+use chrono::{TimeDelta, Utc};
+use diesel::{
+  delete,
+  dsl::{exists, IntervalDsl},
+  insert_into,
+  result::Error,
+  BoolExpressionMethods,
+  ExpressionMethods,
+  NullableExpressionMethods,
+  QueryDsl,
+};
+use diesel_async::RunQueryDsl;
+
+/// After this many failed attempts, a delivery is not retried anymore.
+const MAX_DELIVERY_ATTEMPTS: i32 = 8;
+
+/// Delay before the first retry of a failed delivery. It doubles with each further attempt.
+const RETRY_BASE_DELAY_SECS: i64 = 60;
+
+#[async_trait]
+impl Crud for Webhook {
+  type InsertForm = WebhookInsertForm;
+  type UpdateForm = WebhookUpdateForm;
+  type IdType = WebhookId;
+
+  async fn create(pool: &mut DbPool<'_>, form: &Self::InsertForm) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(webhook::table)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+
+  async fn update(
+    pool: &mut DbPool<'_>,
+    webhook_id: WebhookId,
+    form: &Self::UpdateForm,
+  ) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(webhook::table.find(webhook_id))
+      .set(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+}
+
+impl Webhook {
+  /// Lists the webhooks of a community, or the site-wide webhooks if no community is given.
+  pub async fn list(
+    pool: &mut DbPool<'_>,
+    community_id: Option<CommunityId>,
+  ) -> Result<Vec<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    let query = webhook::table.order_by(webhook::id).into_boxed();
+    let query = match community_id {
+      Some(community_id) => query.filter(webhook::community_id.eq(community_id)),
+      None => query.filter(webhook::community_id.is_null()),
+    };
+    query.load::<Self>(conn).await
+  }
+
+  /// Enabled webhooks which are subscribed to the event. Site-wide webhooks receive the events of
+  /// all communities.
+  pub async fn list_for_event(
+    pool: &mut DbPool<'_>,
+    event: WebhookEventType,
+    community_id: Option<CommunityId>,
+  ) -> Result<Vec<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    let subscribed = exists(
+      webhook_event::table
+        .filter(webhook_event::webhook_id.eq(webhook::id))
+        .filter(webhook_event::event.eq(event)),
+    );
+    let mut query = webhook::table
+      .filter(webhook::enabled)
+      .filter(subscribed)
+      .into_boxed();
+    query = match community_id {
+      Some(community_id) => query.filter(
+        webhook::community_id
+          .is_null()
+          .or(webhook::community_id.eq(community_id)),
+      ),
+      None => query.filter(webhook::community_id.is_null()),
+    };
+    query.load::<Self>(conn).await
+  }
+
+  /// Replaces the event types which the webhook is subscribed to.
+  pub async fn set_events(
+    pool: &mut DbPool<'_>,
+    webhook_id: WebhookId,
+    events: &[WebhookEventType],
+  ) -> Result<(), Error> {
+    let conn = &mut get_conn(pool).await?;
+    let forms = events
+      .iter()
+      .map(|&event| WebhookEvent { webhook_id, event })
+      .collect::<Vec<_>>();
+    conn
+      .build_transaction()
+      .run(|conn| {
+        Box::pin(async move {
+          delete(webhook_event::table.filter(webhook_event::webhook_id.eq(webhook_id)))
+            .execute(conn)
+            .await?;
+          insert_into(webhook_event::table)
+            .values(forms)
+            .on_conflict_do_nothing()
+            .execute(conn)
+            .await?;
+          Ok::<_, Error>(())
+        }) as _
+      })
+      .await
+  }
+
+  /// Reads the subscribed events for all the given webhooks.
+  pub async fn read_events(
+    pool: &mut DbPool<'_>,
+    webhook_ids: &[WebhookId],
+  ) -> Result<Vec<WebhookEvent>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    webhook_event::table
+      .filter(webhook_event::webhook_id.eq_any(webhook_ids))
+      .order_by(webhook_event::event)
+      .load::<WebhookEvent>(conn)
+      .await
+  }
+}
+
+impl WebhookDelivery {
+  pub async fn create(
+    pool: &mut DbPool<'_>,
+    forms: &[WebhookDeliveryInsertForm],
+  ) -> Result<Vec<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(webhook_delivery::table)
+      .values(forms)
+      .get_results::<Self>(conn)
+      .await
+  }
+
+  /// Takes up to `limit` deliveries which are due for another attempt. Their next attempt is
+  /// moved back by `lease`, so that they are retried later if the process dies while sending.
+  pub async fn claim_due(
+    pool: &mut DbPool<'_>,
+    limit: i64,
+    lease: TimeDelta,
+  ) -> Result<Vec<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    conn
+      .build_transaction()
+      .run(|conn| {
+        Box::pin(async move {
+          let ids = webhook_delivery::table
+            .filter(webhook_delivery::next_attempt.le(now().nullable()))
+            .order_by(webhook_delivery::next_attempt)
+            .select(webhook_delivery::id)
+            .limit(limit)
+            .for_update()
+            .skip_locked()
+            .load::<i32>(conn)
+            .await?;
+          diesel::update(webhook_delivery::table.filter(webhook_delivery::id.eq_any(ids)))
+            .set(webhook_delivery::next_attempt.eq(Utc::now() + lease))
+            .get_results::<Self>(conn)
+            .await
+        }) as _
+      })
+      .await
+  }
+
+  /// Stores the result of a delivery attempt. Failed deliveries are retried with exponential
+  /// backoff, until the maximum number of attempts is reached.
+  pub async fn finish_attempt(
+    pool: &mut DbPool<'_>,
+    delivery: &Self,
+    status: Option<i32>,
+    error: Option<String>,
+  ) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    let attempts = delivery.attempts + 1;
+    let (next_attempt, delivered) = match error {
+      None => (None, Some(Utc::now())),
+      Some(_) if attempts >= MAX_DELIVERY_ATTEMPTS => (None, None),
+      Some(_) => (Some(Utc::now() + Self::retry_delay(attempts)), None),
+    };
+    diesel::update(webhook_delivery::table.find(delivery.id))
+      .set((
+        webhook_delivery::attempts.eq(attempts),
+        webhook_delivery::next_attempt.eq(next_attempt),
+        webhook_delivery::delivered.eq(delivered),
+        webhook_delivery::last_status.eq(status),
+        webhook_delivery::last_error.eq(error),
+      ))
+      .get_result::<Self>(conn)
+      .await
+  }
+
+  /// Lists the deliveries of a webhook, newest first.
+  pub async fn list(
+    pool: &mut DbPool<'_>,
+    webhook_id: WebhookId,
+    page: Option<i64>,
+    limit: Option<i64>,
+  ) -> Result<Vec<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    let (limit, offset) = limit_and_offset(page, limit)?;
+    webhook_delivery::table
+      .filter(webhook_delivery::webhook_id.eq(webhook_id))
+      .order_by(webhook_delivery::published.desc())
+      .offset(offset)
+      .limit(limit)
+      .load::<Self>(conn)
+      .await
+  }
+
+  /// Delete the delivery log after a week, unless the delivery is still being retried.
+  pub async fn delete_old(pool: &mut DbPool<'_>) -> Result<usize, Error> {
+    let conn = &mut get_conn(pool).await?;
+    delete(
+      webhook_delivery::table
+        .filter(webhook_delivery::published.lt(now() - 7.days()))
+        .filter(webhook_delivery::next_attempt.is_null()),
+    )
+    .execute(conn)
+    .await
+  }
+
+  /// Delay before the next attempt, after the given number of failed attempts
+  fn retry_delay(attempts: i32) -> TimeDelta {
+    let factor = 2_i64.saturating_pow((attempts - 1).clamp(0, 30).unsigned_abs());
+    TimeDelta::seconds(RETRY_BASE_DELAY_SECS.saturating_mul(factor))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+
+  use super::*;
+  use crate::{
+    source::{
+      community::{Community, CommunityInsertForm},
+      instance::Instance,
+      person::{Person, PersonInsertForm},
+    },
+    utils::build_db_pool_for_tests,
+  };
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_webhook_events_and_deliveries() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let instance = Instance::read_or_create(pool, "my_domain.tld".to_string()).await?;
+    let person_form = PersonInsertForm::test_form(instance.id, "webhook_admin");
+    let person = Person::create(pool, &person_form).await?;
+    let community_form = CommunityInsertForm::new(
+      instance.id,
+      "webhook_community".into(),
+      "nada".to_owned(),
+      "pubkey".to_string(),
+    );
+    let community = Community::create(pool, &community_form).await?;
+    let other_community_form = CommunityInsertForm::new(
+      instance.id,
+      "webhook_other".into(),
+      "nada".to_owned(),
+      "pubkey".to_string(),
+    );
+    let other_community = Community::create(pool, &other_community_form).await?;
+
+    let site_form = WebhookInsertForm::new(
+      None,
+      person.id,
+      "https://example.com/site".to_string(),
+      "secret_secret_secret".to_string(),
+    );
+    let site_webhook = Webhook::create(pool, &site_form).await?;
+    Webhook::set_events(
+      pool,
+      site_webhook.id,
+      &[WebhookEventType::PostCreated, WebhookEventType::ModAction],
+    )
+    .await?;
+    let community_form = WebhookInsertForm::new(
+      Some(community.id),
+      person.id,
+      "https://example.com/community".to_string(),
+      "secret_secret_secret".to_string(),
+    );
+    let community_webhook = Webhook::create(pool, &community_form).await?;
+    Webhook::set_events(pool, community_webhook.id, &[WebhookEventType::PostCreated]).await?;
+
+    // Site-wide webhooks get events from all communities, community webhooks only from their own
+    let ids = |webhooks: Vec<Webhook>| webhooks.into_iter().map(|w| w.id).collect::<Vec<_>>();
+    let post_created = WebhookEventType::PostCreated;
+    assert_eq!(
+      vec![site_webhook.id, community_webhook.id],
+      ids(Webhook::list_for_event(pool, post_created, Some(community.id)).await?)
+    );
+    assert_eq!(
+      vec![site_webhook.id],
+      ids(Webhook::list_for_event(pool, post_created, Some(other_community.id)).await?)
+    );
+    assert_eq!(
+      vec![site_webhook.id],
+      ids(Webhook::list_for_event(pool, WebhookEventType::ModAction, Some(community.id)).await?)
+    );
+    assert_eq!(
+      vec![community_webhook.id],
+      ids(Webhook::list(pool, Some(community.id)).await?)
+    );
+
+    // Disabled webhooks don't get any events
+    let disable_form = WebhookUpdateForm {
+      enabled: Some(false),
+      ..Default::default()
+    };
+    Webhook::update(pool, site_webhook.id, &disable_form).await?;
+    assert_eq!(
+      vec![community_webhook.id],
+      ids(Webhook::list_for_event(pool, post_created, Some(community.id)).await?)
+    );
+
+    let forms = vec![WebhookDeliveryInsertForm {
+      webhook_id: community_webhook.id,
+      event: post_created,
+      payload: "{}".to_string(),
+      next_attempt: Utc::now(),
+    }];
+    WebhookDelivery::create(pool, &forms).await?;
+    let lease = TimeDelta::minutes(1);
+    let claimed = WebhookDelivery::claim_due(pool, 10, lease).await?;
+    assert_eq!(1, claimed.len());
+    // Claimed deliveries are not handed out again until the lease expires
+    assert!(WebhookDelivery::claim_due(pool, 10, lease)
+      .await?
+      .is_empty());
+
+    // A failed attempt is retried, a successful one isn't
+    let Some(delivery) = claimed.into_iter().next() else {
+      panic!("delivery should be claimed");
+    };
+    let failed =
+      WebhookDelivery::finish_attempt(pool, &delivery, Some(500), Some("error".to_string()))
+        .await?;
+    assert_eq!(1, failed.attempts);
+    assert!(failed.next_attempt.is_some());
+    assert!(failed.delivered.is_none());
+    let delivered = WebhookDelivery::finish_attempt(pool, &failed, Some(200), None).await?;
+    assert_eq!(2, delivered.attempts);
+    assert!(delivered.next_attempt.is_none());
+    assert!(delivered.delivered.is_some());
+    assert_eq!(
+      vec![delivered],
+      WebhookDelivery::list(pool, community_webhook.id, None, None).await?
+    );
+
+    Community::delete(pool, community.id).await?;
+    Community::delete(pool, other_community.id).await?;
+    Person::delete(pool, person.id).await?;
+    Instance::delete(pool, instance.id).await?;
+    Ok(())
+  }
+}
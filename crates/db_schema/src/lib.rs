@@ -112,6 +112,8 @@ pub enum SearchSortType {
   New,
   Top,
   Old,
+  /// Best matches for the search term first, using full text search ranking.
+  Relevance,
 }
 
 #[derive(
@@ -222,12 +224,15 @@ pub enum ModlogActionType {
   ModAdd,
   ModBan,
   ModHideCommunity,
+  ModAutomodAction,
+  ModWarnPerson,
   AdminPurgePerson,
   AdminPurgeCommunity,
   AdminPurgePost,
   AdminPurgeComment,
   AdminBlockInstance,
   AdminAllowInstance,
+  AdminLimitInstance,
 }
 
 #[derive(EnumString, Display, Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
@@ -240,6 +245,9 @@ pub enum InboxDataType {
   CommentMention,
   PostMention,
   PrivateMessage,
+  Warning,
+  Modmail,
+  ThreadReply,
 }
 
 #[derive(EnumString, Display, Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
@@ -320,6 +328,169 @@ pub enum FederationMode {
   Disable,
 }
 
+#[derive(EnumString, Display, Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(DbEnum, TS))]
+#[cfg_attr(
+  feature = "full",
+  ExistingTypePath = "crate::schema::sql_types::WebhookEventTypeEnum"
+)]
+#[cfg_attr(feature = "full", DbValueStyle = "verbatim")]
+#[cfg_attr(feature = "full", ts(export))]
+/// The events which can be sent to a webhook.
+pub enum WebhookEventType {
+  PostCreated,
+  PostUpdated,
+  CommentCreated,
+  CommentUpdated,
+  ReportCreated,
+  /// A moderator or admin action, such as removing a post or banning a user.
+  ModAction,
+  /// Only available for site-wide webhooks.
+  RegistrationApplicationCreated,
+}
+
+#[derive(EnumString, Display, Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(DbEnum, TS))]
+#[cfg_attr(
+  feature = "full",
+  ExistingTypePath = "crate::schema::sql_types::ApiTokenScopeEnum"
+)]
+#[cfg_attr(feature = "full", DbValueStyle = "verbatim")]
+#[cfg_attr(feature = "full", ts(export))]
+/// The actions which are allowed with an api token. These only restrict the token, for example
+/// `Admin` still requires the user to be an admin.
+pub enum ApiTokenScope {
+  /// All GET requests, except for admin and moderation endpoints.
+  Read,
+  /// Create, edit, vote on and report posts, and upload images.
+  WritePost,
+  /// Create, edit, vote on and report comments.
+  WriteComment,
+  /// Moderation actions, reports and community settings.
+  Moderate,
+  /// Admin endpoints and site settings.
+  Admin,
+}
+
+#[derive(
+  EnumString, Display, Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash,
+)]
+#[cfg_attr(feature = "full", derive(DbEnum, TS))]
+#[cfg_attr(
+  feature = "full",
+  ExistingTypePath = "crate::schema::sql_types::KeywordFilterModeEnum"
+)]
+#[cfg_attr(feature = "full", DbValueStyle = "verbatim")]
+#[cfg_attr(feature = "full", ts(export))]
+/// How the keyword of a keyword filter is matched. Matching is always case insensitive.
+pub enum KeywordFilterMode {
+  /// Matches the keyword only as a whole word, so `cat` doesn't match `category`.
+  #[default]
+  WholeWord,
+  /// Matches the keyword anywhere in the text.
+  Substring,
+  /// The keyword is a POSIX regular expression.
+  Regex,
+}
+
+#[derive(
+  EnumString, Display, Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash,
+)]
+#[cfg_attr(feature = "full", derive(DbEnum, TS))]
+#[cfg_attr(
+  feature = "full",
+  ExistingTypePath = "crate::schema::sql_types::EmailDigestModeEnum"
+)]
+#[cfg_attr(feature = "full", DbValueStyle = "verbatim")]
+#[cfg_attr(feature = "full", ts(export))]
+/// How often notification emails are sent. Except for instant, all notifications of the period
+/// are combined into a single email.
+pub enum EmailDigestMode {
+  /// Sends an email for each notification.
+  #[default]
+  Instant,
+  Hourly,
+  Daily,
+  Weekly,
+}
+
+#[derive(
+  EnumString, Display, Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash,
+)]
+#[cfg_attr(feature = "full", derive(DbEnum, TS))]
+#[cfg_attr(
+  feature = "full",
+  ExistingTypePath = "crate::schema::sql_types::DuplicateLinkPolicyEnum"
+)]
+#[cfg_attr(feature = "full", DbValueStyle = "verbatim")]
+#[cfg_attr(feature = "full", ts(export))]
+/// What happens when a link is posted again to the same community.
+pub enum DuplicateLinkPolicy {
+  /// Duplicate links are posted without any checks.
+  #[default]
+  Allow,
+  /// The post is created, but the response includes the existing post.
+  Warn,
+  /// The post is rejected.
+  Reject,
+}
+
+#[derive(EnumString, Display, Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(DbEnum, TS))]
+#[cfg_attr(
+  feature = "full",
+  ExistingTypePath = "crate::schema::sql_types::AutomodActionEnum"
+)]
+#[cfg_attr(feature = "full", DbValueStyle = "verbatim")]
+#[cfg_attr(feature = "full", ts(export))]
+/// What the automoderator does with a post or comment which matches a rule.
+pub enum AutomodAction {
+  /// Removes the post or comment.
+  Remove,
+  /// Locks the post. Has no effect on comments.
+  Lock,
+  /// Reports the post or comment to the community moderators.
+  Report,
+  /// Adds the tag of the rule to the post. Has no effect on comments.
+  Tag,
+  /// Replies with a distinguished comment. Only done for new posts and comments.
+  Reply,
+}
+
+#[derive(EnumString, Display, Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// The change which a line of an imported federation blocklist makes.
+pub enum FederationBlocklistImportAction {
+  /// The instance is blocked.
+  Block,
+  /// The instance is limited, or its media is rejected.
+  Limit,
+  /// The instance is already restricted as much as the line requires.
+  Unchanged,
+  /// The line is ignored.
+  Skip,
+}
+
+#[derive(EnumString, Display, Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Why a line of an imported federation blocklist is ignored.
+pub enum FederationBlocklistSkipReason {
+  /// The domain is missing, obfuscated or not a domain at all.
+  InvalidDomain,
+  /// The severity is not one of `suspend`, `silence` or `noop`.
+  InvalidSeverity,
+  /// The domain is the one of this instance.
+  LocalInstance,
+  /// The domain was listed before.
+  DuplicateDomain,
+  /// Instances can't be blocked while federation is limited to an allowlist.
+  AllowlistInUse,
+  /// The line doesn't restrict anything.
+  NoRestrictions,
+}
+
 /// Wrapper for assert_eq! macro. Checks that vec matches the given length, and prints the
 /// vec on failure.
 #[macro_export]
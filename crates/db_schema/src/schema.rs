@@ -5,6 +5,14 @@ pub mod sql_types {
     #[diesel(postgres_type(name = "actor_type_enum"))]
     pub struct ActorTypeEnum;
 
+    #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "api_token_scope_enum"))]
+    pub struct ApiTokenScopeEnum;
+
+    #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "automod_action_enum"))]
+    pub struct AutomodActionEnum;
+
     #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
     #[diesel(postgres_type(name = "comment_sort_type_enum"))]
     pub struct CommentSortTypeEnum;
@@ -17,10 +25,22 @@ pub mod sql_types {
     #[diesel(postgres_type(name = "community_visibility"))]
     pub struct CommunityVisibility;
 
+    #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "duplicate_link_policy_enum"))]
+    pub struct DuplicateLinkPolicyEnum;
+
+    #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "email_digest_mode_enum"))]
+    pub struct EmailDigestModeEnum;
+
     #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
     #[diesel(postgres_type(name = "federation_mode_enum"))]
     pub struct FederationModeEnum;
 
+    #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "keyword_filter_mode_enum"))]
+    pub struct KeywordFilterModeEnum;
+
     #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
     #[diesel(postgres_type(name = "listing_type_enum"))]
     pub struct ListingTypeEnum;
@@ -40,6 +60,14 @@ pub mod sql_types {
     #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
     #[diesel(postgres_type(name = "registration_mode_enum"))]
     pub struct RegistrationModeEnum;
+
+    #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "tsvector", schema = "pg_catalog"))]
+    pub struct Tsvector;
+
+    #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "webhook_event_type_enum"))]
+    pub struct WebhookEventTypeEnum;
 }
 
 diesel::table! {
@@ -65,6 +93,19 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    admin_limit_instance (id) {
+        id -> Int4,
+        instance_id -> Int4,
+        admin_person_id -> Int4,
+        limited -> Bool,
+        reject_media -> Bool,
+        force_nsfw -> Bool,
+        reason -> Nullable<Text>,
+        published -> Timestamptz,
+    }
+}
+
 diesel::table! {
     admin_purge_comment (id) {
         id -> Int4,
@@ -103,6 +144,53 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    api_token (id) {
+        id -> Int4,
+        local_user_id -> Int4,
+        name -> Text,
+        token_hash -> Text,
+        expires -> Nullable<Timestamptz>,
+        published -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::ApiTokenScopeEnum;
+
+    api_token_scope (api_token_id, scope) {
+        api_token_id -> Int4,
+        scope -> ApiTokenScopeEnum,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::AutomodActionEnum;
+
+    automod_rule (id) {
+        id -> Int4,
+        community_id -> Int4,
+        creator_id -> Int4,
+        name -> Text,
+        enabled -> Bool,
+        check_posts -> Bool,
+        check_comments -> Bool,
+        title_regex -> Nullable<Text>,
+        body_regex -> Nullable<Text>,
+        url_domain -> Nullable<Text>,
+        max_author_age_days -> Nullable<Int4>,
+        max_author_karma -> Nullable<Int8>,
+        language_id -> Nullable<Int4>,
+        action -> AutomodActionEnum,
+        tag_id -> Nullable<Int4>,
+        message -> Nullable<Text>,
+        published -> Timestamptz,
+        updated -> Nullable<Timestamptz>,
+    }
+}
+
 diesel::table! {
     captcha_answer (uuid) {
         uuid -> Uuid,
@@ -182,9 +270,20 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    comment_revision (id) {
+        id -> Int4,
+        comment_id -> Int4,
+        editor_id -> Int4,
+        content -> Text,
+        published -> Timestamptz,
+    }
+}
+
 diesel::table! {
     use diesel::sql_types::*;
     use super::sql_types::CommunityVisibility;
+    use super::sql_types::DuplicateLinkPolicyEnum;
 
     community (id) {
         id -> Int4,
@@ -221,6 +320,8 @@ diesel::table! {
         #[max_length = 150]
         description -> Nullable<Varchar>,
         random_number -> Int2,
+        duplicate_link_policy -> DuplicateLinkPolicyEnum,
+        duplicate_link_window_days -> Int4,
     }
 }
 
@@ -259,6 +360,19 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    community_backfill (community_id) {
+        community_id -> Int4,
+        instance_id -> Int4,
+        next_page -> Nullable<Text>,
+        pages_fetched -> Int4,
+        failures -> Int4,
+        next_fetch -> Timestamptz,
+        published -> Timestamptz,
+        pending_replies -> Array<Nullable<Text>>,
+    }
+}
+
 diesel::table! {
     community_language (community_id, language_id) {
         community_id -> Int4,
@@ -306,6 +420,25 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    draft (id) {
+        id -> Int4,
+        local_user_id -> Int4,
+        community_id -> Nullable<Int4>,
+        post_id -> Nullable<Int4>,
+        parent_id -> Nullable<Int4>,
+        name -> Nullable<Text>,
+        url -> Nullable<Text>,
+        body -> Nullable<Text>,
+        alt_text -> Nullable<Text>,
+        nsfw -> Nullable<Bool>,
+        language_id -> Nullable<Int4>,
+        custom_thumbnail -> Nullable<Text>,
+        published -> Timestamptz,
+        updated -> Nullable<Timestamptz>,
+    }
+}
+
 diesel::table! {
     email_verification (id) {
         id -> Int4,
@@ -333,6 +466,17 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    federation_limit (instance_id) {
+        instance_id -> Int4,
+        limited -> Bool,
+        reject_media -> Bool,
+        force_nsfw -> Bool,
+        published -> Timestamptz,
+        updated -> Nullable<Timestamptz>,
+    }
+}
+
 diesel::table! {
     federation_queue_state (instance_id) {
         instance_id -> Int4,
@@ -340,6 +484,28 @@ diesel::table! {
         fail_count -> Int4,
         last_retry -> Nullable<Timestamptz>,
         last_successful_published_time -> Nullable<Timestamptz>,
+        paused -> Bool,
+        reset_backoff_requested -> Bool,
+        skip_backlog_requested -> Bool,
+    }
+}
+
+diesel::table! {
+    feed (id) {
+        id -> Int4,
+        creator_id -> Int4,
+        name -> Text,
+        description -> Nullable<Text>,
+        public -> Bool,
+        published -> Timestamptz,
+        updated -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    feed_community (feed_id, community_id) {
+        feed_id -> Int4,
+        community_id -> Int4,
     }
 }
 
@@ -360,6 +526,9 @@ diesel::table! {
         person_comment_mention_id -> Nullable<Int4>,
         person_post_mention_id -> Nullable<Int4>,
         private_message_id -> Nullable<Int4>,
+        mod_warn_person_id -> Nullable<Int4>,
+        modmail_message_id -> Nullable<Int4>,
+        thread_reply_id -> Nullable<Int4>,
     }
 }
 
@@ -444,6 +613,7 @@ diesel::table! {
         comment_upvotes -> FederationModeEnum,
         comment_downvotes -> FederationModeEnum,
         disable_donation_dialog -> Bool,
+        automod_person_id -> Nullable<Int4>,
     }
 }
 
@@ -517,6 +687,44 @@ diesel::table! {
         auto_mark_fetched_posts_as_read -> Bool,
         last_donation_notification -> Timestamptz,
         hide_media -> Bool,
+        auto_subscribe_own_posts -> Bool,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::EmailDigestModeEnum;
+
+    local_user_email_digest (local_user_id) {
+        local_user_id -> Int4,
+        mode -> EmailDigestModeEnum,
+        last_sent -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::KeywordFilterModeEnum;
+
+    local_user_keyword_filter (id) {
+        id -> Int4,
+        local_user_id -> Int4,
+        keyword -> Text,
+        mode -> KeywordFilterModeEnum,
+        match_title -> Bool,
+        match_body -> Bool,
+        match_url -> Bool,
+        published -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::ListingTypeEnum;
+
+    local_user_keyword_filter_listing (keyword_filter_id, listing_type) {
+        keyword_filter_id -> Int4,
+        listing_type -> ListingTypeEnum,
     }
 }
 
@@ -568,6 +776,22 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::AutomodActionEnum;
+
+    mod_automod_action (id) {
+        id -> Int4,
+        mod_person_id -> Int4,
+        community_id -> Int4,
+        post_id -> Int4,
+        comment_id -> Nullable<Int4>,
+        action -> AutomodActionEnum,
+        reason -> Nullable<Text>,
+        published -> Timestamptz,
+    }
+}
+
 diesel::table! {
     mod_ban (id) {
         id -> Int4,
@@ -625,6 +849,17 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    mod_note (id) {
+        id -> Int4,
+        person_id -> Int4,
+        creator_id -> Int4,
+        community_id -> Nullable<Int4>,
+        content -> Text,
+        published -> Timestamptz,
+    }
+}
+
 diesel::table! {
     mod_remove_comment (id) {
         id -> Int4,
@@ -668,6 +903,18 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    mod_warn_person (id) {
+        id -> Int4,
+        mod_person_id -> Int4,
+        other_person_id -> Int4,
+        community_id -> Nullable<Int4>,
+        reason -> Text,
+        read -> Bool,
+        published -> Timestamptz,
+    }
+}
+
 diesel::table! {
     modlog_combined (id) {
         id -> Int4,
@@ -689,6 +936,33 @@ diesel::table! {
         mod_remove_community_id -> Nullable<Int4>,
         mod_remove_post_id -> Nullable<Int4>,
         mod_transfer_community_id -> Nullable<Int4>,
+        mod_automod_action_id -> Nullable<Int4>,
+        mod_warn_person_id -> Nullable<Int4>,
+        admin_limit_instance_id -> Nullable<Int4>,
+    }
+}
+
+diesel::table! {
+    modmail_message (id) {
+        id -> Int4,
+        thread_id -> Int4,
+        creator_id -> Int4,
+        content -> Text,
+        from_moderator -> Bool,
+        read -> Bool,
+        published -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    modmail_thread (id) {
+        id -> Int4,
+        community_id -> Int4,
+        creator_id -> Int4,
+        subject -> Text,
+        archived -> Bool,
+        published -> Timestamptz,
+        updated -> Nullable<Timestamptz>,
     }
 }
 
@@ -723,6 +997,22 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    outgoing_email (id) {
+        id -> Int4,
+        to_email -> Text,
+        to_name -> Text,
+        subject -> Text,
+        body -> Text,
+        unsubscribe_link -> Nullable<Text>,
+        attempts -> Int4,
+        next_attempt -> Nullable<Timestamptz>,
+        sent -> Nullable<Timestamptz>,
+        last_error -> Nullable<Text>,
+        published -> Timestamptz,
+    }
+}
+
 diesel::table! {
     password_reset_request (id) {
         id -> Int4,
@@ -758,6 +1048,8 @@ diesel::table! {
         bot_account -> Bool,
         ban_expires -> Nullable<Timestamptz>,
         instance_id -> Int4,
+        #[max_length = 255]
+        moved_to -> Nullable<Varchar>,
     }
 }
 
@@ -781,6 +1073,15 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    person_alias (person_id, alias) {
+        person_id -> Int4,
+        #[max_length = 255]
+        alias -> Varchar,
+        published -> Timestamptz,
+    }
+}
+
 diesel::table! {
     person_ban (person_id) {
         person_id -> Int4,
@@ -856,6 +1157,7 @@ diesel::table! {
         url_content_type -> Nullable<Text>,
         alt_text -> Nullable<Text>,
         scheduled_publish_time -> Nullable<Timestamptz>,
+        crosspost_of_id -> Nullable<Int4>,
     }
 }
 
@@ -897,6 +1199,36 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    post_poll (post_id) {
+        post_id -> Int4,
+        multiple_choice -> Bool,
+        end_time -> Nullable<Timestamptz>,
+        voters_count -> Int4,
+        published -> Timestamptz,
+        results_federated -> Bool,
+    }
+}
+
+diesel::table! {
+    post_poll_option (id) {
+        id -> Int4,
+        post_id -> Int4,
+        name -> Text,
+        position -> Int4,
+        vote_count -> Int4,
+    }
+}
+
+diesel::table! {
+    post_poll_vote (option_id, person_id) {
+        option_id -> Int4,
+        person_id -> Int4,
+        post_id -> Int4,
+        published -> Timestamptz,
+    }
+}
+
 diesel::table! {
     post_report (id) {
         id -> Int4,
@@ -914,6 +1246,18 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    post_revision (id) {
+        id -> Int4,
+        post_id -> Int4,
+        editor_id -> Int4,
+        name -> Text,
+        url -> Nullable<Text>,
+        body -> Nullable<Text>,
+        published -> Timestamptz,
+    }
+}
+
 diesel::table! {
     post_tag (post_id, tag_id) {
         post_id -> Int4,
@@ -959,6 +1303,27 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    push_subscription (id) {
+        id -> Int4,
+        local_user_id -> Int4,
+        endpoint -> Text,
+        p256dh -> Text,
+        auth -> Text,
+        published -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    queued_email_notification (id) {
+        id -> Int4,
+        local_user_id -> Int4,
+        subject -> Text,
+        body -> Text,
+        published -> Timestamptz,
+    }
+}
+
 diesel::table! {
     received_activity (ap_id) {
         ap_id -> Text,
@@ -996,6 +1361,35 @@ diesel::table! {
 }
 
 diesel::table! {
+    scheduled_job (id) {
+        id -> Int4,
+        name -> Text,
+        interval_secs -> Int4,
+        next_run -> Timestamptz,
+        locked_until -> Nullable<Timestamptz>,
+        locked_by -> Nullable<Text>,
+        last_started -> Nullable<Timestamptz>,
+        last_finished -> Nullable<Timestamptz>,
+        last_error -> Nullable<Text>,
+        failure_count -> Int4,
+        published -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    scheduled_job_run (id) {
+        id -> Int4,
+        job_id -> Int4,
+        started -> Timestamptz,
+        finished -> Timestamptz,
+        error -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::Tsvector;
+
     search_combined (id) {
         id -> Int4,
         published -> Timestamptz,
@@ -1004,6 +1398,7 @@ diesel::table! {
         comment_id -> Nullable<Int4>,
         community_id -> Nullable<Int4>,
         person_id -> Nullable<Int4>,
+        search_vector -> Tsvector,
     }
 }
 
@@ -1011,6 +1406,8 @@ diesel::table! {
     secret (id) {
         id -> Int4,
         jwt_secret -> Varchar,
+        vapid_private_key -> Nullable<Text>,
+        vapid_public_key -> Nullable<Text>,
     }
 }
 
@@ -1053,6 +1450,7 @@ diesel::table! {
         public_key -> Text,
         instance_id -> Int4,
         content_warning -> Nullable<Text>,
+        edit_history_public -> Bool,
     }
 }
 
@@ -1098,16 +1496,86 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    thread_reply (id) {
+        id -> Int4,
+        recipient_id -> Int4,
+        comment_id -> Int4,
+        read -> Bool,
+        published -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    thread_subscription (id) {
+        id -> Int4,
+        person_id -> Int4,
+        post_id -> Int4,
+        comment_id -> Nullable<Int4>,
+        muted -> Bool,
+        published -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    webhook (id) {
+        id -> Int4,
+        community_id -> Nullable<Int4>,
+        creator_id -> Int4,
+        url -> Text,
+        secret -> Text,
+        enabled -> Bool,
+        published -> Timestamptz,
+        updated -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::WebhookEventTypeEnum;
+
+    webhook_delivery (id) {
+        id -> Int4,
+        webhook_id -> Int4,
+        event -> WebhookEventTypeEnum,
+        payload -> Text,
+        attempts -> Int4,
+        next_attempt -> Nullable<Timestamptz>,
+        delivered -> Nullable<Timestamptz>,
+        last_status -> Nullable<Int4>,
+        last_error -> Nullable<Text>,
+        published -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::WebhookEventTypeEnum;
+
+    webhook_event (webhook_id, event) {
+        webhook_id -> Int4,
+        event -> WebhookEventTypeEnum,
+    }
+}
+
 diesel::joinable!(admin_allow_instance -> instance (instance_id));
 diesel::joinable!(admin_allow_instance -> person (admin_person_id));
 diesel::joinable!(admin_block_instance -> instance (instance_id));
 diesel::joinable!(admin_block_instance -> person (admin_person_id));
+diesel::joinable!(admin_limit_instance -> instance (instance_id));
+diesel::joinable!(admin_limit_instance -> person (admin_person_id));
 diesel::joinable!(admin_purge_comment -> person (admin_person_id));
 diesel::joinable!(admin_purge_comment -> post (post_id));
 diesel::joinable!(admin_purge_community -> person (admin_person_id));
 diesel::joinable!(admin_purge_person -> person (admin_person_id));
 diesel::joinable!(admin_purge_post -> community (community_id));
 diesel::joinable!(admin_purge_post -> person (admin_person_id));
+diesel::joinable!(api_token -> local_user (local_user_id));
+diesel::joinable!(api_token_scope -> api_token (api_token_id));
+diesel::joinable!(automod_rule -> community (community_id));
+diesel::joinable!(automod_rule -> language (language_id));
+diesel::joinable!(automod_rule -> person (creator_id));
+diesel::joinable!(automod_rule -> tag (tag_id));
 diesel::joinable!(comment -> language (language_id));
 diesel::joinable!(comment -> person (creator_id));
 diesel::joinable!(comment -> post (post_id));
@@ -1115,34 +1583,58 @@ diesel::joinable!(comment_actions -> comment (comment_id));
 diesel::joinable!(comment_actions -> person (person_id));
 diesel::joinable!(comment_aggregates -> comment (comment_id));
 diesel::joinable!(comment_reply -> comment (comment_id));
+diesel::joinable!(comment_revision -> comment (comment_id));
+diesel::joinable!(comment_revision -> person (editor_id));
 diesel::joinable!(comment_reply -> person (recipient_id));
 diesel::joinable!(comment_report -> comment (comment_id));
 diesel::joinable!(community -> instance (instance_id));
 diesel::joinable!(community_actions -> community (community_id));
 diesel::joinable!(community_aggregates -> community (community_id));
+diesel::joinable!(community_backfill -> community (community_id));
+diesel::joinable!(community_backfill -> instance (instance_id));
 diesel::joinable!(community_language -> community (community_id));
 diesel::joinable!(community_language -> language (language_id));
 diesel::joinable!(community_report -> community (community_id));
 diesel::joinable!(custom_emoji_keyword -> custom_emoji (custom_emoji_id));
+diesel::joinable!(draft -> comment (parent_id));
+diesel::joinable!(draft -> community (community_id));
+diesel::joinable!(draft -> language (language_id));
+diesel::joinable!(draft -> local_user (local_user_id));
+diesel::joinable!(draft -> post (post_id));
 diesel::joinable!(email_verification -> local_user (local_user_id));
 diesel::joinable!(federation_allowlist -> instance (instance_id));
 diesel::joinable!(federation_blocklist -> instance (instance_id));
+diesel::joinable!(federation_limit -> instance (instance_id));
 diesel::joinable!(federation_queue_state -> instance (instance_id));
+diesel::joinable!(feed -> person (creator_id));
+diesel::joinable!(feed_community -> community (community_id));
+diesel::joinable!(feed_community -> feed (feed_id));
 diesel::joinable!(inbox_combined -> comment_reply (comment_reply_id));
+diesel::joinable!(inbox_combined -> mod_warn_person (mod_warn_person_id));
+diesel::joinable!(inbox_combined -> modmail_message (modmail_message_id));
 diesel::joinable!(inbox_combined -> person_comment_mention (person_comment_mention_id));
 diesel::joinable!(inbox_combined -> person_post_mention (person_post_mention_id));
 diesel::joinable!(inbox_combined -> private_message (private_message_id));
+diesel::joinable!(inbox_combined -> thread_reply (thread_reply_id));
 diesel::joinable!(instance_actions -> instance (instance_id));
 diesel::joinable!(instance_actions -> person (person_id));
 diesel::joinable!(local_image -> local_user (local_user_id));
+diesel::joinable!(local_site -> person (automod_person_id));
 diesel::joinable!(local_site -> site (site_id));
 diesel::joinable!(local_site_rate_limit -> local_site (local_site_id));
 diesel::joinable!(local_user -> person (person_id));
+diesel::joinable!(local_user_email_digest -> local_user (local_user_id));
+diesel::joinable!(local_user_keyword_filter -> local_user (local_user_id));
+diesel::joinable!(local_user_keyword_filter_listing -> local_user_keyword_filter (keyword_filter_id));
 diesel::joinable!(local_user_language -> language (language_id));
 diesel::joinable!(local_user_language -> local_user (local_user_id));
 diesel::joinable!(local_user_vote_display_mode -> local_user (local_user_id));
 diesel::joinable!(login_token -> local_user (user_id));
 diesel::joinable!(mod_add_community -> community (community_id));
+diesel::joinable!(mod_automod_action -> comment (comment_id));
+diesel::joinable!(mod_automod_action -> community (community_id));
+diesel::joinable!(mod_automod_action -> person (mod_person_id));
+diesel::joinable!(mod_automod_action -> post (post_id));
 diesel::joinable!(mod_ban_from_community -> community (community_id));
 diesel::joinable!(mod_feature_post -> person (mod_person_id));
 diesel::joinable!(mod_feature_post -> post (post_id));
@@ -1150,6 +1642,7 @@ diesel::joinable!(mod_hide_community -> community (community_id));
 diesel::joinable!(mod_hide_community -> person (mod_person_id));
 diesel::joinable!(mod_lock_post -> person (mod_person_id));
 diesel::joinable!(mod_lock_post -> post (post_id));
+diesel::joinable!(mod_note -> community (community_id));
 diesel::joinable!(mod_remove_comment -> comment (comment_id));
 diesel::joinable!(mod_remove_comment -> person (mod_person_id));
 diesel::joinable!(mod_remove_community -> community (community_id));
@@ -1157,14 +1650,17 @@ diesel::joinable!(mod_remove_community -> person (mod_person_id));
 diesel::joinable!(mod_remove_post -> person (mod_person_id));
 diesel::joinable!(mod_remove_post -> post (post_id));
 diesel::joinable!(mod_transfer_community -> community (community_id));
+diesel::joinable!(mod_warn_person -> community (community_id));
 diesel::joinable!(modlog_combined -> admin_allow_instance (admin_allow_instance_id));
 diesel::joinable!(modlog_combined -> admin_block_instance (admin_block_instance_id));
+diesel::joinable!(modlog_combined -> admin_limit_instance (admin_limit_instance_id));
 diesel::joinable!(modlog_combined -> admin_purge_comment (admin_purge_comment_id));
 diesel::joinable!(modlog_combined -> admin_purge_community (admin_purge_community_id));
 diesel::joinable!(modlog_combined -> admin_purge_person (admin_purge_person_id));
 diesel::joinable!(modlog_combined -> admin_purge_post (admin_purge_post_id));
 diesel::joinable!(modlog_combined -> mod_add (mod_add_id));
 diesel::joinable!(modlog_combined -> mod_add_community (mod_add_community_id));
+diesel::joinable!(modlog_combined -> mod_automod_action (mod_automod_action_id));
 diesel::joinable!(modlog_combined -> mod_ban (mod_ban_id));
 diesel::joinable!(modlog_combined -> mod_ban_from_community (mod_ban_from_community_id));
 diesel::joinable!(modlog_combined -> mod_feature_post (mod_feature_post_id));
@@ -1174,11 +1670,17 @@ diesel::joinable!(modlog_combined -> mod_remove_comment (mod_remove_comment_id))
 diesel::joinable!(modlog_combined -> mod_remove_community (mod_remove_community_id));
 diesel::joinable!(modlog_combined -> mod_remove_post (mod_remove_post_id));
 diesel::joinable!(modlog_combined -> mod_transfer_community (mod_transfer_community_id));
+diesel::joinable!(modlog_combined -> mod_warn_person (mod_warn_person_id));
+diesel::joinable!(modmail_message -> modmail_thread (thread_id));
+diesel::joinable!(modmail_message -> person (creator_id));
+diesel::joinable!(modmail_thread -> community (community_id));
+diesel::joinable!(modmail_thread -> person (creator_id));
 diesel::joinable!(oauth_account -> local_user (local_user_id));
 diesel::joinable!(oauth_account -> oauth_provider (oauth_provider_id));
 diesel::joinable!(password_reset_request -> local_user (local_user_id));
 diesel::joinable!(person -> instance (instance_id));
 diesel::joinable!(person_aggregates -> person (person_id));
+diesel::joinable!(person_alias -> person (person_id));
 diesel::joinable!(person_ban -> person (person_id));
 diesel::joinable!(person_comment_mention -> comment (comment_id));
 diesel::joinable!(person_comment_mention -> person (recipient_id));
@@ -1198,16 +1700,26 @@ diesel::joinable!(post_aggregates -> community (community_id));
 diesel::joinable!(post_aggregates -> instance (instance_id));
 diesel::joinable!(post_aggregates -> person (creator_id));
 diesel::joinable!(post_aggregates -> post (post_id));
+diesel::joinable!(post_poll -> post (post_id));
+diesel::joinable!(post_poll_option -> post_poll (post_id));
+diesel::joinable!(post_poll_vote -> person (person_id));
+diesel::joinable!(post_poll_vote -> post_poll (post_id));
+diesel::joinable!(post_poll_vote -> post_poll_option (option_id));
 diesel::joinable!(post_report -> post (post_id));
+diesel::joinable!(post_revision -> person (editor_id));
+diesel::joinable!(post_revision -> post (post_id));
 diesel::joinable!(post_tag -> post (post_id));
 diesel::joinable!(post_tag -> tag (tag_id));
 diesel::joinable!(private_message_report -> private_message (private_message_id));
+diesel::joinable!(push_subscription -> local_user (local_user_id));
+diesel::joinable!(queued_email_notification -> local_user (local_user_id));
 diesel::joinable!(registration_application -> local_user (local_user_id));
 diesel::joinable!(registration_application -> person (admin_id));
 diesel::joinable!(report_combined -> comment_report (comment_report_id));
 diesel::joinable!(report_combined -> community_report (community_report_id));
 diesel::joinable!(report_combined -> post_report (post_report_id));
 diesel::joinable!(report_combined -> private_message_report (private_message_report_id));
+diesel::joinable!(scheduled_job_run -> scheduled_job (job_id));
 diesel::joinable!(search_combined -> comment (comment_id));
 diesel::joinable!(search_combined -> community (community_id));
 diesel::joinable!(search_combined -> person (person_id));
@@ -1217,31 +1729,50 @@ diesel::joinable!(site_aggregates -> site (site_id));
 diesel::joinable!(site_language -> language (language_id));
 diesel::joinable!(site_language -> site (site_id));
 diesel::joinable!(tag -> community (community_id));
+diesel::joinable!(thread_reply -> comment (comment_id));
+diesel::joinable!(thread_reply -> person (recipient_id));
+diesel::joinable!(thread_subscription -> comment (comment_id));
+diesel::joinable!(thread_subscription -> person (person_id));
+diesel::joinable!(thread_subscription -> post (post_id));
+diesel::joinable!(webhook -> community (community_id));
+diesel::joinable!(webhook -> person (creator_id));
+diesel::joinable!(webhook_delivery -> webhook (webhook_id));
+diesel::joinable!(webhook_event -> webhook (webhook_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
     admin_allow_instance,
     admin_block_instance,
+    admin_limit_instance,
     admin_purge_comment,
     admin_purge_community,
     admin_purge_person,
     admin_purge_post,
+    api_token,
+    api_token_scope,
+    automod_rule,
     captcha_answer,
     comment,
     comment_actions,
     comment_aggregates,
     comment_reply,
     comment_report,
+    comment_revision,
     community,
     community_actions,
     community_aggregates,
+    community_backfill,
     community_language,
     community_report,
     custom_emoji,
     custom_emoji_keyword,
+    draft,
     email_verification,
     federation_allowlist,
     federation_blocklist,
+    federation_limit,
     federation_queue_state,
+    feed,
+    feed_community,
     image_details,
     inbox_combined,
     instance,
@@ -1252,27 +1783,37 @@ diesel::allow_tables_to_appear_in_same_query!(
     local_site_rate_limit,
     local_site_url_blocklist,
     local_user,
+    local_user_email_digest,
+    local_user_keyword_filter,
+    local_user_keyword_filter_listing,
     local_user_language,
     local_user_vote_display_mode,
     login_token,
     mod_add,
     mod_add_community,
+    mod_automod_action,
     mod_ban,
     mod_ban_from_community,
     mod_feature_post,
     mod_hide_community,
     mod_lock_post,
+    mod_note,
     mod_remove_comment,
     mod_remove_community,
     mod_remove_post,
     mod_transfer_community,
+    mod_warn_person,
     modlog_combined,
+    modmail_message,
+    modmail_thread,
     oauth_account,
     oauth_provider,
+    outgoing_email,
     password_reset_request,
     person,
     person_actions,
     person_aggregates,
+    person_alias,
     person_ban,
     person_comment_mention,
     person_content_combined,
@@ -1281,15 +1822,23 @@ diesel::allow_tables_to_appear_in_same_query!(
     post,
     post_actions,
     post_aggregates,
+    post_poll,
+    post_poll_option,
+    post_poll_vote,
     post_report,
+    post_revision,
     post_tag,
     previously_run_sql,
     private_message,
     private_message_report,
+    push_subscription,
+    queued_email_notification,
     received_activity,
     registration_application,
     remote_image,
     report_combined,
+    scheduled_job,
+    scheduled_job_run,
     search_combined,
     secret,
     sent_activity,
@@ -1298,4 +1847,9 @@ diesel::allow_tables_to_appear_in_same_query!(
     site_language,
     tag,
     tagline,
+    thread_reply,
+    thread_subscription,
+    webhook,
+    webhook_delivery,
+    webhook_event,
 );
@@ -0,0 +1,48 @@
+#[cfg(feature = "full")]
+use crate::schema::{api_token, api_token_scope};
+use crate::{
+  newtypes::{ApiTokenId, LocalUserId},
+  ApiTokenScope,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+#[cfg(feature = "full")]
+use ts_rs::TS;
+
+/// A named token which gives bots and scripts limited access to an account.
+#[skip_serializing_none]
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable, TS))]
+#[cfg_attr(feature = "full", diesel(table_name = api_token))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "full", ts(export))]
+pub struct ApiToken {
+  pub id: ApiTokenId,
+  pub local_user_id: LocalUserId,
+  pub name: String,
+  /// Hash of the token, the token itself is not stored.
+  #[serde(skip)]
+  pub token_hash: String,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub expires: Option<DateTime<Utc>>,
+  pub published: DateTime<Utc>,
+}
+
+#[derive(Clone, Debug, derive_new::new)]
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = api_token))]
+pub struct ApiTokenInsertForm {
+  pub local_user_id: LocalUserId,
+  pub name: String,
+  pub token_hash: String,
+  pub expires: Option<DateTime<Utc>>,
+}
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = api_token_scope))]
+pub struct ApiTokenScopeForm {
+  pub api_token_id: ApiTokenId,
+  pub scope: ApiTokenScope,
+}
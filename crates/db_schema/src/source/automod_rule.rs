@@ -0,0 +1,106 @@
+#[cfg(feature = "full")]
+use crate::schema::automod_rule;
+use crate::{
+  newtypes::{AutomodRuleId, CommunityId, LanguageId, PersonId, TagId},
+  AutomodAction,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+#[cfg(feature = "full")]
+use ts_rs::TS;
+
+#[skip_serializing_none]
+#[derive(PartialEq, Eq, Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable, TS))]
+#[cfg_attr(feature = "full", diesel(table_name = automod_rule))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A community rule which is checked against new and edited posts and comments. It matches if all
+/// of the conditions which are set match.
+pub struct AutomodRule {
+  pub id: AutomodRuleId,
+  pub community_id: CommunityId,
+  pub creator_id: PersonId,
+  pub name: String,
+  pub enabled: bool,
+  pub check_posts: bool,
+  pub check_comments: bool,
+  /// Case insensitive regex for the post title. Never matches comments.
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub title_regex: Option<String>,
+  /// Case insensitive regex for the post body or comment content.
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub body_regex: Option<String>,
+  /// Matches post urls on this domain or one of its subdomains. Never matches comments.
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub url_domain: Option<String>,
+  /// Matches authors whose account is younger than this many days.
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub max_author_age_days: Option<i32>,
+  /// Matches authors whose combined post and comment score is at most this.
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub max_author_karma: Option<i64>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub language_id: Option<LanguageId>,
+  pub action: AutomodAction,
+  /// The tag which is added to the post for [AutomodAction::Tag].
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub tag_id: Option<TagId>,
+  /// The report reason, or the content of the reply.
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub message: Option<String>,
+  pub published: DateTime<Utc>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub updated: Option<DateTime<Utc>>,
+}
+
+#[derive(Clone, Debug, derive_new::new)]
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = automod_rule))]
+pub struct AutomodRuleInsertForm {
+  pub community_id: CommunityId,
+  pub creator_id: PersonId,
+  pub name: String,
+  pub action: AutomodAction,
+  #[new(default)]
+  pub check_posts: Option<bool>,
+  #[new(default)]
+  pub check_comments: Option<bool>,
+  #[new(default)]
+  pub title_regex: Option<String>,
+  #[new(default)]
+  pub body_regex: Option<String>,
+  #[new(default)]
+  pub url_domain: Option<String>,
+  #[new(default)]
+  pub max_author_age_days: Option<i32>,
+  #[new(default)]
+  pub max_author_karma: Option<i64>,
+  #[new(default)]
+  pub language_id: Option<LanguageId>,
+  #[new(default)]
+  pub tag_id: Option<TagId>,
+  #[new(default)]
+  pub message: Option<String>,
+}
+
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "full", derive(AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = automod_rule))]
+pub struct AutomodRuleUpdateForm {
+  pub name: Option<String>,
+  pub enabled: Option<bool>,
+  pub check_posts: Option<bool>,
+  pub check_comments: Option<bool>,
+  pub title_regex: Option<Option<String>>,
+  pub body_regex: Option<Option<String>>,
+  pub url_domain: Option<Option<String>>,
+  pub max_author_age_days: Option<Option<i32>>,
+  pub max_author_karma: Option<Option<i64>>,
+  pub language_id: Option<Option<LanguageId>>,
+  pub action: Option<AutomodAction>,
+  pub tag_id: Option<Option<TagId>>,
+  pub message: Option<Option<String>>,
+  pub updated: Option<Option<DateTime<Utc>>>,
+}
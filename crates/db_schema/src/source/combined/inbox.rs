@@ -1,9 +1,12 @@
 use crate::newtypes::{
   CommentReplyId,
   InboxCombinedId,
+  ModWarnPersonId,
+  ModmailMessageId,
   PersonCommentMentionId,
   PersonPostMentionId,
   PrivateMessageId,
+  ThreadReplyId,
 };
 #[cfg(feature = "full")]
 use crate::schema::inbox_combined;
@@ -30,4 +33,7 @@ pub struct InboxCombined {
   pub person_comment_mention_id: Option<PersonCommentMentionId>,
   pub person_post_mention_id: Option<PersonPostMentionId>,
   pub private_message_id: Option<PrivateMessageId>,
+  pub mod_warn_person_id: Option<ModWarnPersonId>,
+  pub modmail_message_id: Option<ModmailMessageId>,
+  pub thread_reply_id: Option<ThreadReplyId>,
 }
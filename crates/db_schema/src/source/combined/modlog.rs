@@ -1,12 +1,14 @@
 use crate::newtypes::{
   AdminAllowInstanceId,
   AdminBlockInstanceId,
+  AdminLimitInstanceId,
   AdminPurgeCommentId,
   AdminPurgeCommunityId,
   AdminPurgePersonId,
   AdminPurgePostId,
   ModAddCommunityId,
   ModAddId,
+  ModAutomodActionId,
   ModBanFromCommunityId,
   ModBanId,
   ModFeaturePostId,
@@ -16,6 +18,7 @@ use crate::newtypes::{
   ModRemoveCommunityId,
   ModRemovePostId,
   ModTransferCommunityId,
+  ModWarnPersonId,
   ModlogCombinedId,
 };
 #[cfg(feature = "full")]
@@ -54,4 +57,7 @@ pub struct ModlogCombined {
   pub mod_remove_community_id: Option<ModRemoveCommunityId>,
   pub mod_remove_post_id: Option<ModRemovePostId>,
   pub mod_transfer_community_id: Option<ModTransferCommunityId>,
+  pub mod_automod_action_id: Option<ModAutomodActionId>,
+  pub mod_warn_person_id: Option<ModWarnPersonId>,
+  pub admin_limit_instance_id: Option<AdminLimitInstanceId>,
 }
@@ -0,0 +1,32 @@
+use crate::newtypes::{CommentId, CommentRevisionId, PersonId};
+#[cfg(feature = "full")]
+use crate::schema::comment_revision;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "full")]
+use ts_rs::TS;
+
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable, TS))]
+#[cfg_attr(feature = "full", diesel(table_name = comment_revision))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A previous version of a comment, which was stored when the comment got edited.
+pub struct CommentRevision {
+  pub id: CommentRevisionId,
+  pub comment_id: CommentId,
+  /// The person who made the edit which replaced this version.
+  pub editor_id: PersonId,
+  pub content: String,
+  /// When this version was replaced.
+  pub published: DateTime<Utc>,
+}
+
+#[derive(Clone, derive_new::new)]
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = comment_revision))]
+pub struct CommentRevisionInsertForm {
+  pub comment_id: CommentId,
+  pub editor_id: PersonId,
+  pub content: String,
+}
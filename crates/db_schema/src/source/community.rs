@@ -5,6 +5,7 @@ use crate::{
   sensitive::SensitiveString,
   source::placeholder_apub_url,
   CommunityVisibility,
+  DuplicateLinkPolicy,
 };
 use chrono::{DateTime, Utc};
 #[cfg(feature = "full")]
@@ -78,6 +79,10 @@ pub struct Community {
   pub description: Option<String>,
   #[serde(skip)]
   pub random_number: i16,
+  /// What happens when a link is posted which was already posted here recently.
+  pub duplicate_link_policy: DuplicateLinkPolicy,
+  /// How many days back posts are checked for duplicate links.
+  pub duplicate_link_window_days: i32,
 }
 
 #[derive(Debug, Clone, derive_new::new)]
@@ -128,6 +133,10 @@ pub struct CommunityInsertForm {
   pub visibility: Option<CommunityVisibility>,
   #[new(default)]
   pub description: Option<String>,
+  #[new(default)]
+  pub duplicate_link_policy: Option<DuplicateLinkPolicy>,
+  #[new(default)]
+  pub duplicate_link_window_days: Option<i32>,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -156,6 +165,8 @@ pub struct CommunityUpdateForm {
   pub posting_restricted_to_mods: Option<bool>,
   pub visibility: Option<CommunityVisibility>,
   pub description: Option<Option<String>>,
+  pub duplicate_link_policy: Option<DuplicateLinkPolicy>,
+  pub duplicate_link_window_days: Option<i32>,
 }
 
 #[derive(PartialEq, Eq, Debug)]
@@ -0,0 +1,34 @@
+use crate::newtypes::{CommunityId, DbUrl, InstanceId};
+#[cfg(feature = "full")]
+use crate::schema::community_backfill;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// A remote community whose older posts and comments are fetched in the background, one outbox
+/// page at a time.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "full", diesel(table_name = community_backfill))]
+#[cfg_attr(feature = "full", diesel(primary_key(community_id)))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+pub struct CommunityBackfill {
+  pub community_id: CommunityId,
+  pub instance_id: InstanceId,
+  /// The outbox page which is fetched next. Empty if the outbox itself wasn't fetched yet.
+  pub next_page: Option<DbUrl>,
+  pub pages_fetched: i32,
+  pub failures: i32,
+  pub next_fetch: DateTime<Utc>,
+  pub published: DateTime<Utc>,
+  /// Replies collections which are fetched after the outbox, for communities whose outbox only
+  /// contains posts.
+  pub pending_replies: Vec<Option<DbUrl>>,
+}
+
+#[derive(Clone, Debug, derive_new::new)]
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = community_backfill))]
+pub struct CommunityBackfillInsertForm {
+  pub community_id: CommunityId,
+  pub instance_id: InstanceId,
+}
@@ -0,0 +1,89 @@
+use crate::newtypes::{CommentId, CommunityId, DbUrl, DraftId, LanguageId, LocalUserId, PostId};
+#[cfg(feature = "full")]
+use crate::schema::draft;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+#[cfg(feature = "full")]
+use ts_rs::TS;
+
+#[skip_serializing_none]
+#[derive(PartialEq, Eq, Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable, TS))]
+#[cfg_attr(feature = "full", diesel(table_name = draft))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "full", ts(export))]
+/// An unfinished post or comment, stored on the server so it can be continued later.
+///
+/// Drafts with a `post_id` are comments, and their `body` is the comment content.
+pub struct Draft {
+  pub id: DraftId,
+  pub local_user_id: LocalUserId,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub community_id: Option<CommunityId>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub post_id: Option<PostId>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub parent_id: Option<CommentId>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub name: Option<String>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub url: Option<DbUrl>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub body: Option<String>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub alt_text: Option<String>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub nsfw: Option<bool>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub language_id: Option<LanguageId>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub custom_thumbnail: Option<DbUrl>,
+  pub published: DateTime<Utc>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub updated: Option<DateTime<Utc>>,
+}
+
+#[derive(Clone, Debug, derive_new::new)]
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = draft))]
+pub struct DraftInsertForm {
+  pub local_user_id: LocalUserId,
+  #[new(default)]
+  pub community_id: Option<CommunityId>,
+  #[new(default)]
+  pub post_id: Option<PostId>,
+  #[new(default)]
+  pub parent_id: Option<CommentId>,
+  #[new(default)]
+  pub name: Option<String>,
+  #[new(default)]
+  pub url: Option<DbUrl>,
+  #[new(default)]
+  pub body: Option<String>,
+  #[new(default)]
+  pub alt_text: Option<String>,
+  #[new(default)]
+  pub nsfw: Option<bool>,
+  #[new(default)]
+  pub language_id: Option<LanguageId>,
+  #[new(default)]
+  pub custom_thumbnail: Option<DbUrl>,
+}
+
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "full", derive(AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = draft))]
+pub struct DraftUpdateForm {
+  pub community_id: Option<Option<CommunityId>>,
+  pub post_id: Option<Option<PostId>>,
+  pub parent_id: Option<Option<CommentId>>,
+  pub name: Option<Option<String>>,
+  pub url: Option<Option<DbUrl>>,
+  pub body: Option<Option<String>>,
+  pub alt_text: Option<Option<String>>,
+  pub nsfw: Option<Option<bool>>,
+  pub language_id: Option<Option<LanguageId>>,
+  pub custom_thumbnail: Option<Option<DbUrl>>,
+  pub updated: Option<Option<DateTime<Utc>>>,
+}
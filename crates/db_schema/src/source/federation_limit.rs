@@ -0,0 +1,47 @@
+use crate::newtypes::InstanceId;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+use std::fmt::Debug;
+#[cfg(feature = "full")]
+use {crate::schema::federation_limit, ts_rs::TS};
+
+#[skip_serializing_none]
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(
+  feature = "full",
+  derive(TS, Queryable, Selectable, Associations, Identifiable)
+)]
+#[cfg_attr(
+  feature = "full",
+  diesel(belongs_to(crate::source::instance::Instance))
+)]
+#[cfg_attr(feature = "full", diesel(table_name = federation_limit))]
+#[cfg_attr(feature = "full", diesel(primary_key(instance_id)))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Restrictions for a remote instance, which is federated with but not trusted.
+pub struct FederationLimit {
+  pub instance_id: InstanceId,
+  /// Content is only shown to users who follow its community or creator, and its users can only
+  /// send private messages to local users who follow them.
+  pub limited: bool,
+  /// Images and thumbnails are not stored or proxied.
+  pub reject_media: bool,
+  /// All posts are marked as nsfw.
+  pub force_nsfw: bool,
+  pub published: DateTime<Utc>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub updated: Option<DateTime<Utc>>,
+}
+
+#[derive(Clone, Default)]
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = federation_limit))]
+pub struct FederationLimitForm {
+  pub instance_id: InstanceId,
+  pub limited: bool,
+  pub reject_media: bool,
+  pub force_nsfw: bool,
+  pub updated: Option<DateTime<Utc>>,
+}
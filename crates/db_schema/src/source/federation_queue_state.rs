@@ -28,4 +28,10 @@ pub struct FederationQueueState {
   /// timestamp of the last retry attempt (when the last failing activity was resent)
   #[cfg_attr(feature = "full", ts(optional))]
   pub last_retry: Option<DateTime<Utc>>,
+  /// Activities are not sent to the instance while paused, and are sent after resuming
+  pub paused: bool,
+  /// An admin requested to retry sending immediately. Applied by the federation worker.
+  pub reset_backoff_requested: bool,
+  /// An admin requested to drop all unsent activities. Applied by the federation worker.
+  pub skip_backlog_requested: bool,
 }
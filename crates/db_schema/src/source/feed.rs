@@ -0,0 +1,65 @@
+use crate::newtypes::{CommunityId, FeedId, PersonId};
+#[cfg(feature = "full")]
+use crate::schema::{feed, feed_community};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+#[cfg(feature = "full")]
+use ts_rs::TS;
+
+#[skip_serializing_none]
+#[derive(PartialEq, Eq, Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable, TS))]
+#[cfg_attr(feature = "full", diesel(table_name = feed))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A named group of communities, whose posts can be listed together.
+pub struct Feed {
+  pub id: FeedId,
+  pub creator_id: PersonId,
+  pub name: String,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub description: Option<String>,
+  /// Private feeds are only visible to their creator.
+  pub public: bool,
+  pub published: DateTime<Utc>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub updated: Option<DateTime<Utc>>,
+}
+
+#[derive(Clone, Debug, derive_new::new)]
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = feed))]
+pub struct FeedInsertForm {
+  pub creator_id: PersonId,
+  pub name: String,
+  #[new(default)]
+  pub description: Option<String>,
+  #[new(default)]
+  pub public: Option<bool>,
+}
+
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "full", derive(AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = feed))]
+pub struct FeedUpdateForm {
+  pub name: Option<String>,
+  pub description: Option<Option<String>>,
+  pub public: Option<bool>,
+  pub updated: Option<Option<DateTime<Utc>>>,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+#[cfg_attr(
+  feature = "full",
+  derive(Queryable, Selectable, Associations, Identifiable, Insertable)
+)]
+#[cfg_attr(feature = "full", diesel(table_name = feed_community))]
+#[cfg_attr(feature = "full", diesel(belongs_to(crate::source::feed::Feed)))]
+#[cfg_attr(feature = "full", diesel(primary_key(feed_id, community_id)))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+/// A community which is part of a feed.
+pub struct FeedCommunity {
+  pub feed_id: FeedId,
+  pub community_id: CommunityId,
+}
@@ -1,7 +1,7 @@
 #[cfg(feature = "full")]
 use crate::schema::local_site;
 use crate::{
-  newtypes::{LocalSiteId, SiteId},
+  newtypes::{LocalSiteId, PersonId, SiteId},
   CommentSortType,
   FederationMode,
   ListingType,
@@ -86,6 +86,9 @@ pub struct LocalSite {
   /// If this is true, users will never see the dialog asking to support Lemmy development with
   /// donations.
   pub disable_donation_dialog: bool,
+  /// The local bot account which performs the actions of community automod rules.
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub automod_person_id: Option<PersonId>,
 }
 
 #[derive(Clone, derive_new::new)]
@@ -181,4 +184,5 @@ pub struct LocalSiteUpdateForm {
   pub comment_upvotes: Option<FederationMode>,
   pub comment_downvotes: Option<FederationMode>,
   pub disable_donation_dialog: Option<bool>,
+  pub automod_person_id: Option<Option<PersonId>>,
 }
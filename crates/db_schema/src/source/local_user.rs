@@ -76,6 +76,8 @@ pub struct LocalUser {
   pub last_donation_notification: DateTime<Utc>,
   /// Whether to hide posts containing images/videos
   pub hide_media: bool,
+  /// Whether to subscribe to the comments of your own posts.
+  pub auto_subscribe_own_posts: bool,
 }
 
 #[derive(Clone, derive_new::new)]
@@ -138,6 +140,8 @@ pub struct LocalUserInsertForm {
   pub last_donation_notification: Option<DateTime<Utc>>,
   #[new(default)]
   pub hide_media: Option<bool>,
+  #[new(default)]
+  pub auto_subscribe_own_posts: Option<bool>,
 }
 
 #[derive(Clone, Default)]
@@ -172,4 +176,5 @@ pub struct LocalUserUpdateForm {
   pub auto_mark_fetched_posts_as_read: Option<bool>,
   pub last_donation_notification: Option<DateTime<Utc>>,
   pub hide_media: Option<bool>,
+  pub auto_subscribe_own_posts: Option<bool>,
 }
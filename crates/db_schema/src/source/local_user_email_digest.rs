@@ -0,0 +1,16 @@
+#[cfg(feature = "full")]
+use crate::schema::local_user_email_digest;
+use crate::{newtypes::LocalUserId, EmailDigestMode};
+use chrono::{DateTime, Utc};
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "full", diesel(table_name = local_user_email_digest))]
+#[cfg_attr(feature = "full", diesel(primary_key(local_user_id)))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+/// The email digest setting of a user, and when the last digest was sent.
+pub struct LocalUserEmailDigest {
+  pub local_user_id: LocalUserId,
+  pub mode: EmailDigestMode,
+  pub last_sent: DateTime<Utc>,
+}
@@ -0,0 +1,66 @@
+#[cfg(feature = "full")]
+use crate::schema::{local_user_keyword_filter, local_user_keyword_filter_listing};
+use crate::{
+  newtypes::{KeywordFilterId, LocalUserId},
+  KeywordFilterMode,
+  ListingType,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "full")]
+use ts_rs::TS;
+
+/// Hides posts and comments which contain the keyword from the user.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable, TS))]
+#[cfg_attr(feature = "full", diesel(table_name = local_user_keyword_filter))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "full", ts(export))]
+pub struct LocalUserKeywordFilter {
+  pub id: KeywordFilterId,
+  pub local_user_id: LocalUserId,
+  pub keyword: String,
+  pub mode: KeywordFilterMode,
+  /// Match against post titles.
+  pub match_title: bool,
+  /// Match against post bodies and comment contents.
+  pub match_body: bool,
+  /// Match against post urls.
+  pub match_url: bool,
+  pub published: DateTime<Utc>,
+}
+
+#[derive(Clone, Debug, derive_new::new)]
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = local_user_keyword_filter))]
+pub struct LocalUserKeywordFilterInsertForm {
+  pub local_user_id: LocalUserId,
+  pub keyword: String,
+  #[new(default)]
+  pub mode: Option<KeywordFilterMode>,
+  #[new(default)]
+  pub match_title: Option<bool>,
+  #[new(default)]
+  pub match_body: Option<bool>,
+  #[new(default)]
+  pub match_url: Option<bool>,
+}
+
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "full", derive(AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = local_user_keyword_filter))]
+pub struct LocalUserKeywordFilterUpdateForm {
+  pub keyword: Option<String>,
+  pub mode: Option<KeywordFilterMode>,
+  pub match_title: Option<bool>,
+  pub match_body: Option<bool>,
+  pub match_url: Option<bool>,
+}
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = local_user_keyword_filter_listing))]
+pub struct LocalUserKeywordFilterListingForm {
+  pub keyword_filter_id: KeywordFilterId,
+  pub listing_type: ListingType,
+}
@@ -4,20 +4,27 @@ use url::Url;
 #[cfg(feature = "full")]
 pub mod activity;
 pub mod actor_language;
+pub mod api_token;
+pub mod automod_rule;
 pub mod captcha_answer;
 pub mod combined;
 pub mod comment;
 pub mod comment_reply;
 pub mod comment_report;
+pub mod comment_revision;
 pub mod community;
+pub mod community_backfill;
 pub mod community_block;
 pub mod community_report;
 pub mod custom_emoji;
 pub mod custom_emoji_keyword;
+pub mod draft;
 pub mod email_verification;
 pub mod federation_allowlist;
 pub mod federation_blocklist;
+pub mod federation_limit;
 pub mod federation_queue_state;
+pub mod feed;
 pub mod images;
 pub mod instance;
 pub mod instance_block;
@@ -26,25 +33,38 @@ pub mod local_site;
 pub mod local_site_rate_limit;
 pub mod local_site_url_blocklist;
 pub mod local_user;
+pub mod local_user_email_digest;
+pub mod local_user_keyword_filter;
 pub mod local_user_vote_display_mode;
 pub mod login_token;
 pub mod mod_log;
+pub mod mod_note;
+pub mod modmail;
 pub mod oauth_account;
 pub mod oauth_provider;
+pub mod outgoing_email;
 pub mod password_reset_request;
 pub mod person;
+pub mod person_alias;
 pub mod person_block;
 pub mod person_comment_mention;
 pub mod person_post_mention;
 pub mod post;
+pub mod post_poll;
 pub mod post_report;
+pub mod post_revision;
 pub mod private_message;
 pub mod private_message_report;
+pub mod push_subscription;
+pub mod queued_email_notification;
 pub mod registration_application;
+pub mod scheduled_job;
 pub mod secret;
 pub mod site;
 pub mod tag;
 pub mod tagline;
+pub mod thread_subscription;
+pub mod webhook;
 
 /// Default value for columns like [community::Community.inbox_url] which are marked as serde(skip).
 ///
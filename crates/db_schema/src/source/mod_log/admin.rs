@@ -1,6 +1,7 @@
 use crate::newtypes::{
   AdminAllowInstanceId,
   AdminBlockInstanceId,
+  AdminLimitInstanceId,
   AdminPurgeCommentId,
   AdminPurgeCommunityId,
   AdminPurgePersonId,
@@ -14,6 +15,7 @@ use crate::newtypes::{
 use crate::schema::{
   admin_allow_instance,
   admin_block_instance,
+  admin_limit_instance,
   admin_purge_comment,
   admin_purge_community,
   admin_purge_person,
@@ -184,3 +186,41 @@ pub struct AdminBlockInstanceForm {
   pub blocked: bool,
   pub reason: Option<String>,
 }
+
+#[skip_serializing_none]
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(
+  feature = "full",
+  derive(TS, Queryable, Selectable, Associations, Identifiable)
+)]
+#[cfg_attr(
+  feature = "full",
+  diesel(belongs_to(crate::source::instance::Instance))
+)]
+#[cfg_attr(feature = "full", diesel(table_name = admin_limit_instance))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "full", ts(export))]
+/// When an admin changes the limits of an instance.
+pub struct AdminLimitInstance {
+  pub id: AdminLimitInstanceId,
+  pub instance_id: InstanceId,
+  pub admin_person_id: PersonId,
+  pub limited: bool,
+  pub reject_media: bool,
+  pub force_nsfw: bool,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub reason: Option<String>,
+  pub published: DateTime<Utc>,
+}
+
+#[derive(Clone, Default)]
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = admin_limit_instance))]
+pub struct AdminLimitInstanceForm {
+  pub instance_id: InstanceId,
+  pub admin_person_id: PersonId,
+  pub limited: bool,
+  pub reject_media: bool,
+  pub force_nsfw: bool,
+  pub reason: Option<String>,
+}
@@ -1,24 +1,8 @@
-use crate::newtypes::{
-  CommentId,
-  CommunityId,
-  ModAddCommunityId,
-  ModAddId,
-  ModBanFromCommunityId,
-  ModBanId,
-  ModFeaturePostId,
-  ModHideCommunityId,
-  ModLockPostId,
-  ModRemoveCommentId,
-  ModRemoveCommunityId,
-  ModRemovePostId,
-  ModTransferCommunityId,
-  PersonId,
-  PostId,
-};
 #[cfg(feature = "full")]
 use crate::schema::{
   mod_add,
   mod_add_community,
+  mod_automod_action,
   mod_ban,
   mod_ban_from_community,
   mod_feature_post,
@@ -28,6 +12,29 @@ use crate::schema::{
   mod_remove_community,
   mod_remove_post,
   mod_transfer_community,
+  mod_warn_person,
+};
+use crate::{
+  newtypes::{
+    CommentId,
+    CommunityId,
+    ModAddCommunityId,
+    ModAddId,
+    ModAutomodActionId,
+    ModBanFromCommunityId,
+    ModBanId,
+    ModFeaturePostId,
+    ModHideCommunityId,
+    ModLockPostId,
+    ModRemoveCommentId,
+    ModRemoveCommunityId,
+    ModRemovePostId,
+    ModTransferCommunityId,
+    ModWarnPersonId,
+    PersonId,
+    PostId,
+  },
+  AutomodAction,
 };
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -312,3 +319,71 @@ pub struct ModAddForm {
   pub other_person_id: PersonId,
   pub removed: Option<bool>,
 }
+
+#[skip_serializing_none]
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable, TS))]
+#[cfg_attr(feature = "full", diesel(table_name = mod_automod_action))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "full", ts(export))]
+/// When the automoderator acts on a post or comment because it matched a community rule.
+pub struct ModAutomodAction {
+  pub id: ModAutomodActionId,
+  pub mod_person_id: PersonId,
+  pub community_id: CommunityId,
+  pub post_id: PostId,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub comment_id: Option<CommentId>,
+  pub action: AutomodAction,
+  /// The name of the rule which matched.
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub reason: Option<String>,
+  pub published: DateTime<Utc>,
+}
+
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = mod_automod_action))]
+pub struct ModAutomodActionForm {
+  pub mod_person_id: PersonId,
+  pub community_id: CommunityId,
+  pub post_id: PostId,
+  pub comment_id: Option<CommentId>,
+  pub action: AutomodAction,
+  pub reason: Option<String>,
+}
+
+#[skip_serializing_none]
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable, TS))]
+#[cfg_attr(feature = "full", diesel(table_name = mod_warn_person))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "full", ts(export))]
+/// When a moderator or admin warns someone. Warnings without a community are sent by admins.
+pub struct ModWarnPerson {
+  pub id: ModWarnPersonId,
+  pub mod_person_id: PersonId,
+  pub other_person_id: PersonId,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub community_id: Option<CommunityId>,
+  pub reason: String,
+  /// Whether the warned person has read the warning in their inbox.
+  pub read: bool,
+  pub published: DateTime<Utc>,
+}
+
+#[derive(Clone, derive_new::new)]
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = mod_warn_person))]
+pub struct ModWarnPersonForm {
+  pub mod_person_id: PersonId,
+  pub other_person_id: PersonId,
+  pub community_id: Option<CommunityId>,
+  pub reason: String,
+}
+
+#[derive(Clone, Default)]
+#[cfg_attr(feature = "full", derive(AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = mod_warn_person))]
+pub struct ModWarnPersonUpdateForm {
+  pub read: Option<bool>,
+}
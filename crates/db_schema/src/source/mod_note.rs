@@ -0,0 +1,37 @@
+use crate::newtypes::{CommunityId, ModNoteId, PersonId};
+#[cfg(feature = "full")]
+use crate::schema::mod_note;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+#[cfg(feature = "full")]
+use ts_rs::TS;
+
+#[skip_serializing_none]
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable, TS))]
+#[cfg_attr(feature = "full", diesel(table_name = mod_note))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A private note of a moderator about a person. It is only visible to the moderators of the
+/// community, and to admins. Notes without a community are written by admins.
+pub struct ModNote {
+  pub id: ModNoteId,
+  /// The person which the note is about.
+  pub person_id: PersonId,
+  pub creator_id: PersonId,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub community_id: Option<CommunityId>,
+  pub content: String,
+  pub published: DateTime<Utc>,
+}
+
+#[derive(Clone, derive_new::new)]
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = mod_note))]
+pub struct ModNoteInsertForm {
+  pub person_id: PersonId,
+  pub creator_id: PersonId,
+  pub community_id: Option<CommunityId>,
+  pub content: String,
+}
@@ -0,0 +1,80 @@
+use crate::newtypes::{CommunityId, ModmailMessageId, ModmailThreadId, PersonId};
+#[cfg(feature = "full")]
+use crate::schema::{modmail_message, modmail_thread};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+#[cfg(feature = "full")]
+use ts_rs::TS;
+
+#[skip_serializing_none]
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable, TS))]
+#[cfg_attr(feature = "full", diesel(table_name = modmail_thread))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A conversation between a person and the moderators of a community. All moderators of the
+/// community can read and reply to it.
+pub struct ModmailThread {
+  pub id: ModmailThreadId,
+  pub community_id: CommunityId,
+  pub creator_id: PersonId,
+  pub subject: String,
+  /// Archived threads are resolved, they are unarchived again when the creator replies.
+  pub archived: bool,
+  pub published: DateTime<Utc>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub updated: Option<DateTime<Utc>>,
+}
+
+#[derive(Clone, derive_new::new)]
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = modmail_thread))]
+pub struct ModmailThreadInsertForm {
+  pub community_id: CommunityId,
+  pub creator_id: PersonId,
+  pub subject: String,
+}
+
+#[derive(Clone, Default)]
+#[cfg_attr(feature = "full", derive(AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = modmail_thread))]
+pub struct ModmailThreadUpdateForm {
+  pub archived: Option<bool>,
+  pub updated: Option<Option<DateTime<Utc>>>,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable, TS))]
+#[cfg_attr(feature = "full", diesel(table_name = modmail_message))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A message in a modmail thread.
+pub struct ModmailMessage {
+  pub id: ModmailMessageId,
+  pub thread_id: ModmailThreadId,
+  pub creator_id: PersonId,
+  pub content: String,
+  /// Whether the message was sent by a moderator, otherwise it was sent by the thread creator.
+  pub from_moderator: bool,
+  /// Whether the message was read by the other side of the conversation.
+  pub read: bool,
+  pub published: DateTime<Utc>,
+}
+
+#[derive(Clone, derive_new::new)]
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = modmail_message))]
+pub struct ModmailMessageInsertForm {
+  pub thread_id: ModmailThreadId,
+  pub creator_id: PersonId,
+  pub content: String,
+  pub from_moderator: bool,
+}
+
+#[derive(Clone, Default)]
+#[cfg_attr(feature = "full", derive(AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = modmail_message))]
+pub struct ModmailMessageUpdateForm {
+  pub read: Option<bool>,
+}
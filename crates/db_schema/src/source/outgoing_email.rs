@@ -0,0 +1,49 @@
+use crate::newtypes::OutgoingEmailId;
+#[cfg(feature = "full")]
+use crate::schema::outgoing_email;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+#[cfg(feature = "full")]
+use ts_rs::TS;
+
+#[skip_serializing_none]
+#[derive(PartialEq, Eq, Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable, TS))]
+#[cfg_attr(feature = "full", diesel(table_name = outgoing_email))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "full", ts(export))]
+/// An email in the queue of emails which are sent in the background.
+pub struct OutgoingEmail {
+  pub id: OutgoingEmailId,
+  pub to_email: String,
+  pub to_name: String,
+  pub subject: String,
+  /// The html body. It is not returned in the api, as it can contain password reset links.
+  #[serde(skip)]
+  #[cfg_attr(feature = "full", ts(skip))]
+  pub body: String,
+  #[serde(skip)]
+  #[cfg_attr(feature = "full", ts(skip))]
+  pub unsubscribe_link: Option<String>,
+  pub attempts: i32,
+  /// When sending will be attempted again. Empty if it was sent, or if it failed too often.
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub next_attempt: Option<DateTime<Utc>>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub sent: Option<DateTime<Utc>>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub last_error: Option<String>,
+  pub published: DateTime<Utc>,
+}
+
+#[derive(Clone, Debug, derive_new::new)]
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = outgoing_email))]
+pub struct OutgoingEmailInsertForm {
+  pub to_email: String,
+  pub to_name: String,
+  pub subject: String,
+  pub body: String,
+  pub unsubscribe_link: Option<String>,
+}
@@ -64,6 +64,9 @@ pub struct Person {
   #[cfg_attr(feature = "full", ts(optional))]
   pub ban_expires: Option<DateTime<Utc>>,
   pub instance_id: InstanceId,
+  /// The account which this person has moved to. Clients should redirect to it.
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub moved_to: Option<DbUrl>,
 }
 
 #[derive(Clone, derive_new::new)]
@@ -105,6 +108,8 @@ pub struct PersonInsertForm {
   pub bot_account: Option<bool>,
   #[new(default)]
   pub ban_expires: Option<DateTime<Utc>>,
+  #[new(default)]
+  pub moved_to: Option<DbUrl>,
 }
 
 #[derive(Clone, Default)]
@@ -127,6 +132,7 @@ pub struct PersonUpdateForm {
   pub matrix_user_id: Option<Option<String>>,
   pub bot_account: Option<bool>,
   pub ban_expires: Option<Option<DateTime<Utc>>>,
+  pub moved_to: Option<Option<DbUrl>>,
 }
 
 #[derive(PartialEq, Eq, Debug)]
@@ -0,0 +1,26 @@
+use crate::newtypes::{DbUrl, PersonId};
+#[cfg(feature = "full")]
+use crate::schema::person_alias;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Another account of the same person (`alsoKnownAs`). Moving an account is only possible to a
+/// target which lists the old account as alias.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "full", diesel(table_name = person_alias))]
+#[cfg_attr(feature = "full", diesel(primary_key(person_id, alias)))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+pub struct PersonAlias {
+  pub person_id: PersonId,
+  pub alias: DbUrl,
+  pub published: DateTime<Utc>,
+}
+
+#[derive(Clone, Debug, derive_new::new)]
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = person_alias))]
+pub struct PersonAliasForm {
+  pub person_id: PersonId,
+  pub alias: DbUrl,
+}
@@ -69,6 +69,9 @@ pub struct Post {
   /// Time at which the post will be published. None means publish immediately.
   #[cfg_attr(feature = "full", ts(optional))]
   pub scheduled_publish_time: Option<DateTime<Utc>>,
+  /// The original post, if this is a crosspost.
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub crosspost_of_id: Option<PostId>,
 }
 
 #[derive(Debug, Clone, derive_new::new)]
@@ -118,6 +121,8 @@ pub struct PostInsertForm {
   pub alt_text: Option<String>,
   #[new(default)]
   pub scheduled_publish_time: Option<DateTime<Utc>>,
+  #[new(default)]
+  pub crosspost_of_id: Option<PostId>,
 }
 
 #[derive(Debug, Clone, Default)]
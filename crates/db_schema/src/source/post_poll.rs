@@ -0,0 +1,85 @@
+use crate::newtypes::{PersonId, PostId, PostPollOptionId};
+#[cfg(feature = "full")]
+use crate::schema::{post_poll, post_poll_option, post_poll_vote};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+#[skip_serializing_none]
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "full", diesel(table_name = post_poll))]
+#[cfg_attr(feature = "full", diesel(primary_key(post_id)))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+/// A poll which is attached to a post.
+pub struct PostPoll {
+  pub post_id: PostId,
+  /// If true, voters can choose any number of options, otherwise exactly one.
+  pub multiple_choice: bool,
+  /// No more votes are accepted after this time.
+  pub end_time: Option<DateTime<Utc>>,
+  /// The number of people who voted, this can be lower than the sum of option votes for
+  /// multiple choice polls.
+  pub voters_count: i32,
+  pub published: DateTime<Utc>,
+  /// For local polls, if the final vote counts were sent out after the poll ended.
+  #[serde(skip)]
+  pub results_federated: bool,
+}
+
+#[derive(Clone, Debug, derive_new::new)]
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = post_poll))]
+pub struct PostPollInsertForm {
+  pub post_id: PostId,
+  pub multiple_choice: bool,
+  pub end_time: Option<DateTime<Utc>>,
+  /// Only set for remote polls, local polls count the votes themselves.
+  #[new(default)]
+  pub voters_count: Option<i32>,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "full", diesel(table_name = post_poll_option))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+pub struct PostPollOption {
+  pub id: PostPollOptionId,
+  pub post_id: PostId,
+  pub name: String,
+  pub position: i32,
+  pub vote_count: i32,
+}
+
+#[derive(Clone, Debug, derive_new::new)]
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = post_poll_option))]
+pub struct PostPollOptionInsertForm {
+  pub post_id: PostId,
+  pub name: String,
+  pub position: i32,
+  /// Only set for remote polls, local polls count the votes themselves.
+  #[new(default)]
+  pub vote_count: Option<i32>,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "full", diesel(table_name = post_poll_vote))]
+#[cfg_attr(feature = "full", diesel(primary_key(option_id, person_id)))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+pub struct PostPollVote {
+  pub option_id: PostPollOptionId,
+  pub person_id: PersonId,
+  pub post_id: PostId,
+  pub published: DateTime<Utc>,
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = post_poll_vote))]
+pub struct PostPollVoteForm {
+  pub option_id: PostPollOptionId,
+  pub person_id: PersonId,
+  pub post_id: PostId,
+}
@@ -0,0 +1,40 @@
+use crate::newtypes::{DbUrl, PersonId, PostId, PostRevisionId};
+#[cfg(feature = "full")]
+use crate::schema::post_revision;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+#[cfg(feature = "full")]
+use ts_rs::TS;
+
+#[skip_serializing_none]
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable, TS))]
+#[cfg_attr(feature = "full", diesel(table_name = post_revision))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A previous version of a post, which was stored when the post got edited.
+pub struct PostRevision {
+  pub id: PostRevisionId,
+  pub post_id: PostId,
+  /// The person who made the edit which replaced this version.
+  pub editor_id: PersonId,
+  pub name: String,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub url: Option<DbUrl>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub body: Option<String>,
+  /// When this version was replaced.
+  pub published: DateTime<Utc>,
+}
+
+#[derive(Clone, derive_new::new)]
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = post_revision))]
+pub struct PostRevisionInsertForm {
+  pub post_id: PostId,
+  pub editor_id: PersonId,
+  pub name: String,
+  pub url: Option<DbUrl>,
+  pub body: Option<String>,
+}
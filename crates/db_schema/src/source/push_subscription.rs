@@ -0,0 +1,39 @@
+use crate::newtypes::{LocalUserId, PushSubscriptionId};
+#[cfg(feature = "full")]
+use crate::schema::push_subscription;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "full")]
+use ts_rs::TS;
+
+/// A Web Push subscription of a browser or app, which receives notifications for new inbox items.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable, TS))]
+#[cfg_attr(feature = "full", diesel(table_name = push_subscription))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "full", ts(export))]
+pub struct PushSubscription {
+  pub id: PushSubscriptionId,
+  pub local_user_id: LocalUserId,
+  /// The url of the push service where messages are sent to.
+  pub endpoint: String,
+  /// Public key of the subscription, which messages are encrypted for.
+  #[serde(skip)]
+  #[cfg_attr(feature = "full", ts(skip))]
+  pub p256dh: String,
+  /// Authentication secret of the subscription.
+  #[serde(skip)]
+  #[cfg_attr(feature = "full", ts(skip))]
+  pub auth: String,
+  pub published: DateTime<Utc>,
+}
+
+#[derive(Clone, Debug, derive_new::new)]
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = push_subscription))]
+pub struct PushSubscriptionInsertForm {
+  pub local_user_id: LocalUserId,
+  pub endpoint: String,
+  pub p256dh: String,
+  pub auth: String,
+}
@@ -0,0 +1,27 @@
+use crate::newtypes::{LocalUserId, QueuedEmailNotificationId};
+#[cfg(feature = "full")]
+use crate::schema::queued_email_notification;
+use chrono::{DateTime, Utc};
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "full", diesel(table_name = queued_email_notification))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+/// A notification email which waits to be sent as part of a digest.
+pub struct QueuedEmailNotification {
+  pub id: QueuedEmailNotificationId,
+  pub local_user_id: LocalUserId,
+  pub subject: String,
+  /// The html body of the notification.
+  pub body: String,
+  pub published: DateTime<Utc>,
+}
+
+#[derive(Clone, Debug, derive_new::new)]
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = queued_email_notification))]
+pub struct QueuedEmailNotificationInsertForm {
+  pub local_user_id: LocalUserId,
+  pub subject: String,
+  pub body: String,
+}
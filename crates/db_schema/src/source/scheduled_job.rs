@@ -0,0 +1,72 @@
+use crate::newtypes::ScheduledJobId;
+#[cfg(feature = "full")]
+use crate::schema::{scheduled_job, scheduled_job_run};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+#[cfg(feature = "full")]
+use ts_rs::TS;
+
+#[skip_serializing_none]
+#[derive(PartialEq, Eq, Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable, TS))]
+#[cfg_attr(feature = "full", diesel(table_name = scheduled_job))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A background job which runs periodically, and its current status.
+pub struct ScheduledJob {
+  pub id: ScheduledJobId,
+  pub name: String,
+  /// Seconds between successful runs.
+  pub interval_secs: i32,
+  pub next_run: DateTime<Utc>,
+  /// If set, the job is currently being run by the process `locked_by`.
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub locked_until: Option<DateTime<Utc>>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub locked_by: Option<String>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub last_started: Option<DateTime<Utc>>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub last_finished: Option<DateTime<Utc>>,
+  /// The error of the last run, if it failed.
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub last_error: Option<String>,
+  /// Number of consecutive failed runs.
+  pub failure_count: i32,
+  pub published: DateTime<Utc>,
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = scheduled_job))]
+pub struct ScheduledJobForm {
+  pub name: String,
+  pub interval_secs: i32,
+}
+
+#[skip_serializing_none]
+#[derive(PartialEq, Eq, Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable, TS))]
+#[cfg_attr(feature = "full", diesel(table_name = scheduled_job_run))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A single past run of a scheduled job.
+pub struct ScheduledJobRun {
+  pub id: i32,
+  pub job_id: ScheduledJobId,
+  pub started: DateTime<Utc>,
+  pub finished: DateTime<Utc>,
+  /// Null if the run was successful.
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub error: Option<String>,
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = scheduled_job_run))]
+pub struct ScheduledJobRunForm {
+  pub job_id: ScheduledJobId,
+  pub started: DateTime<Utc>,
+  pub error: Option<String>,
+}
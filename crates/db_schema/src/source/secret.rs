@@ -9,4 +9,7 @@ use crate::sensitive::SensitiveString;
 pub struct Secret {
   pub id: i32,
   pub jwt_secret: SensitiveString,
+  /// Key for signing Web Push requests, as base64 encoded PKCS#8 document.
+  pub vapid_private_key: Option<SensitiveString>,
+  pub vapid_public_key: Option<String>,
 }
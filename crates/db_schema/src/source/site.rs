@@ -50,6 +50,9 @@ pub struct Site {
   /// when the site is first opened by a user.
   #[cfg_attr(feature = "full", ts(optional))]
   pub content_warning: Option<String>,
+  /// Whether everyone can see the edit history of posts and comments on this site, instead of
+  /// only mods and admins.
+  pub edit_history_public: bool,
 }
 
 #[derive(Clone, derive_new::new)]
@@ -80,6 +83,8 @@ pub struct SiteInsertForm {
   pub public_key: Option<String>,
   #[new(default)]
   pub content_warning: Option<String>,
+  #[new(default)]
+  pub edit_history_public: Option<bool>,
 }
 
 #[derive(Clone, Default)]
@@ -100,4 +105,5 @@ pub struct SiteUpdateForm {
   pub private_key: Option<Option<String>>,
   pub public_key: Option<String>,
   pub content_warning: Option<Option<String>>,
+  pub edit_history_public: Option<bool>,
 }
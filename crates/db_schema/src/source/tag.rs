@@ -48,6 +48,15 @@ pub struct TagInsertForm {
   pub deleted: bool,
 }
 
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "full", derive(AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = tag))]
+pub struct TagUpdateForm {
+  pub name: Option<String>,
+  pub updated: Option<Option<DateTime<Utc>>>,
+  pub deleted: Option<bool>,
+}
+
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
 #[cfg_attr(feature = "full", diesel(table_name = post_tag))]
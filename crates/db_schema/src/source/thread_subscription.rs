@@ -0,0 +1,69 @@
+use crate::newtypes::{CommentId, PersonId, PostId, ThreadReplyId, ThreadSubscriptionId};
+#[cfg(feature = "full")]
+use crate::schema::{thread_reply, thread_subscription};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+#[cfg(feature = "full")]
+use ts_rs::TS;
+
+#[skip_serializing_none]
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable, TS))]
+#[cfg_attr(feature = "full", diesel(table_name = thread_subscription))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A subscription to all new comments in a post, or in the subtree of a comment.
+pub struct ThreadSubscription {
+  pub id: ThreadSubscriptionId,
+  pub person_id: PersonId,
+  pub post_id: PostId,
+  /// If set, only comments below this comment are included.
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub comment_id: Option<CommentId>,
+  /// Silences notifications for this part of a thread which you are otherwise subscribed to.
+  pub muted: bool,
+  pub published: DateTime<Utc>,
+}
+
+#[derive(Clone, derive_new::new)]
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = thread_subscription))]
+pub struct ThreadSubscriptionForm {
+  pub person_id: PersonId,
+  pub post_id: PostId,
+  pub comment_id: Option<CommentId>,
+  pub muted: bool,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(
+  feature = "full",
+  derive(Queryable, Selectable, Associations, Identifiable, TS)
+)]
+#[cfg_attr(feature = "full", diesel(belongs_to(crate::source::comment::Comment)))]
+#[cfg_attr(feature = "full", diesel(table_name = thread_reply))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A new comment in a thread which you are subscribed to.
+pub struct ThreadReply {
+  pub id: ThreadReplyId,
+  pub recipient_id: PersonId,
+  pub comment_id: CommentId,
+  pub read: bool,
+  pub published: DateTime<Utc>,
+}
+
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = thread_reply))]
+pub struct ThreadReplyInsertForm {
+  pub recipient_id: PersonId,
+  pub comment_id: CommentId,
+  pub read: Option<bool>,
+}
+
+#[cfg_attr(feature = "full", derive(AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = thread_reply))]
+pub struct ThreadReplyUpdateForm {
+  pub read: Option<bool>,
+}
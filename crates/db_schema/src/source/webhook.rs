@@ -0,0 +1,109 @@
+#[cfg(feature = "full")]
+use crate::schema::{webhook, webhook_delivery, webhook_event};
+use crate::{
+  newtypes::{CommunityId, PersonId, WebhookId},
+  WebhookEventType,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+#[cfg(feature = "full")]
+use ts_rs::TS;
+
+#[skip_serializing_none]
+#[derive(PartialEq, Eq, Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable, TS))]
+#[cfg_attr(feature = "full", diesel(table_name = webhook))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "full", ts(export))]
+/// An url which gets notified about events on this instance.
+pub struct Webhook {
+  pub id: WebhookId,
+  /// If set, only events from this community are sent. Otherwise events from the whole site.
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub community_id: Option<CommunityId>,
+  pub creator_id: PersonId,
+  pub url: String,
+  /// Used to sign the deliveries, it is never returned by the API.
+  #[serde(skip)]
+  pub secret: String,
+  pub enabled: bool,
+  pub published: DateTime<Utc>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub updated: Option<DateTime<Utc>>,
+}
+
+#[derive(Clone, Debug, derive_new::new)]
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = webhook))]
+pub struct WebhookInsertForm {
+  pub community_id: Option<CommunityId>,
+  pub creator_id: PersonId,
+  pub url: String,
+  pub secret: String,
+}
+
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "full", derive(AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = webhook))]
+pub struct WebhookUpdateForm {
+  pub url: Option<String>,
+  pub secret: Option<String>,
+  pub enabled: Option<bool>,
+  pub updated: Option<Option<DateTime<Utc>>>,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+#[cfg_attr(
+  feature = "full",
+  derive(Queryable, Selectable, Associations, Identifiable, Insertable)
+)]
+#[cfg_attr(feature = "full", diesel(table_name = webhook_event))]
+#[cfg_attr(feature = "full", diesel(belongs_to(crate::source::webhook::Webhook)))]
+#[cfg_attr(feature = "full", diesel(primary_key(webhook_id, event)))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+/// An event type which a webhook is subscribed to.
+pub struct WebhookEvent {
+  pub webhook_id: WebhookId,
+  pub event: WebhookEventType,
+}
+
+#[skip_serializing_none]
+#[derive(PartialEq, Eq, Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable, TS))]
+#[cfg_attr(feature = "full", diesel(table_name = webhook_delivery))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A single event sent to a webhook.
+pub struct WebhookDelivery {
+  pub id: i32,
+  pub webhook_id: WebhookId,
+  pub event: WebhookEventType,
+  /// The json request body.
+  pub payload: String,
+  pub attempts: i32,
+  /// When the delivery will be attempted again. Empty if it was delivered, or if it failed too
+  /// often.
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub next_attempt: Option<DateTime<Utc>>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub delivered: Option<DateTime<Utc>>,
+  /// The http status of the last attempt.
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub last_status: Option<i32>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub last_error: Option<String>,
+  pub published: DateTime<Utc>,
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = webhook_delivery))]
+pub struct WebhookDeliveryInsertForm {
+  pub webhook_id: WebhookId,
+  pub event: WebhookEventType,
+  pub payload: String,
+  /// Set into the future when the delivery is attempted right away, so that the retry job doesn't
+  /// send it at the same time.
+  pub next_attempt: DateTime<Utc>,
+}
@@ -16,7 +16,7 @@ use diesel::{
     ConnectionResult,
     Error::{self as DieselError, QueryBuilderError},
   },
-  sql_types::{self, SingleValue, Timestamptz},
+  sql_types::{self, SingleValue, Text, Timestamptz},
   Column,
   Expression,
   ExpressionMethods,
@@ -522,7 +522,9 @@ static EMAIL_REGEX: LazyLock<Regex> = LazyLock::new(|| {
 });
 
 pub mod functions {
-  use diesel::sql_types::{BigInt, Text, Timestamptz};
+  use super::Tsquery;
+  use crate::schema::sql_types::Tsvector;
+  use diesel::sql_types::{BigInt, Integer, Nullable, Text, Timestamptz};
 
   define_sql_function! {
     #[sql_name = "r.hot_rank"]
@@ -539,6 +541,21 @@ pub mod functions {
     fn controversy_rank(upvotes: BigInt, downvotes: BigInt, score: BigInt) -> Double;
   }
 
+  define_sql_function! {
+    #[sql_name = "r.person_removal_count"]
+    fn person_removal_count(person_id: Nullable<Integer>) -> BigInt;
+  }
+
+  define_sql_function! {
+    #[sql_name = "r.person_ban_count"]
+    fn person_ban_count(person_id: Nullable<Integer>) -> BigInt;
+  }
+
+  define_sql_function! {
+    #[sql_name = "r.post_poll"]
+    fn post_poll(post_id: Nullable<Integer>, person_id: Nullable<Integer>) -> Nullable<Json>;
+  }
+
   define_sql_function!(fn reverse_timestamp_sort(time: Timestamptz) -> BigInt);
 
   define_sql_function!(fn lower(x: Text) -> Text);
@@ -555,9 +572,48 @@ pub mod functions {
     fn json_agg<T: diesel::sql_types::SqlType + diesel::sql_types::SingleValue>(obj: T) -> Json
   }
 
+  define_sql_function! {
+    #[sql_name = "r.search_query"]
+    fn search_query(search_term: Text) -> Tsquery;
+  }
+
+  define_sql_function! {
+    #[sql_name = "r.title_search_vector"]
+    fn title_search_vector(search_vector: Tsvector) -> Tsvector;
+  }
+
+  define_sql_function!(fn ts_rank(search_vector: Tsvector, query: Tsquery) -> Float);
+
   define_sql_function!(#[sql_name = "coalesce"] fn coalesce_2_nullable<T: diesel::sql_types::SqlType + diesel::sql_types::SingleValue>(x: diesel::sql_types::Nullable<T>, y: diesel::sql_types::Nullable<T>) -> diesel::sql_types::Nullable<T>);
 }
 
+/// The Postgres `tsquery` type, used for full text search.
+#[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+#[diesel(postgres_type(name = "tsquery", schema = "pg_catalog"))]
+pub struct Tsquery;
+
+diesel::infix_operator!(TsMatch, " @@ ", backend: Pg);
+
+/// Checks if a `tsvector` matches a `tsquery` with the `@@` operator.
+pub fn ts_match<V, Q>(search_vector: V, query: Q) -> TsMatch<V, Q>
+where
+  V: Expression<SqlType = crate::schema::sql_types::Tsvector>,
+  Q: Expression<SqlType = Tsquery>,
+{
+  TsMatch::new(search_vector, query)
+}
+
+diesel::infix_operator!(RegexMatchInsensitive, " ~* ", backend: Pg);
+
+/// Checks if the text matches a POSIX regular expression with the case insensitive `~*` operator.
+pub fn regex_match_insensitive<T, P>(text: T, pattern: P) -> RegexMatchInsensitive<T, P>
+where
+  T: Expression<SqlType = Text>,
+  P: Expression<SqlType = Text>,
+{
+  RegexMatchInsensitive::new(text, pattern)
+}
+
 pub const DELETED_REPLACEMENT_TEXT: &str = "*Permanently Deleted*";
 
 pub fn now() -> AsExprOf<diesel::dsl::now, diesel::sql_types::Timestamptz> {
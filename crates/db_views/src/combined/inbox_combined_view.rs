@@ -3,12 +3,15 @@ use crate::structs::{
   InboxCombinedPaginationCursor,
   InboxCombinedView,
   InboxCombinedViewInternal,
+  ModWarnPersonView,
+  ModmailMessageView,
   PersonCommentMentionView,
   PersonPostMentionView,
   PrivateMessageView,
+  ThreadReplyView,
 };
 use diesel::{
-  dsl::not,
+  dsl::{exists, not},
   result::Error,
   BoolExpressionMethods,
   ExpressionMethods,
@@ -33,6 +36,9 @@ use lemmy_db_schema::{
     inbox_combined,
     instance_actions,
     local_user,
+    mod_warn_person,
+    modmail_message,
+    modmail_thread,
     person,
     person_actions,
     person_comment_mention,
@@ -43,13 +49,14 @@ use lemmy_db_schema::{
     post_tag,
     private_message,
     tag,
+    thread_reply,
   },
   source::{
     combined::inbox::{inbox_combined_keys as key, InboxCombined},
     community::CommunityFollower,
   },
   traits::InternalToCombinedView,
-  utils::{actions, actions_alias, functions::coalesce, get_conn, DbPool},
+  utils::{action_query, actions, actions_alias, functions::coalesce, get_conn, DbPool},
   InboxDataType,
 };
 use lemmy_utils::error::LemmyResult;
@@ -76,7 +83,10 @@ impl InboxCombinedViewInternal {
         private_message::read
           .eq(false)
           .and(private_message::recipient_id.eq(my_person_id)),
-      );
+      )
+      .or(mod_warn_person::read.eq(false))
+      .or(modmail_message::read.eq(false))
+      .or(thread_reply::read.eq(false));
 
     let item_creator_join = comment::creator_id
       .eq(item_creator)
@@ -85,17 +95,39 @@ impl InboxCombinedViewInternal {
           .is_not_null()
           .and(post::creator_id.eq(item_creator)),
       )
-      .or(private_message::creator_id.eq(item_creator));
+      .or(private_message::creator_id.eq(item_creator))
+      .or(mod_warn_person::mod_person_id.eq(item_creator))
+      .or(modmail_message::creator_id.eq(item_creator));
 
     let recipient_join = comment_reply::recipient_id
       .eq(recipient_person)
       .or(person_comment_mention::recipient_id.eq(recipient_person))
       .or(person_post_mention::recipient_id.eq(recipient_person))
-      .or(private_message::recipient_id.eq(recipient_person));
+      .or(private_message::recipient_id.eq(recipient_person))
+      .or(mod_warn_person::other_person_id.eq(recipient_person))
+      .or(thread_reply::recipient_id.eq(recipient_person))
+      // Modmail messages from moderators go to the thread creator, all other messages go to the
+      // moderators of the community
+      .or(
+        modmail_message::from_moderator
+          .and(modmail_thread::creator_id.eq(recipient_person))
+          .or(
+            not(modmail_message::from_moderator)
+              .and(recipient_person.eq(my_person_id))
+              // Like the separate modmail count, archived threads are not counted
+              .and(not(modmail_thread::archived))
+              .and(exists(
+                action_query(community_actions::became_moderator)
+                  .filter(community_actions::community_id.eq(modmail_thread::community_id))
+                  .filter(community_actions::person_id.eq(my_person_id)),
+              )),
+          ),
+      );
 
     let comment_join = comment_reply::comment_id
       .eq(comment::id)
       .or(person_comment_mention::comment_id.eq(comment::id))
+      .or(thread_reply::comment_id.eq(comment::id))
       // Filter out the deleted / removed
       .and(not(comment::deleted))
       .and(not(comment::removed));
@@ -117,6 +149,10 @@ impl InboxCombinedViewInternal {
       .left_join(person_comment_mention::table)
       .left_join(person_post_mention::table)
       .left_join(private_message::table.on(private_message_join))
+      .left_join(mod_warn_person::table)
+      .left_join(modmail_message::table)
+      .left_join(modmail_thread::table.on(modmail_message::thread_id.eq(modmail_thread::id)))
+      .left_join(thread_reply::table)
       .left_join(comment::table.on(comment_join))
       .left_join(post::table.on(post_join))
       // The item creator
@@ -162,6 +198,9 @@ impl InboxCombinedPaginationCursor {
       InboxCombinedView::CommentMention(v) => ('C', v.person_comment_mention.id.0),
       InboxCombinedView::PostMention(v) => ('P', v.person_post_mention.id.0),
       InboxCombinedView::PrivateMessage(v) => ('M', v.private_message.id.0),
+      InboxCombinedView::Warning(v) => ('W', v.mod_warn_person.id.0),
+      InboxCombinedView::Modmail(v) => ('D', v.modmail_message.id.0),
+      InboxCombinedView::ThreadReply(v) => ('T', v.thread_reply.id.0),
     };
     // hex encoding to prevent ossification
     InboxCombinedPaginationCursor(format!("{prefix}{id:x}"))
@@ -179,6 +218,9 @@ impl InboxCombinedPaginationCursor {
       "C" => query.filter(inbox_combined::person_comment_mention_id.eq(id)),
       "P" => query.filter(inbox_combined::person_post_mention_id.eq(id)),
       "M" => query.filter(inbox_combined::private_message_id.eq(id)),
+      "W" => query.filter(inbox_combined::mod_warn_person_id.eq(id)),
+      "D" => query.filter(inbox_combined::modmail_message_id.eq(id)),
+      "T" => query.filter(inbox_combined::thread_reply_id.eq(id)),
       _ => return Err(err_msg()),
     };
     let token = query.first(&mut get_conn(pool).await?).await?;
@@ -217,17 +259,37 @@ impl InboxCombinedQuery {
           .is_not_null()
           .and(post::creator_id.eq(item_creator)),
       )
-      .or(private_message::creator_id.eq(item_creator));
+      .or(private_message::creator_id.eq(item_creator))
+      .or(mod_warn_person::mod_person_id.eq(item_creator))
+      .or(modmail_message::creator_id.eq(item_creator));
 
     let recipient_join = comment_reply::recipient_id
       .eq(recipient_person)
       .or(person_comment_mention::recipient_id.eq(recipient_person))
       .or(person_post_mention::recipient_id.eq(recipient_person))
-      .or(private_message::recipient_id.eq(recipient_person));
+      .or(private_message::recipient_id.eq(recipient_person))
+      .or(mod_warn_person::other_person_id.eq(recipient_person))
+      .or(thread_reply::recipient_id.eq(recipient_person))
+      // Modmail messages from moderators go to the thread creator, all other messages go to the
+      // moderators of the community
+      .or(
+        modmail_message::from_moderator
+          .and(modmail_thread::creator_id.eq(recipient_person))
+          .or(
+            not(modmail_message::from_moderator)
+              .and(recipient_person.eq(my_person_id))
+              .and(exists(
+                action_query(community_actions::became_moderator)
+                  .filter(community_actions::community_id.eq(modmail_thread::community_id))
+                  .filter(community_actions::person_id.eq(my_person_id)),
+              )),
+          ),
+      );
 
     let comment_join = comment_reply::comment_id
       .eq(comment::id)
       .or(person_comment_mention::comment_id.eq(comment::id))
+      .or(thread_reply::comment_id.eq(comment::id))
       // Filter out the deleted / removed
       .and(not(comment::deleted))
       .and(not(comment::removed));
@@ -244,7 +306,10 @@ impl InboxCombinedQuery {
       .eq(private_message::id.nullable())
       .and(not(private_message::deleted));
 
-    let community_join = post::community_id.eq(community::id);
+    let community_join = post::community_id
+      .eq(community::id)
+      .or(mod_warn_person::community_id.eq(community::id.nullable()))
+      .or(modmail_thread::community_id.eq(community::id));
 
     let post_tags = post_tag::table
       .inner_join(tag::table)
@@ -260,6 +325,10 @@ impl InboxCombinedQuery {
       .left_join(person_comment_mention::table)
       .left_join(person_post_mention::table)
       .left_join(private_message::table.on(private_message_join))
+      .left_join(mod_warn_person::table)
+      .left_join(modmail_message::table)
+      .left_join(modmail_thread::table.on(modmail_message::thread_id.eq(modmail_thread::id)))
+      .left_join(thread_reply::table)
       .left_join(comment::table.on(comment_join))
       .left_join(post::table.on(post_join))
       .left_join(community::table.on(community_join))
@@ -321,6 +390,10 @@ impl InboxCombinedQuery {
         image_details::all_columns.nullable(),
         post_tags,
         private_message::all_columns.nullable(),
+        mod_warn_person::all_columns.nullable(),
+        modmail_message::all_columns.nullable(),
+        modmail_thread::all_columns.nullable(),
+        thread_reply::all_columns.nullable(),
         // Shared
         post::all_columns.nullable(),
         community::all_columns.nullable(),
@@ -356,7 +429,10 @@ impl InboxCombinedQuery {
             .or(person_comment_mention::read.eq(false))
             .or(person_post_mention::read.eq(false))
             // If its unread, I only want the messages to me
-            .or(private_message::read.eq(false)),
+            .or(private_message::read.eq(false))
+            .or(mod_warn_person::read.eq(false))
+            .or(modmail_message::read.eq(false))
+            .or(thread_reply::read.eq(false)),
         );
     } else {
       // A special case for private messages: show messages FROM you also.
@@ -381,6 +457,21 @@ impl InboxCombinedQuery {
                 .eq(my_person_id)
                 .or(item_creator.eq(my_person_id)),
             ),
+          )
+          .or(
+            inbox_combined::mod_warn_person_id
+              .is_not_null()
+              .and(recipient_person.eq(my_person_id)),
+          )
+          .or(
+            inbox_combined::modmail_message_id
+              .is_not_null()
+              .and(recipient_person.eq(my_person_id)),
+          )
+          .or(
+            inbox_combined::thread_reply_id
+              .is_not_null()
+              .and(recipient_person.eq(my_person_id)),
           ),
       );
     }
@@ -407,6 +498,9 @@ impl InboxCombinedQuery {
         InboxDataType::PrivateMessage => {
           query.filter(inbox_combined::private_message_id.is_not_null())
         }
+        InboxDataType::Warning => query.filter(inbox_combined::mod_warn_person_id.is_not_null()),
+        InboxDataType::Modmail => query.filter(inbox_combined::modmail_message_id.is_not_null()),
+        InboxDataType::ThreadReply => query.filter(inbox_combined::thread_reply_id.is_not_null()),
       }
     }
 
@@ -469,6 +563,30 @@ impl InternalToCombinedView for InboxCombinedViewInternal {
         my_vote: v.my_comment_vote,
         banned_from_community: v.banned_from_community,
       }))
+    } else if let (Some(thread_reply), Some(comment), Some(counts), Some(post), Some(community)) = (
+      v.thread_reply,
+      v.comment.clone(),
+      v.comment_counts.clone(),
+      v.post.clone(),
+      v.community.clone(),
+    ) {
+      Some(InboxCombinedView::ThreadReply(ThreadReplyView {
+        thread_reply,
+        comment,
+        counts,
+        recipient: v.item_recipient,
+        post,
+        community,
+        creator: v.item_creator,
+        creator_banned_from_community: v.item_creator_banned_from_community,
+        creator_is_moderator: v.item_creator_is_moderator,
+        creator_is_admin: v.item_creator_is_admin,
+        creator_blocked: v.item_creator_blocked,
+        subscribed: v.subscribed,
+        saved: v.comment_saved,
+        my_vote: v.my_comment_vote,
+        banned_from_community: v.banned_from_community,
+      }))
     } else if let (
       Some(person_comment_mention),
       Some(comment),
@@ -512,7 +630,7 @@ impl InternalToCombinedView for InboxCombinedViewInternal {
       v.post,
       v.post_counts,
       v.post_unread_comments,
-      v.community,
+      v.community.clone(),
     ) {
       Some(InboxCombinedView::PostMention(PersonPostMentionView {
         person_post_mention,
@@ -541,6 +659,22 @@ impl InternalToCombinedView for InboxCombinedViewInternal {
         creator: v.item_creator,
         recipient: v.item_recipient,
       }))
+    } else if let Some(mod_warn_person) = v.mod_warn_person {
+      Some(InboxCombinedView::Warning(ModWarnPersonView {
+        mod_warn_person,
+        moderator: Some(v.item_creator),
+        other_person: v.item_recipient,
+        community: v.community,
+      }))
+    } else if let (Some(modmail_message), Some(modmail_thread), Some(community)) =
+      (v.modmail_message, v.modmail_thread, v.community)
+    {
+      Some(InboxCombinedView::Modmail(ModmailMessageView {
+        modmail_message,
+        modmail_thread,
+        community,
+        creator: v.item_creator,
+      }))
     } else {
       None
     }
@@ -552,24 +686,38 @@ impl InternalToCombinedView for InboxCombinedViewInternal {
 mod tests {
   use crate::{
     combined::inbox_combined_view::InboxCombinedQuery,
-    structs::{InboxCombinedView, InboxCombinedViewInternal, PrivateMessageView},
+    structs::{
+      InboxCombinedView,
+      InboxCombinedViewInternal,
+      ModmailThreadView,
+      PrivateMessageView,
+    },
   };
   use lemmy_db_schema::{
     assert_length,
     source::{
       comment::{Comment, CommentInsertForm},
       comment_reply::{CommentReply, CommentReplyInsertForm, CommentReplyUpdateForm},
-      community::{Community, CommunityInsertForm},
+      community::{Community, CommunityInsertForm, CommunityModerator, CommunityModeratorForm},
       instance::Instance,
       instance_block::{InstanceBlock, InstanceBlockForm},
+      mod_log::moderator::{ModWarnPerson, ModWarnPersonForm},
+      modmail::{
+        ModmailMessage,
+        ModmailMessageInsertForm,
+        ModmailThread,
+        ModmailThreadInsertForm,
+        ModmailThreadUpdateForm,
+      },
       person::{Person, PersonInsertForm, PersonUpdateForm},
       person_block::{PersonBlock, PersonBlockForm},
       person_comment_mention::{PersonCommentMention, PersonCommentMentionInsertForm},
       person_post_mention::{PersonPostMention, PersonPostMentionInsertForm},
       post::{Post, PostInsertForm},
       private_message::{PrivateMessage, PrivateMessageInsertForm},
+      thread_subscription::{ThreadReply, ThreadReplyInsertForm},
     },
-    traits::{Blockable, Crud},
+    traits::{Blockable, Crud, Joinable},
     utils::{build_db_pool_for_tests, DbPool},
     InboxDataType,
   };
@@ -716,6 +864,202 @@ mod tests {
     Ok(())
   }
 
+  #[tokio::test]
+  #[serial]
+  async fn warnings() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+    let data = init_data(pool).await?;
+
+    // Timmy warns sara
+    let form = ModWarnPersonForm::new(data.timmy.id, data.sara.id, None, "be nice".into());
+    let warning = ModWarnPerson::create(pool, &form).await?;
+
+    let sara_unread = InboxCombinedViewInternal::get_unread_count(pool, data.sara.id, true).await?;
+    assert_eq!(1, sara_unread);
+
+    let sara_inbox = InboxCombinedQuery {
+      type_: Some(InboxDataType::Warning),
+      ..Default::default()
+    }
+    .list(pool, data.sara.id)
+    .await?;
+    assert_length!(1, sara_inbox);
+
+    if let InboxCombinedView::Warning(v) = &sara_inbox[0] {
+      assert_eq!(warning.id, v.mod_warn_person.id);
+      assert_eq!(Some(data.timmy.id), v.moderator.as_ref().map(|m| m.id));
+      assert_eq!(data.sara.id, v.other_person.id);
+      assert!(v.community.is_none());
+    } else {
+      panic!("wrong type");
+    }
+
+    // The warning doesn't show up for the moderator
+    let timmy_inbox = InboxCombinedQuery::default()
+      .list(pool, data.timmy.id)
+      .await?;
+    assert_length!(0, timmy_inbox);
+
+    // Mark it as read
+    ModWarnPerson::mark_all_as_read(pool, data.sara.id).await?;
+    let sara_unread = InboxCombinedViewInternal::get_unread_count(pool, data.sara.id, true).await?;
+    assert_eq!(0, sara_unread);
+
+    cleanup(data, pool).await?;
+
+    Ok(())
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn modmail() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+    let data = init_data(pool).await?;
+    let community_id = data.timmy_post.community_id;
+
+    // Timmy is the moderator, sara writes to the moderators
+    let form = CommunityModeratorForm {
+      community_id,
+      person_id: data.timmy.id,
+    };
+    CommunityModerator::join(pool, &form).await?;
+
+    let thread_form =
+      ModmailThreadInsertForm::new(community_id, data.sara.id, "sara question".into());
+    let thread = ModmailThread::create(pool, &thread_form).await?;
+    let question_form =
+      ModmailMessageInsertForm::new(thread.id, data.sara.id, "question".into(), false);
+    let question = ModmailMessage::create(pool, &question_form).await?;
+
+    let timmy_unread =
+      InboxCombinedViewInternal::get_unread_count(pool, data.timmy.id, true).await?;
+    assert_eq!(1, timmy_unread);
+
+    let timmy_inbox = InboxCombinedQuery {
+      type_: Some(InboxDataType::Modmail),
+      ..Default::default()
+    }
+    .list(pool, data.timmy.id)
+    .await?;
+    assert_length!(1, timmy_inbox);
+    if let InboxCombinedView::Modmail(v) = &timmy_inbox[0] {
+      assert_eq!(question.id, v.modmail_message.id);
+      assert_eq!(thread.id, v.modmail_thread.id);
+      assert_eq!(community_id, v.community.id);
+      assert_eq!(data.sara.id, v.creator.id);
+    } else {
+      panic!("wrong type");
+    }
+
+    // Jessica isn't a moderator, and sara doesn't see her own message
+    let jessica_inbox = InboxCombinedQuery::default()
+      .list(pool, data.jessica.id)
+      .await?;
+    assert_length!(0, jessica_inbox);
+    let sara_inbox = InboxCombinedQuery::default()
+      .list(pool, data.sara.id)
+      .await?;
+    assert_length!(0, sara_inbox);
+
+    // Timmy answers, which goes to sara only
+    let answer_form =
+      ModmailMessageInsertForm::new(thread.id, data.timmy.id, "answer".into(), true);
+    let answer = ModmailMessage::create(pool, &answer_form).await?;
+    ModmailMessage::mark_thread_as_read(pool, thread.id, true).await?;
+
+    let timmy_unread =
+      InboxCombinedViewInternal::get_unread_count(pool, data.timmy.id, true).await?;
+    assert_eq!(0, timmy_unread);
+    let sara_unread = InboxCombinedViewInternal::get_unread_count(pool, data.sara.id, true).await?;
+    assert_eq!(1, sara_unread);
+
+    // Unread messages in archived threads are not counted for the moderators
+    let question_form =
+      ModmailMessageInsertForm::new(thread.id, data.sara.id, "followup".into(), false);
+    ModmailMessage::create(pool, &question_form).await?;
+    let form = ModmailThreadUpdateForm {
+      archived: Some(true),
+      ..Default::default()
+    };
+    ModmailThread::update(pool, thread.id, &form).await?;
+    let timmy_unread =
+      InboxCombinedViewInternal::get_unread_count(pool, data.timmy.id, true).await?;
+    assert_eq!(0, timmy_unread);
+    assert_eq!(
+      0,
+      ModmailThreadView::get_unread_count_for_moderator(pool, data.timmy.id).await?
+    );
+
+    let sara_inbox = InboxCombinedQuery::default()
+      .list(pool, data.sara.id)
+      .await?;
+    assert_length!(1, sara_inbox);
+    if let InboxCombinedView::Modmail(v) = &sara_inbox[0] {
+      assert_eq!(answer.id, v.modmail_message.id);
+      assert_eq!(data.timmy.id, v.creator.id);
+    } else {
+      panic!("wrong type");
+    }
+
+    cleanup(data, pool).await?;
+
+    Ok(())
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn thread_replies() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+    let data = init_data(pool).await?;
+
+    // Jessica is subscribed to timmy's post, and gets notified about sara's comment
+    let thread_reply_form = ThreadReplyInsertForm {
+      recipient_id: data.jessica.id,
+      comment_id: data.sara_comment.id,
+      read: None,
+    };
+    let thread_reply = ThreadReply::create(pool, &thread_reply_form).await?;
+
+    let jessica_unread =
+      InboxCombinedViewInternal::get_unread_count(pool, data.jessica.id, true).await?;
+    assert_eq!(1, jessica_unread);
+
+    let jessica_inbox = InboxCombinedQuery {
+      type_: Some(InboxDataType::ThreadReply),
+      ..Default::default()
+    }
+    .list(pool, data.jessica.id)
+    .await?;
+    assert_length!(1, jessica_inbox);
+    if let InboxCombinedView::ThreadReply(v) = &jessica_inbox[0] {
+      assert_eq!(thread_reply.id, v.thread_reply.id);
+      assert_eq!(data.sara_comment.id, v.comment.id);
+      assert_eq!(data.timmy_post.id, v.post.id);
+      assert_eq!(data.sara.id, v.creator.id);
+      assert_eq!(data.jessica.id, v.recipient.id);
+    } else {
+      panic!("wrong type");
+    }
+
+    // Nobody else sees it
+    let sara_inbox = InboxCombinedQuery::default()
+      .list(pool, data.sara.id)
+      .await?;
+    assert_length!(0, sara_inbox);
+
+    ThreadReply::mark_all_as_read(pool, data.jessica.id).await?;
+    let jessica_unread =
+      InboxCombinedViewInternal::get_unread_count(pool, data.jessica.id, true).await?;
+    assert_eq!(0, jessica_unread);
+
+    cleanup(data, pool).await?;
+
+    Ok(())
+  }
+
   #[tokio::test]
   #[serial]
   async fn mentions() -> LemmyResult<()> {
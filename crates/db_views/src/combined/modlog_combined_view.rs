@@ -1,12 +1,14 @@
 use crate::structs::{
   AdminAllowInstanceView,
   AdminBlockInstanceView,
+  AdminLimitInstanceView,
   AdminPurgeCommentView,
   AdminPurgeCommunityView,
   AdminPurgePersonView,
   AdminPurgePostView,
   ModAddCommunityView,
   ModAddView,
+  ModAutomodActionView,
   ModBanFromCommunityView,
   ModBanView,
   ModFeaturePostView,
@@ -16,6 +18,7 @@ use crate::structs::{
   ModRemoveCommunityView,
   ModRemovePostView,
   ModTransferCommunityView,
+  ModWarnPersonView,
   ModlogCombinedPaginationCursor,
   ModlogCombinedView,
   ModlogCombinedViewInternal,
@@ -38,6 +41,7 @@ use lemmy_db_schema::{
   schema::{
     admin_allow_instance,
     admin_block_instance,
+    admin_limit_instance,
     admin_purge_comment,
     admin_purge_community,
     admin_purge_person,
@@ -47,6 +51,7 @@ use lemmy_db_schema::{
     instance,
     mod_add,
     mod_add_community,
+    mod_automod_action,
     mod_ban,
     mod_ban_from_community,
     mod_feature_post,
@@ -56,6 +61,7 @@ use lemmy_db_schema::{
     mod_remove_community,
     mod_remove_post,
     mod_transfer_community,
+    mod_warn_person,
     modlog_combined,
     person,
     post,
@@ -100,6 +106,11 @@ impl ModlogCombinedPaginationCursor {
       ModlogCombinedView::ModTransferCommunity(v) => {
         ("ModTransferCommunity", v.mod_transfer_community.id.0)
       }
+      ModlogCombinedView::ModAutomodAction(v) => ("ModAutomodAction", v.mod_automod_action.id.0),
+      ModlogCombinedView::ModWarnPerson(v) => ("ModWarnPerson", v.mod_warn_person.id.0),
+      ModlogCombinedView::AdminLimitInstance(v) => {
+        ("AdminLimitInstance", v.admin_limit_instance.id.0)
+      }
     };
     // hex encoding to prevent ossification
     ModlogCombinedPaginationCursor(format!("{prefix}-{id:x}"))
@@ -130,6 +141,9 @@ impl ModlogCombinedPaginationCursor {
       "ModRemoveCommunity" => query.filter(modlog_combined::mod_remove_community_id.eq(id)),
       "ModRemovePost" => query.filter(modlog_combined::mod_remove_post_id.eq(id)),
       "ModTransferCommunity" => query.filter(modlog_combined::mod_transfer_community_id.eq(id)),
+      "ModAutomodAction" => query.filter(modlog_combined::mod_automod_action_id.eq(id)),
+      "ModWarnPerson" => query.filter(modlog_combined::mod_warn_person_id.eq(id)),
+      "AdminLimitInstance" => query.filter(modlog_combined::admin_limit_instance_id.eq(id)),
 
       _ => return Err(err_msg()),
     };
@@ -188,7 +202,10 @@ impl ModlogCombinedQuery {
         .or(mod_remove_comment::mod_person_id.eq(person::id))
         .or(mod_remove_community::mod_person_id.eq(person::id))
         .or(mod_remove_post::mod_person_id.eq(person::id))
-        .or(mod_transfer_community::mod_person_id.eq(person::id)),
+        .or(mod_transfer_community::mod_person_id.eq(person::id))
+        .or(mod_automod_action::mod_person_id.eq(person::id))
+        .or(mod_warn_person::mod_person_id.eq(person::id))
+        .or(admin_limit_instance::admin_person_id.eq(person::id)),
     );
 
     let other_person_join = mod_add::other_person_id
@@ -217,9 +234,24 @@ impl ModlogCombinedQuery {
           .is_not_null()
           .and(post::creator_id.eq(other_person)),
       )
-      .or(mod_transfer_community::other_person_id.eq(other_person));
+      .or(mod_transfer_community::other_person_id.eq(other_person))
+      // The automod acts on a comment if it is set, otherwise on the post
+      .or(
+        mod_automod_action::comment_id
+          .is_not_null()
+          .and(comment::creator_id.eq(other_person)),
+      )
+      .or(
+        mod_automod_action::id
+          .is_not_null()
+          .and(mod_automod_action::comment_id.is_null())
+          .and(post::creator_id.eq(other_person)),
+      )
+      .or(mod_warn_person::other_person_id.eq(other_person));
 
-    let comment_join = mod_remove_comment::comment_id.eq(comment::id);
+    let comment_join = mod_remove_comment::comment_id
+      .eq(comment::id)
+      .or(mod_automod_action::comment_id.eq(comment::id.nullable()));
 
     let post_join = admin_purge_comment::post_id
       .eq(post::id)
@@ -230,7 +262,8 @@ impl ModlogCombinedQuery {
           .is_not_null()
           .and(comment::post_id.eq(post::id)),
       )
-      .or(mod_remove_post::post_id.eq(post::id));
+      .or(mod_remove_post::post_id.eq(post::id))
+      .or(mod_automod_action::post_id.eq(post::id));
 
     let community_join = admin_purge_post::community_id
       .eq(community::id)
@@ -258,11 +291,14 @@ impl ModlogCombinedQuery {
           .is_not_null()
           .and(post::community_id.eq(community::id)),
       )
-      .or(mod_transfer_community::community_id.eq(community::id));
+      .or(mod_transfer_community::community_id.eq(community::id))
+      .or(mod_automod_action::community_id.eq(community::id))
+      .or(mod_warn_person::community_id.eq(community::id.nullable()));
 
     let instance_join = admin_allow_instance::instance_id
       .eq(instance::id)
-      .or(admin_block_instance::instance_id.eq(instance::id));
+      .or(admin_block_instance::instance_id.eq(instance::id))
+      .or(admin_limit_instance::instance_id.eq(instance::id));
 
     let mut query = modlog_combined::table
       .left_join(admin_allow_instance::table)
@@ -282,6 +318,9 @@ impl ModlogCombinedQuery {
       .left_join(mod_remove_community::table)
       .left_join(mod_remove_post::table)
       .left_join(mod_transfer_community::table)
+      .left_join(mod_automod_action::table)
+      .left_join(mod_warn_person::table)
+      .left_join(admin_limit_instance::table)
       // The moderator
       .left_join(person::table.on(moderator_names_join))
       // The comment
@@ -312,6 +351,9 @@ impl ModlogCombinedQuery {
         mod_remove_community::all_columns.nullable(),
         mod_remove_post::all_columns.nullable(),
         mod_transfer_community::all_columns.nullable(),
+        mod_automod_action::all_columns.nullable(),
+        mod_warn_person::all_columns.nullable(),
+        admin_limit_instance::all_columns.nullable(),
         // Shared
         person::all_columns.nullable(),
         aliases::person1.fields(person::all_columns).nullable(),
@@ -369,6 +411,9 @@ impl ModlogCombinedQuery {
         AdminPurgeComment => query.filter(modlog_combined::admin_purge_comment_id.is_not_null()),
         AdminBlockInstance => query.filter(modlog_combined::admin_block_instance_id.is_not_null()),
         AdminAllowInstance => query.filter(modlog_combined::admin_allow_instance_id.is_not_null()),
+        ModAutomodAction => query.filter(modlog_combined::mod_automod_action_id.is_not_null()),
+        ModWarnPerson => query.filter(modlog_combined::mod_warn_person_id.is_not_null()),
+        AdminLimitInstance => query.filter(modlog_combined::admin_limit_instance_id.is_not_null()),
       }
     }
 
@@ -417,7 +462,7 @@ impl InternalToCombinedView for ModlogCombinedViewInternal {
         },
       ))
     } else if let (Some(admin_block_instance), Some(instance)) =
-      (v.admin_block_instance, v.instance)
+      (v.admin_block_instance, v.instance.clone())
     {
       Some(ModlogCombinedView::AdminBlockInstance(
         AdminBlockInstanceView {
@@ -426,6 +471,16 @@ impl InternalToCombinedView for ModlogCombinedViewInternal {
           admin: v.moderator,
         },
       ))
+    } else if let (Some(admin_limit_instance), Some(instance)) =
+      (v.admin_limit_instance, v.instance)
+    {
+      Some(ModlogCombinedView::AdminLimitInstance(
+        AdminLimitInstanceView {
+          admin_limit_instance,
+          instance,
+          admin: v.moderator,
+        },
+      ))
     } else if let (Some(admin_purge_comment), Some(post)) = (v.admin_purge_comment, v.post.clone())
     {
       Some(ModlogCombinedView::AdminPurgeComment(
@@ -536,7 +591,7 @@ impl InternalToCombinedView for ModlogCombinedViewInternal {
       v.other_person.clone(),
       v.community.clone(),
       v.post.clone(),
-      v.comment,
+      v.comment.clone(),
     ) {
       Some(ModlogCombinedView::ModRemoveComment(ModRemoveCommentView {
         mod_remove_comment,
@@ -582,6 +637,28 @@ impl InternalToCombinedView for ModlogCombinedViewInternal {
           community,
         },
       ))
+    } else if let (Some(mod_automod_action), Some(other_person), Some(community), Some(post)) = (
+      v.mod_automod_action,
+      v.other_person.clone(),
+      v.community.clone(),
+      v.post,
+    ) {
+      Some(ModlogCombinedView::ModAutomodAction(ModAutomodActionView {
+        mod_automod_action,
+        moderator: v.moderator,
+        other_person,
+        post,
+        comment: v.comment,
+        community,
+      }))
+    } else if let (Some(mod_warn_person), Some(other_person)) = (v.mod_warn_person, v.other_person)
+    {
+      Some(ModlogCombinedView::ModWarnPerson(ModWarnPersonView {
+        mod_warn_person,
+        moderator: v.moderator,
+        other_person,
+        community: v.community,
+      }))
     } else {
       None
     }
@@ -42,7 +42,13 @@ use lemmy_db_schema::{
     community::CommunityFollower,
   },
   traits::InternalToCombinedView,
-  utils::{actions, actions_alias, functions::coalesce, get_conn, DbPool},
+  utils::{
+    actions,
+    actions_alias,
+    functions::{coalesce, post_poll},
+    get_conn,
+    DbPool,
+  },
   PersonContentType,
 };
 use lemmy_utils::error::LemmyResult;
@@ -185,6 +191,7 @@ impl PersonContentCombinedQuery {
         post_actions::like_score.nullable(),
         image_details::all_columns.nullable(),
         post_tags,
+        post_poll(post::id.nullable(), my_person_id),
         // Comment-specific
         comment::all_columns.nullable(),
         comment_aggregates::all_columns.nullable(),
@@ -291,6 +298,7 @@ impl InternalToCombinedView for PersonContentCombinedViewInternal {
         image_details: v.image_details,
         banned_from_community: v.banned_from_community,
         tags: v.post_tags,
+        poll: v.post_poll,
       }))
     }
   }
@@ -39,7 +39,13 @@ use lemmy_db_schema::{
     community::CommunityFollower,
   },
   traits::InternalToCombinedView,
-  utils::{actions, actions_alias, functions::coalesce, get_conn, DbPool},
+  utils::{
+    actions,
+    actions_alias,
+    functions::{coalesce, post_poll},
+    get_conn,
+    DbPool,
+  },
   PersonContentType,
 };
 use lemmy_utils::error::LemmyResult;
@@ -186,6 +192,7 @@ impl PersonSavedCombinedQuery {
         post_actions::like_score.nullable(),
         image_details::all_columns.nullable(),
         post_tags,
+        post_poll(post::id.nullable(), my_person_id),
         // Comment-specific
         comment::all_columns.nullable(),
         comment_aggregates::all_columns.nullable(),
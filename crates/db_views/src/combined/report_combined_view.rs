@@ -48,7 +48,14 @@ use lemmy_db_schema::{
     community::CommunityFollower,
   },
   traits::InternalToCombinedView,
-  utils::{actions, actions_alias, functions::coalesce, get_conn, DbPool, ReverseTimestampKey},
+  utils::{
+    actions,
+    actions_alias,
+    functions::{coalesce, person_ban_count, person_removal_count},
+    get_conn,
+    DbPool,
+    ReverseTimestampKey,
+  },
   ReportType,
 };
 use lemmy_utils::error::LemmyResult;
@@ -302,6 +309,8 @@ impl ReportCombinedQuery {
           .nullable()
           .is_not_null(),
         person_actions::blocked.nullable().is_not_null(),
+        person_removal_count(item_creator.nullable()),
+        person_ban_count(item_creator.nullable()),
       ))
       .into_boxed();
 
@@ -421,6 +430,8 @@ impl InternalToCombinedView for ReportCombinedViewInternal {
         hidden: v.post_hidden,
         my_vote: v.my_post_vote,
         resolver: v.resolver,
+        post_creator_removal_count: v.item_creator_removal_count,
+        post_creator_ban_count: v.item_creator_ban_count,
       }))
     } else if let (
       Some(comment_report),
@@ -453,6 +464,8 @@ impl InternalToCombinedView for ReportCombinedViewInternal {
         saved: v.comment_saved,
         my_vote: v.my_comment_vote,
         resolver: v.resolver,
+        comment_creator_removal_count: v.item_creator_removal_count,
+        comment_creator_ban_count: v.item_creator_ban_count,
       }))
     } else if let (
       Some(private_message_report),
@@ -467,6 +480,8 @@ impl InternalToCombinedView for ReportCombinedViewInternal {
           creator: v.report_creator,
           private_message_creator,
           resolver: v.resolver,
+          private_message_creator_removal_count: v.item_creator_removal_count,
+          private_message_creator_ban_count: v.item_creator_ban_count,
         },
       ))
     } else if let (Some(community), Some(community_report), Some(counts)) =
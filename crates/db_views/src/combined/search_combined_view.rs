@@ -9,13 +9,12 @@ use crate::structs::{
   SearchCombinedViewInternal,
 };
 use diesel::{
-  dsl::not,
+  dsl::{exists, not},
   result::Error,
   BoolExpressionMethods,
   ExpressionMethods,
   JoinOnDsl,
   NullableExpressionMethods,
-  PgTextExpressionMethods,
   QueryDsl,
   SelectableHelper,
 };
@@ -31,6 +30,7 @@ use lemmy_db_schema::{
     community,
     community_actions,
     community_aggregates,
+    federation_limit,
     image_details,
     local_user,
     person,
@@ -51,9 +51,9 @@ use lemmy_db_schema::{
   utils::{
     actions,
     actions_alias,
-    functions::coalesce,
-    fuzzy_search,
+    functions::{coalesce, post_poll, search_query, title_search_vector, ts_rank},
     get_conn,
+    ts_match,
     DbPool,
     ReverseTimestampKey,
   },
@@ -230,6 +230,7 @@ impl SearchCombinedQuery {
         post_actions::like_score.nullable(),
         image_details::all_columns.nullable(),
         post_tags,
+        post_poll(post::id.nullable(), my_person_id),
         // Comment-specific
         comment::all_columns.nullable(),
         comment_aggregates::all_columns.nullable(),
@@ -261,29 +262,20 @@ impl SearchCombinedQuery {
     // The filters
 
     // The search term
+    let mut ts_query = None;
     if let Some(search_term) = &self.search_term {
       if self.post_url_only.unwrap_or_default() {
         query = query.filter(post::url.eq(search_term));
       } else {
-        let searcher = fuzzy_search(search_term);
-
-        let name_or_title_filter = post::name
-          .ilike(searcher.clone())
-          .or(comment::content.ilike(searcher.clone()))
-          .or(community::name.ilike(searcher.clone()))
-          .or(community::title.ilike(searcher.clone()))
-          .or(person::name.ilike(searcher.clone()))
-          .or(person::display_name.ilike(searcher.clone()));
-
-        let body_or_description_filter = post::body
-          .ilike(searcher.clone())
-          .or(community::description.ilike(searcher.clone()));
-
-        query = if self.title_only.unwrap_or_default() {
-          query.filter(name_or_title_filter)
-        } else {
-          query.filter(name_or_title_filter.or(body_or_description_filter))
+        let q = search_query(search_term.clone());
+        query = query.filter(ts_match(search_combined::search_vector, q.clone()));
+        if self.title_only.unwrap_or_default() {
+          query = query.filter(ts_match(
+            title_search_vector(search_combined::search_vector),
+            q.clone(),
+          ));
         }
+        ts_query = Some(q);
       }
     }
 
@@ -344,32 +336,99 @@ impl SearchCombinedQuery {
         );
       }
       ListingType::All => {
-        query = query.filter(
-          community::hidden
-            .eq(false)
-            .or(is_subscribed)
-            .or(search_combined::person_id.is_not_null()),
-        )
+        // Content of limited instances is only shown to followers of the community or creator
+        let is_limited = exists(
+          federation_limit::table
+            .filter(federation_limit::limited)
+            .filter(
+              federation_limit::instance_id
+                .eq(community::instance_id)
+                .or(federation_limit::instance_id.eq(person::instance_id)),
+            ),
+        );
+        query = query
+          .filter(
+            community::hidden
+              .eq(false)
+              .or(is_subscribed)
+              .or(search_combined::person_id.is_not_null()),
+          )
+          .filter(
+            not(is_limited)
+              .or(is_subscribed)
+              .or(person_actions::followed.is_not_null()),
+          )
       }
       ListingType::ModeratorView => {
         query = query.filter(community_actions::became_moderator.is_not_null());
       }
     }
 
-    let mut query = PaginatedQueryBuilder::new(query);
-
     let page_after = self.page_after.map(|c| c.0);
+    let page_back = self.page_back.unwrap_or_default();
+
+    // Relevance depends on the search term, so it can't be a cursor key. Instead the rank of the
+    // cursor item is read and compared manually.
+    let relevance = match (self.sort.unwrap_or_default(), ts_query) {
+      (Relevance, Some(ts_query)) => {
+        let rank = ts_rank(search_combined::search_vector, ts_query.clone());
+        if let Some(page_after) = &page_after {
+          let cursor_rank = search_combined::table
+            .find(page_after.id)
+            .select(ts_rank(search_combined::search_vector, ts_query))
+            .first::<f32>(conn)
+            .await?;
+          let cursor_id = page_after.id;
+          query = if page_back {
+            query.filter(
+              rank.clone().gt(cursor_rank).or(
+                rank
+                  .clone()
+                  .eq(cursor_rank)
+                  .and(search_combined::id.gt(cursor_id)),
+              ),
+            )
+          } else {
+            query.filter(
+              rank.clone().lt(cursor_rank).or(
+                rank
+                  .clone()
+                  .eq(cursor_rank)
+                  .and(search_combined::id.lt(cursor_id)),
+              ),
+            )
+          };
+        }
+        query = if page_back {
+          query.order_by(rank.asc())
+        } else {
+          query.order_by(rank.desc())
+        };
+        true
+      }
+      _ => false,
+    };
 
-    if self.page_back.unwrap_or_default() {
-      query = query.before(page_after).limit_and_offset_from_end();
-    } else {
-      query = query.after(page_after);
+    let mut query = PaginatedQueryBuilder::new(query);
+
+    if page_back {
+      query = query.limit_and_offset_from_end();
+    }
+    if !relevance {
+      query = if page_back {
+        query.before(page_after)
+      } else {
+        query.after(page_after)
+      };
     }
 
     query = match self.sort.unwrap_or_default() {
       New => query.then_desc(key::published),
       Old => query.then_desc(ReverseTimestampKey(key::published)),
       Top => query.then_desc(key::score),
+      // Without a search term there is nothing to rank by, so use the newest first
+      Relevance if !relevance => query.then_desc(key::published),
+      Relevance => query,
     };
     // finally use unique id as tie breaker
     query = query.then_desc(key::id);
@@ -446,6 +505,7 @@ impl InternalToCombinedView for SearchCombinedViewInternal {
         image_details: v.image_details,
         banned_from_community: v.banned_from_community,
         tags: v.post_tags,
+        poll: v.post_poll,
       }))
     } else if let (Some(community), Some(counts)) = (v.community, v.community_counts) {
       Some(SearchCombinedView::Community(CommunityView {
@@ -473,7 +533,7 @@ mod tests {
 
   use crate::{
     combined::search_combined_view::SearchCombinedQuery,
-    structs::{LocalUserView, SearchCombinedView},
+    structs::{LocalUserView, SearchCombinedPaginationCursor, SearchCombinedView},
   };
   use lemmy_db_schema::{
     assert_length,
@@ -481,6 +541,7 @@ mod tests {
       comment::{Comment, CommentInsertForm, CommentLike, CommentLikeForm, CommentUpdateForm},
       community::{Community, CommunityInsertForm},
       instance::Instance,
+      language::Language,
       local_user::{LocalUser, LocalUserInsertForm},
       local_user_vote_display_mode::LocalUserVoteDisplayMode,
       person::{Person, PersonInsertForm},
@@ -1191,4 +1252,126 @@ mod tests {
 
     Ok(())
   }
+
+  #[tokio::test]
+  #[serial]
+  async fn relevance() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+    let data = init_data(pool).await?;
+
+    let english = Language::read_id_from_code(pool, "en").await?;
+    let title_post_form = PostInsertForm {
+      language_id: Some(english),
+      ..PostInsertForm::new(
+        "Running with scissors".into(),
+        data.sara.id,
+        data.community.id,
+      )
+    };
+    let title_post = Post::create(pool, &title_post_form).await?;
+
+    let body_post_form = PostInsertForm {
+      body: Some("My dogs love running outside".into()),
+      language_id: Some(english),
+      ..PostInsertForm::new("Pets".into(), data.sara.id, data.community.id)
+    };
+    let body_post = Post::create(pool, &body_post_form).await?;
+
+    // Newest first, even though the search term is only in the body
+    let search_new = SearchCombinedQuery {
+      search_term: Some("run".into()),
+      type_: Some(SearchType::Posts),
+      ..Default::default()
+    }
+    .list(pool, &None)
+    .await?;
+    assert_length!(2, search_new);
+    if let SearchCombinedView::Post(v) = &search_new[0] {
+      assert_eq!(body_post.id, v.post.id);
+    } else {
+      panic!("wrong type");
+    }
+
+    // Matches in the title are ranked higher than matches in the body
+    let search_relevance = SearchCombinedQuery {
+      search_term: Some("run".into()),
+      type_: Some(SearchType::Posts),
+      sort: Some(SearchSortType::Relevance),
+      ..Default::default()
+    }
+    .list(pool, &None)
+    .await?;
+    assert_length!(2, search_relevance);
+    if let SearchCombinedView::Post(v) = &search_relevance[0] {
+      assert_eq!(title_post.id, v.post.id);
+    } else {
+      panic!("wrong type");
+    }
+
+    // The next page continues after the first result
+    let page_after = SearchCombinedPaginationCursor::after_post(&search_relevance[0])
+      .read(pool)
+      .await?;
+    let search_relevance_page_2 = SearchCombinedQuery {
+      search_term: Some("run".into()),
+      type_: Some(SearchType::Posts),
+      sort: Some(SearchSortType::Relevance),
+      page_after: Some(page_after),
+      ..Default::default()
+    }
+    .list(pool, &None)
+    .await?;
+    assert_length!(1, search_relevance_page_2);
+    if let SearchCombinedView::Post(v) = &search_relevance_page_2[0] {
+      assert_eq!(body_post.id, v.post.id);
+    } else {
+      panic!("wrong type");
+    }
+
+    // The search term is stemmed like the content, so "runs" matches "running"
+    let search_stemmed = SearchCombinedQuery {
+      search_term: Some("runs".into()),
+      type_: Some(SearchType::Posts),
+      ..Default::default()
+    }
+    .list(pool, &None)
+    .await?;
+    assert_length!(2, search_stemmed);
+
+    // Title only search ignores the body
+    let search_stemmed = SearchCombinedQuery {
+      search_term: Some("dog".into()),
+      type_: Some(SearchType::Posts),
+      title_only: Some(true),
+      ..Default::default()
+    }
+    .list(pool, &None)
+    .await?;
+    assert!(search_stemmed.is_empty());
+
+    // Editing the post updates the search index
+    Post::update(
+      pool,
+      body_post.id,
+      &PostUpdateForm {
+        name: Some("Dogs".into()),
+        ..Default::default()
+      },
+    )
+    .await?;
+    let search_stemmed = SearchCombinedQuery {
+      search_term: Some("dog".into()),
+      type_: Some(SearchType::Posts),
+      title_only: Some(true),
+      ..Default::default()
+    }
+    .list(pool, &None)
+    .await?;
+    assert_length!(1, search_stemmed);
+
+    cleanup(data, pool).await?;
+
+    Ok(())
+  }
 }
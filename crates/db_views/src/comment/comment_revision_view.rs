@@ -0,0 +1,93 @@
+use crate::structs::CommentRevisionView;
+use diesel::{result::Error, ExpressionMethods, QueryDsl};
+use diesel_async::RunQueryDsl;
+use lemmy_db_schema::{
+  newtypes::CommentId,
+  schema::{comment_revision, person},
+  utils::{get_conn, DbPool},
+};
+
+impl CommentRevisionView {
+  /// Lists the previous versions of a comment, newest first.
+  pub async fn list(pool: &mut DbPool<'_>, comment_id: CommentId) -> Result<Vec<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    comment_revision::table
+      .inner_join(person::table)
+      .filter(comment_revision::comment_id.eq(comment_id))
+      .select((comment_revision::all_columns, person::all_columns))
+      .order_by(comment_revision::published.desc())
+      .then_order_by(comment_revision::id.desc())
+      .load::<Self>(conn)
+      .await
+  }
+}
+
+#[cfg(test)]
+#[expect(clippy::indexing_slicing)]
+mod tests {
+
+  use crate::structs::CommentRevisionView;
+  use lemmy_db_schema::{
+    source::{
+      comment::{Comment, CommentInsertForm, CommentUpdateForm},
+      comment_revision::CommentRevision,
+      community::{Community, CommunityInsertForm},
+      instance::Instance,
+      person::{Person, PersonInsertForm},
+      post::{Post, PostInsertForm},
+    },
+    traits::Crud,
+    utils::build_db_pool_for_tests,
+  };
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_list_comment_revisions() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let instance = Instance::read_or_create(pool, "my_domain.tld".to_string()).await?;
+    let person =
+      Person::create(pool, &PersonInsertForm::test_form(instance.id, "crv_user")).await?;
+    let community_form = CommunityInsertForm::new(
+      instance.id,
+      "comment_revision_community".to_string(),
+      "nada".to_owned(),
+      "pubkey".to_string(),
+    );
+    let community = Community::create(pool, &community_form).await?;
+    let post_form = PostInsertForm::new("revisions".into(), person.id, community.id);
+    let post = Post::create(pool, &post_form).await?;
+    let comment_form = CommentInsertForm::new(person.id, post.id, "first".into());
+    let mut comment = Comment::create(pool, &comment_form, None).await?;
+
+    for content in ["second", "third"] {
+      let form = CommentUpdateForm {
+        content: Some(content.into()),
+        ..Default::default()
+      };
+      let updated = Comment::update(pool, comment.id, &form).await?;
+      CommentRevision::record(pool, &comment, &updated, person.id).await?;
+      comment = updated;
+    }
+    // Unchanged content doesn't create a revision
+    CommentRevision::record(pool, &comment, &comment, person.id).await?;
+
+    let revisions = CommentRevisionView::list(pool, comment.id).await?;
+    assert_eq!(
+      vec!["second", "first"],
+      revisions
+        .iter()
+        .map(|r| r.comment_revision.content.as_str())
+        .collect::<Vec<_>>()
+    );
+    assert_eq!(person.id, revisions[0].editor.id);
+
+    Instance::delete(pool, instance.id).await?;
+
+    Ok(())
+  }
+}
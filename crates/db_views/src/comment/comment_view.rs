@@ -3,8 +3,10 @@ use diesel::{
   dsl::{exists, not},
   pg::Pg,
   result::Error,
+  sql_types::Text,
   BoolExpressionMethods,
   ExpressionMethods,
+  IntoSql,
   JoinOnDsl,
   NullableExpressionMethods,
   PgTextExpressionMethods,
@@ -22,6 +24,7 @@ use lemmy_db_schema::{
     comment_aggregates,
     community,
     community_actions,
+    federation_limit,
     instance_actions,
     local_user,
     local_user_language,
@@ -32,6 +35,7 @@ use lemmy_db_schema::{
   source::{
     community::{CommunityFollower, CommunityFollowerState},
     local_user::LocalUser,
+    local_user_keyword_filter::LocalUserKeywordFilter,
     site::Site,
   },
   utils::{
@@ -39,6 +43,7 @@ use lemmy_db_schema::{
     actions_alias,
     fuzzy_search,
     limit_and_offset,
+    regex_match_insensitive,
     DbConn,
     DbPool,
     ListFn,
@@ -180,7 +185,25 @@ fn queries<'a>() -> Queries<
           .filter(community::local.eq(true))
           .filter(community::hidden.eq(false).or(is_subscribed))
       }
-      ListingType::All => query = query.filter(community::hidden.eq(false).or(is_subscribed)),
+      ListingType::All => {
+        // Content of limited instances is only shown to followers of the community or creator
+        let is_limited = exists(
+          federation_limit::table
+            .filter(federation_limit::limited)
+            .filter(
+              federation_limit::instance_id
+                .eq(community::instance_id)
+                .or(federation_limit::instance_id.eq(person::instance_id)),
+            ),
+        );
+        query = query
+          .filter(community::hidden.eq(false).or(is_subscribed))
+          .filter(
+            not(is_limited)
+              .or(is_subscribed)
+              .or(person_actions::followed.is_not_null()),
+          )
+      }
       ListingType::ModeratorView => {
         query = query.filter(community_actions::became_moderator.is_not_null());
       }
@@ -203,6 +226,23 @@ fn queries<'a>() -> Queries<
       query = query.filter(person::bot_account.eq(false));
     };
 
+    // Hide comments which match any of the keyword filters of the user. Only the body option
+    // applies to comments.
+    if let Some(local_user_id) = o.local_user.local_user_id() {
+      let filters = LocalUserKeywordFilter::list_for_listing(
+        &mut DbPool::Conn(&mut conn),
+        local_user_id,
+        Some(o.listing_type.unwrap_or_default()),
+      )
+      .await?;
+      for filter in filters.into_iter().filter(|f| f.match_body) {
+        query = query.filter(not(regex_match_insensitive(
+          comment::content,
+          filter.pattern().into_sql::<Text>(),
+        )));
+      }
+    }
+
     if o.local_user.is_some() && o.listing_type.unwrap_or_default() != ListingType::ModeratorView {
       // Filter out the rows with missing languages
       query = query.filter(exists(
@@ -409,6 +449,7 @@ mod tests {
     traits::{Bannable, Blockable, Crud, Followable, Joinable, Likeable},
     utils::{build_db_pool_for_tests, RANK_DEFAULT},
     CommunityVisibility,
+    DuplicateLinkPolicy,
     SubscribedType,
   };
   use lemmy_utils::error::LemmyResult;
@@ -956,6 +997,7 @@ mod tests {
         matrix_user_id: None,
         ban_expires: None,
         instance_id: data.inserted_instance.id,
+        moved_to: None,
         private_key: data.timmy_local_user_view.person.private_key.clone(),
         public_key: data.timmy_local_user_view.person.public_key.clone(),
         last_refreshed_at: data.timmy_local_user_view.person.last_refreshed_at,
@@ -985,6 +1027,7 @@ mod tests {
         featured_local: false,
         url_content_type: None,
         scheduled_publish_time: None,
+        crosspost_of_id: None,
       },
       community: Community {
         id: data.inserted_community.id,
@@ -1013,6 +1056,8 @@ mod tests {
         featured_url: data.inserted_community.featured_url.clone(),
         visibility: CommunityVisibility::Public,
         random_number: data.inserted_community.random_number,
+        duplicate_link_policy: DuplicateLinkPolicy::Allow,
+        duplicate_link_window_days: 30,
       },
       counts: CommentAggregates {
         comment_id: data.inserted_comment_0.id,
@@ -1,2 +1,4 @@
 #[cfg(feature = "full")]
+pub mod comment_revision_view;
+#[cfg(feature = "full")]
 pub mod comment_view;
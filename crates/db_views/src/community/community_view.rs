@@ -367,6 +367,7 @@ mod tests {
       public_key: String::new(),
       instance_id: Default::default(),
       content_warning: None,
+      edit_history_public: false,
     };
 
     Ok(Data {
@@ -0,0 +1,75 @@
+use crate::structs::FeedView;
+use diesel::{result::Error, ExpressionMethods, QueryDsl, SelectableHelper};
+use diesel_async::RunQueryDsl;
+use lemmy_db_schema::{
+  newtypes::{FeedId, PersonId},
+  schema::{community, feed, feed_community, person},
+  source::{community::Community, feed::Feed, person::Person},
+  utils::{get_conn, DbPool},
+};
+
+impl FeedView {
+  pub async fn read(pool: &mut DbPool<'_>, feed_id: FeedId) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    let (feed, creator) = feed::table
+      .find(feed_id)
+      .inner_join(person::table)
+      .select((Feed::as_select(), Person::as_select()))
+      .first::<(Feed, Person)>(conn)
+      .await?;
+    let communities = feed_community::table
+      .inner_join(community::table)
+      .filter(feed_community::feed_id.eq(feed_id))
+      .order_by(community::name)
+      .select(Community::as_select())
+      .load::<Community>(conn)
+      .await?;
+    Ok(FeedView {
+      feed,
+      creator,
+      communities,
+    })
+  }
+
+  /// Lists the feeds of a person. Private feeds are only included if `include_private` is true.
+  pub async fn list(
+    pool: &mut DbPool<'_>,
+    creator_id: PersonId,
+    include_private: bool,
+  ) -> Result<Vec<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    let mut query = feed::table
+      .inner_join(person::table)
+      .filter(feed::creator_id.eq(creator_id))
+      .order_by(feed::name)
+      .select((Feed::as_select(), Person::as_select()))
+      .into_boxed();
+    if !include_private {
+      query = query.filter(feed::public);
+    }
+    let feeds = query.load::<(Feed, Person)>(conn).await?;
+
+    let feed_ids = feeds.iter().map(|(f, _)| f.id).collect::<Vec<_>>();
+    let communities = feed_community::table
+      .inner_join(community::table)
+      .filter(feed_community::feed_id.eq_any(feed_ids))
+      .order_by(community::name)
+      .select((feed_community::feed_id, Community::as_select()))
+      .load::<(FeedId, Community)>(conn)
+      .await?;
+    Ok(
+      feeds
+        .into_iter()
+        .map(|(feed, creator)| FeedView {
+          communities: communities
+            .iter()
+            .filter(|(feed_id, _)| *feed_id == feed.id)
+            .map(|(_, c)| c.clone())
+            .collect(),
+          feed,
+          creator,
+        })
+        .collect(),
+    )
+  }
+}
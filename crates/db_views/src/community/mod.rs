@@ -6,3 +6,5 @@ pub mod community_moderator_view;
 pub mod community_person_ban_view;
 #[cfg(feature = "full")]
 pub mod community_view;
+#[cfg(feature = "full")]
+pub mod feed_view;
@@ -10,6 +10,8 @@ pub mod community;
 #[cfg(feature = "full")]
 pub mod local_user;
 #[cfg(feature = "full")]
+pub mod modmail;
+#[cfg(feature = "full")]
 pub mod person;
 #[cfg(feature = "full")]
 pub mod post;
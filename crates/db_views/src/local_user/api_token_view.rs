@@ -0,0 +1,34 @@
+use crate::structs::ApiTokenView;
+use diesel::result::Error;
+use lemmy_db_schema::{newtypes::LocalUserId, source::api_token::ApiToken, utils::DbPool};
+
+impl ApiTokenView {
+  pub async fn from_token(pool: &mut DbPool<'_>, api_token: ApiToken) -> Result<Self, Error> {
+    let scopes = ApiToken::read_scopes(pool, &[api_token.id])
+      .await?
+      .into_iter()
+      .map(|(_, scope)| scope)
+      .collect();
+    Ok(ApiTokenView { api_token, scopes })
+  }
+
+  /// Lists all api tokens of the user, including expired ones.
+  pub async fn list(pool: &mut DbPool<'_>, local_user_id: LocalUserId) -> Result<Vec<Self>, Error> {
+    let tokens = ApiToken::list(pool, local_user_id).await?;
+    let ids = tokens.iter().map(|t| t.id).collect::<Vec<_>>();
+    let scopes = ApiToken::read_scopes(pool, &ids).await?;
+    Ok(
+      tokens
+        .into_iter()
+        .map(|api_token| ApiTokenView {
+          scopes: scopes
+            .iter()
+            .filter(|(id, _)| *id == api_token.id)
+            .map(|(_, scope)| *scope)
+            .collect(),
+          api_token,
+        })
+        .collect(),
+    )
+  }
+}
@@ -0,0 +1,44 @@
+use crate::structs::KeywordFilterView;
+use diesel::result::Error;
+use lemmy_db_schema::{
+  newtypes::LocalUserId,
+  source::local_user_keyword_filter::LocalUserKeywordFilter,
+  utils::DbPool,
+};
+
+impl KeywordFilterView {
+  pub async fn from_filter(
+    pool: &mut DbPool<'_>,
+    keyword_filter: LocalUserKeywordFilter,
+  ) -> Result<Self, Error> {
+    let listing_types = LocalUserKeywordFilter::read_listing_types(pool, &[keyword_filter.id])
+      .await?
+      .into_iter()
+      .map(|(_, listing_type)| listing_type)
+      .collect();
+    Ok(KeywordFilterView {
+      keyword_filter,
+      listing_types,
+    })
+  }
+
+  /// Lists all keyword filters of the user.
+  pub async fn list(pool: &mut DbPool<'_>, local_user_id: LocalUserId) -> Result<Vec<Self>, Error> {
+    let filters = LocalUserKeywordFilter::list(pool, local_user_id).await?;
+    let ids = filters.iter().map(|f| f.id).collect::<Vec<_>>();
+    let listing_types = LocalUserKeywordFilter::read_listing_types(pool, &ids).await?;
+    Ok(
+      filters
+        .into_iter()
+        .map(|keyword_filter| KeywordFilterView {
+          listing_types: listing_types
+            .iter()
+            .filter(|(id, _)| *id == keyword_filter.id)
+            .map(|(_, listing_type)| *listing_type)
+            .collect(),
+          keyword_filter,
+        })
+        .collect(),
+    )
+  }
+}
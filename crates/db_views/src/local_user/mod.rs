@@ -1,2 +1,6 @@
 #[cfg(feature = "full")]
+pub mod api_token_view;
+#[cfg(feature = "full")]
+pub mod keyword_filter_view;
+#[cfg(feature = "full")]
 pub mod local_user_view;
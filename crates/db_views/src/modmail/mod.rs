@@ -0,0 +1,2 @@
+pub mod modmail_message_view;
+pub mod modmail_thread_view;
@@ -0,0 +1,48 @@
+use crate::structs::ModmailMessageView;
+use diesel::{result::Error, ExpressionMethods, JoinOnDsl, QueryDsl};
+use diesel_async::RunQueryDsl;
+use lemmy_db_schema::{
+  newtypes::{ModmailMessageId, ModmailThreadId},
+  schema::{community, modmail_message, modmail_thread, person},
+  utils::{get_conn, DbPool},
+};
+
+impl ModmailMessageView {
+  pub async fn read(pool: &mut DbPool<'_>, message_id: ModmailMessageId) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    modmail_message::table
+      .find(message_id)
+      .inner_join(modmail_thread::table.inner_join(community::table))
+      .inner_join(person::table.on(modmail_message::creator_id.eq(person::id)))
+      .select((
+        modmail_message::all_columns,
+        modmail_thread::all_columns,
+        community::all_columns,
+        person::all_columns,
+      ))
+      .first(conn)
+      .await
+  }
+
+  /// Lists all messages of a thread, oldest first.
+  pub async fn list_for_thread(
+    pool: &mut DbPool<'_>,
+    thread_id: ModmailThreadId,
+  ) -> Result<Vec<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    modmail_message::table
+      .inner_join(modmail_thread::table.inner_join(community::table))
+      .inner_join(person::table.on(modmail_message::creator_id.eq(person::id)))
+      .filter(modmail_message::thread_id.eq(thread_id))
+      .select((
+        modmail_message::all_columns,
+        modmail_thread::all_columns,
+        community::all_columns,
+        person::all_columns,
+      ))
+      .order_by(modmail_message::published)
+      .then_order_by(modmail_message::id)
+      .load::<Self>(conn)
+      .await
+  }
+}
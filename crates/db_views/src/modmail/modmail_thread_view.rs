@@ -0,0 +1,225 @@
+use crate::structs::ModmailThreadView;
+use diesel::{
+  dsl::{count_star, not},
+  result::Error,
+  ExpressionMethods,
+  JoinOnDsl,
+  QueryDsl,
+};
+use diesel_async::RunQueryDsl;
+use lemmy_db_schema::{
+  newtypes::{CommunityId, ModmailThreadId, PersonId},
+  schema::{community, community_actions, modmail_message, modmail_thread, person},
+  utils::{action_query, functions::coalesce, get_conn, limit_and_offset, DbPool},
+};
+
+impl ModmailThreadView {
+  /// Reads a thread. The unread count is for the moderators if `as_moderator` is true, otherwise
+  /// for the thread creator.
+  pub async fn read(
+    pool: &mut DbPool<'_>,
+    thread_id: ModmailThreadId,
+    as_moderator: bool,
+  ) -> Result<Self, Error> {
+    ModmailThreadQuery::default()
+      .list_inner(pool, Some(thread_id), as_moderator)
+      .await?
+      .pop()
+      .ok_or(Error::NotFound)
+  }
+
+  /// Gets the number of unread messages in the threads of communities which the person moderates.
+  pub async fn get_unread_count_for_moderator(
+    pool: &mut DbPool<'_>,
+    my_person_id: PersonId,
+  ) -> Result<i64, Error> {
+    let conn = &mut get_conn(pool).await?;
+    let moderated_communities = action_query(community_actions::became_moderator)
+      .filter(community_actions::person_id.eq(my_person_id))
+      .select(community_actions::community_id);
+    modmail_message::table
+      .inner_join(modmail_thread::table)
+      .filter(modmail_thread::community_id.eq_any(moderated_communities))
+      .filter(not(modmail_thread::archived))
+      .filter(not(modmail_message::from_moderator))
+      .filter(not(modmail_message::read))
+      .select(count_star())
+      .first::<i64>(conn)
+      .await
+  }
+}
+
+#[derive(Default)]
+pub struct ModmailThreadQuery {
+  pub community_id: Option<CommunityId>,
+  pub creator_id: Option<PersonId>,
+  pub archived: Option<bool>,
+  pub page: Option<i64>,
+  pub limit: Option<i64>,
+}
+
+impl ModmailThreadQuery {
+  /// Lists threads with the most recent activity first. The unread counts are for the moderators
+  /// if `as_moderator` is true, otherwise for the thread creator.
+  pub async fn list(
+    self,
+    pool: &mut DbPool<'_>,
+    as_moderator: bool,
+  ) -> Result<Vec<ModmailThreadView>, Error> {
+    self.list_inner(pool, None, as_moderator).await
+  }
+
+  async fn list_inner(
+    self,
+    pool: &mut DbPool<'_>,
+    thread_id: Option<ModmailThreadId>,
+    as_moderator: bool,
+  ) -> Result<Vec<ModmailThreadView>, Error> {
+    let conn = &mut get_conn(pool).await?;
+
+    let unread_count = modmail_message::table
+      .filter(modmail_message::thread_id.eq(modmail_thread::id))
+      .filter(modmail_message::from_moderator.eq(!as_moderator))
+      .filter(not(modmail_message::read))
+      .select(count_star())
+      .single_value();
+
+    let mut query = modmail_thread::table
+      .inner_join(community::table)
+      .inner_join(person::table.on(modmail_thread::creator_id.eq(person::id)))
+      .select((
+        modmail_thread::all_columns,
+        community::all_columns,
+        person::all_columns,
+        coalesce(unread_count, 0),
+      ))
+      .into_boxed();
+
+    if let Some(thread_id) = thread_id {
+      query = query.filter(modmail_thread::id.eq(thread_id));
+    }
+    if let Some(community_id) = self.community_id {
+      query = query.filter(modmail_thread::community_id.eq(community_id));
+    }
+    if let Some(creator_id) = self.creator_id {
+      query = query.filter(modmail_thread::creator_id.eq(creator_id));
+    }
+    if let Some(archived) = self.archived {
+      query = query.filter(modmail_thread::archived.eq(archived));
+    }
+
+    let (limit, offset) = limit_and_offset(self.page, self.limit)?;
+
+    query
+      .order_by(coalesce(modmail_thread::updated, modmail_thread::published).desc())
+      .then_order_by(modmail_thread::id.desc())
+      .limit(limit)
+      .offset(offset)
+      .load::<ModmailThreadView>(conn)
+      .await
+  }
+}
+
+#[cfg(test)]
+#[expect(clippy::indexing_slicing)]
+mod tests {
+
+  use crate::{modmail::modmail_thread_view::ModmailThreadQuery, structs::ModmailThreadView};
+  use lemmy_db_schema::{
+    source::{
+      community::{Community, CommunityInsertForm, CommunityModerator, CommunityModeratorForm},
+      instance::Instance,
+      modmail::{
+        ModmailMessage,
+        ModmailMessageInsertForm,
+        ModmailThread,
+        ModmailThreadInsertForm,
+        ModmailThreadUpdateForm,
+      },
+      person::{Person, PersonInsertForm},
+    },
+    traits::{Crud, Joinable},
+    utils::build_db_pool_for_tests,
+  };
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_modmail_threads() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let instance = Instance::read_or_create(pool, "my_domain.tld".to_string()).await?;
+    let user_form = PersonInsertForm::test_form(instance.id, "modmail_thread_user");
+    let user = Person::create(pool, &user_form).await?;
+    let moderator_form = PersonInsertForm::test_form(instance.id, "modmail_thread_mod");
+    let moderator = Person::create(pool, &moderator_form).await?;
+
+    let community_form = CommunityInsertForm::new(
+      instance.id,
+      "test_modmail_thread".to_string(),
+      "nada".to_owned(),
+      "pubkey".to_string(),
+    );
+    let community = Community::create(pool, &community_form).await?;
+    let form = CommunityModeratorForm {
+      community_id: community.id,
+      person_id: moderator.id,
+    };
+    CommunityModerator::join(pool, &form).await?;
+
+    let thread_form = ModmailThreadInsertForm::new(community.id, user.id, "first".to_string());
+    let first = ModmailThread::create(pool, &thread_form).await?;
+    let thread_form = ModmailThreadInsertForm::new(community.id, user.id, "second".to_string());
+    let second = ModmailThread::create(pool, &thread_form).await?;
+    for thread_id in [first.id, first.id, second.id] {
+      let form = ModmailMessageInsertForm::new(thread_id, user.id, "hello".to_string(), false);
+      ModmailMessage::create(pool, &form).await?;
+    }
+
+    // The unread counts differ between the moderators and the user
+    let first_view = ModmailThreadView::read(pool, first.id, true).await?;
+    assert_eq!(2, first_view.unread_count);
+    assert_eq!(user.id, first_view.creator.id);
+    let first_view = ModmailThreadView::read(pool, first.id, false).await?;
+    assert_eq!(0, first_view.unread_count);
+
+    let unread = ModmailThreadView::get_unread_count_for_moderator(pool, moderator.id).await?;
+    assert_eq!(3, unread);
+    let unread = ModmailThreadView::get_unread_count_for_moderator(pool, user.id).await?;
+    assert_eq!(0, unread);
+
+    // Archived threads are not counted, and can be filtered
+    let form = ModmailThreadUpdateForm {
+      archived: Some(true),
+      ..Default::default()
+    };
+    ModmailThread::update(pool, first.id, &form).await?;
+    let unread = ModmailThreadView::get_unread_count_for_moderator(pool, moderator.id).await?;
+    assert_eq!(1, unread);
+
+    let threads = ModmailThreadQuery {
+      community_id: Some(community.id),
+      archived: Some(false),
+      ..Default::default()
+    }
+    .list(pool, true)
+    .await?;
+    assert_eq!(1, threads.len());
+    assert_eq!(second.id, threads[0].modmail_thread.id);
+
+    let threads = ModmailThreadQuery {
+      creator_id: Some(user.id),
+      ..Default::default()
+    }
+    .list(pool, false)
+    .await?;
+    assert_eq!(2, threads.len());
+
+    Instance::delete(pool, instance.id).await?;
+
+    Ok(())
+  }
+}
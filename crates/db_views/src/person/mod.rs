@@ -1,2 +1,4 @@
 #[cfg(feature = "full")]
+pub mod mod_note_view;
+#[cfg(feature = "full")]
 pub mod person_view;
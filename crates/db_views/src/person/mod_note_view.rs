@@ -0,0 +1,114 @@
+use crate::structs::ModNoteView;
+use diesel::{result::Error, ExpressionMethods, JoinOnDsl, NullableExpressionMethods, QueryDsl};
+use diesel_async::RunQueryDsl;
+use lemmy_db_schema::{
+  newtypes::{CommunityId, PersonId},
+  schema::{community, mod_note, person},
+  utils::{get_conn, DbPool},
+};
+
+impl ModNoteView {
+  /// Lists the notes about a person, newest first. If a community is given, only the notes of
+  /// that community are returned, otherwise the notes of all communities and of the admins.
+  pub async fn list(
+    pool: &mut DbPool<'_>,
+    person_id: PersonId,
+    community_id: Option<CommunityId>,
+  ) -> Result<Vec<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    let mut query = mod_note::table
+      .inner_join(person::table.on(mod_note::creator_id.eq(person::id)))
+      .left_join(community::table)
+      .filter(mod_note::person_id.eq(person_id))
+      .select((
+        mod_note::all_columns,
+        person::all_columns,
+        community::all_columns.nullable(),
+      ))
+      .into_boxed();
+
+    if let Some(community_id) = community_id {
+      query = query.filter(mod_note::community_id.eq(community_id));
+    }
+
+    query
+      .order_by(mod_note::published.desc())
+      .then_order_by(mod_note::id.desc())
+      .load::<Self>(conn)
+      .await
+  }
+}
+
+#[cfg(test)]
+#[expect(clippy::indexing_slicing)]
+mod tests {
+
+  use crate::structs::ModNoteView;
+  use lemmy_db_schema::{
+    source::{
+      community::{Community, CommunityInsertForm},
+      instance::Instance,
+      mod_note::{ModNote, ModNoteInsertForm},
+      person::{Person, PersonInsertForm},
+    },
+    traits::Crud,
+    utils::build_db_pool_for_tests,
+  };
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_list_mod_notes() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let instance = Instance::read_or_create(pool, "my_domain.tld".to_string()).await?;
+    let moderator =
+      Person::create(pool, &PersonInsertForm::test_form(instance.id, "mnv_mod")).await?;
+    let person =
+      Person::create(pool, &PersonInsertForm::test_form(instance.id, "mnv_user")).await?;
+    let community_form = CommunityInsertForm::new(
+      instance.id,
+      "mod_note_community".to_string(),
+      "nada".to_owned(),
+      "pubkey".to_string(),
+    );
+    let community = Community::create(pool, &community_form).await?;
+
+    let community_note = ModNoteInsertForm::new(
+      person.id,
+      moderator.id,
+      Some(community.id),
+      "posts spam links".to_string(),
+    );
+    let community_note = ModNote::create(pool, &community_note).await?;
+    let site_note =
+      ModNoteInsertForm::new(person.id, moderator.id, None, "alt account".to_string());
+    let site_note = ModNote::create(pool, &site_note).await?;
+    // Notes about other people are not listed
+    let other_note = ModNoteInsertForm::new(moderator.id, person.id, None, "other".to_string());
+    ModNote::create(pool, &other_note).await?;
+
+    let notes = ModNoteView::list(pool, person.id, Some(community.id)).await?;
+    assert_eq!(1, notes.len());
+    assert_eq!(community_note, notes[0].mod_note);
+    assert_eq!(moderator.id, notes[0].creator.id);
+    assert_eq!(
+      Some(community.id),
+      notes[0].community.as_ref().map(|c| c.id)
+    );
+
+    let notes = ModNoteView::list(pool, person.id, None).await?;
+    assert_eq!(
+      vec![site_note.id, community_note.id],
+      notes.iter().map(|n| n.mod_note.id).collect::<Vec<_>>()
+    );
+    assert!(notes[0].community.is_none());
+
+    Instance::delete(pool, instance.id).await?;
+
+    Ok(())
+  }
+}
@@ -1,4 +1,8 @@
 #[cfg(feature = "full")]
+pub mod post_poll_view;
+#[cfg(feature = "full")]
+pub mod post_revision_view;
+#[cfg(feature = "full")]
 pub mod post_tags_view;
 #[cfg(feature = "full")]
 pub mod post_view;
@@ -0,0 +1,14 @@
+//! see post_view.rs for the reason for this json decoding
+use crate::structs::PostPollView;
+use diesel::{
+  deserialize::FromSql,
+  pg::{Pg, PgValue},
+  sql_types,
+};
+
+impl FromSql<sql_types::Json, Pg> for PostPollView {
+  fn from_sql(bytes: PgValue) -> diesel::deserialize::Result<Self> {
+    let value = <serde_json::Value as FromSql<sql_types::Json, Pg>>::from_sql(bytes)?;
+    Ok(serde_json::from_value::<PostPollView>(value)?)
+  }
+}
@@ -0,0 +1,23 @@
+use crate::structs::PostRevisionView;
+use diesel::{result::Error, ExpressionMethods, QueryDsl};
+use diesel_async::RunQueryDsl;
+use lemmy_db_schema::{
+  newtypes::PostId,
+  schema::{person, post_revision},
+  utils::{get_conn, DbPool},
+};
+
+impl PostRevisionView {
+  /// Lists the previous versions of a post, newest first.
+  pub async fn list(pool: &mut DbPool<'_>, post_id: PostId) -> Result<Vec<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    post_revision::table
+      .inner_join(person::table)
+      .filter(post_revision::post_id.eq(post_id))
+      .select((post_revision::all_columns, person::all_columns))
+      .order_by(post_revision::published.desc())
+      .then_order_by(post_revision::id.desc())
+      .load::<Self>(conn)
+      .await
+  }
+}
@@ -5,8 +5,10 @@ use diesel::{
   pg::Pg,
   query_builder::AsQuery,
   result::Error,
+  sql_types::Text,
   BoolExpressionMethods,
   ExpressionMethods,
+  IntoSql,
   JoinOnDsl,
   NullableExpressionMethods,
   OptionalExtension,
@@ -19,10 +21,12 @@ use lemmy_db_schema::{
   aggregates::structs::{post_aggregates_keys as key, PostAggregates},
   aliases::creator_community_actions,
   impls::local_user::LocalUserOptionHelper,
-  newtypes::{CommunityId, LocalUserId, PersonId, PostId},
+  newtypes::{CommunityId, DbUrl, FeedId, LocalUserId, PersonId, PostId, TagId},
   schema::{
     community,
     community_actions,
+    federation_limit,
+    feed_community,
     image_details,
     instance_actions,
     local_user,
@@ -38,19 +42,21 @@ use lemmy_db_schema::{
   source::{
     community::{CommunityFollower, CommunityFollowerState},
     local_user::LocalUser,
-    post::{post_actions_keys, PostActionsCursor},
+    local_user_keyword_filter::LocalUserKeywordFilter,
+    post::{post_actions_keys, Post, PostActionsCursor},
     site::Site,
   },
   utils::{
     action_query,
     actions,
     actions_alias,
-    functions::coalesce,
+    functions::{coalesce, post_poll},
     fuzzy_search,
     get_conn,
     limit_and_offset,
     now,
     paginate,
+    regex_match_insensitive,
     Commented,
     DbConn,
     DbPool,
@@ -94,9 +100,8 @@ fn queries<'a>() -> Queries<
     // the posts, then fetch all relevant post-tag-association tuples from the db, and then fetch
     // all the relevant tag objects.
     //
-    // If we want to filter by post tag we will have to add
-    // separate logic below since this subquery can't affect filtering, but it is simple (`WHERE
-    // exists (select 1 from post_community_post_tags where community_post_tag_id in (1,2,3,4)`).
+    // Filtering by post tag is done separately below using `tag_ids`, since this subquery can't
+    // affect filtering.
     let post_tags = post_tag::table
       .inner_join(tag::table)
       .select(diesel::dsl::sql::<diesel::sql_types::Json>(
@@ -163,6 +168,7 @@ fn queries<'a>() -> Queries<
           post_aggregates::comments,
         ),
         post_tags,
+        post_poll(post_aggregates::post_id.nullable(), my_person_id),
       ))
   };
 
@@ -264,16 +270,45 @@ fn queries<'a>() -> Queries<
     }
 
     let is_subscribed = community_actions::followed.is_not_null();
-    match o.listing_type.unwrap_or_default() {
-      ListingType::Subscribed => query = query.filter(is_subscribed),
-      ListingType::Local => {
-        query = query
-          .filter(community::local.eq(true))
-          .filter(community::hidden.eq(false).or(is_subscribed));
-      }
-      ListingType::All => query = query.filter(community::hidden.eq(false).or(is_subscribed)),
-      ListingType::ModeratorView => {
-        query = query.filter(community_actions::became_moderator.is_not_null());
+    if let Some(feed_id) = o.feed_id {
+      // The communities of a feed are chosen explicitly, so the listing type doesn't apply
+      query = query.filter(
+        post_aggregates::community_id.eq_any(
+          feed_community::table
+            .filter(feed_community::feed_id.eq(feed_id))
+            .select(feed_community::community_id),
+        ),
+      );
+    } else {
+      match o.listing_type.unwrap_or_default() {
+        ListingType::Subscribed => query = query.filter(is_subscribed),
+        ListingType::Local => {
+          query = query
+            .filter(community::local.eq(true))
+            .filter(community::hidden.eq(false).or(is_subscribed));
+        }
+        ListingType::All => {
+          // Content of limited instances is only shown to followers of the community or creator
+          let is_limited = exists(
+            federation_limit::table
+              .filter(federation_limit::limited)
+              .filter(
+                federation_limit::instance_id
+                  .eq(community::instance_id)
+                  .or(federation_limit::instance_id.eq(person::instance_id)),
+              ),
+          );
+          query = query
+            .filter(community::hidden.eq(false).or(is_subscribed))
+            .filter(
+              not(is_limited)
+                .or(is_subscribed)
+                .or(person_actions::followed.is_not_null()),
+            )
+        }
+        ListingType::ModeratorView => {
+          query = query.filter(community_actions::became_moderator.is_not_null());
+        }
       }
     }
 
@@ -293,6 +328,21 @@ fn queries<'a>() -> Queries<
       }
     }
 
+    if let Some(cross_posts_of) = o.cross_posts_of {
+      let chain_id = cross_posts_of.crosspost_of_id.unwrap_or(cross_posts_of.id);
+      let same_chain = post::id.eq(chain_id).or(post::crosspost_of_id.eq(chain_id));
+      query = if let Some(url) = &cross_posts_of.url {
+        query.filter(same_chain.or(post::url.eq(url)))
+      } else {
+        query.filter(same_chain)
+      }
+      .filter(post::id.ne(cross_posts_of.id));
+    }
+
+    if let Some(urls) = o.urls.clone() {
+      query = query.filter(post::url.eq_any(urls));
+    }
+
     if !o.show_nsfw.unwrap_or(o.local_user.show_nsfw(site)) {
       query = query
         .filter(post::nsfw.eq(false))
@@ -308,6 +358,15 @@ fn queries<'a>() -> Queries<
       query = query.filter(post_aggregates::comments.eq(0));
     };
 
+    // Only show posts which have at least one of the given tags
+    if let Some(tag_ids) = o.tag_ids.clone() {
+      query = query.filter(exists(
+        post_tag::table
+          .filter(post_tag::post_id.eq(post_aggregates::post_id))
+          .filter(post_tag::tag_id.eq_any(tag_ids)),
+      ));
+    }
+
     if !o.show_read.unwrap_or(o.local_user.show_read_posts()) {
       // Do not hide read posts when it is a user profile view
       // Or, only hide read posts on non-profile views
@@ -331,6 +390,42 @@ fn queries<'a>() -> Queries<
       ));
     }
 
+    // Hide posts which match any of the keyword filters of the user. Feeds are not a listing
+    // type, so only the filters which apply everywhere are used for them.
+    if let Some(local_user_id) = o.local_user.local_user_id() {
+      let listing_type = o
+        .feed_id
+        .is_none()
+        .then(|| o.listing_type.unwrap_or_default());
+      let filters = LocalUserKeywordFilter::list_for_listing(
+        &mut DbPool::Conn(&mut conn),
+        local_user_id,
+        listing_type,
+      )
+      .await?;
+      for filter in filters {
+        let pattern = filter.pattern();
+        if filter.match_title {
+          query = query.filter(not(regex_match_insensitive(
+            post::name,
+            pattern.clone().into_sql::<Text>(),
+          )));
+        }
+        if filter.match_body {
+          query = query.filter(not(regex_match_insensitive(
+            coalesce(post::body, ""),
+            pattern.clone().into_sql::<Text>(),
+          )));
+        }
+        if filter.match_url {
+          query = query.filter(not(regex_match_insensitive(
+            coalesce(post::url, ""),
+            pattern.into_sql::<Text>(),
+          )));
+        }
+      }
+    }
+
     if let Some(my_id) = o.local_user.person_id() {
       let not_creator_filter = post_aggregates::creator_id.ne(my_id);
       if o.liked_only.unwrap_or_default() {
@@ -514,6 +609,8 @@ pub struct PostQuery<'a> {
   // if true, the query should be handled as if community_id was not given except adding the
   // literal filter
   pub community_id_just_for_prefetch: bool,
+  /// Only show posts from the communities of this feed. Overrides the listing type.
+  pub feed_id: Option<FeedId>,
   pub local_user: Option<&'a LocalUser>,
   pub search_term: Option<String>,
   pub url_only: Option<bool>,
@@ -531,6 +628,12 @@ pub struct PostQuery<'a> {
   pub show_nsfw: Option<bool>,
   pub hide_media: Option<bool>,
   pub no_comments_only: Option<bool>,
+  pub tag_ids: Option<Vec<TagId>>,
+  /// Only show other discussions of this post, meaning posts with the same url or from the same
+  /// crosspost chain.
+  pub cross_posts_of: Option<&'a Post>,
+  /// Only show posts which link to one of these urls.
+  pub urls: Option<Vec<DbUrl>>,
 }
 
 impl<'a> PostQuery<'a> {
@@ -616,6 +719,7 @@ impl<'a> PostQuery<'a> {
   pub async fn list(self, site: &Site, pool: &mut DbPool<'_>) -> Result<Vec<PostView>, Error> {
     if self.listing_type == Some(ListingType::Subscribed)
       && self.community_id.is_none()
+      && self.feed_id.is_none()
       && self.local_user.is_some()
       && self.page_before_or_equal.is_none()
     {
@@ -646,7 +750,7 @@ mod tests {
   use lemmy_db_schema::{
     aggregates::structs::PostAggregates,
     impls::actor_language::UNDETERMINED_ID,
-    newtypes::LanguageId,
+    newtypes::{DbUrl, LanguageId},
     source::{
       actor_language::LocalUserLanguage,
       comment::{Comment, CommentInsertForm},
@@ -663,10 +767,13 @@ mod tests {
         CommunityUpdateForm,
       },
       community_block::{CommunityBlock, CommunityBlockForm},
+      federation_limit::{FederationLimit, FederationLimitForm},
+      feed::{Feed, FeedInsertForm},
       instance::Instance,
       instance_block::{InstanceBlock, InstanceBlockForm},
       language::Language,
       local_user::{LocalUser, LocalUserInsertForm, LocalUserUpdateForm},
+      local_user_keyword_filter::{LocalUserKeywordFilter, LocalUserKeywordFilterInsertForm},
       local_user_vote_display_mode::LocalUserVoteDisplayMode,
       person::{Person, PersonInsertForm},
       person_block::{PersonBlock, PersonBlockForm},
@@ -680,12 +787,16 @@ mod tests {
         PostReadForm,
         PostUpdateForm,
       },
+      post_poll::{PostPoll, PostPollInsertForm, PostPollOptionInsertForm, PostPollVote},
       site::Site,
       tag::{PostTagInsertForm, Tag, TagInsertForm},
     },
     traits::{Bannable, Blockable, Crud, Followable, Joinable, Likeable},
     utils::{build_db_pool, get_conn, uplete, ActualDbPool, DbPool, RANK_DEFAULT},
     CommunityVisibility,
+    DuplicateLinkPolicy,
+    KeywordFilterMode,
+    ListingType,
     PostSortType,
     SubscribedType,
   };
@@ -889,6 +1000,7 @@ mod tests {
         public_key: String::new(),
         instance_id: Default::default(),
         content_warning: None,
+        edit_history_public: false,
       };
 
       Ok(Data {
@@ -1535,6 +1647,74 @@ mod tests {
     Ok(())
   }
 
+  #[test_context(Data)]
+  #[tokio::test]
+  #[serial]
+  async fn post_listing_limited_instance(data: &mut Data) -> LemmyResult<()> {
+    const POST_FROM_LIMITED_INSTANCE: &str = "post on limited instance";
+
+    let pool = &data.pool();
+    let pool = &mut pool.into();
+
+    let limited_instance = Instance::read_or_create(pool, "limited_domain.tld".to_string()).await?;
+
+    let community_form = CommunityInsertForm::new(
+      limited_instance.id,
+      "test_community_limited".to_string(),
+      "none".to_owned(),
+      "pubkey".to_string(),
+    );
+    let inserted_community = Community::create(pool, &community_form).await?;
+
+    let post_form = PostInsertForm {
+      language_id: Some(LanguageId(1)),
+      ..PostInsertForm::new(
+        POST_FROM_LIMITED_INSTANCE.to_string(),
+        data.inserted_bot.id,
+        inserted_community.id,
+      )
+    };
+    Post::create(pool, &post_form).await?;
+
+    let limit_form = FederationLimitForm {
+      instance_id: limited_instance.id,
+      limited: true,
+      ..Default::default()
+    };
+    FederationLimit::upsert(pool, &limit_form).await?;
+
+    // Posts of the limited instance are hidden from the all listing
+    let query = PostQuery {
+      listing_type: Some(ListingType::All),
+      ..data.default_post_query()
+    };
+    let post_listings = query.clone().list(&data.site, pool).await?;
+    assert_eq!(
+      vec![POST_WITH_TAGS, POST_BY_BOT, POST],
+      names(&post_listings)
+    );
+
+    // They are shown again after following the community
+    let follow_form = CommunityFollowerForm {
+      state: Some(CommunityFollowerState::Accepted),
+      ..CommunityFollowerForm::new(inserted_community.id, data.local_user_view.person.id)
+    };
+    CommunityFollower::follow(pool, &follow_form).await?;
+    let post_listings = query.list(&data.site, pool).await?;
+    assert_eq!(
+      vec![
+        POST_FROM_LIMITED_INSTANCE,
+        POST_WITH_TAGS,
+        POST_BY_BOT,
+        POST
+      ],
+      names(&post_listings)
+    );
+
+    Instance::delete(pool, limited_instance.id).await?;
+    Ok(())
+  }
+
   #[test_context(Data)]
   #[tokio::test]
   #[serial]
@@ -1821,6 +2001,7 @@ mod tests {
         featured_local: false,
         url_content_type: None,
         scheduled_publish_time: None,
+        crosspost_of_id: None,
       },
       my_vote: None,
       unread_comments: 0,
@@ -1842,6 +2023,7 @@ mod tests {
         matrix_user_id: None,
         ban_expires: None,
         instance_id: data.inserted_instance.id,
+        moved_to: None,
         private_key: inserted_person.private_key.clone(),
         public_key: inserted_person.public_key.clone(),
         last_refreshed_at: inserted_person.last_refreshed_at,
@@ -1878,6 +2060,8 @@ mod tests {
         featured_url: inserted_community.featured_url.clone(),
         visibility: CommunityVisibility::Public,
         random_number: inserted_community.random_number,
+        duplicate_link_policy: DuplicateLinkPolicy::Allow,
+        duplicate_link_window_days: 30,
       },
       counts: PostAggregates {
         post_id: inserted_post.id,
@@ -1906,6 +2090,7 @@ mod tests {
       saved: false,
       creator_blocked: false,
       tags: PostTags::default(),
+      poll: None,
     })
   }
 
@@ -2291,4 +2476,247 @@ mod tests {
 
     Ok(())
   }
+
+  #[test_context(Data)]
+  #[tokio::test]
+  #[serial]
+  async fn post_poll_results_hidden_until_voted(data: &mut Data) -> LemmyResult<()> {
+    let pool = &data.pool();
+    let pool = &mut pool.into();
+    let post_id = data.inserted_post.id;
+
+    let poll_form = PostPollInsertForm::new(post_id, false, None);
+    let option_forms = vec![
+      PostPollOptionInsertForm::new(post_id, "yes".to_string(), 0),
+      PostPollOptionInsertForm::new(post_id, "no".to_string(), 1),
+    ];
+    PostPoll::upsert(pool, &poll_form, &option_forms).await?;
+
+    let local_user = Some(&data.local_user_view.local_user);
+    let poll = PostView::read(pool, post_id, local_user, false)
+      .await?
+      .poll
+      .expect("post has poll");
+    assert_eq!(
+      vec!["yes", "no"],
+      poll
+        .options
+        .iter()
+        .map(|o| o.name.as_str())
+        .collect::<Vec<_>>()
+    );
+    assert!(poll.options.iter().all(|o| o.vote_count.is_none()));
+    assert_eq!(None, poll.voters_count);
+    assert!(poll.my_votes.is_empty());
+
+    let yes = poll.options[0].id;
+    PostPollVote::vote(pool, post_id, data.local_user_view.person.id, &[yes], true).await?;
+    let poll = PostView::read(pool, post_id, local_user, false)
+      .await?
+      .poll
+      .expect("post has poll");
+    assert_eq!(Some(1), poll.voters_count);
+    assert_eq!(
+      vec![Some(1), Some(0)],
+      poll
+        .options
+        .iter()
+        .map(|o| o.vote_count)
+        .collect::<Vec<_>>()
+    );
+    assert_eq!(vec![yes], poll.my_votes);
+
+    // Others only see the results after voting themselves
+    let other_user = Some(&data.blocked_local_user_view.local_user);
+    let poll = PostView::read(pool, post_id, other_user, false)
+      .await?
+      .poll
+      .expect("post has poll");
+    assert_eq!(None, poll.voters_count);
+
+    // Posts without poll don't have poll data
+    let post_view = PostView::read(pool, data.inserted_bot_post.id, local_user, false).await?;
+    assert_eq!(None, post_view.poll);
+
+    Ok(())
+  }
+
+  #[test_context(Data)]
+  #[tokio::test]
+  #[serial]
+  async fn post_listings_filter_by_tag(data: &mut Data) -> LemmyResult<()> {
+    let pool = &data.pool();
+    let pool = &mut pool.into();
+
+    let tagged_posts = PostQuery {
+      tag_ids: Some(vec![data.tag_1.id]),
+      ..data.default_post_query()
+    }
+    .list(&data.site, pool)
+    .await?;
+    assert_eq!(
+      vec![data.inserted_post_with_tags.id],
+      tagged_posts.iter().map(|p| p.post.id).collect::<Vec<_>>()
+    );
+
+    // Replace the tags of the post, so it no longer has tag 1
+    PostTagInsertForm::set_for_post(pool, data.inserted_post_with_tags.id, &[data.tag_2.id])
+      .await?;
+    let tagged_posts = PostQuery {
+      tag_ids: Some(vec![data.tag_1.id]),
+      ..data.default_post_query()
+    }
+    .list(&data.site, pool)
+    .await?;
+    assert!(tagged_posts.is_empty());
+
+    let post_view = PostView::read(
+      pool,
+      data.inserted_post_with_tags.id,
+      Some(&data.local_user_view.local_user),
+      false,
+    )
+    .await?;
+    assert_eq!(vec![data.tag_2.clone()], post_view.tags.tags);
+
+    Ok(())
+  }
+  #[test_context(Data)]
+  #[tokio::test]
+  #[serial]
+  async fn post_listings_feed(data: &mut Data) -> LemmyResult<()> {
+    let pool = &data.pool();
+    let pool = &mut pool.into();
+
+    let community_form = CommunityInsertForm::new(
+      data.inserted_instance.id,
+      "not_in_feed".to_string(),
+      "nada".to_owned(),
+      "pubkey".to_string(),
+    );
+    let other_community = Community::create(pool, &community_form).await?;
+    let post_form = PostInsertForm::new(
+      "post outside of feed".to_string(),
+      data.local_user_view.person.id,
+      other_community.id,
+    );
+    Post::create(pool, &post_form).await?;
+
+    let feed_form = FeedInsertForm::new(data.local_user_view.person.id, "my feed".to_string());
+    let feed = Feed::create(pool, &feed_form).await?;
+    Feed::set_communities(pool, feed.id, &[data.inserted_community.id]).await?;
+
+    // The feed overrides the listing type, even though the user doesn't follow the community
+    let feed_posts = PostQuery {
+      feed_id: Some(feed.id),
+      listing_type: Some(ListingType::Subscribed),
+      ..data.default_post_query()
+    }
+    .list(&data.site, pool)
+    .await?;
+    assert!(!feed_posts.is_empty());
+    assert!(feed_posts
+      .iter()
+      .all(|p| p.community.id == data.inserted_community.id));
+
+    let all_posts = data.default_post_query().list(&data.site, pool).await?;
+    assert!(all_posts
+      .iter()
+      .any(|p| p.community.id == other_community.id));
+
+    Community::delete(pool, other_community.id).await?;
+    Ok(())
+  }
+
+  #[test_context(Data)]
+  #[tokio::test]
+  #[serial]
+  async fn post_listings_cross_posts(data: &mut Data) -> LemmyResult<()> {
+    let pool = &data.pool();
+    let pool = &mut pool.into();
+
+    let person_id = data.local_user_view.person.id;
+    let community_id = data.inserted_community.id;
+    let url: DbUrl = Url::parse("https://example.com/article")?.into();
+    let original_form = PostInsertForm {
+      url: Some(url.clone()),
+      ..PostInsertForm::new("original".to_string(), person_id, community_id)
+    };
+    let original = Post::create(pool, &original_form).await?;
+    let crosspost_form = PostInsertForm {
+      crosspost_of_id: Some(original.id),
+      ..PostInsertForm::new("crosspost".to_string(), person_id, community_id)
+    };
+    let crosspost = Post::create(pool, &crosspost_form).await?;
+    let same_url_form = PostInsertForm {
+      url: Some(url),
+      ..PostInsertForm::new("same url".to_string(), person_id, community_id)
+    };
+    let same_url = Post::create(pool, &same_url_form).await?;
+
+    // The crosspost itself has no url, but still finds the rest of the chain
+    let expected = [
+      (&original, vec!["same url", "crosspost"]),
+      (&crosspost, vec!["original"]),
+      (&same_url, vec!["original"]),
+      (&data.inserted_post, vec![]),
+    ];
+    for (post, expected_names) in expected {
+      let cross_posts = PostQuery {
+        cross_posts_of: Some(post),
+        ..data.default_post_query()
+      }
+      .list(&data.site, pool)
+      .await?;
+      assert_eq!(expected_names, names(&cross_posts));
+    }
+
+    Post::delete(pool, same_url.id).await?;
+    Post::delete(pool, crosspost.id).await?;
+    Post::delete(pool, original.id).await?;
+    Ok(())
+  }
+
+  #[test_context(Data)]
+  #[tokio::test]
+  #[serial]
+  async fn post_listings_keyword_filters(data: &mut Data) -> LemmyResult<()> {
+    let pool = &data.pool();
+    let pool = &mut pool.into();
+    let local_user_id = data.local_user_view.local_user.id;
+
+    let tags_form = LocalUserKeywordFilterInsertForm::new(local_user_id, "TAGS".to_string());
+    LocalUserKeywordFilter::create(pool, &tags_form).await?;
+    // Only matches whole words, so it doesn't hide "post"
+    let partial_form = LocalUserKeywordFilterInsertForm::new(local_user_id, "pos".to_string());
+    LocalUserKeywordFilter::create(pool, &partial_form).await?;
+    let regex_form = LocalUserKeywordFilterInsertForm {
+      mode: Some(KeywordFilterMode::Regex),
+      ..LocalUserKeywordFilterInsertForm::new(local_user_id, "^post$".to_string())
+    };
+    let regex_filter = LocalUserKeywordFilter::create(pool, &regex_form).await?;
+    LocalUserKeywordFilter::set_listing_types(pool, regex_filter.id, &[ListingType::All]).await?;
+
+    let local_posts = data.default_post_query().list(&data.site, pool).await?;
+    assert_eq!(vec![POST_BY_BOT, POST], names(&local_posts));
+
+    let all_posts = PostQuery {
+      listing_type: Some(ListingType::All),
+      ..data.default_post_query()
+    }
+    .list(&data.site, pool)
+    .await?;
+    assert_eq!(vec![POST_BY_BOT], names(&all_posts));
+
+    // Filters don't apply to logged out users
+    let logged_out_posts = PostQuery {
+      local_user: None,
+      ..data.default_post_query()
+    }
+    .list(&data.site, pool)
+    .await?;
+    assert!(names(&logged_out_posts).contains(&POST_WITH_TAGS));
+
+    Ok(())
+  }
 }
@@ -262,6 +262,7 @@ mod tests {
         inbox_url: inserted_sara_person.inbox_url.clone(),
         matrix_user_id: None,
         instance_id: inserted_instance.id,
+        moved_to: None,
         private_key: inserted_sara_person.private_key,
         public_key: inserted_sara_person.public_key,
         last_refreshed_at: inserted_sara_person.last_refreshed_at,
@@ -331,6 +332,7 @@ mod tests {
       inbox_url: inserted_timmy_person.inbox_url.clone(),
       matrix_user_id: None,
       instance_id: inserted_instance.id,
+      moved_to: None,
       private_key: inserted_timmy_person.private_key,
       public_key: inserted_timmy_person.public_key,
       last_refreshed_at: inserted_timmy_person.last_refreshed_at,
@@ -25,7 +25,13 @@ use lemmy_db_schema::{
     post,
   },
   source::community::CommunityFollower,
-  utils::{actions, actions_alias, functions::coalesce, get_conn, DbPool},
+  utils::{
+    actions,
+    actions_alias,
+    functions::{coalesce, person_ban_count, person_removal_count},
+    get_conn,
+    DbPool,
+  },
 };
 
 impl CommentReportView {
@@ -110,6 +116,8 @@ impl CommentReportView {
         comment_actions::saved.nullable().is_not_null(),
         comment_actions::like_score.nullable(),
         aliases::person2.fields(person::all_columns).nullable(),
+        person_removal_count(comment::creator_id.nullable()),
+        person_ban_count(comment::creator_id.nullable()),
       ))
       .first(conn)
       .await
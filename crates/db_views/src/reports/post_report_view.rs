@@ -23,7 +23,13 @@ use lemmy_db_schema::{
     post_report,
   },
   source::community::CommunityFollower,
-  utils::{actions, actions_alias, functions::coalesce, get_conn, DbPool},
+  utils::{
+    actions,
+    actions_alias,
+    functions::{coalesce, person_ban_count, person_removal_count},
+    get_conn,
+    DbPool,
+  },
 };
 
 impl PostReportView {
@@ -98,6 +104,8 @@ impl PostReportView {
         ),
         post_aggregates::all_columns,
         aliases::person2.fields(person::all_columns.nullable()),
+        person_removal_count(post::creator_id.nullable()),
+        person_ban_count(post::creator_id.nullable()),
       ))
       .first(conn)
       .await
@@ -5,7 +5,11 @@ use lemmy_db_schema::{
   aliases,
   newtypes::PrivateMessageReportId,
   schema::{person, private_message, private_message_report},
-  utils::{get_conn, DbPool},
+  utils::{
+    functions::{person_ban_count, person_removal_count},
+    get_conn,
+    DbPool,
+  },
 };
 
 impl PrivateMessageReportView {
@@ -36,6 +40,8 @@ impl PrivateMessageReportView {
         person::all_columns,
         aliases::person1.fields(person::all_columns),
         aliases::person2.fields(person::all_columns).nullable(),
+        person_removal_count(private_message::creator_id.nullable()),
+        person_ban_count(private_message::creator_id.nullable()),
       ))
       .first(conn)
       .await
@@ -6,3 +6,5 @@ pub mod local_image_view;
 pub mod site_view;
 #[cfg(feature = "full")]
 pub mod vote_view;
+#[cfg(feature = "full")]
+pub mod webhook_view;
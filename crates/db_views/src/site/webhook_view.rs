@@ -0,0 +1,43 @@
+use crate::structs::WebhookView;
+use diesel::result::Error;
+use lemmy_db_schema::{
+  newtypes::{CommunityId, WebhookId},
+  source::webhook::Webhook,
+  traits::Crud,
+  utils::DbPool,
+};
+
+impl WebhookView {
+  pub async fn read(pool: &mut DbPool<'_>, webhook_id: WebhookId) -> Result<Self, Error> {
+    let webhook = Webhook::read(pool, webhook_id).await?;
+    let events = Webhook::read_events(pool, &[webhook_id])
+      .await?
+      .into_iter()
+      .map(|e| e.event)
+      .collect();
+    Ok(WebhookView { webhook, events })
+  }
+
+  /// Lists the webhooks of a community, or the site-wide webhooks if no community is given.
+  pub async fn list(
+    pool: &mut DbPool<'_>,
+    community_id: Option<CommunityId>,
+  ) -> Result<Vec<Self>, Error> {
+    let webhooks = Webhook::list(pool, community_id).await?;
+    let ids = webhooks.iter().map(|w| w.id).collect::<Vec<_>>();
+    let events = Webhook::read_events(pool, &ids).await?;
+    Ok(
+      webhooks
+        .into_iter()
+        .map(|webhook| WebhookView {
+          events: events
+            .iter()
+            .filter(|e| e.webhook_id == webhook.id)
+            .map(|e| e.event)
+            .collect(),
+          webhook,
+        })
+        .collect(),
+    )
+  }
+}
@@ -1,3 +1,4 @@
+use chrono::{DateTime, Utc};
 #[cfg(feature = "full")]
 use diesel::Queryable;
 #[cfg(feature = "full")]
@@ -10,24 +11,30 @@ use lemmy_db_schema::{
     PostAggregates,
     SiteAggregates,
   },
+  newtypes::PostPollOptionId,
   source::{
+    api_token::ApiToken,
     comment::Comment,
     comment_reply::CommentReply,
     comment_report::CommentReport,
+    comment_revision::CommentRevision,
     community::Community,
     community_report::CommunityReport,
     custom_emoji::CustomEmoji,
     custom_emoji_keyword::CustomEmojiKeyword,
+    feed::Feed,
     images::{ImageDetails, LocalImage},
     instance::Instance,
     local_site::LocalSite,
     local_site_rate_limit::LocalSiteRateLimit,
     local_user::LocalUser,
+    local_user_keyword_filter::LocalUserKeywordFilter,
     local_user_vote_display_mode::LocalUserVoteDisplayMode,
     mod_log::{
       admin::{
         AdminAllowInstance,
         AdminBlockInstance,
+        AdminLimitInstance,
         AdminPurgeComment,
         AdminPurgeCommunity,
         AdminPurgePerson,
@@ -36,6 +43,7 @@ use lemmy_db_schema::{
       moderator::{
         ModAdd,
         ModAddCommunity,
+        ModAutomodAction,
         ModBan,
         ModBanFromCommunity,
         ModFeaturePost,
@@ -45,20 +53,29 @@ use lemmy_db_schema::{
         ModRemoveCommunity,
         ModRemovePost,
         ModTransferCommunity,
+        ModWarnPerson,
       },
     },
+    mod_note::ModNote,
+    modmail::{ModmailMessage, ModmailThread},
     person::Person,
     person_comment_mention::PersonCommentMention,
     person_post_mention::PersonPostMention,
     post::Post,
     post_report::PostReport,
+    post_revision::PostRevision,
     private_message::PrivateMessage,
     private_message_report::PrivateMessageReport,
     registration_application::RegistrationApplication,
     site::Site,
     tag::Tag,
+    thread_subscription::ThreadReply,
+    webhook::Webhook,
   },
+  ApiTokenScope,
+  ListingType,
   SubscribedType,
+  WebhookEventType,
 };
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
@@ -89,6 +106,10 @@ pub struct CommentReportView {
   pub my_vote: Option<i16>,
   #[cfg_attr(feature = "full", ts(optional))]
   pub resolver: Option<Person>,
+  /// How many posts and comments of the comment creator were removed by moderators.
+  pub comment_creator_removal_count: i64,
+  /// How many times the comment creator was banned from the site or a community.
+  pub comment_creator_ban_count: i64,
 }
 
 #[skip_serializing_none]
@@ -114,6 +135,26 @@ pub struct CommentView {
   pub my_vote: Option<i16>,
 }
 
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS, Queryable))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A previous version of a comment.
+pub struct CommentRevisionView {
+  pub comment_revision: CommentRevision,
+  pub editor: Person,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS, Queryable))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A previous version of a post.
+pub struct PostRevisionView {
+  pub post_revision: PostRevision,
+  pub editor: Person,
+}
+
 #[skip_serializing_none]
 #[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "full", derive(TS, Queryable))]
@@ -163,6 +204,25 @@ pub struct LocalUserView {
   pub counts: PersonAggregates,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// An api token view.
+pub struct ApiTokenView {
+  pub api_token: ApiToken,
+  pub scopes: Vec<ApiTokenScope>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A keyword filter view.
+pub struct KeywordFilterView {
+  pub keyword_filter: LocalUserKeywordFilter,
+  /// The filter is only applied in these listings. If empty, it is applied everywhere.
+  pub listing_types: Vec<ListingType>,
+}
+
 #[skip_serializing_none]
 #[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "full", derive(TS, Queryable))]
@@ -189,6 +249,10 @@ pub struct PostReportView {
   pub counts: PostAggregates,
   #[cfg_attr(feature = "full", ts(optional))]
   pub resolver: Option<Person>,
+  /// How many posts and comments of the post creator were removed by moderators.
+  pub post_creator_removal_count: i64,
+  /// How many times the post creator was banned from the site or a community.
+  pub post_creator_ban_count: i64,
 }
 
 /// currently this is just a wrapper around post id, but should be seen as opaque from the client's
@@ -244,6 +308,8 @@ pub struct PostView {
   pub my_vote: Option<i16>,
   pub unread_comments: i64,
   pub tags: PostTags,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub poll: Option<PostPollView>,
 }
 
 #[skip_serializing_none]
@@ -259,6 +325,10 @@ pub struct PrivateMessageReportView {
   pub creator: Person,
   #[cfg_attr(feature = "full", ts(optional))]
   pub resolver: Option<Person>,
+  /// How many posts and comments of the message creator were removed by moderators.
+  pub private_message_creator_removal_count: i64,
+  /// How many times the message creator was banned from the site or a community.
+  pub private_message_creator_ban_count: i64,
 }
 
 #[skip_serializing_none]
@@ -297,6 +367,25 @@ pub struct CustomEmojiView {
   pub keywords: Vec<CustomEmojiKeyword>,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A feed view.
+pub struct FeedView {
+  pub feed: Feed,
+  pub creator: Person,
+  pub communities: Vec<Community>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A webhook view.
+pub struct WebhookView {
+  pub webhook: Webhook,
+  pub events: Vec<WebhookEventType>,
+}
+
 #[skip_serializing_none]
 #[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "full", derive(TS, Queryable))]
@@ -356,6 +445,8 @@ pub struct ReportCombinedViewInternal {
   pub item_creator_banned_from_community: bool,
   pub item_creator_is_moderator: bool,
   pub item_creator_blocked: bool,
+  pub item_creator_removal_count: i64,
+  pub item_creator_ban_count: i64,
 }
 
 #[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
@@ -384,6 +475,7 @@ pub struct PersonContentCombinedViewInternal {
   pub my_post_vote: Option<i16>,
   pub image_details: Option<ImageDetails>,
   pub post_tags: PostTags,
+  pub post_poll: Option<PostPollView>,
   // Comment-specific
   pub comment: Option<Comment>,
   pub comment_counts: Option<CommentAggregates>,
@@ -562,6 +654,31 @@ pub struct CommentReplyView {
   pub my_vote: Option<i16>,
 }
 
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS, Queryable))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A new comment in a thread which you are subscribed to.
+pub struct ThreadReplyView {
+  pub thread_reply: ThreadReply,
+  pub comment: Comment,
+  pub creator: Person,
+  pub post: Post,
+  pub community: Community,
+  pub recipient: Person,
+  pub counts: CommentAggregates,
+  pub creator_banned_from_community: bool,
+  pub banned_from_community: bool,
+  pub creator_is_moderator: bool,
+  pub creator_is_admin: bool,
+  pub subscribed: SubscribedType,
+  pub saved: bool,
+  pub creator_blocked: bool,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub my_vote: Option<i16>,
+}
+
 #[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "full", derive(TS, Queryable))]
 #[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
@@ -622,6 +739,13 @@ pub struct InboxCombinedViewInternal {
   pub post_tags: PostTags,
   // Private message
   pub private_message: Option<PrivateMessage>,
+  // Warning
+  pub mod_warn_person: Option<ModWarnPerson>,
+  // Modmail
+  pub modmail_message: Option<ModmailMessage>,
+  pub modmail_thread: Option<ModmailThread>,
+  // Thread reply
+  pub thread_reply: Option<ThreadReply>,
   // Shared
   pub post: Option<Post>,
   pub community: Option<Community>,
@@ -649,6 +773,9 @@ pub enum InboxCombinedView {
   CommentMention(PersonCommentMentionView),
   PostMention(PersonPostMentionView),
   PrivateMessage(PrivateMessageView),
+  Warning(ModWarnPersonView),
+  Modmail(ModmailMessageView),
+  ThreadReply(ThreadReplyView),
 }
 #[skip_serializing_none]
 #[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
@@ -805,6 +932,76 @@ pub struct ModTransferCommunityView {
   pub other_person: Person,
 }
 
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS, Queryable))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "full", ts(export))]
+/// When the automoderator acts on a post or comment because it matched a community rule.
+pub struct ModAutomodActionView {
+  pub mod_automod_action: ModAutomodAction,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub moderator: Option<Person>,
+  pub other_person: Person,
+  pub post: Post,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub comment: Option<Comment>,
+  pub community: Community,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS, Queryable))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "full", ts(export))]
+/// When a moderator or admin warns someone.
+pub struct ModWarnPersonView {
+  pub mod_warn_person: ModWarnPerson,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub moderator: Option<Person>,
+  pub other_person: Person,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub community: Option<Community>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS, Queryable))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A private moderator note about a person.
+pub struct ModNoteView {
+  pub mod_note: ModNote,
+  pub creator: Person,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub community: Option<Community>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS, Queryable))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A modmail thread.
+pub struct ModmailThreadView {
+  pub modmail_thread: ModmailThread,
+  pub community: Community,
+  pub creator: Person,
+  /// The number of messages which you haven't read yet.
+  pub unread_count: i64,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS, Queryable))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A modmail message.
+pub struct ModmailMessageView {
+  pub modmail_message: ModmailMessage,
+  pub modmail_thread: ModmailThread,
+  pub community: Community,
+  pub creator: Person,
+}
+
 #[skip_serializing_none]
 #[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "full", derive(TS, Queryable))]
@@ -881,6 +1078,19 @@ pub struct AdminAllowInstanceView {
   pub admin: Option<Person>,
 }
 
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS, Queryable))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "full", ts(export))]
+/// When an admin changes the limits of an instance.
+pub struct AdminLimitInstanceView {
+  pub admin_limit_instance: AdminLimitInstance,
+  pub instance: Instance,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub admin: Option<Person>,
+}
+
 /// like PaginationCursor but for the modlog_combined
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "full", derive(TS))]
@@ -910,6 +1120,9 @@ pub struct ModlogCombinedViewInternal {
   pub mod_remove_community: Option<ModRemoveCommunity>,
   pub mod_remove_post: Option<ModRemovePost>,
   pub mod_transfer_community: Option<ModTransferCommunity>,
+  pub mod_automod_action: Option<ModAutomodAction>,
+  pub mod_warn_person: Option<ModWarnPerson>,
+  pub admin_limit_instance: Option<AdminLimitInstance>,
   // Specific fields
 
   // Shared
@@ -944,6 +1157,9 @@ pub enum ModlogCombinedView {
   ModRemoveCommunity(ModRemoveCommunityView),
   ModRemovePost(ModRemovePostView),
   ModTransferCommunity(ModTransferCommunityView),
+  ModAutomodAction(ModAutomodActionView),
+  ModWarnPerson(ModWarnPersonView),
+  AdminLimitInstance(AdminLimitInstanceView),
 }
 
 /// like PaginationCursor but for the modlog_combined
@@ -968,6 +1184,7 @@ pub struct SearchCombinedViewInternal {
   pub my_post_vote: Option<i16>,
   pub image_details: Option<ImageDetails>,
   pub post_tags: PostTags,
+  pub post_poll: Option<PostPollView>,
   // // Comment-specific
   pub comment: Option<Comment>,
   pub comment_counts: Option<CommentAggregates>,
@@ -1009,3 +1226,31 @@ pub enum SearchCombinedView {
 pub struct PostTags {
   pub tags: Vec<Tag>,
 }
+
+#[skip_serializing_none]
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "full", derive(TS, FromSqlRow))]
+#[cfg_attr(feature = "full", ts(export))]
+/// The poll of a post. The vote counts are only included once you voted, or after the poll
+/// ended.
+pub struct PostPollView {
+  pub multiple_choice: bool,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub end_time: Option<DateTime<Utc>>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub voters_count: Option<i32>,
+  pub options: Vec<PostPollOptionView>,
+  /// The options which you voted for.
+  pub my_votes: Vec<PostPollOptionId>,
+}
+
+#[skip_serializing_none]
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+pub struct PostPollOptionView {
+  pub id: PostPollOptionId,
+  pub name: String,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub vote_count: Option<i32>,
+}
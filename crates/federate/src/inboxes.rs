@@ -291,6 +291,7 @@ mod tests {
       public_key: "test_key".to_string(),
       instance_id: InstanceId(1),
       content_warning: None,
+      edit_history_public: false,
     };
 
     collector
@@ -414,6 +415,7 @@ mod tests {
       public_key: "test_key".to_string(),
       instance_id: InstanceId(1),
       content_warning: None,
+      edit_history_public: false,
     };
 
     collector
@@ -531,6 +533,7 @@ mod tests {
       public_key: "test_key".to_string(),
       instance_id: InstanceId(1),
       content_warning: None,
+      edit_history_public: false,
     };
 
     collector
@@ -4,7 +4,10 @@ use lemmy_api_common::{
   context::LemmyContext,
   lemmy_utils::settings::structs::FederationWorkerConfig,
 };
-use lemmy_db_schema::{newtypes::InstanceId, source::instance::Instance};
+use lemmy_db_schema::{
+  newtypes::InstanceId,
+  source::{federation_queue_state::FederationQueueState, instance::Instance},
+};
 use lemmy_utils::error::LemmyResult;
 use stats::receive_print_stats;
 use std::{collections::HashMap, time::Duration};
@@ -108,10 +111,25 @@ impl SendManager {
     let local_domain = self.context.settings().get_hostname_without_port()?;
     let mut pool = self.context.pool();
     loop {
+      // Admins can reset or skip the queue of an instance. This is applied while the worker is
+      // stopped, as it would otherwise overwrite the changes with its own state.
+      for state in FederationQueueState::list_requested(&mut pool).await? {
+        if state.instance_id.inner() % self.opts.process_count != process_index {
+          continue;
+        }
+        if let Some(worker) = self.workers.remove(&state.instance_id) {
+          if let Err(e) = worker.cancel().await {
+            tracing::error!("error stopping worker: {e}");
+          }
+        }
+        FederationQueueState::apply_requested(&mut pool, &state).await?;
+      }
+
       let mut total_count = 0;
       let mut dead_count = 0;
       let mut disallowed_count = 0;
-      for (instance, allowed, is_dead) in
+      let mut paused_count = 0;
+      for (instance, allowed, is_dead, is_paused) in
         Instance::read_federated_with_blocked_and_dead(&mut pool).await?
       {
         if instance.domain == local_domain {
@@ -127,7 +145,10 @@ impl SendManager {
         if is_dead {
           dead_count += 1;
         }
-        let should_federate = allowed && !is_dead;
+        if is_paused {
+          paused_count += 1;
+        }
+        let should_federate = allowed && !is_dead && !is_paused;
         if should_federate {
           if self.workers.contains_key(&instance.id) {
             // worker already running
@@ -162,7 +183,7 @@ impl SendManager {
         }
       }
       let worker_count = self.workers.len();
-      tracing::info!("Federating to {worker_count}/{total_count} instances ({dead_count} dead, {disallowed_count} disallowed)");
+      tracing::info!("Federating to {worker_count}/{total_count} instances ({dead_count} dead, {disallowed_count} disallowed, {paused_count} paused)");
       tokio::select! {
         () = sleep(INSTANCES_RECHECK_DELAY) => {},
         _ = cancel.cancelled() => { return Ok(()) }
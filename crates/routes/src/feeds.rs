@@ -3,11 +3,12 @@ use anyhow::anyhow;
 use chrono::{DateTime, Utc};
 use lemmy_api_common::{
   context::LemmyContext,
-  utils::{check_private_instance, local_user_view_from_jwt},
+  utils::{check_feed_visible, check_private_instance, local_user_view_from_jwt},
 };
 use lemmy_db_schema::{
-  source::{community::Community, person::Person},
-  traits::ApubActor,
+  newtypes::FeedId,
+  source::{community::Community, feed::Feed, person::Person},
+  traits::{ApubActor, Crud},
   CommunityVisibility,
   ListingType,
   PostSortType,
@@ -19,7 +20,7 @@ use lemmy_db_views::{
 };
 use lemmy_utils::{
   cache_header::cache_1hour,
-  error::{LemmyError, LemmyErrorType, LemmyResult},
+  error::{LemmyError, LemmyErrorExt, LemmyErrorType, LemmyResult},
   utils::markdown::markdown_to_html,
 };
 use rss::{
@@ -63,6 +64,7 @@ enum RequestType {
   User,
   Front,
   Inbox,
+  Feed,
 }
 
 pub fn config(cfg: &mut web::ServiceConfig) {
@@ -178,6 +180,7 @@ async fn get_feed(
     "c" => RequestType::Community,
     "front" => RequestType::Front,
     "inbox" => RequestType::Inbox,
+    "feed" => RequestType::Feed,
     _ => return Err(ErrorBadRequest(LemmyError::from(anyhow!("wrong_type")))),
   };
 
@@ -213,6 +216,16 @@ async fn get_feed(
       .await
     }
     RequestType::Inbox => get_feed_inbox(&context, &param).await,
+    RequestType::Feed => {
+      get_feed_custom(
+        &context,
+        &info.sort_type()?,
+        &info.get_limit(),
+        &info.get_page(),
+        &param,
+      )
+      .await
+    }
   }
   .map_err(ErrorBadRequest)?;
 
@@ -306,6 +319,48 @@ async fn get_feed_community(
   Ok(channel)
 }
 
+/// Posts of a custom feed, which is identified by its id. Only works for public feeds.
+async fn get_feed_custom(
+  context: &LemmyContext,
+  sort_type: &PostSortType,
+  limit: &i64,
+  page: &i64,
+  feed_id: &str,
+) -> LemmyResult<Channel> {
+  let site_view = SiteView::read_local(&mut context.pool()).await?;
+  let feed_id = FeedId(feed_id.parse().with_lemmy_type(LemmyErrorType::NotFound)?);
+  let feed = Feed::read(&mut context.pool(), feed_id).await?;
+  check_feed_visible(&feed, &None)?;
+
+  check_private_instance(&None, &site_view.local_site)?;
+
+  let posts = PostQuery {
+    feed_id: (Some(feed.id)),
+    sort: (Some(*sort_type)),
+    limit: (Some(*limit)),
+    page: (Some(*page)),
+    ..Default::default()
+  }
+  .list(&site_view.site, &mut context.pool())
+  .await?;
+
+  let protocol_and_hostname = context.settings().get_protocol_and_hostname();
+  let items = create_post_items(posts, &protocol_and_hostname)?;
+  let mut channel = Channel {
+    namespaces: RSS_NAMESPACE.clone(),
+    title: format!("{} - {}", site_view.site.name, feed.name),
+    link: protocol_and_hostname,
+    items,
+    ..Default::default()
+  };
+
+  if let Some(feed_desc) = feed.description {
+    channel.set_description(markdown_to_html(&feed_desc));
+  }
+
+  Ok(channel)
+}
+
 async fn get_feed_front(
   context: &LemmyContext,
   sort_type: &PostSortType,
@@ -426,6 +481,41 @@ fn create_reply_and_mention_items(
           protocol_and_hostname,
         )
       }
+      InboxCombinedView::Warning(v) => {
+        let inbox_url = format!("{}/inbox", protocol_and_hostname);
+        let moderator_name = v
+          .moderator
+          .as_ref()
+          .map(|m| m.name.as_str())
+          .unwrap_or_default();
+        build_item(
+          moderator_name,
+          &v.mod_warn_person.published,
+          &inbox_url,
+          &v.mod_warn_person.reason,
+          protocol_and_hostname,
+        )
+      }
+      InboxCombinedView::Modmail(v) => {
+        let inbox_url = format!("{}/inbox", protocol_and_hostname);
+        build_item(
+          &v.creator.name,
+          &v.modmail_message.published,
+          &inbox_url,
+          &v.modmail_message.content,
+          protocol_and_hostname,
+        )
+      }
+      InboxCombinedView::ThreadReply(v) => {
+        let reply_url = format!("{}/comment/{}", protocol_and_hostname, v.comment.id);
+        build_item(
+          &v.creator.name,
+          &v.comment.published,
+          &reply_url,
+          &v.comment.content,
+          protocol_and_hostname,
+        )
+      }
     })
     .collect::<LemmyResult<Vec<Item>>>()?;
 
@@ -1,7 +1,10 @@
 use actix_web::{
   body::MessageBody,
   dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform},
-  http::header::{HeaderValue, CACHE_CONTROL},
+  http::{
+    header::{HeaderValue, CACHE_CONTROL},
+    Method,
+  },
   Error,
   HttpMessage,
 };
@@ -9,8 +12,16 @@ use core::future::Ready;
 use futures_util::future::LocalBoxFuture;
 use lemmy_api_common::{
   context::LemmyContext,
-  utils::{local_user_view_from_jwt, read_auth_token},
+  utils::{
+    is_api_token,
+    local_user_view_from_api_token,
+    local_user_view_from_jwt,
+    read_auth_token,
+    ApiTokenSession,
+  },
 };
+use lemmy_db_schema::ApiTokenScope;
+use lemmy_utils::error::{LemmyError, LemmyErrorType};
 use std::{future::ready, rc::Rc};
 
 #[derive(Clone)]
@@ -67,7 +78,17 @@ where
     Box::pin(async move {
       let jwt = read_auth_token(req.request())?;
 
-      if let Some(jwt) = &jwt {
+      if let Some(token) = jwt.as_deref().filter(|t| is_api_token(t)) {
+        // Unlike login tokens, invalid api tokens are rejected. Otherwise a script with an expired
+        // token would silently act as logged out.
+        let (local_user_view, scopes) = local_user_view_from_api_token(token, &context).await?;
+        let scope = required_scope(req.method(), req.path());
+        if !scope.is_some_and(|s| scopes.contains(&s)) {
+          Err(LemmyError::from(LemmyErrorType::MissingApiTokenScope))?
+        }
+        req.extensions_mut().insert(local_user_view);
+        req.extensions_mut().insert(ApiTokenSession);
+      } else if let Some(jwt) = &jwt {
         // Ignore any invalid auth so the site can still be used
         // This means it is be impossible to get any error message for invalid jwt. Need
         // to use `/api/v4/account/validate_auth` for that.
@@ -97,10 +118,115 @@ where
   }
 }
 
+/// Returns the scope which an api token needs for the given request, or `None` if api tokens
+/// can't be used for it at all. This is the case for account management, private messages and
+/// other actions which don't fit into any scope.
+fn required_scope(method: &Method, path: &str) -> Option<ApiTokenScope> {
+  // The router decodes the path, so an encoded path could reach a different endpoint than the one
+  // which is checked here
+  if path.contains('%') {
+    return None;
+  }
+  let path = path
+    .strip_prefix("/api/v4")
+    .or_else(|| path.strip_prefix("/api/v3"))
+    .unwrap_or(path);
+  let is_read = method == Method::GET || method == Method::HEAD;
+  let in_scope = |prefix: &str| {
+    path == prefix
+      || path
+        .strip_prefix(prefix)
+        .is_some_and(|rest| rest.starts_with('/'))
+  };
+
+  if in_scope("/admin")
+    || (!is_read
+      && ["/site", "/custom_emoji", "/oauth_provider"]
+        .into_iter()
+        .any(in_scope))
+  {
+    Some(ApiTokenScope::Admin)
+  } else if [
+    "/report",
+    "/webhook",
+    "/community/pending_follows",
+    "/community/automod",
+    "/person/note",
+    "/person/warn",
+  ]
+  .into_iter()
+  .any(in_scope)
+    || (!is_read && (MOD_PATHS.contains(&path) || in_scope("/community/tag")))
+    || (method == Method::PUT && path == "/community")
+  {
+    Some(ApiTokenScope::Moderate)
+  } else if is_read {
+    // Only public content can be read, private data of the account is never available to api
+    // tokens
+    let is_image = path
+      .strip_prefix("/image/")
+      .is_some_and(|rest| !rest.contains('/'));
+    (READ_PATHS.contains(&path) || is_image).then_some(ApiTokenScope::Read)
+  } else if in_scope("/post") || in_scope("/image") {
+    Some(ApiTokenScope::WritePost)
+  } else if in_scope("/comment") {
+    Some(ApiTokenScope::WriteComment)
+  } else {
+    None
+  }
+}
+
+/// Endpoints with public content, which can be read with api tokens. Paths of api v3 and v4 are
+/// mixed, images are handled separately.
+const READ_PATHS: [&str; 22] = [
+  "/site",
+  "/modlog",
+  "/search",
+  "/resolve_object",
+  "/federated_instances",
+  "/community",
+  "/community/list",
+  "/community/random",
+  "/community/tag/list",
+  "/post",
+  "/post/list",
+  "/post/revision/list",
+  "/post/site_metadata",
+  "/comment",
+  "/comment/list",
+  "/comment/list/slim",
+  "/comment/revision/list",
+  "/person",
+  "/person/content",
+  "/user",
+  "/custom_emoji/list",
+  "/image_proxy",
+];
+
+/// Endpoints which require moderator permissions.
+const MOD_PATHS: [&str; 15] = [
+  "/post/remove",
+  "/post/lock",
+  "/post/feature",
+  "/post/report/resolve",
+  "/comment/remove",
+  "/comment/distinguish",
+  "/comment/report/resolve",
+  "/private_message/report/resolve",
+  "/community/delete",
+  "/community/remove",
+  "/community/transfer",
+  "/community/ban_user",
+  "/community/mod",
+  "/community/icon",
+  "/community/banner",
+];
+
 #[cfg(test)]
 mod tests {
 
-  use actix_web::test::TestRequest;
+  use super::required_scope;
+  use actix_web::{http::Method, test::TestRequest};
   use lemmy_api_common::{claims::Claims, context::LemmyContext};
   use lemmy_db_schema::{
     source::{
@@ -109,6 +235,7 @@ mod tests {
       person::{Person, PersonInsertForm},
     },
     traits::Crud,
+    ApiTokenScope,
   };
   use lemmy_utils::error::LemmyResult;
   use pretty_assertions::assert_eq;
@@ -142,4 +269,141 @@ mod tests {
 
     Ok(())
   }
+  #[test]
+  fn test_required_scope() {
+    use ApiTokenScope::*;
+    assert_eq!(
+      Some(Read),
+      required_scope(&Method::GET, "/api/v4/post/list")
+    );
+    assert_eq!(
+      Some(Read),
+      required_scope(&Method::GET, "/api/v3/post/list")
+    );
+    assert_eq!(
+      Some(WritePost),
+      required_scope(&Method::POST, "/api/v4/post")
+    );
+    assert_eq!(
+      Some(WritePost),
+      required_scope(&Method::POST, "/api/v4/post/like")
+    );
+    assert_eq!(
+      Some(WritePost),
+      required_scope(&Method::POST, "/api/v4/image")
+    );
+    assert_eq!(
+      Some(WriteComment),
+      required_scope(&Method::PUT, "/api/v4/comment")
+    );
+    assert_eq!(
+      Some(Moderate),
+      required_scope(&Method::POST, "/api/v4/post/remove")
+    );
+    assert_eq!(
+      Some(Moderate),
+      required_scope(&Method::GET, "/api/v4/report/list")
+    );
+    assert_eq!(
+      Some(Moderate),
+      required_scope(&Method::PUT, "/api/v4/community")
+    );
+    assert_eq!(
+      Some(Read),
+      required_scope(&Method::GET, "/api/v4/community/tag/list")
+    );
+    assert_eq!(
+      Some(Moderate),
+      required_scope(&Method::GET, "/api/v4/community/automod/list")
+    );
+    assert_eq!(
+      Some(Moderate),
+      required_scope(&Method::GET, "/api/v4/person/note/list")
+    );
+    assert_eq!(
+      Some(Admin),
+      required_scope(&Method::GET, "/api/v4/admin/instance/list")
+    );
+    assert_eq!(Some(Admin), required_scope(&Method::PUT, "/api/v4/site"));
+    assert_eq!(Some(Read), required_scope(&Method::GET, "/api/v4/site"));
+    // Prefixes only match whole path segments
+    assert_eq!(None, required_scope(&Method::POST, "/api/v4/postfix"));
+    // Account management, private messages and modmail are not possible with api tokens
+    assert_eq!(
+      None,
+      required_scope(&Method::POST, "/api/v4/account/api_token")
+    );
+    assert_eq!(
+      None,
+      required_scope(&Method::PUT, "/api/v4/account/settings/save")
+    );
+    assert_eq!(
+      None,
+      required_scope(&Method::POST, "/api/v4/private_message")
+    );
+    assert_eq!(None, required_scope(&Method::POST, "/api/v4/modmail"));
+    // This includes reading them with a read token
+    assert_eq!(
+      None,
+      required_scope(&Method::GET, "/api/v4/account/settings/export")
+    );
+    assert_eq!(
+      None,
+      required_scope(&Method::GET, "/api/v4/account/list_logins")
+    );
+    assert_eq!(
+      None,
+      required_scope(&Method::GET, "/api/v4/account/unsubscribe")
+    );
+    assert_eq!(
+      None,
+      required_scope(&Method::GET, "/api/v4/private_message/list")
+    );
+    assert_eq!(None, required_scope(&Method::GET, "/api/v4/modmail/list"));
+    assert_eq!(
+      None,
+      required_scope(&Method::GET, "/api/v3/user/export_settings")
+    );
+    assert_eq!(Some(Read), required_scope(&Method::GET, "/api/v3/user"));
+    assert_eq!(
+      Some(Read),
+      required_scope(&Method::GET, "/api/v4/image/abc.png")
+    );
+    // Only public content can be read, other endpoints are not allowed
+    assert_eq!(
+      None,
+      required_scope(&Method::GET, "/api/v3/user/unread_count")
+    );
+    assert_eq!(
+      None,
+      required_scope(&Method::GET, "/api/v3/user/report_count")
+    );
+    assert_eq!(
+      None,
+      required_scope(&Method::GET, "/api/v3/private_message/list")
+    );
+    assert_eq!(
+      None,
+      required_scope(&Method::GET, "/pictrs/image/delete/token/abc.png")
+    );
+    // Encoded paths are decoded by the router, so they are rejected
+    assert_eq!(
+      None,
+      required_scope(&Method::GET, "/api/v4/%61ccount/list_logins")
+    );
+    assert_eq!(
+      None,
+      required_scope(&Method::GET, "/api/v4/%61ccount/settings/export")
+    );
+    assert_eq!(
+      None,
+      required_scope(&Method::GET, "/api/v4/%70rivate_message/list")
+    );
+    assert_eq!(None, required_scope(&Method::POST, "/api/v4/post/%72emove"));
+    // Resolving private message reports is still a mod action
+    assert_eq!(
+      Some(Moderate),
+      required_scope(&Method::PUT, "/api/v4/private_message/report/resolve")
+    );
+  }
 }
@@ -1,7 +1,6 @@
 use crate::nodeinfo::{NodeInfo, NodeInfoWellKnown};
 use activitypub_federation::config::Data;
-use chrono::{DateTime, TimeZone, Utc};
-use clokwerk::{AsyncScheduler, TimeUnits as CTimeUnits};
+use chrono::{DateTime, TimeDelta, TimeZone, Utc};
 use diesel::{
   dsl::{exists, not, IntervalDsl},
   query_builder::AsQuery,
@@ -16,7 +15,10 @@ use diesel::{
 use diesel_async::{AsyncPgConnection, RunQueryDsl};
 use lemmy_api_common::{
   context::LemmyContext,
+  run_automod::run_post_automod,
   send_activity::{ActivityChannel, SendActivityData},
+  send_email_digest::send_email_digests,
+  send_webhook::retry_webhook_deliveries,
   utils::send_webmention,
 };
 use lemmy_db_schema::{
@@ -36,7 +38,11 @@ use lemmy_db_schema::{
     community::Community,
     instance::{Instance, InstanceForm},
     local_user::LocalUser,
+    outgoing_email::OutgoingEmail,
     post::{Post, PostUpdateForm},
+    post_poll::PostPoll,
+    scheduled_job::{ScheduledJob, ScheduledJobForm, ScheduledJobRun},
+    webhook::WebhookDelivery,
   },
   traits::Crud,
   utils::{
@@ -51,114 +57,124 @@ use lemmy_db_schema::{
 };
 use lemmy_utils::error::{LemmyErrorType, LemmyResult};
 use reqwest_middleware::ClientWithMiddleware;
-use std::time::Duration;
+use std::{str::FromStr, time::Duration};
+use strum::{Display, EnumIter, EnumString, IntoEnumIterator};
 use tracing::{info, warn};
+use uuid::Uuid;
+
+/// How long a process may run a job before other processes assume that it died, and take over.
+const JOB_LEASE: TimeDelta = TimeDelta::hours(1);
+
+/// How often to check for due jobs
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Background tasks which run periodically. Their schedule is stored in the `scheduled_job` table,
+/// so that multiple Lemmy processes can share the work, and jobs don't wait for a whole interval
+/// after a restart.
+#[derive(Clone, Copy, Debug, Display, EnumIter, EnumString)]
+#[strum(serialize_all = "snake_case")]
+enum Job {
+  ActiveCounts,
+  UpdateBannedWhenExpired,
+  DeleteInstanceBlockWhenExpired,
+  UpdateHotRanks,
+  DeleteExpiredCaptchaAnswers,
+  PublishScheduledPosts,
+  ClearOldActivities,
+  OverwriteDeletedPostsAndComments,
+  DeleteOldDeniedUsers,
+  UpdateInstanceSoftware,
+  RetryWebhookDeliveries,
+  SendEmailDigests,
+  FederatePollResults,
+}
 
-/// Schedules various cleanup tasks for lemmy in a background thread
-pub async fn setup(context: Data<LemmyContext>) -> LemmyResult<()> {
-  // Setup the connections
-  let mut scheduler = AsyncScheduler::new();
-  startup_jobs(&mut context.pool())
-    .await
-    .inspect_err(|e| warn!("Failed to run startup tasks: {e}"))
-    .ok();
-
-  let context_1 = context.clone();
-  // Update active counts expired bans and unpublished posts every hour
-  scheduler.every(CTimeUnits::hour(1)).run(move || {
-    let context = context_1.clone();
-
-    async move {
-      active_counts(&mut context.pool())
-        .await
-        .inspect_err(|e| warn!("Failed to update active counts: {e}"))
-        .ok();
-      update_banned_when_expired(&mut context.pool())
-        .await
-        .inspect_err(|e| warn!("Failed to update expired bans: {e}"))
-        .ok();
-      delete_instance_block_when_expired(&mut context.pool())
-        .await
-        .inspect_err(|e| warn!("Failed to delete expired instance bans: {e}"))
-        .ok();
-    }
-  });
-
-  let context_1 = context.reset_request_count();
-  // Every 10 minutes update hot ranks, delete expired captchas and publish scheduled posts
-  scheduler.every(CTimeUnits::minutes(10)).run(move || {
-    let context = context_1.reset_request_count();
-
-    async move {
-      update_hot_ranks(&mut context.pool())
-        .await
-        .inspect_err(|e| warn!("Failed to update hot ranks: {e}"))
-        .ok();
-      delete_expired_captcha_answers(&mut context.pool())
-        .await
-        .inspect_err(|e| warn!("Failed to delete expired captcha answers: {e}"))
-        .ok();
-      publish_scheduled_posts(&context)
-        .await
-        .inspect_err(|e| warn!("Failed to publish scheduled posts: {e}"))
-        .ok();
+impl Job {
+  /// Time between successful runs
+  fn interval(self) -> TimeDelta {
+    use Job::*;
+    match self {
+      ActiveCounts | UpdateBannedWhenExpired | DeleteInstanceBlockWhenExpired => {
+        TimeDelta::hours(1)
+      }
+      UpdateHotRanks | DeleteExpiredCaptchaAnswers | PublishScheduledPosts => {
+        TimeDelta::minutes(10)
+      }
+      ClearOldActivities => TimeDelta::weeks(1),
+      RetryWebhookDeliveries | FederatePollResults => TimeDelta::minutes(1),
+      SendEmailDigests => TimeDelta::minutes(5),
+      OverwriteDeletedPostsAndComments | DeleteOldDeniedUsers | UpdateInstanceSoftware => {
+        TimeDelta::days(1)
+      }
     }
-  });
-
-  let context_1 = context.clone();
-  // Clear old activities every week
-  scheduler.every(CTimeUnits::weeks(1)).run(move || {
-    let context = context_1.clone();
-
-    async move {
-      clear_old_activities(&mut context.pool())
-        .await
-        .inspect_err(|e| warn!("Failed to clear old activities: {e}"))
-        .ok();
-    }
-  });
-
-  let context_1 = context.clone();
-  // Daily tasks:
-  // - Overwrite deleted & removed posts and comments every day
-  // - Delete old denied users
-  // - Update instance software
-  scheduler.every(CTimeUnits::days(1)).run(move || {
-    let context = context_1.clone();
-
-    async move {
-      overwrite_deleted_posts_and_comments(&mut context.pool())
-        .await
-        .inspect_err(|e| warn!("Failed to overwrite deleted posts/comments: {e}"))
-        .ok();
-      delete_old_denied_users(&mut context.pool())
-        .await
-        .inspect_err(|e| warn!("Failed to delete old denied users: {e}"))
-        .ok();
-      update_instance_software(&mut context.pool(), context.client())
-        .await
-        .inspect_err(|e| warn!("Failed to update instance software: {e}"))
-        .ok();
+  }
+
+  async fn run(self, context: &Data<LemmyContext>) -> LemmyResult<()> {
+    use Job::*;
+    let pool = &mut context.pool();
+    match self {
+      ActiveCounts => active_counts(pool).await,
+      UpdateBannedWhenExpired => update_banned_when_expired(pool).await,
+      DeleteInstanceBlockWhenExpired => delete_instance_block_when_expired(pool).await,
+      UpdateHotRanks => update_hot_ranks(pool).await,
+      DeleteExpiredCaptchaAnswers => delete_expired_captcha_answers(pool).await,
+      PublishScheduledPosts => publish_scheduled_posts(context).await,
+      ClearOldActivities => clear_old_activities(pool).await,
+      OverwriteDeletedPostsAndComments => overwrite_deleted_posts_and_comments(pool).await,
+      DeleteOldDeniedUsers => delete_old_denied_users(pool).await,
+      UpdateInstanceSoftware => update_instance_software(pool, context.client()).await,
+      RetryWebhookDeliveries => retry_webhook_deliveries(context).await,
+      SendEmailDigests => send_email_digests(context).await,
+      FederatePollResults => federate_poll_results(context).await,
     }
-  });
+  }
+}
 
-  // Manually run the scheduler in an event loop
+/// Runs the scheduled background jobs for lemmy, forever
+pub async fn setup(context: Data<LemmyContext>) -> LemmyResult<()> {
+  let forms = Job::iter()
+    .map(|job| ScheduledJobForm {
+      name: job.to_string(),
+      interval_secs: i32::try_from(job.interval().num_seconds()).unwrap_or(i32::MAX),
+    })
+    .collect::<Vec<_>>();
+  ScheduledJob::register(&mut context.pool(), &forms).await?;
+  let names = forms.into_iter().map(|f| f.name).collect::<Vec<_>>();
+
+  // Identifies this process in the job table
+  let worker = format!("{}-{}", std::process::id(), Uuid::new_v4());
   loop {
-    scheduler.run_pending().await;
-    tokio::time::sleep(Duration::from_millis(1000)).await;
+    match ScheduledJob::acquire(&mut context.pool(), &names, &worker, JOB_LEASE).await {
+      Ok(Some(job)) => run_job(job, &context.reset_request_count()).await,
+      Ok(None) => tokio::time::sleep(POLL_INTERVAL).await,
+      Err(e) => {
+        warn!("Failed to acquire scheduled job: {e}");
+        tokio::time::sleep(POLL_INTERVAL).await
+      }
+    }
   }
 }
 
-/// Run these on server startup
-async fn startup_jobs(pool: &mut DbPool<'_>) -> LemmyResult<()> {
-  active_counts(pool).await?;
-  update_hot_ranks(pool).await?;
-  update_banned_when_expired(pool).await?;
-  delete_instance_block_when_expired(pool).await?;
-  clear_old_activities(pool).await?;
-  overwrite_deleted_posts_and_comments(pool).await?;
-  delete_old_denied_users(pool).await?;
-  Ok(())
+/// Runs a single job which was acquired by this process, and stores the result.
+async fn run_job(job: ScheduledJob, context: &Data<LemmyContext>) {
+  let started = Utc::now();
+  let result = match Job::from_str(&job.name) {
+    Ok(j) => j.run(context).await,
+    Err(e) => Err(LemmyErrorType::Unknown(format!("Unknown job {}: {e}", job.name)).into()),
+  };
+  let error = result
+    .inspect_err(|e| warn!("Failed to run scheduled job {}: {e}", job.name))
+    .err()
+    .map(|e| e.to_string());
+  info!(
+    "Finished scheduled job {} in {}ms",
+    job.name,
+    (Utc::now() - started).num_milliseconds()
+  );
+  ScheduledJob::finish(&mut context.pool(), &job, started, error)
+    .await
+    .inspect_err(|e| warn!("Failed to store result of scheduled job {}: {e}", job.name))
+    .ok();
 }
 
 /// Update the hot_rank columns for the aggregates tables
@@ -298,9 +314,13 @@ async fn delete_expired_captcha_answers(pool: &mut DbPool<'_>) -> LemmyResult<()
   Ok(())
 }
 
-/// Clear old activities (this table gets very large)
+/// Clear old activities (this table gets very large), the history of scheduled job runs and sent
+/// emails
 async fn clear_old_activities(pool: &mut DbPool<'_>) -> LemmyResult<()> {
   info!("Clearing old activities...");
+  ScheduledJobRun::delete_old(pool).await?;
+  WebhookDelivery::delete_old(pool).await?;
+  OutgoingEmail::delete_old(pool).await?;
   let mut conn = get_conn(pool).await?;
 
   diesel::delete(
@@ -455,11 +475,23 @@ async fn publish_scheduled_posts(context: &Data<LemmyContext>) -> LemmyResult<()
     // send out post via federation and webmention
     let send_activity = SendActivityData::CreatePost(post.clone());
     ActivityChannel::submit_activity(send_activity, context)?;
+    run_post_automod(&post, true, context).await;
     send_webmention(post, community);
   }
   Ok(())
 }
 
+/// Sends out the final vote counts of local polls which ended. While a poll with end time is open,
+/// the counts are not federated.
+async fn federate_poll_results(context: &Data<LemmyContext>) -> LemmyResult<()> {
+  let post_ids = PostPoll::mark_ended_as_federated(&mut context.pool()).await?;
+  for post_id in post_ids {
+    let post = Post::read(&mut context.pool(), post_id).await?;
+    ActivityChannel::submit_activity(SendActivityData::UpdatePost(post), context)?;
+  }
+  Ok(())
+}
+
 /// Updates the instance software and version.
 ///
 /// Does so using the /.well-known/nodeinfo protocol described here:
@@ -587,10 +619,9 @@ mod tests {
   async fn test_scheduled_tasks_no_errors() -> LemmyResult<()> {
     let context = LemmyContext::init_test_context().await;
 
-    startup_jobs(&mut context.pool()).await?;
-    update_instance_software(&mut context.pool(), context.client()).await?;
-    delete_expired_captcha_answers(&mut context.pool()).await?;
-    publish_scheduled_posts(&context).await?;
+    for job in Job::iter() {
+      job.run(&context).await?;
+    }
     Ok(())
   }
 }
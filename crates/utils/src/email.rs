@@ -4,13 +4,17 @@ use crate::{
 };
 use html2text;
 use lettre::{
-  message::{Mailbox, MultiPart},
+  message::{
+    header::{Header, HeaderName, HeaderValue},
+    Mailbox,
+    MultiPart,
+  },
   transport::smtp::{authentication::Credentials, extension::ClientId},
   Address,
   AsyncTransport,
   Message,
 };
-use std::str::FromStr;
+use std::{error::Error, str::FromStr};
 use uuid::Uuid;
 
 pub mod translations {
@@ -19,12 +23,81 @@ pub mod translations {
 
 type AsyncSmtpTransport = lettre::AsyncSmtpTransport<lettre::Tokio1Executor>;
 
+/// The `List-Unsubscribe` header from RFC 2369.
+#[derive(Clone)]
+struct ListUnsubscribe(String);
+
+impl Header for ListUnsubscribe {
+  fn name() -> HeaderName {
+    HeaderName::new_from_ascii_str("List-Unsubscribe")
+  }
+
+  fn parse(s: &str) -> Result<Self, Box<dyn Error + Send + Sync>> {
+    Ok(Self(s.to_string()))
+  }
+
+  fn display(&self) -> HeaderValue {
+    HeaderValue::new(Self::name(), format!("<{}>", self.0))
+  }
+}
+
+/// The `List-Unsubscribe-Post` header from RFC 8058, which tells mail clients that they can
+/// unsubscribe with a single POST request to the `List-Unsubscribe` url.
+#[derive(Clone)]
+struct ListUnsubscribePost;
+
+impl Header for ListUnsubscribePost {
+  fn name() -> HeaderName {
+    HeaderName::new_from_ascii_str("List-Unsubscribe-Post")
+  }
+
+  fn parse(_s: &str) -> Result<Self, Box<dyn Error + Send + Sync>> {
+    Ok(Self)
+  }
+
+  fn display(&self) -> HeaderValue {
+    HeaderValue::new(Self::name(), "List-Unsubscribe=One-Click".to_string())
+  }
+}
+
 pub async fn send_email(
   subject: &str,
   to_email: &str,
   to_username: &str,
   html: &str,
   settings: &Settings,
+) -> LemmyResult<()> {
+  send_email_inner(subject, to_email, to_username, html, None, settings).await
+}
+
+/// Sends an email which the recipient can unsubscribe from with a single click in their mail
+/// client, by sending a POST request to `unsubscribe_link`.
+pub async fn send_notification_email(
+  subject: &str,
+  to_email: &str,
+  to_username: &str,
+  html: &str,
+  unsubscribe_link: &str,
+  settings: &Settings,
+) -> LemmyResult<()> {
+  send_email_inner(
+    subject,
+    to_email,
+    to_username,
+    html,
+    Some(unsubscribe_link),
+    settings,
+  )
+  .await
+}
+
+async fn send_email_inner(
+  subject: &str,
+  to_email: &str,
+  to_username: &str,
+  html: &str,
+  unsubscribe_link: Option<&str>,
+  settings: &Settings,
 ) -> LemmyResult<()> {
   let email_config = settings.email.clone().ok_or(LemmyErrorType::NoEmailSetup)?;
   let domain = settings.hostname.clone();
@@ -47,7 +120,7 @@ pub async fn send_email(
 
   let smtp_from_address = &email_config.smtp_from_address;
 
-  let email = Message::builder()
+  let mut message_builder = Message::builder()
     .from(
       smtp_from_address
         .parse()
@@ -61,7 +134,13 @@ pub async fn send_email(
         .with_lemmy_type(LemmyErrorType::InvalidEmailAddress(to_email.into()))?,
     ))
     .message_id(Some(format!("<{}@{}>", Uuid::new_v4(), settings.hostname)))
-    .subject(subject)
+    .subject(subject);
+  if let Some(unsubscribe_link) = unsubscribe_link {
+    message_builder = message_builder
+      .header(ListUnsubscribe(unsubscribe_link.to_string()))
+      .header(ListUnsubscribePost);
+  }
+  let email = message_builder
     .multipart(MultiPart::alternative_plain_html(
       plain_text,
       html.to_string(),
@@ -93,3 +172,32 @@ pub async fn send_email(
 
   Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use pretty_assertions::assert_eq;
+
+  #[test]
+  fn test_unsubscribe_headers() -> LemmyResult<()> {
+    let message = Message::builder()
+      .from("lemmy <noreply@example.com>".parse()?)
+      .to("user@example.com".parse()?)
+      .subject("test")
+      .header(ListUnsubscribe(
+        "https://example.com/api/v4/account/unsubscribe?token=abc".to_string(),
+      ))
+      .header(ListUnsubscribePost)
+      .body(String::new())?;
+    let headers = message.headers();
+    assert_eq!(
+      Some("<https://example.com/api/v4/account/unsubscribe?token=abc>"),
+      headers.get_raw("List-Unsubscribe")
+    );
+    assert_eq!(
+      Some("List-Unsubscribe=One-Click"),
+      headers.get_raw("List-Unsubscribe-Post")
+    );
+    Ok(())
+  }
+}
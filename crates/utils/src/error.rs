@@ -153,6 +153,36 @@ pub enum LemmyErrorType {
   PostScheduleTimeMustBeInFuture,
   TooManyScheduledPosts,
   CannotCombineFederationBlocklistAndAllowlist,
+  InvalidTagName,
+  TagNotInCommunity,
+  InvalidWebhookSecret,
+  InvalidWebhookEvent,
+  InvalidApiTokenName,
+  MissingApiTokenScope,
+  InvalidFeedName,
+  FeedAlreadyExists,
+  NoFeedEditAllowed,
+  InvalidKeywordFilter,
+  KeywordFilterAlreadyExists,
+  InvalidAutomodRule,
+  AutomodRequiresLocalCommunity,
+  CouldntUpdateWarning,
+  CouldntCreateModmail,
+  InvalidPoll,
+  PollEnded,
+  AlreadyVotedInPoll,
+  InvalidDraft,
+  InvalidDuplicateLinkWindow,
+  InvalidPushSubscription,
+  InvalidUnsubscribeToken,
+  TooManyAliases,
+  CantMoveToSelf,
+  /// The target account doesn't list the moving account in its aliases (`alsoKnownAs`).
+  MoveTargetMissingAlias,
+  /// The link was already posted to the community recently.
+  DuplicateLink {
+    post_id: i32,
+  },
   FederationError {
     #[cfg_attr(feature = "full", ts(optional))]
     error: Option<FederationError>,
@@ -187,6 +217,7 @@ pub enum FederationError {
   ObjectIsNotPublic,
   ObjectIsNotPrivate,
   Unreachable,
+  InstanceIsLimited,
 }
 
 cfg_if! {
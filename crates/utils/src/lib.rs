@@ -9,6 +9,7 @@ cfg_if! {
     pub mod response;
     pub mod settings;
     pub mod utils;
+    pub mod web_push;
   }
 }
 
@@ -217,4 +217,14 @@ pub struct FederationWorkerConfig {
   /// per second) and if a receiving instance is not keeping up.
   #[default(1)]
   pub concurrent_sends_per_instance: i8,
+  /// When a remote community is followed by a local user for the first time, fetch its older
+  /// posts and comments in the background by walking through the community outbox.
+  #[default(false)]
+  pub backfill_on_first_follow: bool,
+  /// Maximum number of outbox pages which are fetched for each backfilled community.
+  #[default(50)]
+  pub backfill_max_pages: i32,
+  /// Minimum number of seconds between two backfill requests to the same remote instance.
+  #[default(10)]
+  pub backfill_interval_secs: i32,
 }
@@ -25,6 +25,15 @@ const ALT_TEXT_MAX_LENGTH: usize = 1500;
 const SITE_NAME_MAX_LENGTH: usize = 20;
 const SITE_NAME_MIN_LENGTH: usize = 1;
 const SITE_DESCRIPTION_MAX_LENGTH: usize = 150;
+const TAG_NAME_MAX_LENGTH: usize = 50;
+const WEBHOOK_SECRET_MIN_LENGTH: usize = 16;
+const WEBHOOK_SECRET_MAX_LENGTH: usize = 200;
+const API_TOKEN_NAME_MAX_LENGTH: usize = 50;
+const FEED_NAME_MAX_LENGTH: usize = 50;
+const KEYWORD_FILTER_MAX_LENGTH: usize = 100;
+const AUTOMOD_RULE_NAME_MAX_LENGTH: usize = 50;
+const POLL_OPTION_MAX_LENGTH: usize = 200;
+const POLL_MAX_OPTIONS: usize = 20;
 //Invisible unicode characters, taken from https://invisible-characters.com/
 const FORBIDDEN_DISPLAY_CHARS: [char; 53] = [
   '\u{0009}',
@@ -153,6 +162,104 @@ pub fn is_valid_post_title(title: &str) -> LemmyResult<()> {
   }
 }
 
+/// Like [is_valid_post_title], but drafts may be saved before the title is long enough.
+pub fn is_valid_draft_title(title: &str) -> LemmyResult<()> {
+  let length = title.trim().chars().count();
+  if length > 200 || has_newline(title) {
+    Err(LemmyErrorType::InvalidPostTitle.into())
+  } else {
+    Ok(())
+  }
+}
+
+/// Polls need between 2 and 20 distinct options, each on a single line.
+pub fn is_valid_poll_options(options: &[String]) -> LemmyResult<()> {
+  let valid_count = (2..=POLL_MAX_OPTIONS).contains(&options.len());
+  let valid_options = options.iter().all(|o| {
+    let length = o.trim().chars().count();
+    (1..=POLL_OPTION_MAX_LENGTH).contains(&length) && !has_newline(o)
+  });
+  let unique = options.iter().map(|o| o.trim()).unique().count() == options.len();
+  if valid_count && valid_options && unique {
+    Ok(())
+  } else {
+    Err(LemmyErrorType::InvalidPoll.into())
+  }
+}
+
+pub fn is_valid_tag_name(name: &str) -> LemmyResult<()> {
+  let length = name.trim().chars().count();
+  let check = (1..=TAG_NAME_MAX_LENGTH).contains(&length) && !has_newline(name);
+  if !check {
+    Err(LemmyErrorType::InvalidTagName.into())
+  } else {
+    Ok(())
+  }
+}
+
+/// Webhook secrets are used as HMAC keys, so they shouldn't be easy to guess.
+pub fn is_valid_webhook_secret(secret: &str) -> LemmyResult<()> {
+  let length = secret.chars().count();
+  if !(WEBHOOK_SECRET_MIN_LENGTH..=WEBHOOK_SECRET_MAX_LENGTH).contains(&length) {
+    Err(LemmyErrorType::InvalidWebhookSecret.into())
+  } else {
+    Ok(())
+  }
+}
+
+/// Webhooks are sent with http POST requests, so other url schemes make no sense.
+pub fn is_valid_webhook_url(url: &Url) -> LemmyResult<()> {
+  if !["http", "https"].contains(&url.scheme()) {
+    Err(LemmyErrorType::InvalidUrlScheme)?
+  }
+  max_length_check(
+    url.as_str(),
+    URL_MAX_LENGTH,
+    LemmyErrorType::UrlLengthOverflow,
+  )
+}
+
+pub fn is_valid_api_token_name(name: &str) -> LemmyResult<()> {
+  let length = name.trim().chars().count();
+  let check = (1..=API_TOKEN_NAME_MAX_LENGTH).contains(&length) && !has_newline(name);
+  if !check {
+    Err(LemmyErrorType::InvalidApiTokenName.into())
+  } else {
+    Ok(())
+  }
+}
+
+pub fn is_valid_feed_name(name: &str) -> LemmyResult<()> {
+  let length = name.trim().chars().count();
+  let check = (1..=FEED_NAME_MAX_LENGTH).contains(&length) && !has_newline(name);
+  if !check {
+    Err(LemmyErrorType::InvalidFeedName.into())
+  } else {
+    Ok(())
+  }
+}
+
+/// Checks the length of a keyword filter. Regex filters are additionally checked by the database.
+pub fn is_valid_keyword_filter(keyword: &str) -> LemmyResult<()> {
+  let length = keyword.trim().chars().count();
+  let check = (1..=KEYWORD_FILTER_MAX_LENGTH).contains(&length) && !has_newline(keyword);
+  if !check {
+    Err(LemmyErrorType::InvalidKeywordFilter.into())
+  } else {
+    Ok(())
+  }
+}
+
+pub fn is_valid_automod_rule_name(name: &str) -> LemmyResult<()> {
+  let length = name.trim().chars().count();
+  let check = (1..=AUTOMOD_RULE_NAME_MAX_LENGTH).contains(&length) && !has_newline(name);
+  if !check {
+    Err(LemmyErrorType::InvalidAutomodRule.into())
+  } else {
+    Ok(())
+  }
+}
+
 /// This could be post bodies, comments, or any description field
 pub fn is_valid_body_field(body: &str, post: bool) -> LemmyResult<()> {
   if post {
@@ -258,6 +365,42 @@ pub fn clean_url(url: &Url) -> Url {
   }
 }
 
+/// Returns the urls which count as the same link for duplicate detection. They are all
+/// combinations of http and https, with and without `www.` and with and without trailing slash,
+/// after removing tracking parameters.
+pub fn equivalent_urls(url: &Url) -> Vec<Url> {
+  let url = clean_url(url);
+  let (Some(host), true) = (url.host_str(), ["http", "https"].contains(&url.scheme())) else {
+    return vec![url];
+  };
+  let host = host.strip_prefix("www.").unwrap_or(host).to_string();
+  let path = url.path().trim_end_matches('/').to_string();
+
+  let mut urls = vec![];
+  for scheme in ["https", "http"] {
+    for host in [host.clone(), format!("www.{host}")] {
+      for path in [path.clone(), format!("{path}/")] {
+        let mut equivalent = url.clone();
+        if equivalent.set_scheme(scheme).is_ok() && equivalent.set_host(Some(&host)).is_ok() {
+          equivalent.set_path(&path);
+          urls.push(equivalent);
+        }
+      }
+    }
+  }
+  urls.sort();
+  urls.dedup();
+  urls
+}
+
+pub fn is_valid_duplicate_link_window(days: i32) -> LemmyResult<()> {
+  if !(1..=365).contains(&days) {
+    Err(LemmyErrorType::InvalidDuplicateLinkWindow.into())
+  } else {
+    Ok(())
+  }
+}
+
 /// Cleans all the links in a string of tracking parameters.
 pub fn clean_urls_in_text(text: &str) -> String {
   match URL_CLEANER.clear_text(text) {
@@ -356,13 +499,23 @@ mod tests {
       check_urls_are_valid,
       clean_url,
       clean_urls_in_text,
+      equivalent_urls,
       is_url_blocked,
       is_valid_actor_name,
+      is_valid_api_token_name,
+      is_valid_automod_rule_name,
       is_valid_bio_field,
       is_valid_display_name,
+      is_valid_draft_title,
+      is_valid_feed_name,
+      is_valid_keyword_filter,
       is_valid_matrix_id,
+      is_valid_poll_options,
       is_valid_post_title,
+      is_valid_tag_name,
       is_valid_url,
+      is_valid_webhook_secret,
+      is_valid_webhook_url,
       site_name_length_check,
       site_or_community_description_length_check,
       BIO_MAX_LENGTH,
@@ -391,6 +544,28 @@ mod tests {
     Ok(())
   }
 
+  #[test]
+  fn test_equivalent_urls() -> LemmyResult<()> {
+    let url = Url::parse("http://www.example.com/path/?utm_content=buffercf3b2&id=123")?;
+    let urls = equivalent_urls(&url)
+      .iter()
+      .map(Url::to_string)
+      .collect::<Vec<_>>();
+    assert_eq!(8, urls.len());
+    assert!(urls.contains(&"https://example.com/path?id=123".to_string()));
+    assert!(urls.contains(&"http://www.example.com/path/?id=123".to_string()));
+
+    // The root path always has a slash
+    let url = Url::parse("https://example.com")?;
+    assert_eq!(4, equivalent_urls(&url).len());
+
+    // Other schemes are only cleaned
+    let url = Url::parse("magnet:?xt=urn:btih:123")?;
+    assert_eq!(vec![clean_url(&url)], equivalent_urls(&url));
+
+    Ok(())
+  }
+
   #[test]
   fn test_clean_body() -> LemmyResult<()> {
     let text = format!("[a link]({URL_WITH_TRACKING})");
@@ -477,6 +652,80 @@ Line3",
     assert!(is_valid_post_title("\n \n \n \n    		").is_err()); // tabs/spaces/newlines
   }
 
+  #[test]
+  fn test_valid_draft_title() {
+    assert!(is_valid_draft_title("").is_ok());
+    assert!(is_valid_draft_title("Po").is_ok());
+    assert!(is_valid_draft_title("Post\nTitle").is_err());
+    assert!(is_valid_draft_title(&"a".repeat(201)).is_err());
+  }
+
+  #[test]
+  fn test_valid_tag_name() {
+    assert!(is_valid_tag_name("Discussion").is_ok());
+    assert!(is_valid_tag_name("🐈 cats").is_ok());
+    assert!(is_valid_tag_name("   ").is_err());
+    assert!(is_valid_tag_name("two\nlines").is_err());
+    assert!(is_valid_tag_name(&"a".repeat(51)).is_err());
+  }
+
+  #[test]
+  fn test_valid_poll_options() {
+    let options = |o: &[&str]| o.iter().map(ToString::to_string).collect::<Vec<_>>();
+    assert!(is_valid_poll_options(&options(&["yes", "no"])).is_ok());
+    assert!(is_valid_poll_options(&options(&["yes"])).is_err());
+    assert!(is_valid_poll_options(&options(&["yes", " yes "])).is_err());
+    assert!(is_valid_poll_options(&options(&["yes", "  "])).is_err());
+    assert!(is_valid_poll_options(&options(&["yes", "n\no"])).is_err());
+    assert!(is_valid_poll_options(&(0..21).map(|i| i.to_string()).collect::<Vec<_>>()).is_err());
+  }
+
+  #[test]
+  fn test_valid_webhook() -> LemmyResult<()> {
+    assert!(is_valid_webhook_secret("correct horse battery staple").is_ok());
+    assert!(is_valid_webhook_secret("hunter2").is_err());
+    assert!(is_valid_webhook_secret(&"a".repeat(201)).is_err());
+
+    assert!(is_valid_webhook_url(&Url::parse("https://example.com/hook")?).is_ok());
+    assert!(is_valid_webhook_url(&Url::parse("magnet:?xt=urn:btih:123")?).is_err());
+    Ok(())
+  }
+
+  #[test]
+  fn test_valid_api_token_name() {
+    assert!(is_valid_api_token_name("my bot").is_ok());
+    assert!(is_valid_api_token_name(" ").is_err());
+    assert!(is_valid_api_token_name("my\nbot").is_err());
+    assert!(is_valid_api_token_name(&"a".repeat(51)).is_err());
+  }
+
+  #[test]
+  fn test_valid_feed_name() {
+    assert!(is_valid_feed_name("Tech news").is_ok());
+    assert!(is_valid_feed_name("").is_err());
+    assert!(is_valid_feed_name("tech\nnews").is_err());
+    assert!(is_valid_feed_name(&"a".repeat(51)).is_err());
+  }
+
+  #[test]
+  fn test_valid_keyword_filter() {
+    assert!(is_valid_keyword_filter("spoiler").is_ok());
+    assert!(is_valid_keyword_filter("  ").is_err());
+    assert!(is_valid_keyword_filter(
+      "spoiler
+alert"
+    )
+    .is_err());
+    assert!(is_valid_keyword_filter(&"a".repeat(101)).is_err());
+  }
+
+  #[test]
+  fn test_valid_automod_rule_name() {
+    assert!(is_valid_automod_rule_name("No link shorteners").is_ok());
+    assert!(is_valid_automod_rule_name(" ").is_err());
+    assert!(is_valid_automod_rule_name(&"a".repeat(51)).is_err());
+  }
+
   #[test]
   fn test_valid_matrix_id() {
     assert!(is_valid_matrix_id("@dess:matrix.org").is_ok());
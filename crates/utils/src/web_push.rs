@@ -0,0 +1,434 @@
+//! Web Push messages, encrypted with the aes128gcm content encoding from
+//! [RFC 8291](https://www.rfc-editor.org/rfc/rfc8291) and authenticated with
+//! [VAPID](https://www.rfc-editor.org/rfc/rfc8292).
+
+use crate::{
+  error::{LemmyErrorExt, LemmyErrorType, LemmyResult},
+  REQWEST_TIMEOUT,
+};
+use base64::{
+  engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD},
+  Engine,
+};
+use reqwest_middleware::{reqwest::StatusCode, ClientWithMiddleware};
+use ring::{
+  aead,
+  agreement,
+  error::Unspecified,
+  hkdf,
+  rand::{SecureRandom, SystemRandom},
+  signature::{EcdsaKeyPair, KeyPair, ECDSA_P256_SHA256_FIXED_SIGNING},
+};
+use serde::Serialize;
+use serde_json::json;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use url::Url;
+
+/// The whole message is sent as a single record, so the record size only needs to be larger than
+/// the message.
+const RECORD_SIZE: u32 = 4096;
+
+/// Length of the uncompressed P-256 public keys.
+const PUBLIC_KEY_LENGTH: usize = 65;
+
+/// Length of the authentication secret of a subscription.
+const AUTH_SECRET_LENGTH: usize = 16;
+
+/// Push services only need to accept 4096 bytes, which includes the 86 byte header, the 16 byte
+/// authentication tag and the padding delimiter.
+const MAX_PAYLOAD_LENGTH: usize = 3993;
+
+/// How long the push service should keep the message if the device is offline.
+const TIME_TO_LIVE: Duration = Duration::from_secs(60 * 60 * 24);
+
+/// How long the VAPID token is valid, at most 24 hours are allowed.
+const VAPID_TOKEN_VALIDITY: Duration = Duration::from_secs(60 * 60 * 12);
+
+/// The keypair which the server uses to identify itself to push services.
+pub struct VapidKeys {
+  /// PKCS#8 document, base64 encoded.
+  pub private_key: String,
+  /// Uncompressed P-256 point, base64url encoded. This is the `applicationServerKey` which
+  /// clients need to pass when subscribing.
+  pub public_key: String,
+}
+
+impl VapidKeys {
+  pub fn generate() -> LemmyResult<Self> {
+    let rng = SystemRandom::new();
+    let pkcs8 = EcdsaKeyPair::generate_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, &rng)?;
+    let key_pair =
+      EcdsaKeyPair::from_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, pkcs8.as_ref(), &rng)?;
+    Ok(VapidKeys {
+      private_key: STANDARD.encode(pkcs8.as_ref()),
+      public_key: URL_SAFE_NO_PAD.encode(key_pair.public_key().as_ref()),
+    })
+  }
+
+  /// Builds the value of the `Authorization` header for a request to the push service.
+  fn authorization(&self, endpoint: &Url, subject: &str) -> LemmyResult<String> {
+    let rng = SystemRandom::new();
+    let private_key = STANDARD.decode(&self.private_key)?;
+    let key_pair = EcdsaKeyPair::from_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, &private_key, &rng)?;
+
+    let expires = SystemTime::now().duration_since(UNIX_EPOCH)? + VAPID_TOKEN_VALIDITY;
+    let header = json!({ "typ": "JWT", "alg": "ES256" });
+    let claims = json!({
+      "aud": endpoint.origin().ascii_serialization(),
+      "exp": expires.as_secs(),
+      "sub": subject,
+    });
+    let unsigned_token = format!(
+      "{}.{}",
+      URL_SAFE_NO_PAD.encode(header.to_string()),
+      URL_SAFE_NO_PAD.encode(claims.to_string())
+    );
+    let signature = key_pair.sign(&rng, unsigned_token.as_bytes())?;
+
+    Ok(format!(
+      "vapid t={unsigned_token}.{}, k={}",
+      URL_SAFE_NO_PAD.encode(signature.as_ref()),
+      self.public_key
+    ))
+  }
+}
+
+/// The content of a push message, which is shown by the service worker of the client.
+#[derive(Serialize, Debug, Clone)]
+pub struct PushMessage {
+  pub title: String,
+  pub body: String,
+  /// Where the notification leads to when it is clicked.
+  pub url: String,
+}
+
+/// Checks that the keys of a push subscription, as returned by `PushSubscription.toJSON()` in
+/// browsers, are valid.
+pub fn check_subscription_keys(p256dh: &str, auth: &str) -> LemmyResult<()> {
+  let p256dh = decode_key(p256dh)?;
+  let auth = decode_key(auth)?;
+  if p256dh.len() != PUBLIC_KEY_LENGTH || auth.len() != AUTH_SECRET_LENGTH {
+    Err(LemmyErrorType::InvalidPushSubscription)?
+  }
+  Ok(())
+}
+
+fn decode_key(key: &str) -> LemmyResult<Vec<u8>> {
+  URL_SAFE_NO_PAD
+    .decode(key.trim_end_matches('='))
+    .with_lemmy_type(LemmyErrorType::InvalidPushSubscription)
+}
+
+/// Sends a push message to the endpoint of a subscription. Returns false if the push service
+/// rejected the subscription, because it expired or doesn't belong to our VAPID key. Such
+/// subscriptions won't work again and should be removed.
+pub async fn send_web_push(
+  client: &ClientWithMiddleware,
+  endpoint: &str,
+  p256dh: &str,
+  auth: &str,
+  message: &PushMessage,
+  vapid_keys: &VapidKeys,
+  subject: &str,
+) -> LemmyResult<bool> {
+  let endpoint = Url::parse(endpoint).with_lemmy_type(LemmyErrorType::InvalidPushSubscription)?;
+  let mut message = message.clone();
+  let mut payload = serde_json::to_vec(&message)?;
+  // Shorten long comments so that the message fits
+  while payload.len() > MAX_PAYLOAD_LENGTH && !message.body.is_empty() {
+    let excess = payload.len() - MAX_PAYLOAD_LENGTH;
+    let keep = message.body.chars().count().saturating_sub(excess.max(16));
+    message.body = message.body.chars().take(keep).collect();
+    payload = serde_json::to_vec(&message)?;
+  }
+  let body = encrypt(&payload, &decode_key(p256dh)?, &decode_key(auth)?)?;
+
+  let res = client
+    .post(endpoint.clone())
+    .timeout(REQWEST_TIMEOUT)
+    .header(
+      "Authorization",
+      vapid_keys.authorization(&endpoint, subject)?,
+    )
+    .header("Content-Encoding", "aes128gcm")
+    .header("Content-Type", "application/octet-stream")
+    .header("TTL", TIME_TO_LIVE.as_secs())
+    .body(body)
+    .send()
+    .await?;
+
+  match res.status() {
+    StatusCode::NOT_FOUND | StatusCode::GONE | StatusCode::FORBIDDEN => Ok(false),
+    status if status.is_success() => Ok(true),
+    status => Err(LemmyErrorType::Unknown(format!("Push service returned {status}")).into()),
+  }
+}
+
+/// Encrypts the payload for the public key and authentication secret of a subscription.
+fn encrypt(payload: &[u8], ua_public: &[u8], auth_secret: &[u8]) -> LemmyResult<Vec<u8>> {
+  let rng = SystemRandom::new();
+  let as_private = agreement::EphemeralPrivateKey::generate(&agreement::ECDH_P256, &rng)?;
+  let as_public = as_private.compute_public_key()?;
+
+  // Combine the shared secret with the authentication secret of the subscription
+  let ua_public_key = agreement::UnparsedPublicKey::new(&agreement::ECDH_P256, ua_public);
+  let ikm = agreement::agree_ephemeral(as_private, &ua_public_key, |ecdh_secret| {
+    let prk = hkdf::Salt::new(hkdf::HKDF_SHA256, auth_secret).extract(ecdh_secret);
+    let key_info = [b"WebPush: info\0".as_slice(), ua_public, as_public.as_ref()].concat();
+    hkdf_expand(&prk, &key_info, 32)
+  })
+  .with_lemmy_type(LemmyErrorType::InvalidPushSubscription)??;
+
+  let mut salt = [0u8; 16];
+  rng.fill(&mut salt)?;
+  let prk = hkdf::Salt::new(hkdf::HKDF_SHA256, &salt).extract(&ikm);
+  let content_encryption_key = hkdf_expand(&prk, b"Content-Encoding: aes128gcm\0", 16)?;
+  let nonce = hkdf_expand(&prk, b"Content-Encoding: nonce\0", 12)?;
+
+  // A single record, which ends with the delimiter of the last record
+  let mut record = payload.to_vec();
+  record.push(2);
+  let key = aead::LessSafeKey::new(aead::UnboundKey::new(
+    &aead::AES_128_GCM,
+    &content_encryption_key,
+  )?);
+  key.seal_in_place_append_tag(
+    aead::Nonce::try_assume_unique_for_key(&nonce)?,
+    aead::Aad::empty(),
+    &mut record,
+  )?;
+
+  let as_public = as_public.as_ref();
+  let mut body = Vec::with_capacity(salt.len() + 5 + as_public.len() + record.len());
+  body.extend_from_slice(&salt);
+  body.extend_from_slice(&RECORD_SIZE.to_be_bytes());
+  body.push(u8::try_from(as_public.len())?);
+  body.extend_from_slice(as_public);
+  body.extend_from_slice(&record);
+  Ok(body)
+}
+
+/// Output length for HKDF, which ring only accepts as a key type.
+struct OutputLength(usize);
+
+impl hkdf::KeyType for OutputLength {
+  fn len(&self) -> usize {
+    self.0
+  }
+}
+
+fn hkdf_expand(prk: &hkdf::Prk, info: &[u8], len: usize) -> Result<Vec<u8>, Unspecified> {
+  let mut out = vec![0; len];
+  prk.expand(&[info], OutputLength(len))?.fill(&mut out)?;
+  Ok(out)
+}
+
+#[cfg(test)]
+#[expect(clippy::indexing_slicing)]
+mod tests {
+  use super::*;
+  use pretty_assertions::assert_eq;
+  use reqwest_middleware::{reqwest::Client, ClientBuilder};
+  use ring::signature::{UnparsedPublicKey, ECDSA_P256_SHA256_FIXED};
+  use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpListener,
+  };
+
+  /// The keys which a browser generates for a subscription.
+  struct UserAgent {
+    private_key: agreement::EphemeralPrivateKey,
+    p256dh: String,
+    auth: String,
+    auth_secret: [u8; 16],
+  }
+
+  impl UserAgent {
+    fn new() -> LemmyResult<Self> {
+      let rng = SystemRandom::new();
+      let private_key = agreement::EphemeralPrivateKey::generate(&agreement::ECDH_P256, &rng)?;
+      let public_key = private_key.compute_public_key()?;
+      let mut auth_secret = [0u8; 16];
+      rng.fill(&mut auth_secret)?;
+      Ok(UserAgent {
+        p256dh: URL_SAFE_NO_PAD.encode(public_key.as_ref()),
+        auth: URL_SAFE_NO_PAD.encode(auth_secret),
+        private_key,
+        auth_secret,
+      })
+    }
+
+    /// Decrypts a message like the browser does, following RFC 8291
+    fn decrypt(self, body: &[u8]) -> LemmyResult<Vec<u8>> {
+      let salt = &body[..16];
+      assert_eq!(RECORD_SIZE.to_be_bytes(), body[16..20]);
+      let key_id_length = usize::from(body[20]);
+      let as_public = &body[21..21 + key_id_length];
+      let mut record = body[21 + key_id_length..].to_vec();
+
+      let ua_public = decode_key(&self.p256dh)?;
+      let as_public_key = agreement::UnparsedPublicKey::new(&agreement::ECDH_P256, as_public);
+      let ikm = agreement::agree_ephemeral(self.private_key, &as_public_key, |ecdh_secret| {
+        let prk = hkdf::Salt::new(hkdf::HKDF_SHA256, &self.auth_secret).extract(ecdh_secret);
+        let key_info = [
+          b"WebPush: info\0".as_slice(),
+          ua_public.as_slice(),
+          as_public,
+        ]
+        .concat();
+        hkdf_expand(&prk, &key_info, 32)
+      })??;
+      let prk = hkdf::Salt::new(hkdf::HKDF_SHA256, salt).extract(&ikm);
+      let cek = hkdf_expand(&prk, b"Content-Encoding: aes128gcm\0", 16)?;
+      let nonce = hkdf_expand(&prk, b"Content-Encoding: nonce\0", 12)?;
+      let key = aead::LessSafeKey::new(aead::UnboundKey::new(&aead::AES_128_GCM, &cek)?);
+      let plaintext = key.open_in_place(
+        aead::Nonce::try_assume_unique_for_key(&nonce)?,
+        aead::Aad::empty(),
+        &mut record,
+      )?;
+      assert_eq!(Some(&2), plaintext.last());
+      Ok(plaintext[..plaintext.len() - 1].to_vec())
+    }
+  }
+
+  #[test]
+  fn test_encrypt() -> LemmyResult<()> {
+    let user_agent = UserAgent::new()?;
+    check_subscription_keys(&user_agent.p256dh, &user_agent.auth)?;
+
+    let payload = b"hello from lemmy";
+    let body = encrypt(
+      payload,
+      &decode_key(&user_agent.p256dh)?,
+      &decode_key(&user_agent.auth)?,
+    )?;
+    assert_eq!(payload.to_vec(), user_agent.decrypt(&body)?);
+    Ok(())
+  }
+
+  #[test]
+  fn test_check_subscription_keys() -> LemmyResult<()> {
+    let user_agent = UserAgent::new()?;
+    // Padding is accepted
+    check_subscription_keys(&user_agent.p256dh, &format!("{}==", user_agent.auth))?;
+    assert!(check_subscription_keys(&user_agent.auth, &user_agent.p256dh).is_err());
+    assert!(check_subscription_keys("not+base64url", &user_agent.auth).is_err());
+    Ok(())
+  }
+
+  #[test]
+  fn test_vapid_authorization() -> LemmyResult<()> {
+    let keys = VapidKeys::generate()?;
+    let endpoint = Url::parse("https://push.example.com/send/abc")?;
+    let authorization = keys.authorization(&endpoint, "https://lemmy.example.com")?;
+
+    let (token, public_key) = authorization
+      .strip_prefix("vapid t=")
+      .and_then(|a| a.split_once(", k="))
+      .ok_or(LemmyErrorType::NotFound)?;
+    assert_eq!(keys.public_key, public_key);
+    let (unsigned_token, signature) = token.rsplit_once('.').ok_or(LemmyErrorType::NotFound)?;
+    UnparsedPublicKey::new(&ECDSA_P256_SHA256_FIXED, decode_key(public_key)?).verify(
+      unsigned_token.as_bytes(),
+      &URL_SAFE_NO_PAD.decode(signature)?,
+    )?;
+
+    let claims = unsigned_token
+      .split_once('.')
+      .ok_or(LemmyErrorType::NotFound)?
+      .1;
+    let claims: serde_json::Value = serde_json::from_slice(&URL_SAFE_NO_PAD.decode(claims)?)?;
+    assert_eq!("https://push.example.com", claims["aud"]);
+    assert_eq!("https://lemmy.example.com", claims["sub"]);
+    Ok(())
+  }
+
+  /// Accepts a single request like a push service would, and returns its headers and body.
+  async fn push_service(listener: TcpListener, status: &str) -> LemmyResult<(String, Vec<u8>)> {
+    let (mut stream, _) = listener.accept().await?;
+    let mut request = Vec::new();
+    let mut buf = [0u8; 1024];
+    let header_end = loop {
+      let read = stream.read(&mut buf).await?;
+      request.extend_from_slice(&buf[..read]);
+      if let Some(pos) = request.windows(4).position(|w| w == b"\r\n\r\n") {
+        break pos + 4;
+      }
+    };
+    let headers = String::from_utf8(request[..header_end].to_vec())?.to_lowercase();
+    let content_length: usize = headers
+      .lines()
+      .find_map(|l| l.strip_prefix("content-length: "))
+      .ok_or(LemmyErrorType::NotFound)?
+      .parse()?;
+    while request.len() < header_end + content_length {
+      let read = stream.read(&mut buf).await?;
+      request.extend_from_slice(&buf[..read]);
+    }
+    stream
+      .write_all(format!("HTTP/1.1 {status}\r\ncontent-length: 0\r\n\r\n").as_bytes())
+      .await?;
+    Ok((headers, request[header_end..].to_vec()))
+  }
+
+  #[tokio::test]
+  async fn test_send_web_push() -> LemmyResult<()> {
+    let client = ClientBuilder::new(Client::new()).build();
+    let keys = VapidKeys::generate()?;
+    let message = PushMessage {
+      title: "Reply from lemmy".to_string(),
+      body: "a".repeat(5000),
+      url: "https://lemmy.example.com/comment/1".to_string(),
+    };
+
+    let user_agent = UserAgent::new()?;
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let endpoint = format!("http://{}/push/1", listener.local_addr()?);
+    let service = tokio::spawn(push_service(listener, "201 Created"));
+    let sent = send_web_push(
+      &client,
+      &endpoint,
+      &user_agent.p256dh,
+      &user_agent.auth,
+      &message,
+      &keys,
+      "https://lemmy.example.com",
+    )
+    .await?;
+    assert!(sent);
+
+    let (headers, body) = service.await??;
+    assert!(headers.starts_with("post /push/1 "));
+    assert!(headers.contains("content-encoding: aes128gcm"));
+    assert!(headers.contains("ttl: 86400"));
+    assert!(headers.contains("authorization: vapid t="));
+    assert!(body.len() <= 4096);
+    let received: serde_json::Value = serde_json::from_slice(&user_agent.decrypt(&body)?)?;
+    assert_eq!(message.title, received["title"]);
+    assert_eq!(message.url, received["url"]);
+    assert!(received["body"]
+      .as_str()
+      .is_some_and(|b| b.starts_with("aaa")));
+
+    // Expired subscriptions are reported
+    let user_agent = UserAgent::new()?;
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let endpoint = format!("http://{}/push/2", listener.local_addr()?);
+    let service = tokio::spawn(push_service(listener, "410 Gone"));
+    let sent = send_web_push(
+      &client,
+      &endpoint,
+      &user_agent.p256dh,
+      &user_agent.auth,
+      &message,
+      &keys,
+      "https://lemmy.example.com",
+    )
+    .await?;
+    assert!(!sent);
+    service.await??;
+
+    Ok(())
+  }
+}
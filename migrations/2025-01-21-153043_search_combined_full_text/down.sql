@@ -0,0 +1,5 @@
+DROP INDEX idx_search_combined_search_vector;
+
+ALTER TABLE search_combined
+    DROP COLUMN search_vector;
+
@@ -0,0 +1,105 @@
+-- Full text search for the search_combined table. The vector is maintained by triggers on
+-- post, comment, community and person (see replaceable_schema/triggers.sql).
+ALTER TABLE search_combined
+    ADD COLUMN search_vector tsvector NOT NULL DEFAULT '';
+
+-- Fill the history. These are inlined versions of r.text_search_config and r.search_vector.
+CREATE TEMPORARY TABLE language_search_config AS
+SELECT
+    id AS language_id,
+    coalesce(
+        CASE code
+        WHEN 'ar' THEN
+            'arabic'
+        WHEN 'da' THEN
+            'danish'
+        WHEN 'de' THEN
+            'german'
+        WHEN 'el' THEN
+            'greek'
+        WHEN 'en' THEN
+            'english'
+        WHEN 'es' THEN
+            'spanish'
+        WHEN 'fi' THEN
+            'finnish'
+        WHEN 'fr' THEN
+            'french'
+        WHEN 'ga' THEN
+            'irish'
+        WHEN 'hu' THEN
+            'hungarian'
+        WHEN 'id' THEN
+            'indonesian'
+        WHEN 'it' THEN
+            'italian'
+        WHEN 'lt' THEN
+            'lithuanian'
+        WHEN 'nb' THEN
+            'norwegian'
+        WHEN 'ne' THEN
+            'nepali'
+        WHEN 'nl' THEN
+            'dutch'
+        WHEN 'nn' THEN
+            'norwegian'
+        WHEN 'no' THEN
+            'norwegian'
+        WHEN 'pt' THEN
+            'portuguese'
+        WHEN 'ro' THEN
+            'romanian'
+        WHEN 'ru' THEN
+            'russian'
+        WHEN 'sv' THEN
+            'swedish'
+        WHEN 'ta' THEN
+            'tamil'
+        WHEN 'tr' THEN
+            'turkish'
+        END, 'simple')::regconfig AS config
+FROM
+    LANGUAGE;
+
+UPDATE
+    search_combined sc
+SET
+    search_vector = setweight(to_tsvector(l.config, p.name), 'A') || setweight(to_tsvector(l.config, coalesce(p.body, '')), 'B') || setweight(to_tsvector('simple', p.name), 'A') || setweight(to_tsvector('simple', coalesce(p.body, '')), 'B')
+FROM
+    post p
+    INNER JOIN language_search_config l ON l.language_id = p.language_id
+WHERE
+    sc.post_id = p.id;
+
+UPDATE
+    search_combined sc
+SET
+    search_vector = setweight(to_tsvector(l.config, c.content), 'A') || setweight(to_tsvector('simple', c.content), 'A')
+FROM
+    comment c
+    INNER JOIN language_search_config l ON l.language_id = c.language_id
+WHERE
+    sc.comment_id = c.id;
+
+UPDATE
+    search_combined sc
+SET
+    search_vector = setweight(to_tsvector('simple', c.name || ' ' || c.title), 'A') || setweight(to_tsvector('simple', coalesce(c.description, '')), 'B')
+FROM
+    community c
+WHERE
+    sc.community_id = c.id;
+
+UPDATE
+    search_combined sc
+SET
+    search_vector = setweight(to_tsvector('simple', p.name || ' ' || coalesce(p.display_name, '')), 'A')
+FROM
+    person p
+WHERE
+    sc.person_id = p.id;
+
+DROP TABLE language_search_config;
+
+CREATE INDEX idx_search_combined_search_vector ON search_combined USING gin (search_vector);
+
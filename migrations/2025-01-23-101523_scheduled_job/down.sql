@@ -0,0 +1,4 @@
+DROP TABLE scheduled_job_run;
+
+DROP TABLE scheduled_job;
+
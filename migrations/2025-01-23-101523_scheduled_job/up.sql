@@ -0,0 +1,33 @@
+-- Background jobs which run periodically. Rows are created on startup for every job known to the
+-- Lemmy backend. Multiple Lemmy processes can share the work, a process takes a lease on a job
+-- before running it.
+CREATE TABLE scheduled_job (
+    id serial PRIMARY KEY,
+    name text NOT NULL UNIQUE,
+    -- seconds between successful runs
+    interval_secs int NOT NULL,
+    next_run timestamptz NOT NULL DEFAULT now(),
+    -- the job is being run by another process until this time
+    locked_until timestamptz,
+    locked_by text,
+    last_started timestamptz,
+    last_finished timestamptz,
+    last_error text,
+    -- number of consecutive failures, used for retry backoff
+    failure_count int NOT NULL DEFAULT 0,
+    published timestamptz NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_scheduled_job_next_run ON scheduled_job (next_run);
+
+CREATE TABLE scheduled_job_run (
+    id serial PRIMARY KEY,
+    job_id int REFERENCES scheduled_job ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    started timestamptz NOT NULL,
+    finished timestamptz NOT NULL DEFAULT now(),
+    -- null if the run was successful
+    error text
+);
+
+CREATE INDEX idx_scheduled_job_run_job_started ON scheduled_job_run (job_id, started DESC);
+
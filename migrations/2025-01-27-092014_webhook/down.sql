@@ -0,0 +1,3 @@
+DROP TABLE webhook_delivery, webhook_event, webhook;
+
+DROP TYPE webhook_event_type_enum;
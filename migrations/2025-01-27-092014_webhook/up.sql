@@ -0,0 +1,50 @@
+CREATE TYPE webhook_event_type_enum AS enum (
+    'PostCreated',
+    'PostUpdated',
+    'CommentCreated',
+    'CommentUpdated',
+    'ReportCreated',
+    'ModAction',
+    'RegistrationApplicationCreated'
+);
+
+-- Webhooks without a community receive events from the whole site, and can only be managed by admins.
+CREATE TABLE webhook (
+    id serial PRIMARY KEY,
+    community_id int REFERENCES community ON UPDATE CASCADE ON DELETE CASCADE,
+    creator_id int REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    url text NOT NULL,
+    secret text NOT NULL,
+    enabled boolean NOT NULL DEFAULT TRUE,
+    published timestamptz NOT NULL DEFAULT now(),
+    updated timestamptz
+);
+
+CREATE INDEX idx_webhook_community ON webhook (community_id);
+
+CREATE TABLE webhook_event (
+    webhook_id int REFERENCES webhook ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    event webhook_event_type_enum NOT NULL,
+    PRIMARY KEY (webhook_id, event)
+);
+
+-- next_attempt is null once the delivery succeeded, or all attempts have failed.
+CREATE TABLE webhook_delivery (
+    id serial PRIMARY KEY,
+    webhook_id int REFERENCES webhook ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    event webhook_event_type_enum NOT NULL,
+    -- the exact request body, so that retries have the same signature
+    payload text NOT NULL,
+    attempts int NOT NULL DEFAULT 0,
+    next_attempt timestamptz DEFAULT now(),
+    delivered timestamptz,
+    last_status int,
+    last_error text,
+    published timestamptz NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_webhook_delivery_next_attempt ON webhook_delivery (next_attempt)
+WHERE
+    next_attempt IS NOT NULL;
+
+CREATE INDEX idx_webhook_delivery_webhook ON webhook_delivery (webhook_id, published DESC);
@@ -0,0 +1,3 @@
+DROP TABLE api_token_scope, api_token;
+
+DROP TYPE api_token_scope_enum;
@@ -0,0 +1,26 @@
+CREATE TYPE api_token_scope_enum AS enum (
+    'Read',
+    'WritePost',
+    'WriteComment',
+    'Moderate',
+    'Admin'
+);
+
+-- Long-lived tokens for bots and scripts, which are separate from login sessions.
+CREATE TABLE api_token (
+    id serial PRIMARY KEY,
+    local_user_id int REFERENCES local_user ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    name text NOT NULL,
+    -- sha256 of the token, which itself is only shown once after creation
+    token_hash text NOT NULL UNIQUE,
+    expires timestamptz,
+    published timestamptz NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_api_token_local_user ON api_token (local_user_id);
+
+CREATE TABLE api_token_scope (
+    api_token_id int REFERENCES api_token ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    scope api_token_scope_enum NOT NULL,
+    PRIMARY KEY (api_token_id, scope)
+);
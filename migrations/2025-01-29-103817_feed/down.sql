@@ -0,0 +1,3 @@
+DROP TABLE feed_community;
+
+DROP TABLE feed;
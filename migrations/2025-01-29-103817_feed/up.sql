@@ -0,0 +1,20 @@
+-- A named group of communities, which can be used as post listing.
+CREATE TABLE feed (
+    id serial PRIMARY KEY,
+    creator_id int REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    name text NOT NULL,
+    description text,
+    -- private feeds are only visible to the creator
+    public boolean NOT NULL DEFAULT FALSE,
+    published timestamptz NOT NULL DEFAULT now(),
+    updated timestamptz,
+    UNIQUE (creator_id, name)
+);
+
+CREATE TABLE feed_community (
+    feed_id int REFERENCES feed ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    community_id int REFERENCES community ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    PRIMARY KEY (feed_id, community_id)
+);
+
+CREATE INDEX idx_feed_community_community ON feed_community (community_id);
@@ -0,0 +1,3 @@
+DROP TABLE local_user_keyword_filter_listing, local_user_keyword_filter;
+
+DROP TYPE keyword_filter_mode_enum;
@@ -0,0 +1,25 @@
+CREATE TYPE keyword_filter_mode_enum AS enum (
+    'WholeWord',
+    'Substring',
+    'Regex'
+);
+
+-- Hides posts and comments which contain the keyword from the user.
+CREATE TABLE local_user_keyword_filter (
+    id serial PRIMARY KEY,
+    local_user_id int REFERENCES local_user ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    keyword text NOT NULL,
+    mode keyword_filter_mode_enum NOT NULL DEFAULT 'WholeWord',
+    match_title boolean NOT NULL DEFAULT TRUE,
+    match_body boolean NOT NULL DEFAULT TRUE,
+    match_url boolean NOT NULL DEFAULT FALSE,
+    published timestamptz NOT NULL DEFAULT now(),
+    UNIQUE (local_user_id, keyword, mode)
+);
+
+-- The listings in which a filter is applied. Filters without any listing are applied everywhere.
+CREATE TABLE local_user_keyword_filter_listing (
+    keyword_filter_id int REFERENCES local_user_keyword_filter ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    listing_type listing_type_enum NOT NULL,
+    PRIMARY KEY (keyword_filter_id, listing_type)
+);
@@ -0,0 +1,14 @@
+ALTER TABLE local_site
+    DROP COLUMN automod_person_id;
+
+DELETE FROM modlog_combined
+WHERE mod_automod_action_id IS NOT NULL;
+
+ALTER TABLE modlog_combined
+    DROP CONSTRAINT modlog_combined_check,
+    ADD CHECK (num_nonnulls (admin_allow_instance_id, admin_block_instance_id, admin_purge_comment_id, admin_purge_community_id, admin_purge_person_id, admin_purge_post_id, mod_add_id, mod_add_community_id, mod_ban_id, mod_ban_from_community_id, mod_feature_post_id, mod_hide_community_id, mod_lock_post_id, mod_remove_comment_id, mod_remove_community_id, mod_remove_post_id, mod_transfer_community_id) = 1),
+    DROP COLUMN mod_automod_action_id;
+
+DROP TABLE mod_automod_action, automod_rule;
+
+DROP TYPE automod_action_enum;
@@ -0,0 +1,55 @@
+CREATE TYPE automod_action_enum AS enum (
+    'Remove',
+    'Lock',
+    'Report',
+    'Tag',
+    'Reply'
+);
+
+-- A rule matches if all of its conditions which are set match. Rules without any condition are
+-- rejected by the API.
+CREATE TABLE automod_rule (
+    id serial PRIMARY KEY,
+    community_id int REFERENCES community ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    creator_id int REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    name text NOT NULL,
+    enabled boolean NOT NULL DEFAULT TRUE,
+    check_posts boolean NOT NULL DEFAULT TRUE,
+    check_comments boolean NOT NULL DEFAULT TRUE,
+    title_regex text,
+    body_regex text,
+    url_domain text,
+    max_author_age_days int,
+    max_author_karma bigint,
+    language_id int REFERENCES
+    LANGUAGE ON UPDATE CASCADE ON DELETE CASCADE,
+    action automod_action_enum NOT NULL,
+    tag_id int REFERENCES tag ON UPDATE CASCADE ON DELETE CASCADE,
+    -- the report reason, or the content of the reply
+    message text,
+    published timestamptz NOT NULL DEFAULT now(),
+    updated timestamptz
+);
+
+CREATE INDEX idx_automod_rule_community ON automod_rule (community_id);
+
+CREATE TABLE mod_automod_action (
+    id serial PRIMARY KEY,
+    mod_person_id int REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    community_id int REFERENCES community ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    post_id int REFERENCES post ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    comment_id int REFERENCES comment ON UPDATE CASCADE ON DELETE CASCADE,
+    action automod_action_enum NOT NULL,
+    -- the name of the rule which triggered the action
+    reason text,
+    published timestamptz NOT NULL DEFAULT now()
+);
+
+ALTER TABLE modlog_combined
+    ADD COLUMN mod_automod_action_id int UNIQUE REFERENCES mod_automod_action ON UPDATE CASCADE ON DELETE CASCADE,
+    DROP CONSTRAINT modlog_combined_check,
+    ADD CHECK (num_nonnulls (admin_allow_instance_id, admin_block_instance_id, admin_purge_comment_id, admin_purge_community_id, admin_purge_person_id, admin_purge_post_id, mod_add_id, mod_add_community_id, mod_ban_id, mod_ban_from_community_id, mod_feature_post_id, mod_hide_community_id, mod_lock_post_id, mod_remove_comment_id, mod_remove_community_id, mod_remove_post_id, mod_transfer_community_id, mod_automod_action_id) = 1);
+
+-- The local bot account which performs the automod actions, created on first use.
+ALTER TABLE local_site
+    ADD COLUMN automod_person_id int REFERENCES person ON UPDATE CASCADE ON DELETE SET NULL;
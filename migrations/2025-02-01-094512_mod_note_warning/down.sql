@@ -0,0 +1,17 @@
+DELETE FROM modlog_combined
+WHERE mod_warn_person_id IS NOT NULL;
+
+ALTER TABLE modlog_combined
+    DROP CONSTRAINT modlog_combined_check,
+    ADD CHECK (num_nonnulls (admin_allow_instance_id, admin_block_instance_id, admin_purge_comment_id, admin_purge_community_id, admin_purge_person_id, admin_purge_post_id, mod_add_id, mod_add_community_id, mod_ban_id, mod_ban_from_community_id, mod_feature_post_id, mod_hide_community_id, mod_lock_post_id, mod_remove_comment_id, mod_remove_community_id, mod_remove_post_id, mod_transfer_community_id, mod_automod_action_id) = 1),
+    DROP COLUMN mod_warn_person_id;
+
+DELETE FROM inbox_combined
+WHERE mod_warn_person_id IS NOT NULL;
+
+ALTER TABLE inbox_combined
+    DROP CONSTRAINT inbox_combined_check,
+    ADD CHECK (num_nonnulls (comment_reply_id, person_comment_mention_id, person_post_mention_id, private_message_id) = 1),
+    DROP COLUMN mod_warn_person_id;
+
+DROP TABLE mod_warn_person, mod_note;
@@ -0,0 +1,32 @@
+-- Private notes of moderators about a person. Notes without a community are written by admins.
+CREATE TABLE mod_note (
+    id serial PRIMARY KEY,
+    person_id int REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    creator_id int REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    community_id int REFERENCES community ON UPDATE CASCADE ON DELETE CASCADE,
+    content text NOT NULL,
+    published timestamptz NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_mod_note_person ON mod_note (person_id);
+
+-- A formal warning, which is shown in the modlog and in the inbox of the warned person
+CREATE TABLE mod_warn_person (
+    id serial PRIMARY KEY,
+    mod_person_id int REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    other_person_id int REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    community_id int REFERENCES community ON UPDATE CASCADE ON DELETE CASCADE,
+    reason text NOT NULL,
+    read boolean NOT NULL DEFAULT FALSE,
+    published timestamptz NOT NULL DEFAULT now()
+);
+
+ALTER TABLE modlog_combined
+    ADD COLUMN mod_warn_person_id int UNIQUE REFERENCES mod_warn_person ON UPDATE CASCADE ON DELETE CASCADE,
+    DROP CONSTRAINT modlog_combined_check,
+    ADD CHECK (num_nonnulls (admin_allow_instance_id, admin_block_instance_id, admin_purge_comment_id, admin_purge_community_id, admin_purge_person_id, admin_purge_post_id, mod_add_id, mod_add_community_id, mod_ban_id, mod_ban_from_community_id, mod_feature_post_id, mod_hide_community_id, mod_lock_post_id, mod_remove_comment_id, mod_remove_community_id, mod_remove_post_id, mod_transfer_community_id, mod_automod_action_id, mod_warn_person_id) = 1);
+
+ALTER TABLE inbox_combined
+    ADD COLUMN mod_warn_person_id int UNIQUE REFERENCES mod_warn_person ON UPDATE CASCADE ON DELETE CASCADE,
+    DROP CONSTRAINT inbox_combined_check,
+    ADD CHECK (num_nonnulls (comment_reply_id, person_comment_mention_id, person_post_mention_id, private_message_id, mod_warn_person_id) = 1);
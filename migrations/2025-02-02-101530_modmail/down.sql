@@ -0,0 +1,9 @@
+DELETE FROM inbox_combined
+WHERE modmail_message_id IS NOT NULL;
+
+ALTER TABLE inbox_combined
+    DROP CONSTRAINT inbox_combined_check,
+    ADD CHECK (num_nonnulls (comment_reply_id, person_comment_mention_id, person_post_mention_id, private_message_id, mod_warn_person_id) = 1),
+    DROP COLUMN modmail_message_id;
+
+DROP TABLE modmail_message, modmail_thread;
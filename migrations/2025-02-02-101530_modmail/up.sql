@@ -0,0 +1,34 @@
+-- A conversation between a person and the moderators of a community, which any moderator can
+-- read and reply to
+CREATE TABLE modmail_thread (
+    id serial PRIMARY KEY,
+    community_id int REFERENCES community ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    creator_id int REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    subject text NOT NULL,
+    archived boolean NOT NULL DEFAULT FALSE,
+    published timestamptz NOT NULL DEFAULT now(),
+    updated timestamptz
+);
+
+CREATE INDEX idx_modmail_thread_community ON modmail_thread (community_id);
+
+CREATE INDEX idx_modmail_thread_creator ON modmail_thread (creator_id);
+
+-- Messages which are sent by a moderator are read by the thread creator, all other messages are
+-- read by the moderators.
+CREATE TABLE modmail_message (
+    id serial PRIMARY KEY,
+    thread_id int REFERENCES modmail_thread ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    creator_id int REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    content text NOT NULL,
+    from_moderator boolean NOT NULL,
+    read boolean NOT NULL DEFAULT FALSE,
+    published timestamptz NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_modmail_message_thread ON modmail_message (thread_id);
+
+ALTER TABLE inbox_combined
+    ADD COLUMN modmail_message_id int UNIQUE REFERENCES modmail_message ON UPDATE CASCADE ON DELETE CASCADE,
+    DROP CONSTRAINT inbox_combined_check,
+    ADD CHECK (num_nonnulls (comment_reply_id, person_comment_mention_id, person_post_mention_id, private_message_id, mod_warn_person_id, modmail_message_id) = 1);
@@ -0,0 +1,2 @@
+DROP TABLE post_poll_vote, post_poll_option, post_poll;
+
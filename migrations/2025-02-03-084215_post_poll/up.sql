@@ -0,0 +1,28 @@
+-- Poll attached to a post. For remote polls the vote counts are taken from the origin instance.
+CREATE TABLE post_poll (
+    post_id int PRIMARY KEY REFERENCES post ON UPDATE CASCADE ON DELETE CASCADE,
+    multiple_choice boolean NOT NULL DEFAULT FALSE,
+    end_time timestamptz,
+    voters_count int NOT NULL DEFAULT 0,
+    published timestamptz NOT NULL DEFAULT now()
+);
+
+CREATE TABLE post_poll_option (
+    id serial PRIMARY KEY,
+    post_id int REFERENCES post_poll ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    name text NOT NULL,
+    position int NOT NULL,
+    vote_count int NOT NULL DEFAULT 0,
+    UNIQUE (post_id, name)
+);
+
+CREATE TABLE post_poll_vote (
+    option_id int REFERENCES post_poll_option ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    person_id int REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    post_id int REFERENCES post_poll ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    published timestamptz NOT NULL DEFAULT now(),
+    PRIMARY KEY (option_id, person_id)
+);
+
+CREATE INDEX idx_post_poll_vote_post_person ON post_poll_vote (post_id, person_id);
+
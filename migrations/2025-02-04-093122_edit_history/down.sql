@@ -0,0 +1,4 @@
+ALTER TABLE site
+    DROP COLUMN edit_history_public;
+
+DROP TABLE post_revision, comment_revision;
@@ -0,0 +1,27 @@
+-- Each row is a previous version of a post or comment, stored when it gets edited. The editor
+-- is the person who made the edit, and published is the time of the edit.
+CREATE TABLE post_revision (
+    id serial PRIMARY KEY,
+    post_id int REFERENCES post ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    editor_id int REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    name text NOT NULL,
+    url text,
+    body text,
+    published timestamptz NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_post_revision_post ON post_revision (post_id);
+
+CREATE TABLE comment_revision (
+    id serial PRIMARY KEY,
+    comment_id int REFERENCES comment ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    editor_id int REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    content text NOT NULL,
+    published timestamptz NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_comment_revision_comment ON comment_revision (comment_id);
+
+-- By default only mods and admins can see the edit history
+ALTER TABLE site
+    ADD COLUMN edit_history_public boolean NOT NULL DEFAULT FALSE;
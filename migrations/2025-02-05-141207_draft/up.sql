@@ -0,0 +1,24 @@
+-- Unfinished posts and comments which are stored on the server, so that they can be continued
+-- on another device. Drafts with a post_id are comments, in which case body holds the comment
+-- content. All other columns are optional because drafts may be incomplete.
+CREATE TABLE draft (
+    id serial PRIMARY KEY,
+    local_user_id int REFERENCES local_user ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    community_id int REFERENCES community ON UPDATE CASCADE ON DELETE CASCADE,
+    post_id int REFERENCES post ON UPDATE CASCADE ON DELETE CASCADE,
+    parent_id int REFERENCES comment ON UPDATE CASCADE ON DELETE CASCADE,
+    name text,
+    url text,
+    body text,
+    alt_text text,
+    nsfw boolean,
+    language_id int REFERENCES
+    LANGUAGE ON UPDATE CASCADE ON DELETE CASCADE,
+    custom_thumbnail text,
+    published timestamptz NOT NULL DEFAULT now(),
+    updated timestamptz,
+    CHECK (parent_id IS NULL OR post_id IS NOT NULL)
+);
+
+CREATE INDEX idx_draft_local_user ON draft (local_user_id);
+
@@ -0,0 +1,9 @@
+-- Points to the first post of a crosspost chain. Crossposts of crossposts also point to the
+-- original post, so the whole chain can be found with a single lookup.
+ALTER TABLE post
+    ADD COLUMN crosspost_of_id int REFERENCES post ON UPDATE CASCADE ON DELETE SET NULL;
+
+CREATE INDEX idx_post_crosspost_of ON post (crosspost_of_id)
+WHERE
+    crosspost_of_id IS NOT NULL;
+
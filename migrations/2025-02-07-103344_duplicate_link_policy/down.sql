@@ -0,0 +1,6 @@
+ALTER TABLE community
+    DROP COLUMN duplicate_link_policy,
+    DROP COLUMN duplicate_link_window_days;
+
+DROP TYPE duplicate_link_policy_enum;
+
@@ -0,0 +1,12 @@
+CREATE TYPE duplicate_link_policy_enum AS enum (
+    'Allow',
+    'Warn',
+    'Reject'
+);
+
+-- What happens when a link is posted which was already posted to the same community within the
+-- last duplicate_link_window_days.
+ALTER TABLE community
+    ADD COLUMN duplicate_link_policy duplicate_link_policy_enum NOT NULL DEFAULT 'Allow',
+    ADD COLUMN duplicate_link_window_days int NOT NULL DEFAULT 30;
+
@@ -0,0 +1,13 @@
+ALTER TABLE local_user
+    DROP COLUMN auto_subscribe_own_posts;
+
+DELETE FROM inbox_combined
+WHERE thread_reply_id IS NOT NULL;
+
+ALTER TABLE inbox_combined
+    DROP CONSTRAINT inbox_combined_check,
+    ADD CHECK (num_nonnulls (comment_reply_id, person_comment_mention_id, person_post_mention_id, private_message_id, mod_warn_person_id, modmail_message_id) = 1),
+    DROP COLUMN thread_reply_id;
+
+DROP TABLE thread_reply, thread_subscription;
+
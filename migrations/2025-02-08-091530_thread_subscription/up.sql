@@ -0,0 +1,39 @@
+-- Subscriptions to all new comments in a post, or in the subtree of a comment. A muted
+-- subscription silences a subtree of a thread which the person is otherwise subscribed to.
+CREATE TABLE thread_subscription (
+    id serial PRIMARY KEY,
+    person_id int REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    post_id int REFERENCES post ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    comment_id int REFERENCES comment ON UPDATE CASCADE ON DELETE CASCADE,
+    muted boolean NOT NULL DEFAULT FALSE,
+    published timestamptz NOT NULL DEFAULT now()
+);
+
+CREATE UNIQUE INDEX idx_thread_subscription_post ON thread_subscription (person_id, post_id)
+WHERE
+    comment_id IS NULL;
+
+CREATE UNIQUE INDEX idx_thread_subscription_comment ON thread_subscription (person_id, comment_id)
+WHERE
+    comment_id IS NOT NULL;
+
+CREATE INDEX idx_thread_subscription_post_id ON thread_subscription (post_id);
+
+-- A new comment in a subscribed thread
+CREATE TABLE thread_reply (
+    id serial PRIMARY KEY,
+    recipient_id int REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    comment_id int REFERENCES comment ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    read boolean NOT NULL DEFAULT FALSE,
+    published timestamptz NOT NULL DEFAULT now(),
+    UNIQUE (recipient_id, comment_id)
+);
+
+ALTER TABLE inbox_combined
+    ADD COLUMN thread_reply_id int UNIQUE REFERENCES thread_reply ON UPDATE CASCADE ON DELETE CASCADE,
+    DROP CONSTRAINT inbox_combined_check,
+    ADD CHECK (num_nonnulls (comment_reply_id, person_comment_mention_id, person_post_mention_id, private_message_id, mod_warn_person_id, modmail_message_id, thread_reply_id) = 1);
+
+ALTER TABLE local_user
+    ADD COLUMN auto_subscribe_own_posts boolean NOT NULL DEFAULT FALSE;
+
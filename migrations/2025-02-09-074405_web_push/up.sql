@@ -0,0 +1,17 @@
+-- The keypair which identifies this server to push services. It is generated on startup.
+ALTER TABLE secret
+    ADD COLUMN vapid_private_key text,
+    ADD COLUMN vapid_public_key text;
+
+-- Web Push subscriptions of the browsers and apps of a user
+CREATE TABLE push_subscription (
+    id serial PRIMARY KEY,
+    local_user_id int REFERENCES local_user ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    endpoint text NOT NULL UNIQUE,
+    p256dh text NOT NULL,
+    auth text NOT NULL,
+    published timestamptz NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_push_subscription_local_user ON push_subscription (local_user_id);
+
@@ -0,0 +1,6 @@
+DROP TABLE queued_email_notification;
+
+DROP TABLE local_user_email_digest;
+
+DROP TYPE email_digest_mode_enum;
+
@@ -0,0 +1,25 @@
+CREATE TYPE email_digest_mode_enum AS enum (
+    'Instant',
+    'Hourly',
+    'Daily',
+    'Weekly'
+);
+
+-- How often notification emails are sent to the user. Users without a row get instant emails.
+CREATE TABLE local_user_email_digest (
+    local_user_id int PRIMARY KEY REFERENCES local_user ON UPDATE CASCADE ON DELETE CASCADE,
+    mode email_digest_mode_enum NOT NULL DEFAULT 'Instant',
+    last_sent timestamptz NOT NULL DEFAULT now()
+);
+
+-- Notification emails which wait to be sent as part of a digest.
+CREATE TABLE queued_email_notification (
+    id serial PRIMARY KEY,
+    local_user_id int REFERENCES local_user ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    subject text NOT NULL,
+    body text NOT NULL,
+    published timestamptz NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_queued_email_notification_local_user ON queued_email_notification (local_user_id);
+
@@ -0,0 +1,24 @@
+-- Emails are sent in the background from this queue, and retried if the SMTP server is not
+-- available.
+CREATE TABLE outgoing_email (
+    id serial PRIMARY KEY,
+    to_email text NOT NULL,
+    to_name text NOT NULL,
+    subject text NOT NULL,
+    -- the html body
+    body text NOT NULL,
+    -- set for notification emails, which get a List-Unsubscribe header
+    unsubscribe_link text,
+    attempts int NOT NULL DEFAULT 0,
+    next_attempt timestamptz DEFAULT now(),
+    sent timestamptz,
+    last_error text,
+    published timestamptz NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_outgoing_email_next_attempt ON outgoing_email (next_attempt)
+WHERE
+    next_attempt IS NOT NULL;
+
+CREATE INDEX idx_outgoing_email_published ON outgoing_email (published DESC);
+
@@ -0,0 +1,5 @@
+DROP TABLE person_alias;
+
+ALTER TABLE person
+    DROP COLUMN moved_to;
+
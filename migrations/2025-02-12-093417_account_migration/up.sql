@@ -0,0 +1,13 @@
+-- The account which this person has moved to, set by a `Move` activity
+ALTER TABLE person
+    ADD COLUMN moved_to varchar(255);
+
+-- Other accounts which belong to the same person (`alsoKnownAs`). An account can only be moved
+-- to a target which lists the old account as alias.
+CREATE TABLE person_alias (
+    person_id int REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    alias varchar(255) NOT NULL,
+    published timestamptz NOT NULL DEFAULT now(),
+    PRIMARY KEY (person_id, alias)
+);
+
@@ -0,0 +1,15 @@
+-- Remote communities whose history of posts and comments is fetched in the background, after
+-- they were followed by a local user for the first time.
+CREATE TABLE community_backfill (
+    community_id int PRIMARY KEY REFERENCES community ON UPDATE CASCADE ON DELETE CASCADE,
+    instance_id int NOT NULL REFERENCES instance ON UPDATE CASCADE ON DELETE CASCADE,
+    -- the outbox page which is fetched next, empty if the outbox itself wasnt fetched yet
+    next_page text,
+    pages_fetched int NOT NULL DEFAULT 0,
+    failures int NOT NULL DEFAULT 0,
+    next_fetch timestamptz NOT NULL DEFAULT now(),
+    published timestamptz NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_community_backfill_next_fetch ON community_backfill (next_fetch);
+
@@ -0,0 +1,9 @@
+DELETE FROM modlog_combined
+WHERE admin_limit_instance_id IS NOT NULL;
+
+ALTER TABLE modlog_combined
+    DROP CONSTRAINT modlog_combined_check,
+    ADD CHECK (num_nonnulls (admin_allow_instance_id, admin_block_instance_id, admin_purge_comment_id, admin_purge_community_id, admin_purge_person_id, admin_purge_post_id, mod_add_id, mod_add_community_id, mod_ban_id, mod_ban_from_community_id, mod_feature_post_id, mod_hide_community_id, mod_lock_post_id, mod_remove_comment_id, mod_remove_community_id, mod_remove_post_id, mod_transfer_community_id, mod_automod_action_id, mod_warn_person_id) = 1),
+    DROP COLUMN admin_limit_instance_id;
+
+DROP TABLE admin_limit_instance, federation_limit;
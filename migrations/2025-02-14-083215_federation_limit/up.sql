@@ -0,0 +1,26 @@
+-- Instances which are limited, or whose media is rejected or marked as nsfw. Content of limited
+-- instances is only shown to users who follow the community or person.
+CREATE TABLE federation_limit (
+    instance_id int PRIMARY KEY REFERENCES instance ON UPDATE CASCADE ON DELETE CASCADE,
+    limited boolean NOT NULL DEFAULT FALSE,
+    reject_media boolean NOT NULL DEFAULT FALSE,
+    force_nsfw boolean NOT NULL DEFAULT FALSE,
+    published timestamptz NOT NULL DEFAULT now(),
+    updated timestamptz
+);
+
+CREATE TABLE admin_limit_instance (
+    id serial PRIMARY KEY,
+    instance_id int NOT NULL REFERENCES instance ON UPDATE CASCADE ON DELETE CASCADE,
+    admin_person_id int NOT NULL REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE,
+    limited boolean NOT NULL,
+    reject_media boolean NOT NULL,
+    force_nsfw boolean NOT NULL,
+    reason text,
+    published timestamptz NOT NULL DEFAULT now()
+);
+
+ALTER TABLE modlog_combined
+    ADD COLUMN admin_limit_instance_id int UNIQUE REFERENCES admin_limit_instance ON UPDATE CASCADE ON DELETE CASCADE,
+    DROP CONSTRAINT modlog_combined_check,
+    ADD CHECK (num_nonnulls (admin_allow_instance_id, admin_block_instance_id, admin_purge_comment_id, admin_purge_community_id, admin_purge_person_id, admin_purge_post_id, mod_add_id, mod_add_community_id, mod_ban_id, mod_ban_from_community_id, mod_feature_post_id, mod_hide_community_id, mod_lock_post_id, mod_remove_comment_id, mod_remove_community_id, mod_remove_post_id, mod_transfer_community_id, mod_automod_action_id, mod_warn_person_id, admin_limit_instance_id) = 1);
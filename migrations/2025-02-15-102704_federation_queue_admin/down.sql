@@ -0,0 +1,5 @@
+ALTER TABLE federation_queue_state
+    DROP COLUMN paused,
+    DROP COLUMN reset_backoff_requested,
+    DROP COLUMN skip_backlog_requested;
+
@@ -0,0 +1,7 @@
+-- Controls for the outgoing federation queue, which are set by admins and applied by the
+-- federation workers.
+ALTER TABLE federation_queue_state
+    ADD COLUMN paused boolean NOT NULL DEFAULT FALSE,
+    ADD COLUMN reset_backoff_requested boolean NOT NULL DEFAULT FALSE,
+    ADD COLUMN skip_backlog_requested boolean NOT NULL DEFAULT FALSE;
+
@@ -0,0 +1,3 @@
+ALTER TABLE post_poll
+    DROP COLUMN results_federated;
+
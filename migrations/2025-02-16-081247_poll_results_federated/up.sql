@@ -0,0 +1,12 @@
+-- Vote counts of local polls are only federated once the poll ended. Polls which already ended
+-- don't need to be sent again.
+ALTER TABLE post_poll
+    ADD COLUMN results_federated boolean NOT NULL DEFAULT FALSE;
+
+UPDATE
+    post_poll
+SET
+    results_federated = TRUE
+WHERE
+    end_time < now();
+
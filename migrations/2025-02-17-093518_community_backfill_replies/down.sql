@@ -0,0 +1,3 @@
+ALTER TABLE community_backfill
+    DROP COLUMN pending_replies;
+
@@ -0,0 +1,5 @@
+-- Older versions don't include comments in the community outbox, so they are fetched from the
+-- replies collections of posts and comments instead.
+ALTER TABLE community_backfill
+    ADD COLUMN pending_replies text[] NOT NULL DEFAULT '{}';
+
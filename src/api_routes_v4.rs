@@ -4,6 +4,7 @@ use lemmy_api::{
     distinguish::distinguish_comment,
     like::like_comment,
     list_comment_likes::list_comment_likes,
+    list_comment_revisions::list_comment_revisions,
     save::save_comment,
   },
   community::{
@@ -22,6 +23,7 @@ use lemmy_api::{
   },
   local_user::{
     add_admin::add_admin,
+    api_token::{create::create_api_token, delete::delete_api_token},
     ban_person::ban_from_site,
     block::user_block_person,
     change_password::change_password,
@@ -29,27 +31,44 @@ use lemmy_api::{
     donation_dialog_shown::donation_dialog_shown,
     generate_totp_secret::generate_totp_secret,
     get_captcha::get_captcha,
+    keyword_filter::{
+      create::create_keyword_filter,
+      delete::delete_keyword_filter,
+      update::update_keyword_filter,
+    },
     list_banned::list_banned_users,
     list_logins::list_logins,
     list_media::list_media,
     list_saved::list_person_saved,
+    list_thread_subscriptions::list_thread_subscriptions,
     login::login,
     logout::logout,
+    mod_note::{create::create_mod_note, list::list_mod_notes},
     notifications::{
       list_inbox::list_inbox,
       mark_all_read::mark_all_notifications_read,
       mark_comment_mention_read::mark_comment_mention_as_read,
+      mark_modmail_read::mark_modmail_thread_as_read,
       mark_post_mention_read::mark_post_mention_as_read,
       mark_reply_read::mark_reply_as_read,
+      mark_thread_reply_read::mark_thread_reply_as_read,
+      mark_warning_read::mark_warning_as_read,
       unread_count::unread_count,
     },
+    push_subscription::{
+      create::create_push_subscription,
+      delete::delete_push_subscription,
+      list::list_push_subscriptions,
+    },
     report_count::report_count,
     reset_password::reset_password,
     save_settings::save_user_settings,
+    unsubscribe_email::{unsubscribe_email, unsubscribe_email_confirm},
     update_totp::update_totp,
     user_block_instance::user_block_instance,
     validate_auth::validate_auth,
     verify_email::verify_email,
+    warn_person::warn_person,
   },
   post::{
     feature::feature_post,
@@ -57,10 +76,14 @@ use lemmy_api::{
     hide::hide_post,
     like::like_post,
     list_post_likes::list_post_likes,
+    list_post_revisions::list_post_revisions,
     lock::lock_post,
     mark_many_read::mark_posts_as_read,
     mark_read::mark_post_as_read,
+    mute_thread::mute_thread,
     save::save_post,
+    subscribe_thread::subscribe_to_thread,
+    vote_poll::vote_post_poll,
   },
   private_message::mark_read::mark_pm_as_read,
   reports::{
@@ -72,7 +95,19 @@ use lemmy_api::{
   site::{
     admin_allow_instance::admin_allow_instance,
     admin_block_instance::admin_block_instance,
+    admin_limit_instance::admin_limit_instance,
+    email_queue::{list_failed::list_failed_emails, retry::retry_email},
     federated_instances::get_federated_instances,
+    federation_blocklist::{
+      export::export_federation_blocklist,
+      import::import_federation_blocklist,
+    },
+    federation_queue::{
+      list::list_federation_queues,
+      pause::pause_federation,
+      reset_backoff::reset_federation_backoff,
+      skip_backlog::skip_federation_backlog,
+    },
     leave_admin::leave_admin,
     list_all_media::list_all_media,
     mod_log::get_mod_log,
@@ -88,9 +123,20 @@ use lemmy_api::{
       list::list_registration_applications,
       unread_count::get_unread_registration_application_count,
     },
+    scheduled_jobs::{
+      list::list_scheduled_jobs,
+      list_runs::list_scheduled_job_runs,
+      run::run_scheduled_job,
+    },
   },
 };
 use lemmy_api_crud::{
+  automod::{
+    create::create_automod_rule,
+    delete::delete_automod_rule,
+    list::list_automod_rules,
+    update::update_automod_rule,
+  },
   comment::{
     create::create_comment,
     delete::delete_comment,
@@ -111,6 +157,27 @@ use lemmy_api_crud::{
     list::list_custom_emojis,
     update::update_custom_emoji,
   },
+  draft::{
+    create::create_draft,
+    delete::delete_draft,
+    list::list_drafts,
+    publish::publish_draft,
+    update::update_draft,
+  },
+  feed::{
+    create::create_feed,
+    delete::delete_feed,
+    list::list_feeds,
+    read::get_feed,
+    update::update_feed,
+  },
+  modmail::{
+    archive::archive_modmail_thread,
+    create::create_modmail_thread,
+    create_message::create_modmail_message,
+    list::list_modmail_threads,
+    read::get_modmail_thread,
+  },
   oauth_provider::{
     create::create_oauth_provider,
     delete::delete_oauth_provider,
@@ -129,6 +196,12 @@ use lemmy_api_crud::{
     update::update_private_message,
   },
   site::{create::create_site, read::get_site_v4, update::update_site},
+  tag::{
+    create::create_community_tag,
+    delete::delete_community_tag,
+    list::list_community_tags,
+    update::update_community_tag,
+  },
   tagline::{
     create::create_tagline,
     delete::delete_tagline,
@@ -140,8 +213,16 @@ use lemmy_api_crud::{
     delete::delete_account,
     my_user::get_my_user,
   },
+  webhook::{
+    create::create_webhook,
+    delete::delete_webhook,
+    list::list_webhooks,
+    list_deliveries::list_webhook_deliveries,
+    update::update_webhook,
+  },
 };
 use lemmy_apub::api::{
+  account_move::{list_account_aliases, move_account, update_account_aliases},
   list_comments::{list_comments, list_comments_slim},
   list_person_content::list_person_content,
   list_posts::list_posts,
@@ -222,6 +303,20 @@ pub fn config(cfg: &mut ServiceConfig, rate_limit: &RateLimitCell) {
           .route("/icon", delete().to(delete_community_icon))
           .route("/banner", post().to(upload_community_banner))
           .route("/banner", delete().to(delete_community_banner))
+          .service(
+            scope("/tag")
+              .route("", post().to(create_community_tag))
+              .route("", put().to(update_community_tag))
+              .route("/delete", post().to(delete_community_tag))
+              .route("/list", get().to(list_community_tags)),
+          )
+          .service(
+            scope("/automod")
+              .route("", post().to(create_automod_rule))
+              .route("", put().to(update_automod_rule))
+              .route("/delete", post().to(delete_automod_rule))
+              .route("/list", get().to(list_automod_rules)),
+          )
           .service(
             scope("/pending_follows")
               .route("/count", get().to(get_pending_follows_count))
@@ -252,7 +347,11 @@ pub fn config(cfg: &mut ServiceConfig, rate_limit: &RateLimitCell) {
           .route("/list", get().to(list_posts))
           .route("/like", post().to(like_post))
           .route("/like/list", get().to(list_post_likes))
+          .route("/revision/list", get().to(list_post_revisions))
+          .route("/poll/vote", post().to(vote_post_poll))
           .route("/save", put().to(save_post))
+          .route("/subscribe", post().to(subscribe_to_thread))
+          .route("/mute", post().to(mute_thread))
           .route("/report", post().to(create_post_report))
           .route("/report/resolve", put().to(resolve_post_report))
           .route("/site_metadata", get().to(get_link_metadata)),
@@ -275,12 +374,31 @@ pub fn config(cfg: &mut ServiceConfig, rate_limit: &RateLimitCell) {
           .route("/distinguish", post().to(distinguish_comment))
           .route("/like", post().to(like_comment))
           .route("/like/list", get().to(list_comment_likes))
+          .route("/revision/list", get().to(list_comment_revisions))
           .route("/save", put().to(save_comment))
           .route("/list", get().to(list_comments))
           .route("/list/slim", get().to(list_comments_slim))
           .route("/report", post().to(create_comment_report))
           .route("/report/resolve", put().to(resolve_comment_report)),
       )
+      // Feed
+      .service(
+        scope("/feed")
+          .route("", get().to(get_feed))
+          .route("", post().to(create_feed))
+          .route("", put().to(update_feed))
+          .route("/delete", post().to(delete_feed))
+          .route("/list", get().to(list_feeds)),
+      )
+      // Modmail
+      .service(
+        scope("/modmail")
+          .route("", get().to(get_modmail_thread))
+          .route("", post().to(create_modmail_thread))
+          .route("/message", post().to(create_modmail_message))
+          .route("/archive", post().to(archive_modmail_thread))
+          .route("/list", get().to(list_modmail_threads)),
+      )
       // Private Message
       .service(
         scope("/private_message")
@@ -320,6 +438,11 @@ pub fn config(cfg: &mut ServiceConfig, rate_limit: &RateLimitCell) {
           .route("/list_media", get().to(list_media))
           .route("/inbox", get().to(list_inbox))
           .route("/delete", post().to(delete_account))
+          .route("/aliases", get().to(list_account_aliases))
+          .route("/aliases", put().to(update_account_aliases))
+          .route("/move", post().to(move_account))
+          .route("/unsubscribe", get().to(unsubscribe_email_confirm))
+          .route("/unsubscribe", post().to(unsubscribe_email))
           .service(
             scope("/mention")
               .route(
@@ -328,10 +451,39 @@ pub fn config(cfg: &mut ServiceConfig, rate_limit: &RateLimitCell) {
               )
               .route("/post/mark_as_read", post().to(mark_post_mention_as_read)),
           )
+          .route("/warning/mark_as_read", post().to(mark_warning_as_read))
+          .route(
+            "/thread_reply/mark_as_read",
+            post().to(mark_thread_reply_as_read),
+          )
+          .route("/thread_subscriptions", get().to(list_thread_subscriptions))
+          .route(
+            "/modmail/mark_as_read",
+            post().to(mark_modmail_thread_as_read),
+          )
           .route("/mark_as_read/all", post().to(mark_all_notifications_read))
           .route("/report_count", get().to(report_count))
           .route("/unread_count", get().to(unread_count))
           .route("/list_logins", get().to(list_logins))
+          .route("/api_token", post().to(create_api_token))
+          .route("/api_token/delete", post().to(delete_api_token))
+          .service(
+            scope("/push_subscription")
+              .route("", get().to(list_push_subscriptions))
+              .route("", post().to(create_push_subscription))
+              .route("/delete", post().to(delete_push_subscription)),
+          )
+          .route("/keyword_filter", post().to(create_keyword_filter))
+          .route("/keyword_filter", put().to(update_keyword_filter))
+          .route("/keyword_filter/delete", post().to(delete_keyword_filter))
+          .service(
+            scope("/drafts")
+              .route("", get().to(list_drafts))
+              .route("", post().to(create_draft))
+              .route("", put().to(update_draft))
+              .route("/delete", post().to(delete_draft))
+              .route("/publish", post().to(publish_draft)),
+          )
           .route("/validate_auth", get().to(validate_auth))
           .route("/donation_dialog_shown", post().to(donation_dialog_shown))
           .route("/avatar", post().to(upload_user_avatar))
@@ -357,7 +509,10 @@ pub fn config(cfg: &mut ServiceConfig, rate_limit: &RateLimitCell) {
       .service(
         scope("/person")
           .route("", get().to(read_person))
-          .route("/content", get().to(list_person_content)),
+          .route("/content", get().to(list_person_content))
+          .route("/warn", post().to(warn_person))
+          .route("/note", post().to(create_mod_note))
+          .route("/note/list", get().to(list_mod_notes)),
       )
       // Admin Actions
       .service(
@@ -400,7 +555,25 @@ pub fn config(cfg: &mut ServiceConfig, rate_limit: &RateLimitCell) {
           .service(
             scope("/instance")
               .route("/block", post().to(admin_block_instance))
-              .route("/allow", post().to(admin_allow_instance)),
+              .route("/allow", post().to(admin_allow_instance))
+              .route("/limit", post().to(admin_limit_instance))
+              .route("/blocklist/import", post().to(import_federation_blocklist))
+              .route("/blocklist/export", get().to(export_federation_blocklist))
+              .route("/queue/list", get().to(list_federation_queues))
+              .route("/queue/reset_backoff", post().to(reset_federation_backoff))
+              .route("/queue/skip_backlog", post().to(skip_federation_backlog))
+              .route("/queue/pause", post().to(pause_federation)),
+          )
+          .service(
+            scope("/scheduled_job")
+              .route("/list", get().to(list_scheduled_jobs))
+              .route("/runs", get().to(list_scheduled_job_runs))
+              .route("/run", post().to(run_scheduled_job)),
+          )
+          .service(
+            scope("/email")
+              .route("/failed", get().to(list_failed_emails))
+              .route("/retry", post().to(retry_email)),
           ),
       )
       .service(
@@ -416,6 +589,14 @@ pub fn config(cfg: &mut ServiceConfig, rate_limit: &RateLimitCell) {
           .route("", put().to(update_oauth_provider))
           .route("/delete", post().to(delete_oauth_provider)),
       )
+      .service(
+        scope("/webhook")
+          .route("", post().to(create_webhook))
+          .route("", put().to(update_webhook))
+          .route("/delete", post().to(delete_webhook))
+          .route("/list", get().to(list_webhooks))
+          .route("/deliveries", get().to(list_webhook_deliveries)),
+      )
       .service(
         scope("/oauth")
           .wrap(rate_limit.register())
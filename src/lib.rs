@@ -14,7 +14,9 @@ use clap::{Parser, Subcommand};
 use lemmy_api::sitemap::get_sitemap;
 use lemmy_api_common::{
   context::LemmyContext,
-  lemmy_db_views::structs::SiteView,
+  email_queue::send_queued_emails,
+  federation_blocklist::{apply_blocklist_import, export_blocklist, plan_blocklist_import},
+  lemmy_db_views::structs::{PersonView, SiteView},
   request::client_builder,
   send_activity::{ActivityChannel, MATCH_OUTGOING_ACTIVITIES},
   utils::{
@@ -24,11 +26,17 @@ use lemmy_api_common::{
 };
 use lemmy_apub::{
   activities::{handle_outgoing_activities, match_outgoing_activities},
+  fetcher::community_backfill::backfill_communities,
   objects::instance::ApubSite,
   VerifyUrlData,
   FEDERATION_HTTP_FETCH_LIMIT,
 };
-use lemmy_db_schema::{schema_setup, source::secret::Secret, utils::build_db_pool};
+use lemmy_db_schema::{
+  schema_setup,
+  source::secret::Secret,
+  utils::build_db_pool,
+  FederationBlocklistImportAction,
+};
 use lemmy_federate::{Opts, SendManager};
 use lemmy_routes::{
   feeds,
@@ -50,12 +58,13 @@ use lemmy_utils::{
   rate_limit::RateLimitCell,
   response::jsonify_plain_text_errors,
   settings::{structs::Settings, SETTINGS},
+  web_push::VapidKeys,
   VERSION,
 };
 use reqwest_middleware::ClientBuilder;
 use reqwest_tracing::TracingMiddleware;
 use serde_json::json;
-use std::{ops::Deref, time::Duration};
+use std::{ops::Deref, path::PathBuf, time::Duration};
 use tokio::signal::unix::SignalKind;
 use tracing_actix_web::{DefaultRootSpanBuilder, TracingLogger};
 
@@ -126,6 +135,11 @@ enum CmdSubcommand {
     #[arg(long, default_value_t = 1)]
     number: u64,
   },
+  /// Import or export federation blocklists in the Mastodon CSV format, then exit.
+  Blocklist {
+    #[command(subcommand)]
+    subcommand: BlocklistSubcommand,
+  },
 }
 
 #[derive(Subcommand, Debug)]
@@ -136,6 +150,20 @@ enum MigrationSubcommand {
   Revert,
 }
 
+#[derive(Subcommand, Debug)]
+enum BlocklistSubcommand {
+  /// Block and limit the instances listed in a CSV file. The changes are logged in the modlog as
+  /// made by the first admin.
+  Import {
+    file: PathBuf,
+    /// Only print the changes, without applying them.
+    #[arg(long, default_value_t = false)]
+    dry_run: bool,
+  },
+  /// Print the blocked and limited instances as CSV.
+  Export,
+}
+
 /// Placing the main function in lib.rs allows other crates to import it and embed Lemmy
 pub async fn start_lemmy_server(args: CmdArgs) -> LemmyResult<()> {
   if let Some(CmdSubcommand::Migration {
@@ -159,6 +187,42 @@ pub async fn start_lemmy_server(args: CmdArgs) -> LemmyResult<()> {
     return Ok(());
   }
 
+  if let Some(CmdSubcommand::Blocklist { subcommand }) = args.subcommand {
+    let pool = build_db_pool()?;
+    let pool = &mut (&pool).into();
+    match subcommand {
+      BlocklistSubcommand::Import { file, dry_run } => {
+        let csv = std::fs::read_to_string(file)?;
+        let local_domain = SETTINGS.get_hostname_without_port()?;
+        let entries = plan_blocklist_import(&csv, &local_domain, pool).await?;
+        for entry in &entries {
+          let details = match (entry.action, entry.skip_reason) {
+            (_, Some(skip_reason)) => skip_reason.to_string(),
+            (FederationBlocklistImportAction::Limit, _) => {
+              format!(
+                "limited={} reject_media={}",
+                entry.limited, entry.reject_media
+              )
+            }
+            _ => String::new(),
+          };
+          let action = entry.action.to_string();
+          println!("{action:<9} {} {details}", entry.domain);
+        }
+        if !dry_run {
+          let admin = PersonView::admins(pool)
+            .await?
+            .into_iter()
+            .next()
+            .ok_or(LemmyErrorType::NotAnAdmin)?;
+          apply_blocklist_import(&entries, admin.person.id, pool).await?;
+        }
+      }
+      BlocklistSubcommand::Export => print!("{}", export_blocklist(pool).await?),
+    }
+    return Ok(());
+  }
+
   // Print version number to log
   println!("Starting Lemmy v{VERSION}");
 
@@ -175,7 +239,11 @@ pub async fn start_lemmy_server(args: CmdArgs) -> LemmyResult<()> {
   run_advanced_migrations(&mut (&pool).into(), &SETTINGS).await?;
 
   // Initialize the secrets
-  let secret = Secret::init(&mut (&pool).into()).await?;
+  let mut secret = Secret::init(&mut (&pool).into()).await?;
+  if secret.vapid_private_key.is_none() {
+    let keys = VapidKeys::generate()?;
+    secret = Secret::set_vapid_keys(&mut (&pool).into(), keys.private_key, keys.public_key).await?;
+  }
 
   // Make sure the local site is set up.
   let site_view = SiteView::read_local(&mut (&pool).into()).await?;
@@ -244,6 +312,11 @@ pub async fn start_lemmy_server(args: CmdArgs) -> LemmyResult<()> {
     // Schedules various cleanup tasks for the DB
     let _scheduled_tasks =
       tokio::task::spawn(scheduled_tasks::setup(request_data.reset_request_count()));
+    // Sends the emails which are queued by api actions
+    let _email_queue = tokio::task::spawn(send_queued_emails(request_data.reset_request_count()));
+    // Fetches the history of remote communities after they were followed for the first time
+    let _community_backfill =
+      tokio::task::spawn(backfill_communities(request_data.reset_request_count()));
   }
 
   let server = if !args.disable_http_server {